@@ -5,7 +5,10 @@ use cf_utilities::{
 };
 use chainflip_api::{
 	self, clean_foreign_chain_address,
-	primitives::{AccountRole, BasisPoints, BlockNumber, CcmChannelMetadata, ChannelId},
+	primitives::{
+		AccountRole, Asset, BasisPoints, BlockNumber, CcmChannelMetadata, ChannelId,
+		SwapRefundParameters,
+	},
 	settings::StateChain,
 	BrokerApi, OperatorApi, StateChainApi,
 };
@@ -52,6 +55,7 @@ pub trait Rpc {
 		destination_address: String,
 		broker_commission_bps: BasisPoints,
 		channel_metadata: Option<CcmChannelMetadata>,
+		refund_parameters: Option<SwapRefundParameters<String>>,
 	) -> Result<BrokerSwapDepositAddress, AnyhowRpcError>;
 }
 
@@ -89,17 +93,26 @@ impl RpcServer for RpcServerImpl {
 		destination_address: String,
 		broker_commission_bps: BasisPoints,
 		channel_metadata: Option<CcmChannelMetadata>,
+		refund_parameters: Option<SwapRefundParameters<String>>,
 	) -> Result<BrokerSwapDepositAddress, AnyhowRpcError> {
+		let source_asset: Asset = source_asset.try_into()?;
 		let destination_asset = destination_asset.try_into()?;
 		Ok(self
 			.api
 			.broker_api()
 			.request_swap_deposit_address(
-				source_asset.try_into()?,
+				source_asset,
 				destination_asset,
 				clean_foreign_chain_address(destination_asset.into(), &destination_address)?,
 				broker_commission_bps,
 				channel_metadata,
+				refund_parameters
+					.map(|params| {
+						params.try_map_address(|refund_address| {
+							clean_foreign_chain_address(source_asset.into(), &refund_address)
+						})
+					})
+					.transpose()?,
 			)
 			.await
 			.map(BrokerSwapDepositAddress::from)?)
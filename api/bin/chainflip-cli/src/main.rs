@@ -73,6 +73,7 @@ async fn run_cli() -> Result<()> {
 							)?,
 							params.broker_commission,
 							None,
+							None,
 						)
 						.await?;
 					println!("Deposit Address: {address}");
@@ -24,8 +24,7 @@ struct EnvironmentParameters {
 	eth_chain_id: u64,
 	eth_vault_address: H160,
 	eth_address_checker_address: H160,
-	flip_contract_address: H160,
-	usdc_contract_address: H160,
+	erc20_token_registry: HashMap<H160, Asset>,
 	supported_erc20_tokens: HashMap<H160, cf_primitives::Asset>,
 	dot_genesis_hash: PolkadotHash,
 	btc_network: cf_chains::btc::BitcoinNetwork,
@@ -55,22 +54,16 @@ async fn get_env_parameters(state_chain_client: &StateChainClient<()>) -> Enviro
 		.await
 		.expect("State Chain client connection failed");
 
-	let supported_erc20_tokens: HashMap<_, _> = state_chain_client
-		.storage_map::<pallet_cf_environment::EthereumSupportedAssets<state_chain_runtime::Runtime>, _>(
+	let erc20_token_registry: HashMap<_, _> = state_chain_client
+		.storage_map::<pallet_cf_environment::EthereumErc20Tokens<state_chain_runtime::Runtime>, _>(
 			state_chain_client.latest_finalized_block().hash,
 		)
 		.await
-		.expect("Failed to fetch Ethereum supported assets");
+		.expect("Failed to fetch the Ethereum ERC-20 token registry");
 
-	let flip_contract_address =
-		*supported_erc20_tokens.get(&Asset::Flip).expect("FLIP not supported");
-
-	let usdc_contract_address =
-		*supported_erc20_tokens.get(&Asset::Usdc).expect("USDC not supported");
-
-	let supported_erc20_tokens: HashMap<H160, cf_primitives::Asset> = supported_erc20_tokens
-		.into_iter()
-		.map(|(asset, address)| (address, asset.into()))
+	let supported_erc20_tokens: HashMap<H160, cf_primitives::Asset> = erc20_token_registry
+		.iter()
+		.map(|(address, asset)| (*address, (*asset).into()))
 		.collect();
 
 	let dot_genesis_hash = state_chain_client
@@ -91,8 +84,7 @@ async fn get_env_parameters(state_chain_client: &StateChainClient<()>) -> Enviro
 	EnvironmentParameters {
 		eth_chain_id,
 		eth_vault_address,
-		flip_contract_address,
-		usdc_contract_address,
+		erc20_token_registry,
 		eth_address_checker_address,
 		supported_erc20_tokens,
 		dot_genesis_hash,
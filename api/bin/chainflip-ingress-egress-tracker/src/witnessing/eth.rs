@@ -9,10 +9,7 @@ use chainflip_engine::{
 	state_chain_observer::client::{StateChainClient, StateChainStreamApi},
 	witness::{
 		common::{chain_source::extension::ChainSourceExt, epoch_source::EpochSourceBuilder},
-		eth::{
-			erc20_deposits::{flip::FlipEvents, usdc::UsdcEvents},
-			EthSource,
-		},
+		eth::{erc20_deposits::erc20_token::Erc20TokenEvents, EthSource},
 	},
 };
 
@@ -45,6 +42,8 @@ where
 			settings.eth_key_path,
 			nodes,
 			env_params.eth_chain_id.into(),
+			// The tracker doesn't broadcast, so the fee estimation settings are irrelevant.
+			Default::default(),
 		)?
 	};
 
@@ -58,29 +57,19 @@ where
 		.deposit_addresses(scope, state_chain_stream, state_chain_client.clone())
 		.await;
 
-	eth_source_deposit_addresses
-		.clone()
-		.erc20_deposits::<_, _, _, UsdcEvents>(
-			witness_call.clone(),
-			eth_client.clone(),
-			Asset::Usdc,
-			env_params.usdc_contract_address,
-		)
-		.await?
-		.logging("witnessing USDCDeposits")
-		.spawn(scope);
-
-	eth_source_deposit_addresses
-		.clone()
-		.erc20_deposits::<_, _, _, FlipEvents>(
-			witness_call.clone(),
-			eth_client.clone(),
-			Asset::Flip,
-			env_params.flip_contract_address,
-		)
-		.await?
-		.logging("witnessing FlipDeposits")
-		.spawn(scope);
+	for (address, asset) in env_params.erc20_token_registry.iter() {
+		eth_source_deposit_addresses
+			.clone()
+			.erc20_deposits::<_, _, _, Erc20TokenEvents>(
+				witness_call.clone(),
+				eth_client.clone(),
+				*asset,
+				*address,
+			)
+			.await?
+			.logging(format!("witnessing {asset:?}Deposits"))
+			.spawn(scope);
+	}
 
 	eth_source_deposit_addresses
 		.clone()
@@ -6,23 +6,32 @@ use cf_utilities::{
 use chainflip_api::{
 	self,
 	lp::{
-		types::{LimitOrder, RangeOrder},
-		LpApi, Tick,
+		self,
+		types::{BlockUpdate, LimitOrder, LimitOrderFill, OrderUpdates, RangeOrder},
+		LimitOrderFillTracker, LpApi, PoolEvents, Tick,
 	},
 	primitives::{
 		chains::{Bitcoin, Ethereum, Polkadot},
-		AccountRole, Asset, ForeignChain, Hash,
+		AccountRole, Asset, BlockNumber, ForeignChain, Hash, STABLE_ASSET,
 	},
 	settings::StateChain,
-	OperatorApi, StateChainApi,
+	ChainApi, OperatorApi, SignedExtrinsicApi, StateChainApi,
 };
 use clap::Parser;
 use custom_rpc::RpcAsset;
-use futures::FutureExt;
-use jsonrpsee::{core::async_trait, proc_macros::rpc, server::ServerBuilder};
-use pallet_cf_pools::{IncreaseOrDecrease, OrderId, RangeOrderSize};
-use rpc_types::{AssetBalance, OpenSwapChannels, OrderIdJson, RangeOrderSizeJson};
-use std::{collections::BTreeMap, ops::Range, path::PathBuf};
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{async_trait, SubscriptionResult},
+	proc_macros::rpc,
+	server::ServerBuilder,
+	PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+use pallet_cf_pools::{
+	AssetsMap, IncreaseOrDecrease, OrderId, OrderUpdate, RangeOrderSize, UnidirectionalPoolDepth,
+};
+use rpc_types::{AssetBalance, OpenSwapChannels, OrderIdJson, OrderUpdateJson, RangeOrderSizeJson};
+use std::{collections::BTreeMap, ops::Range, path::PathBuf, sync::Arc};
+use tokio::sync::broadcast;
 use tracing::log;
 
 /// Contains RPC interface types that differ from internal types.
@@ -72,6 +81,100 @@ pub mod rpc_types {
 		}
 	}
 
+	#[derive(Clone, Debug, Serialize, Deserialize)]
+	#[serde(rename_all = "snake_case")]
+	pub enum OrderUpdateJson {
+		UpdateRangeOrder {
+			base_asset: RpcAsset,
+			pair_asset: RpcAsset,
+			id: OrderIdJson,
+			tick_range: Option<Range<Tick>>,
+			size_change: IncreaseOrDecrease<RangeOrderSizeJson>,
+		},
+		SetRangeOrder {
+			base_asset: RpcAsset,
+			pair_asset: RpcAsset,
+			id: OrderIdJson,
+			tick_range: Option<Range<Tick>>,
+			size: RangeOrderSizeJson,
+		},
+		UpdateLimitOrder {
+			sell_asset: RpcAsset,
+			buy_asset: RpcAsset,
+			id: OrderIdJson,
+			tick: Option<Tick>,
+			amount_change: IncreaseOrDecrease<NumberOrHex>,
+			expiry: Option<BlockNumber>,
+		},
+		SetLimitOrder {
+			sell_asset: RpcAsset,
+			buy_asset: RpcAsset,
+			id: OrderIdJson,
+			tick: Option<Tick>,
+			sell_amount: NumberOrHex,
+			expiry: Option<BlockNumber>,
+		},
+	}
+	impl TryFrom<OrderUpdateJson> for OrderUpdate<BlockNumber> {
+		type Error = anyhow::Error;
+
+		fn try_from(value: OrderUpdateJson) -> Result<Self, Self::Error> {
+			Ok(match value {
+				OrderUpdateJson::UpdateRangeOrder {
+					base_asset,
+					pair_asset,
+					id,
+					tick_range,
+					size_change,
+				} => OrderUpdate::UpdateRangeOrder {
+					base_asset: base_asset.try_into()?,
+					pair_asset: pair_asset.try_into()?,
+					id: id.try_into()?,
+					option_tick_range: tick_range,
+					size_change: size_change.try_map(TryInto::try_into)?,
+				},
+				OrderUpdateJson::SetRangeOrder { base_asset, pair_asset, id, tick_range, size } =>
+					OrderUpdate::SetRangeOrder {
+						base_asset: base_asset.try_into()?,
+						pair_asset: pair_asset.try_into()?,
+						id: id.try_into()?,
+						option_tick_range: tick_range,
+						size: size.try_into()?,
+					},
+				OrderUpdateJson::UpdateLimitOrder {
+					sell_asset,
+					buy_asset,
+					id,
+					tick,
+					amount_change,
+					expiry,
+				} => OrderUpdate::UpdateLimitOrder {
+					sell_asset: sell_asset.try_into()?,
+					buy_asset: buy_asset.try_into()?,
+					id: id.try_into()?,
+					option_tick: tick,
+					amount_change: amount_change.try_map(try_parse_number_or_hex)?,
+					expiry,
+				},
+				OrderUpdateJson::SetLimitOrder {
+					sell_asset,
+					buy_asset,
+					id,
+					tick,
+					sell_amount,
+					expiry,
+				} => OrderUpdate::SetLimitOrder {
+					sell_asset: sell_asset.try_into()?,
+					buy_asset: buy_asset.try_into()?,
+					id: id.try_into()?,
+					option_tick: tick,
+					sell_amount: try_parse_number_or_hex(sell_amount)?,
+					expiry,
+				},
+			})
+		}
+	}
+
 	#[derive(Serialize, Deserialize, Clone)]
 	pub struct OpenSwapChannels {
 		pub ethereum: Vec<SwapChannelInfo<Ethereum>>,
@@ -140,6 +243,7 @@ pub trait Rpc {
 		id: OrderIdJson,
 		tick: Option<Tick>,
 		amount_change: IncreaseOrDecrease<NumberOrHex>,
+		expiry: Option<BlockNumber>,
 	) -> Result<Vec<LimitOrder>, AnyhowRpcError>;
 
 	#[method(name = "set_limit_order")]
@@ -150,8 +254,21 @@ pub trait Rpc {
 		id: OrderIdJson,
 		tick: Option<Tick>,
 		amount: NumberOrHex,
+		expiry: Option<BlockNumber>,
 	) -> Result<Vec<LimitOrder>, AnyhowRpcError>;
 
+	#[method(name = "update_orders")]
+	async fn update_orders(
+		&self,
+		orders: Vec<OrderUpdateJson>,
+	) -> Result<OrderUpdates, AnyhowRpcError>;
+
+	#[method(name = "cancel_all_orders")]
+	async fn cancel_all_orders(
+		&self,
+		asset_pair: Option<(RpcAsset, RpcAsset)>,
+	) -> Result<OrderUpdates, AnyhowRpcError>;
+
 	#[method(name = "asset_balances")]
 	async fn asset_balances(
 		&self,
@@ -159,10 +276,48 @@ pub trait Rpc {
 
 	#[method(name = "get_open_swap_channels")]
 	async fn get_open_swap_channels(&self) -> Result<OpenSwapChannels, AnyhowRpcError>;
+
+	/// Changes made to this account's orders, per finalized block. Ends with an error if the
+	/// subscriber falls too far behind, in which case it should resubscribe.
+	#[subscription(
+		name = "subscribe_order_changes",
+		unsubscribe = "unsubscribe_order_changes",
+		item = BlockUpdate<OrderUpdates>
+	)]
+	async fn subscribe_order_changes(&self) -> SubscriptionResult;
+
+	/// Amounts sold by this account's limit orders, per finalized block. Fills are reported from
+	/// the block after the subscription starts. Ends with an error if the subscriber falls too far
+	/// behind, in which case it should resubscribe.
+	#[subscription(
+		name = "subscribe_order_fills",
+		unsubscribe = "unsubscribe_order_fills",
+		item = BlockUpdate<Vec<LimitOrderFill>>
+	)]
+	async fn subscribe_order_fills(&self) -> SubscriptionResult;
+
+	/// The depth of a pool within the given tick range, sent initially and then whenever it
+	/// changes.
+	#[subscription(
+		name = "subscribe_pool_depth",
+		unsubscribe = "unsubscribe_pool_depth",
+		item = BlockUpdate<AssetsMap<UnidirectionalPoolDepth>>
+	)]
+	async fn subscribe_pool_depth(
+		&self,
+		base_asset: RpcAsset,
+		pair_asset: RpcAsset,
+		tick_range: Range<Tick>,
+	) -> SubscriptionResult;
 }
 
+// Broadcast channel will drop old messages when the buffer is full to
+// avoid "memory leaks" due to slow receivers.
+const POOL_EVENTS_BUFFER_SIZE: usize = 1024;
+
 pub struct RpcServerImpl {
 	api: StateChainApi,
+	pool_events_sender: broadcast::Sender<Arc<PoolEvents>>,
 }
 
 impl RpcServerImpl {
@@ -170,11 +325,50 @@ impl RpcServerImpl {
 		scope: &Scope<'_, anyhow::Error>,
 		LPOptions { ws_endpoint, signing_key_file, .. }: LPOptions,
 	) -> Result<Self, anyhow::Error> {
-		Ok(Self {
-			api: StateChainApi::connect(scope, StateChain { ws_endpoint, signing_key_file })
-				.await?,
-		})
+		let api =
+			StateChainApi::connect(scope, StateChain { ws_endpoint, signing_key_file }).await?;
+
+		let (pool_events_sender, _) = broadcast::channel(POOL_EVENTS_BUFFER_SIZE);
+		scope.spawn({
+			let pool_events_sender = pool_events_sender.clone();
+			let mut pool_events =
+				Box::pin(lp::pool_events_stream(api.state_chain_client.clone()).await);
+			async move {
+				while let Some(pool_events) = pool_events.next().await {
+					// Sending only fails if there are currently no subscribers.
+					let _ = pool_events_sender.send(Arc::new(pool_events?));
+				}
+				Err(anyhow::anyhow!("State chain block stream ended"))
+			}
+		});
+
+		Ok(Self { api, pool_events_sender })
 	}
+
+	/// Waits for the next block's pool events. Returns `None` if the subscription was closed, or
+	/// the number of blocks that were missed if the subscriber fell too far behind.
+	async fn next_pool_events(
+		sink: &SubscriptionSink,
+		pool_events_receiver: &mut broadcast::Receiver<Arc<PoolEvents>>,
+	) -> Result<Option<Arc<PoolEvents>>, u64> {
+		tokio::select! {
+			_ = sink.closed() => Ok(None),
+			pool_events = pool_events_receiver.recv() => match pool_events {
+				Ok(pool_events) => Ok(Some(pool_events)),
+				Err(broadcast::error::RecvError::Lagged(skipped)) => Err(skipped),
+				// The server holds a sender, so the channel is never closed.
+				Err(broadcast::error::RecvError::Closed) => Ok(None),
+			},
+		}
+	}
+}
+
+/// The error a subscription is ended with when it can no longer be served consistently because
+/// it fell behind the state chain.
+fn subscription_lagged(skipped: u64) -> anyhow::Error {
+	anyhow::anyhow!(
+		"Subscription fell behind and missed {skipped} blocks, please resubscribe to resync"
+	)
 }
 
 #[async_trait]
@@ -283,6 +477,7 @@ impl RpcServer for RpcServerImpl {
 		id: OrderIdJson,
 		tick: Option<Tick>,
 		amount_change: IncreaseOrDecrease<NumberOrHex>,
+		expiry: Option<BlockNumber>,
 	) -> Result<Vec<LimitOrder>, AnyhowRpcError> {
 		Ok(self
 			.api
@@ -293,6 +488,7 @@ impl RpcServer for RpcServerImpl {
 				id.try_into()?,
 				tick,
 				amount_change.try_map(try_parse_number_or_hex)?,
+				expiry,
 			)
 			.await?)
 	}
@@ -304,6 +500,7 @@ impl RpcServer for RpcServerImpl {
 		id: OrderIdJson,
 		tick: Option<Tick>,
 		sell_amount: NumberOrHex,
+		expiry: Option<BlockNumber>,
 	) -> Result<Vec<LimitOrder>, AnyhowRpcError> {
 		Ok(self
 			.api
@@ -314,6 +511,35 @@ impl RpcServer for RpcServerImpl {
 				id.try_into()?,
 				tick,
 				try_parse_number_or_hex(sell_amount)?,
+				expiry,
+			)
+			.await?)
+	}
+
+	async fn update_orders(
+		&self,
+		orders: Vec<OrderUpdateJson>,
+	) -> Result<OrderUpdates, AnyhowRpcError> {
+		Ok(self
+			.api
+			.lp_api()
+			.update_orders(orders.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?)
+			.await?)
+	}
+
+	async fn cancel_all_orders(
+		&self,
+		asset_pair: Option<(RpcAsset, RpcAsset)>,
+	) -> Result<OrderUpdates, AnyhowRpcError> {
+		Ok(self
+			.api
+			.lp_api()
+			.cancel_all_orders(
+				asset_pair
+					.map(|(base_asset, pair_asset)| {
+						Ok::<_, anyhow::Error>((base_asset.try_into()?, pair_asset.try_into()?))
+					})
+					.transpose()?,
 			)
 			.await?)
 	}
@@ -337,6 +563,154 @@ impl RpcServer for RpcServerImpl {
 		)?;
 		Ok(OpenSwapChannels { ethereum, bitcoin, polkadot })
 	}
+
+	async fn subscribe_order_changes(
+		&self,
+		pending_sink: PendingSubscriptionSink,
+	) -> SubscriptionResult {
+		let mut pool_events_receiver = self.pool_events_sender.subscribe();
+		let account_id = self.api.state_chain_client.account_id();
+		let sink = pending_sink.accept().await?;
+
+		while let Some(pool_events) = Self::next_pool_events(&sink, &mut pool_events_receiver)
+			.await
+			.map_err(subscription_lagged)?
+		{
+			let order_updates = pool_events.own_order_updates(&account_id);
+			if !order_updates.range_orders.is_empty() || !order_updates.limit_orders.is_empty() {
+				sink.send(SubscriptionMessage::from_json(&BlockUpdate {
+					block_hash: pool_events.block_hash,
+					block_number: pool_events.block_number,
+					data: order_updates,
+				})?)
+				.await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn subscribe_order_fills(
+		&self,
+		pending_sink: PendingSubscriptionSink,
+	) -> SubscriptionResult {
+		let mut pool_events_receiver = self.pool_events_sender.subscribe();
+		let account_id = self.api.state_chain_client.account_id();
+		let query_api = self.api.query_api();
+		let sink = pending_sink.accept().await?;
+
+		let mut fill_tracker = None;
+		while let Some(pool_events) = Self::next_pool_events(&sink, &mut pool_events_receiver)
+			.await
+			.map_err(subscription_lagged)?
+		{
+			match &mut fill_tracker {
+				None => {
+					// Take the initial snapshot of all pools, which later blocks are compared to.
+					let mut tracker = LimitOrderFillTracker::default();
+					for asset in Asset::all().into_iter().filter(|asset| *asset != STABLE_ASSET) {
+						if let Some(pool_orders) = query_api
+							.get_pool_orders(asset, STABLE_ASSET, Some(pool_events.block_hash))
+							.await?
+						{
+							tracker.update_pool(asset, pool_orders, &[]);
+						}
+					}
+					fill_tracker = Some(tracker);
+				},
+				Some(tracker) => {
+					let own_events = pool_events.own_order_events(&account_id);
+					let mut fills = Vec::new();
+					for asset in pool_events.affected_pools() {
+						if let Some(pool_orders) = query_api
+							.get_pool_orders(asset, STABLE_ASSET, Some(pool_events.block_hash))
+							.await?
+						{
+							fills.extend(tracker.update_pool(asset, pool_orders, &own_events));
+						}
+					}
+					if !fills.is_empty() {
+						sink.send(SubscriptionMessage::from_json(&BlockUpdate {
+							block_hash: pool_events.block_hash,
+							block_number: pool_events.block_number,
+							data: fills,
+						})?)
+						.await?;
+					}
+				},
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn subscribe_pool_depth(
+		&self,
+		pending_sink: PendingSubscriptionSink,
+		base_asset: RpcAsset,
+		pair_asset: RpcAsset,
+		tick_range: Range<Tick>,
+	) -> SubscriptionResult {
+		let base_asset: Asset = base_asset.try_into()?;
+		let pair_asset: Asset = pair_asset.try_into()?;
+		let unstable_asset = if base_asset == STABLE_ASSET { pair_asset } else { base_asset };
+
+		let mut pool_events_receiver = self.pool_events_sender.subscribe();
+		let query_api = self.api.query_api();
+		let block = self.api.state_chain_client.latest_finalized_block();
+		let mut depth = query_api
+			.get_pool_depth(base_asset, pair_asset, tick_range.clone(), Some(block.hash))
+			.await?
+			.ok_or_else(|| anyhow::anyhow!("Pool does not exist"))?;
+
+		let sink = pending_sink.accept().await?;
+		sink.send(SubscriptionMessage::from_json(&BlockUpdate {
+			block_hash: block.hash,
+			block_number: block.number,
+			data: depth.clone(),
+		})?)
+		.await?;
+
+		// The depth is state rather than a sequence of changes, so after falling behind it is
+		// enough to query it again at the next block that is received.
+		let mut resync = false;
+		loop {
+			let pool_events = match Self::next_pool_events(&sink, &mut pool_events_receiver).await {
+				Ok(Some(pool_events)) => pool_events,
+				Ok(None) => break,
+				Err(skipped) => {
+					log::warn!("Pool depth subscription missed {skipped} blocks, resyncing");
+					resync = true;
+					continue
+				},
+			};
+			if pool_events.block_number > block.number &&
+				(resync || pool_events.affected_pools().contains(&unstable_asset))
+			{
+				resync = false;
+				let new_depth = query_api
+					.get_pool_depth(
+						base_asset,
+						pair_asset,
+						tick_range.clone(),
+						Some(pool_events.block_hash),
+					)
+					.await?
+					.ok_or_else(|| anyhow::anyhow!("Pool does not exist"))?;
+				if new_depth != depth {
+					depth = new_depth;
+					sink.send(SubscriptionMessage::from_json(&BlockUpdate {
+						block_hash: pool_events.block_hash,
+						block_number: pool_events.block_number,
+						data: depth.clone(),
+					})?)
+					.await?;
+				}
+			}
+		}
+
+		Ok(())
+	}
 }
 
 #[derive(Parser, Debug, Clone, Default)]
@@ -27,11 +27,12 @@ pub mod primitives {
 	pub type RedemptionAmount = pallet_cf_funding::RedemptionAmount<FlipBalance>;
 	pub use cf_chains::{
 		address::{EncodedAddress, ForeignChainAddress},
-		CcmChannelMetadata, CcmDepositMetadata,
+		CcmChannelMetadata, CcmDepositMetadata, SwapRefundParameters,
 	};
 }
 pub use chainflip_engine::state_chain_observer::client::{
 	base_rpc_api::{BaseRpcApi, RawRpcApi},
+	chain_api::ChainApi,
 	extrinsic_api::signed::{SignedExtrinsicApi, UntilFinalized},
 };
 
@@ -303,6 +304,7 @@ pub trait BrokerApi: SignedExtrinsicApi {
 		destination_address: EncodedAddress,
 		broker_commission_bps: BasisPoints,
 		channel_metadata: Option<CcmChannelMetadata>,
+		refund_parameters: Option<SwapRefundParameters<EncodedAddress>>,
 	) -> Result<SwapDepositAddress> {
 		let (_tx_hash, events, header, ..) = self
 			.submit_signed_extrinsic_with_dry_run(
@@ -312,6 +314,7 @@ pub trait BrokerApi: SignedExtrinsicApi {
 					destination_address,
 					broker_commission_bps,
 					channel_metadata,
+					refund_parameters,
 				},
 			)
 			.await?
@@ -353,6 +356,7 @@ pub fn clean_foreign_chain_address(chain: ForeignChain, address: &str) -> Result
 		ForeignChain::Polkadot =>
 			EncodedAddress::Dot(PolkadotAccountId::from_str(address).map(|id| *id.aliased_ref())?),
 		ForeignChain::Bitcoin => EncodedAddress::Btc(address.as_bytes().to_vec()),
+		ForeignChain::Arbitrum => EncodedAddress::Arb(clean_hex_address(address)?),
 	})
 }
 
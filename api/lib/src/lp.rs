@@ -1,20 +1,30 @@
 use anyhow::{bail, Context, Result};
 use async_trait::async_trait;
+use cf_amm::common::Amount;
 pub use cf_amm::{
 	common::{Order, SideMap, Tick},
 	range_orders::Liquidity,
 };
 use cf_chains::address::EncodedAddress;
-use cf_primitives::{Asset, AssetAmount, EgressId};
+use cf_primitives::{Asset, AssetAmount, BlockNumber, EgressId, STABLE_ASSET};
 use chainflip_engine::state_chain_observer::client::{
+	chain_api::ChainApi,
 	extrinsic_api::signed::{SignedExtrinsicApi, UntilInBlock},
+	storage_api::StorageApi,
 	StateChainClient,
 };
-use pallet_cf_pools::{AssetsMap, IncreaseOrDecrease, OrderId, RangeOrderSize};
+use futures::{Stream, StreamExt};
+use pallet_cf_pools::{
+	AssetsMap, IncreaseOrDecrease, OrderId, OrderUpdate, PoolOrders, RangeOrderSize,
+};
 use serde::{Deserialize, Serialize};
 use sp_core::H256;
 use state_chain_runtime::RuntimeCall;
-use std::ops::Range;
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	ops::Range,
+	sync::Arc,
+};
 use utilities::rpc::NumberOrHex;
 
 pub mod types {
@@ -47,6 +57,28 @@ pub mod types {
 		pub bought_amount: NumberOrHex,
 		pub amount_change: Option<IncreaseOrDecrease<NumberOrHex>>,
 	}
+
+	#[derive(Serialize, Deserialize, Clone)]
+	pub struct OrderUpdates {
+		pub range_orders: Vec<RangeOrder>,
+		pub limit_orders: Vec<LimitOrder>,
+	}
+
+	#[derive(Serialize, Deserialize, Clone)]
+	pub struct LimitOrderFill {
+		pub sell_asset: Asset,
+		pub buy_asset: Asset,
+		pub id: OrderId,
+		pub tick: Tick,
+		pub sold_amount: NumberOrHex,
+	}
+
+	#[derive(Serialize, Deserialize, Clone)]
+	pub struct BlockUpdate<T> {
+		pub block_hash: H256,
+		pub block_number: BlockNumber,
+		pub data: T,
+	}
 }
 
 fn collect_range_order_returns(
@@ -119,6 +151,202 @@ fn collect_limit_order_returns(
 		.collect()
 }
 
+/// The `cf-pools` events emitted in a single finalized block.
+#[derive(Clone)]
+pub struct PoolEvents {
+	pub block_hash: state_chain_runtime::Hash,
+	pub block_number: BlockNumber,
+	pub events: Vec<pallet_cf_pools::Event<state_chain_runtime::Runtime>>,
+}
+
+impl PoolEvents {
+	/// The unstable asset of each pool whose orders or price may have changed in this block.
+	pub fn affected_pools(&self) -> BTreeSet<Asset> {
+		self.events
+			.iter()
+			.flat_map(|event| match event {
+				pallet_cf_pools::Event::RangeOrderUpdated { base_asset, pair_asset, .. } =>
+					vec![*base_asset, *pair_asset],
+				pallet_cf_pools::Event::LimitOrderUpdated { sell_asset, buy_asset, .. } =>
+					vec![*sell_asset, *buy_asset],
+				pallet_cf_pools::Event::AssetSwapped { from, to, .. } => vec![*from, *to],
+				_ => vec![],
+			})
+			.filter(|asset| *asset != STABLE_ASSET)
+			.collect()
+	}
+
+	/// The order update events caused by the given LP, in the order they were emitted.
+	pub fn own_order_events(
+		&self,
+		account_id: &state_chain_runtime::AccountId,
+	) -> Vec<pallet_cf_pools::Event<state_chain_runtime::Runtime>> {
+		self.events
+			.iter()
+			.filter(|event| {
+				matches!(
+					event,
+					pallet_cf_pools::Event::RangeOrderUpdated { lp, .. } |
+					pallet_cf_pools::Event::LimitOrderUpdated { lp, .. } if lp == account_id
+				)
+			})
+			.cloned()
+			.collect()
+	}
+
+	/// The changes the given LP made to its own orders in this block.
+	pub fn own_order_updates(
+		&self,
+		account_id: &state_chain_runtime::AccountId,
+	) -> types::OrderUpdates {
+		let events = self
+			.own_order_events(account_id)
+			.into_iter()
+			.map(state_chain_runtime::RuntimeEvent::LiquidityPools)
+			.collect::<Vec<_>>();
+
+		types::OrderUpdates {
+			range_orders: collect_range_order_returns(events.clone()),
+			limit_orders: collect_limit_order_returns(events),
+		}
+	}
+}
+
+/// Yields the `cf-pools` events of every finalized block, in block order.
+pub async fn pool_events_stream(
+	state_chain_client: Arc<StateChainClient>,
+) -> impl Stream<Item = Result<PoolEvents>> {
+	state_chain_client.finalized_block_stream().await.then(move |block| {
+		let state_chain_client = state_chain_client.clone();
+		async move {
+			let events = state_chain_client
+				.storage_value::<frame_system::Events<state_chain_runtime::Runtime>>(block.hash)
+				.await?;
+
+			Ok(PoolEvents {
+				block_hash: block.hash,
+				block_number: block.number,
+				events: events
+					.into_iter()
+					.filter_map(|event_record| match event_record.event {
+						state_chain_runtime::RuntimeEvent::LiquidityPools(event) => Some(event),
+						_ => None,
+					})
+					.collect(),
+			})
+		}
+	})
+}
+
+/// Limit order fills don't emit events of their own, so they are derived by comparing snapshots of
+/// an LP's orders, after accounting for the size changes the LP made itself.
+#[derive(Default)]
+pub struct LimitOrderFillTracker {
+	orders: BTreeMap<(Asset, Asset, OrderId), (Tick, Amount)>,
+}
+
+impl LimitOrderFillTracker {
+	/// Records the LP's current limit orders in the pool of `unstable_asset`, and returns the
+	/// amounts sold by each order since the pool was last recorded. `own_events` should contain
+	/// the LP's own order update events from the blocks in between.
+	pub fn update_pool(
+		&mut self,
+		unstable_asset: Asset,
+		pool_orders: PoolOrders,
+		own_events: &[pallet_cf_pools::Event<state_chain_runtime::Runtime>],
+	) -> Vec<types::LimitOrderFill> {
+		let in_pool = |sell_asset: &Asset, buy_asset: &Asset| {
+			(*sell_asset, *buy_asset) == (unstable_asset, STABLE_ASSET) ||
+				(*sell_asset, *buy_asset) == (STABLE_ASSET, unstable_asset)
+		};
+
+		let current_orders = [
+			(unstable_asset, STABLE_ASSET, pool_orders.limit_orders.base),
+			(STABLE_ASSET, unstable_asset, pool_orders.limit_orders.pair),
+		]
+		.into_iter()
+		.flat_map(|(sell_asset, buy_asset, orders)| {
+			orders
+				.into_iter()
+				.map(move |(id, tick, amount)| ((sell_asset, buy_asset, id), (tick, amount)))
+		})
+		.collect::<BTreeMap<_, _>>();
+
+		// Amounts added to and removed from each order by the LP itself.
+		let mut own_changes = BTreeMap::<_, (Tick, Amount, Amount)>::new();
+		for event in own_events {
+			if let pallet_cf_pools::Event::LimitOrderUpdated {
+				sell_asset,
+				buy_asset,
+				id,
+				tick,
+				amount_change: Some(amount_change),
+				..
+			} = event
+			{
+				if in_pool(sell_asset, buy_asset) {
+					let (_, increased, decreased) = own_changes
+						.entry((*sell_asset, *buy_asset, *id))
+						.or_insert((*tick, Amount::zero(), Amount::zero()));
+					match amount_change {
+						IncreaseOrDecrease::Increase(amount) =>
+							*increased = increased.saturating_add((*amount).into()),
+						IncreaseOrDecrease::Decrease(amount) =>
+							*decreased = decreased.saturating_add((*amount).into()),
+					}
+				}
+			}
+		}
+
+		let previous_orders = self
+			.orders
+			.iter()
+			.filter(|((sell_asset, buy_asset, _), _)| in_pool(sell_asset, buy_asset))
+			.map(|(key, order)| (*key, *order))
+			.collect::<BTreeMap<_, _>>();
+
+		let fills = previous_orders
+			.keys()
+			.chain(own_changes.keys())
+			.chain(current_orders.keys())
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.filter_map(|key| {
+				let (sell_asset, buy_asset, id) = key;
+				let (previous_tick, previous_amount) = previous_orders.get(key).copied().unzip();
+				let (own_tick, increased, decreased) = own_changes.get(key).copied().map_or(
+					(None, Amount::zero(), Amount::zero()),
+					|(tick, increased, decreased)| (Some(tick), increased, decreased),
+				);
+				let (current_tick, current_amount) = current_orders.get(key).copied().unzip();
+
+				let sold_amount = previous_amount
+					.unwrap_or_default()
+					.saturating_add(increased)
+					.saturating_sub(decreased)
+					.saturating_sub(current_amount.unwrap_or_default());
+
+				(!sold_amount.is_zero()).then(|| types::LimitOrderFill {
+					sell_asset: *sell_asset,
+					buy_asset: *buy_asset,
+					id: *id,
+					tick: current_tick
+						.or(own_tick)
+						.or(previous_tick)
+						.expect("Every order is either in a snapshot or in the LP's own events"),
+					sold_amount: sold_amount.into(),
+				})
+			})
+			.collect();
+
+		self.orders
+			.retain(|(sell_asset, buy_asset, _), _| !in_pool(sell_asset, buy_asset));
+		self.orders.extend(current_orders);
+
+		fills
+	}
+}
+
 impl LpApi for StateChainClient {}
 
 #[async_trait]
@@ -241,6 +469,7 @@ pub trait LpApi: SignedExtrinsicApi {
 		id: OrderId,
 		option_tick: Option<Tick>,
 		amount_change: IncreaseOrDecrease<AssetAmount>,
+		expiry: Option<BlockNumber>,
 	) -> Result<Vec<types::LimitOrder>> {
 		// Submit the mint order
 		let (_tx_hash, events, ..) = self
@@ -250,6 +479,7 @@ pub trait LpApi: SignedExtrinsicApi {
 				id,
 				option_tick,
 				amount_change,
+				expiry,
 			})
 			.await
 			.until_in_block()
@@ -265,6 +495,7 @@ pub trait LpApi: SignedExtrinsicApi {
 		id: OrderId,
 		option_tick: Option<Tick>,
 		sell_amount: AssetAmount,
+		expiry: Option<BlockNumber>,
 	) -> Result<Vec<types::LimitOrder>> {
 		// Submit the burn order
 		let (_tx_hash, events, ..) = self
@@ -274,6 +505,7 @@ pub trait LpApi: SignedExtrinsicApi {
 				id,
 				option_tick,
 				sell_amount,
+				expiry,
 			})
 			.await
 			.until_in_block()
@@ -281,4 +513,100 @@ pub trait LpApi: SignedExtrinsicApi {
 
 		Ok(collect_limit_order_returns(events))
 	}
+
+	async fn update_orders(
+		&self,
+		orders: Vec<OrderUpdate<BlockNumber>>,
+	) -> Result<types::OrderUpdates> {
+		// Submit all the order updates in a single extrinsic
+		let (_tx_hash, events, ..) = self
+			.submit_signed_extrinsic(pallet_cf_pools::Call::update_orders {
+				orders: orders
+					.try_into()
+					.map_err(|_| anyhow::anyhow!("Too many orders in a single update"))?,
+			})
+			.await
+			.until_in_block()
+			.await?;
+
+		Ok(types::OrderUpdates {
+			range_orders: collect_range_order_returns(events.clone()),
+			limit_orders: collect_limit_order_returns(events),
+		})
+	}
+
+	async fn cancel_all_orders(
+		&self,
+		asset_pair: Option<(Asset, Asset)>,
+	) -> Result<types::OrderUpdates> {
+		let (_tx_hash, events, ..) = self
+			.submit_signed_extrinsic(pallet_cf_pools::Call::cancel_all_orders { asset_pair })
+			.await
+			.until_in_block()
+			.await?;
+
+		Ok(types::OrderUpdates {
+			range_orders: collect_range_order_returns(events.clone()),
+			limit_orders: collect_limit_order_returns(events),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn limit_order_fills_exclude_own_changes() {
+		const ID: OrderId = 0;
+		const FILLED_ID: OrderId = 1;
+		const TICK: Tick = 10;
+
+		let mut tracker = LimitOrderFillTracker::default();
+		assert!(tracker
+			.update_pool(
+				Asset::Eth,
+				PoolOrders {
+					limit_orders: AssetsMap {
+						base: vec![(ID, TICK, 100.into())],
+						pair: vec![(FILLED_ID, TICK, 30.into())],
+					},
+					range_orders: vec![],
+				},
+				&[],
+			)
+			.is_empty());
+
+		// The LP added 10 to the first order, which was then partially filled, and the second
+		// order was filled completely.
+		let fills = tracker.update_pool(
+			Asset::Eth,
+			PoolOrders {
+				limit_orders: AssetsMap { base: vec![(ID, TICK, 60.into())], pair: vec![] },
+				range_orders: vec![],
+			},
+			&[pallet_cf_pools::Event::LimitOrderUpdated {
+				lp: [0; 32].into(),
+				sell_asset: Asset::Eth,
+				buy_asset: Asset::Usdc,
+				id: ID,
+				tick: TICK,
+				amount_change: Some(IncreaseOrDecrease::Increase(10)),
+				amount_total: 70,
+				collected_fees: 0,
+				bought_amount: 0,
+			}],
+		);
+
+		assert_eq!(
+			fills
+				.into_iter()
+				.map(|fill| (fill.sell_asset, fill.id, fill.tick, fill.sold_amount))
+				.collect::<Vec<_>>(),
+			vec![
+				(Asset::Eth, ID, TICK, 50u128.into()),
+				(Asset::Usdc, FILLED_ID, TICK, 30u128.into()),
+			]
+		);
+	}
 }
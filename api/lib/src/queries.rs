@@ -1,17 +1,24 @@
 use super::*;
+use cf_amm::common::Tick;
 use cf_chains::{address::ToHumanreadableAddress, Chain};
 use cf_primitives::{chains::assets::any, AssetAmount, FlipBalance};
 use chainflip_engine::state_chain_observer::client::{
 	chain_api::ChainApi, storage_api::StorageApi,
 };
 use codec::Decode;
+use custom_rpc::{CustomApiClient, RpcAsset};
 use frame_support::sp_runtime::DigestItem;
 use pallet_cf_ingress_egress::DepositChannelDetails;
+use pallet_cf_pools::{AssetsMap, PoolOrders, UnidirectionalPoolDepth};
 use pallet_cf_validator::RotationPhase;
 use serde::Deserialize;
 use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
 use state_chain_runtime::PalletInstanceAlias;
-use std::{collections::BTreeMap, ops::Deref, sync::Arc};
+use std::{
+	collections::BTreeMap,
+	ops::{Deref, Range},
+	sync::Arc,
+};
 use tracing::log;
 use utilities::task_scope;
 
@@ -119,6 +126,51 @@ impl QueryApi {
 		.collect()
 	}
 
+	pub async fn get_pool_orders(
+		&self,
+		base_asset: Asset,
+		pair_asset: Asset,
+		block_hash: Option<state_chain_runtime::Hash>,
+	) -> Result<Option<PoolOrders>> {
+		let block_hash =
+			block_hash.unwrap_or_else(|| self.state_chain_client.latest_finalized_block().hash);
+
+		Ok(self
+			.state_chain_client
+			.base_rpc_client
+			.raw_rpc_client
+			.cf_pool_orders(
+				RpcAsset::ImplicitChain(base_asset),
+				RpcAsset::ImplicitChain(pair_asset),
+				self.state_chain_client.account_id(),
+				Some(block_hash),
+			)
+			.await?)
+	}
+
+	pub async fn get_pool_depth(
+		&self,
+		base_asset: Asset,
+		pair_asset: Asset,
+		tick_range: Range<Tick>,
+		block_hash: Option<state_chain_runtime::Hash>,
+	) -> Result<Option<AssetsMap<UnidirectionalPoolDepth>>> {
+		let block_hash =
+			block_hash.unwrap_or_else(|| self.state_chain_client.latest_finalized_block().hash);
+
+		Ok(self
+			.state_chain_client
+			.base_rpc_client
+			.raw_rpc_client
+			.cf_pool_depth(
+				RpcAsset::ImplicitChain(base_asset),
+				RpcAsset::ImplicitChain(pair_asset),
+				tick_range,
+				Some(block_hash),
+			)
+			.await?)
+	}
+
 	pub async fn get_bound_redeem_address(
 		&self,
 		block_hash: Option<state_chain_runtime::Hash>,
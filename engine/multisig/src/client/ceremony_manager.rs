@@ -5,9 +5,10 @@ use anyhow::{anyhow, bail, Context, Result};
 use futures::FutureExt;
 use serde::Serialize;
 use std::{
-	collections::{BTreeSet, HashMap},
+	collections::{BTreeMap, BTreeSet, HashMap},
 	fmt::{Debug, Display},
 	marker::PhantomData,
+	ops::RangeInclusive,
 	sync::Arc,
 };
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
@@ -18,7 +19,13 @@ use crate::{
 	client::{
 		ceremony_id_string,
 		common::{KeygenFailureReason, SigningFailureReason},
-		signing::PayloadAndKey,
+		signing::{
+			nonce_pool::{
+				preprocessing_batch_start, preprocessing_ceremony_id, NoncePool, PreprocessedBatch,
+				MAX_PREPROCESSING_BATCH_SIZE, MAX_PREPROCESSING_KEYS,
+			},
+			PayloadAndKey,
+		},
 		CeremonyRequestDetails,
 	},
 	crypto::{CryptoScheme, Rng},
@@ -28,7 +35,7 @@ use crate::{
 use cf_primitives::{AuthorityCount, CeremonyId};
 use state_chain_runtime::AccountId;
 use utilities::{
-	metrics::{CEREMONY_BAD_MSG, UNAUTHORIZED_CEREMONY},
+	metrics::{AUTHORIZED_CEREMONY, CEREMONY_BAD_MSG, CEREMONY_FAILED, UNAUTHORIZED_CEREMONY},
 	task_scope::{task_scope, Scope, ScopedJoinHandle},
 };
 
@@ -60,6 +67,11 @@ pub type CeremonyResultReceiver<Ceremony> = oneshot::Receiver<CeremonyOutcome<Ce
 
 const KEYGEN_LABEL: &str = "keygen";
 const SIGNING_LABEL: &str = "signing";
+const PREPROCESSING_LABEL: &str = "signing_preprocessing";
+
+/// Nonces are preprocessed for the keys and signers of the signing ceremonies with (up to)
+/// this many of the latest ceremony ids
+const PREPROCESSING_SIGNING_WINDOW: CeremonyId = 64;
 
 /// Ceremony trait combines type parameters that are often used together
 pub trait CeremonyTrait: 'static {
@@ -113,6 +125,21 @@ impl<C: CryptoScheme> CeremonyTrait for SigningCeremony<C> {
 	type CeremonyStageName = SigningStageName;
 }
 
+/// Generates a batch of signing nonces ahead of time, see [`NoncePool`]
+pub struct PreprocessingCeremony<C> {
+	_phantom: PhantomData<C>,
+}
+
+impl<C: CryptoScheme> CeremonyTrait for PreprocessingCeremony<C> {
+	const CEREMONY_TYPE: &'static str = PREPROCESSING_LABEL;
+	type Crypto = C;
+	type Data = SigningData<<C as CryptoScheme>::Point>;
+	type Request = CeremonyRequest<C>;
+	type Output = PreprocessedBatch<<C as CryptoScheme>::Point>;
+	type FailureReason = SigningFailureReason;
+	type CeremonyStageName = SigningStageName;
+}
+
 /// Responsible for mapping ceremonies to the corresponding states and
 /// generating signer indexes based on the list of parties
 pub struct CeremonyManager<Chain: ChainSigning> {
@@ -120,7 +147,15 @@ pub struct CeremonyManager<Chain: ChainSigning> {
 	outgoing_p2p_message_sender: UnboundedSender<OutgoingMultisigStageMessages>,
 	signing_states: CeremonyStates<SigningCeremony<Chain::CryptoScheme>>,
 	keygen_states: CeremonyStates<KeygenCeremony<Chain::CryptoScheme>>,
+	preprocessing_states: CeremonyStates<PreprocessingCeremony<Chain::CryptoScheme>>,
 	latest_ceremony_id: CeremonyId,
+	/// If set, signing nonces are preprocessed in batches of this size
+	preprocessing_batch_size: Option<usize>,
+	nonce_pool: NoncePool<<Chain::CryptoScheme as CryptoScheme>::Point>,
+	/// The key and signers of recent single payload signing ceremonies (including those we
+	/// don't participate in), which determine what we preprocess nonces for
+	recent_signing_ceremonies:
+		BTreeMap<CeremonyId, (KeygenResultInfo<Chain::CryptoScheme>, BTreeSet<AccountId>)>,
 }
 
 // A CeremonyStage for either keygen or signing
@@ -151,6 +186,7 @@ pub fn prepare_signing_request<Crypto: CryptoScheme>(
 	signing_info: Vec<(KeygenResultInfo<Crypto>, Crypto::SigningPayload)>,
 	outgoing_p2p_message_sender: &UnboundedSender<OutgoingMultisigStageMessages>,
 	rng: Rng,
+	nonce_pool: Option<&mut NoncePool<Crypto::Point>>,
 ) -> Result<PreparedRequest<SigningCeremony<Crypto>>, SigningFailureReason> {
 	// Sanity check: all keys must have the same parameters
 	if !are_key_parameters_same(signing_info.iter().map(|(info, _)| info)) {
@@ -183,6 +219,13 @@ pub fn prepare_signing_request<Crypto: CryptoScheme>(
 			},
 		};
 
+	// Nonces are only preprocessed for single payload ceremonies
+	let preprocessed_nonce = match (nonce_pool, &signing_info[..]) {
+		(Some(nonce_pool), [(key_info, _)]) =>
+			nonce_pool.take(&key_info.key.get_agg_public_key_point(), ceremony_id, &signer_idxs),
+		_ => None,
+	};
+
 	// Prepare initial ceremony stage
 	let initial_stage = {
 		use super::signing::{AwaitCommitments1, SigningStateCommonInfo};
@@ -205,6 +248,58 @@ pub fn prepare_signing_request<Crypto: CryptoScheme>(
 					.map(|(key_info, payload)| PayloadAndKey { payload, key: key_info.key })
 					.collect(),
 			},
+			preprocessed_nonce,
+		);
+
+		Box::new(BroadcastStage::new(processor, common))
+	};
+
+	Ok(PreparedRequest { initial_stage })
+}
+
+// Setup of a ceremony that generates a batch of nonces for the given key, in which
+// the signing set participates
+pub fn prepare_preprocessing_request<Crypto: CryptoScheme>(
+	ceremony_id: CeremonyId,
+	own_account_id: &AccountId,
+	key_info: &KeygenResultInfo<Crypto>,
+	signing_set: &BTreeSet<AccountId>,
+	batch_size: usize,
+	outgoing_p2p_message_sender: &UnboundedSender<OutgoingMultisigStageMessages>,
+	rng: Rng,
+) -> Result<PreparedRequest<PreprocessingCeremony<Crypto>>, SigningFailureReason> {
+	let batch_start =
+		preprocessing_batch_start(ceremony_id).expect("must be a preprocessing ceremony id");
+	let validator_mapping = key_info.validator_mapping.clone();
+
+	let (own_idx, all_idxs) =
+		match map_ceremony_parties(own_account_id, signing_set, &validator_mapping) {
+			Ok(result) => result,
+			Err(reason) => {
+				debug!("Preprocessing request invalid: {reason}");
+				return Err(SigningFailureReason::InvalidParticipants)
+			},
+		};
+
+	let initial_stage = {
+		use super::signing::AwaitPreprocessedCommitments1;
+
+		let common = CeremonyCommon {
+			ceremony_id,
+			outgoing_p2p_message_sender: outgoing_p2p_message_sender.clone(),
+			validator_mapping,
+			own_idx,
+			all_idxs,
+			rng,
+			// Each slot in the batch is treated as a payload for the purpose of size checks
+			number_of_signing_payloads: Some(batch_size),
+		};
+
+		let processor = AwaitPreprocessedCommitments1::<Crypto>::new(
+			common.clone(),
+			key_info.key.get_agg_public_key_point(),
+			batch_start,
+			batch_size,
 		);
 
 		Box::new(BroadcastStage::new(processor, common))
@@ -343,10 +438,26 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 			outgoing_p2p_message_sender,
 			signing_states: CeremonyStates::new(),
 			keygen_states: CeremonyStates::new(),
+			preprocessing_states: CeremonyStates::new(),
 			latest_ceremony_id,
+			preprocessing_batch_size: None,
+			nonce_pool: Default::default(),
+			recent_signing_ceremonies: BTreeMap::new(),
 		}
 	}
 
+	/// Preprocess signing nonces in batches of `batch_size`, which allows signing ceremonies
+	/// with a single payload to complete after two rounds instead of four. This only has an effect
+	/// if all signers enable it (with the same batch size).
+	pub fn with_signing_preprocessing(mut self, batch_size: usize) -> Self {
+		assert!(
+			(1..=MAX_PREPROCESSING_BATCH_SIZE).contains(&batch_size),
+			"preprocessing batch size must be between 1 and {MAX_PREPROCESSING_BATCH_SIZE}"
+		);
+		self.preprocessing_batch_size = Some(batch_size);
+		self
+	}
+
 	async fn on_request(
 		&mut self,
 		request: CeremonyRequest<Chain::CryptoScheme>,
@@ -379,8 +490,19 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 					&[Chain::NAME, KEYGEN_LABEL],
 					self.keygen_states.count_unauthorised_ceremonies(),
 				);
+				AUTHORIZED_CEREMONY.set(
+					&[Chain::NAME, KEYGEN_LABEL],
+					self.keygen_states.count_authorised_ceremonies(),
+				);
 			},
 			Some(CeremonyRequestDetails::Sign(details)) => {
+				if let [(key_info, _)] = &details.signing_info[..] {
+					self.track_signing_ceremony(
+						request.ceremony_id,
+						key_info,
+						&details.participants,
+					);
+				}
 				self.on_request_to_sign(
 					request.ceremony_id,
 					details.participants,
@@ -393,27 +515,232 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 					&[Chain::NAME, SIGNING_LABEL],
 					self.signing_states.count_unauthorised_ceremonies(),
 				);
+				AUTHORIZED_CEREMONY.set(
+					&[Chain::NAME, SIGNING_LABEL],
+					self.signing_states.count_authorised_ceremonies(),
+				);
 			},
-			None => {
-				// Because unauthorised ceremonies don't timeout, We must check the id of ceremonies
-				// that we are not participating in and cleanup any unauthorised ceremonies that may
-				// have been created by a bad p2p message.
-				if self.signing_states.cleanup_unauthorised_ceremony(&request.ceremony_id) {
-					SigningFailureReason::NotParticipatingInUnauthorisedCeremony
-						.log(&BTreeSet::default());
-					UNAUTHORIZED_CEREMONY.set(
-						&[Chain::NAME, SIGNING_LABEL],
-						self.signing_states.count_unauthorised_ceremonies(),
-					);
-				}
-				if self.keygen_states.cleanup_unauthorised_ceremony(&request.ceremony_id) {
-					KeygenFailureReason::NotParticipatingInUnauthorisedCeremony
-						.log(&BTreeSet::default());
-					UNAUTHORIZED_CEREMONY.set(
-						&[Chain::NAME, KEYGEN_LABEL],
-						self.keygen_states.count_unauthorised_ceremonies(),
+			Some(CeremonyRequestDetails::ObserveSigning(details)) => {
+				if let [key_info] = &details.keys[..] {
+					self.track_signing_ceremony(
+						request.ceremony_id,
+						key_info,
+						&details.participants,
 					);
 				}
+				self.on_not_participating(request.ceremony_id);
+			},
+			None => self.on_not_participating(request.ceremony_id),
+		}
+
+		self.maintain_nonce_pool(scope);
+	}
+
+	fn on_not_participating(&mut self, ceremony_id: CeremonyId) {
+		// Because unauthorised ceremonies don't timeout, We must check the id of ceremonies
+		// that we are not participating in and cleanup any unauthorised ceremonies that may
+		// have been created by a bad p2p message.
+		if self.signing_states.cleanup_unauthorised_ceremony(&ceremony_id) {
+			let reason = SigningFailureReason::NotParticipatingInUnauthorisedCeremony;
+			reason.log(&BTreeSet::default());
+			CEREMONY_FAILED.inc(&[Chain::NAME, SIGNING_LABEL, reason.metric_label()]);
+			UNAUTHORIZED_CEREMONY.set(
+				&[Chain::NAME, SIGNING_LABEL],
+				self.signing_states.count_unauthorised_ceremonies(),
+			);
+		}
+		if self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id) {
+			let reason = KeygenFailureReason::NotParticipatingInUnauthorisedCeremony;
+			reason.log(&BTreeSet::default());
+			CEREMONY_FAILED.inc(&[Chain::NAME, KEYGEN_LABEL, reason.metric_label()]);
+			UNAUTHORIZED_CEREMONY.set(
+				&[Chain::NAME, KEYGEN_LABEL],
+				self.keygen_states.count_unauthorised_ceremonies(),
+			);
+		}
+	}
+
+	/// All key holders track the key and signers of every single payload signing ceremony,
+	/// whether they participate or not, so they agree on what to preprocess nonces for.
+	fn track_signing_ceremony(
+		&mut self,
+		ceremony_id: CeremonyId,
+		key_info: &KeygenResultInfo<Chain::CryptoScheme>,
+		signers: &BTreeSet<AccountId>,
+	) {
+		if self.preprocessing_batch_size.is_some() {
+			self.recent_signing_ceremonies
+				.insert(ceremony_id, (key_info.clone(), signers.clone()));
+		}
+	}
+
+	/// The range of preprocessing ceremony ids we accept p2p messages for: those
+	/// that generate one of the next two batches (for any key) after the latest ceremony id
+	fn preprocessing_ceremony_id_window(&self, batch_size: usize) -> RangeInclusive<CeremonyId> {
+		let next_batch_start = next_batch_start(self.latest_ceremony_id, batch_size);
+		preprocessing_ceremony_id(next_batch_start, 0)..=
+			preprocessing_ceremony_id(
+				next_batch_start + batch_size as CeremonyId,
+				MAX_PREPROCESSING_KEYS - 1,
+			)
+	}
+
+	/// Remove nonces that can no longer be used, and start preprocessing the next batch once
+	/// the latest ceremony id is half way through the current one. All key holders see the same
+	/// ceremony ids (and the same keys and signers, see [Self::track_signing_ceremony]), so they
+	/// all start the same preprocessing ceremonies at the same point.
+	///
+	/// A batch is generated for every key signed with during the [PREPROCESSING_SIGNING_WINDOW],
+	/// among the signers of that key's ceremonies during the window (the signing set).
+	fn maintain_nonce_pool(&mut self, scope: &Scope<'_, anyhow::Error>) {
+		let Some(batch_size) = self.preprocessing_batch_size else { return };
+
+		self.nonce_pool.remove_expired(self.latest_ceremony_id);
+
+		// Preprocessing ceremonies are never requested by the SC, so we must clean up any
+		// unauthorised ones (created by p2p messages) once their batch is no longer useful.
+		let expired_ceremonies = self.preprocessing_states.cleanup_unauthorised_ceremonies_up_to(
+			preprocessing_ceremony_id(self.latest_ceremony_id, MAX_PREPROCESSING_KEYS - 1),
+		);
+		if expired_ceremonies > 0 {
+			trace!("Removed {expired_ceremonies} unauthorised preprocessing ceremonies");
+			UNAUTHORIZED_CEREMONY.set(
+				&[Chain::NAME, PREPROCESSING_LABEL],
+				self.preprocessing_states.count_unauthorised_ceremonies(),
+			);
+		}
+
+		let latest_ceremony_id = self.latest_ceremony_id;
+		self.recent_signing_ceremonies.retain(|ceremony_id, _| {
+			ceremony_id + PREPROCESSING_SIGNING_WINDOW > latest_ceremony_id
+		});
+
+		let batch_start = next_batch_start(self.latest_ceremony_id, batch_size);
+		if batch_start - self.latest_ceremony_id != (batch_size as CeremonyId + 1) / 2 {
+			return
+		}
+
+		let mut signing_sets = BTreeMap::new();
+		for (key_info, signers) in self.recent_signing_ceremonies.values() {
+			signing_sets
+				.entry(key_info.key.get_agg_public_key_point())
+				.or_insert_with(|| (key_info.clone(), BTreeSet::new()))
+				.1
+				.extend(signers.iter().cloned());
+		}
+
+		if signing_sets.len() > MAX_PREPROCESSING_KEYS {
+			warn!(
+				"Signed with {} keys recently, only preprocessing nonces for {MAX_PREPROCESSING_KEYS} of them",
+				signing_sets.len()
+			);
+		}
+
+		// The keys are ordered the same way by everyone, so each key gets the same index
+		for (key_index, (key_info, signing_set)) in
+			signing_sets.into_values().take(MAX_PREPROCESSING_KEYS).enumerate()
+		{
+			// The ceremony doesn't need us if we are not in the signing set
+			if signing_set.contains(&self.my_account_id) {
+				self.start_preprocessing(
+					&key_info,
+					key_index,
+					&signing_set,
+					batch_start,
+					batch_size,
+					scope,
+				);
+			}
+		}
+	}
+
+	fn start_preprocessing(
+		&mut self,
+		key_info: &KeygenResultInfo<Chain::CryptoScheme>,
+		key_index: usize,
+		signing_set: &BTreeSet<AccountId>,
+		batch_start: CeremonyId,
+		batch_size: usize,
+		scope: &Scope<'_, anyhow::Error>,
+	) {
+		let ceremony_id = preprocessing_ceremony_id(batch_start, key_index);
+
+		let span = info_span!(
+			"Preprocessing Ceremony",
+			ceremony_id = ceremony_id_string::<Chain>(ceremony_id)
+		);
+		let _entered = span.enter();
+
+		debug!(batch_start, batch_size, "Preprocessing a batch of signing nonces");
+
+		use rand::SeedableRng;
+
+		let request = match prepare_preprocessing_request(
+			ceremony_id,
+			&self.my_account_id,
+			key_info,
+			signing_set,
+			batch_size,
+			&self.outgoing_p2p_message_sender,
+			Rng::from_entropy(),
+		) {
+			Ok(request) => request,
+			Err(failed_outcome) => {
+				CEREMONY_FAILED.inc(&[
+					Chain::NAME,
+					PREPROCESSING_LABEL,
+					failed_outcome.metric_label(),
+				]);
+				failed_outcome.log(&BTreeSet::default());
+
+				// Remove a possible unauthorised ceremony
+				self.preprocessing_states.cleanup_unauthorised_ceremony(&ceremony_id);
+				return
+			},
+		};
+
+		// Nobody waits on the result, the outcome is handled by the manager itself
+		let (result_sender, _result_receiver) = oneshot::channel();
+
+		self.preprocessing_states
+			.get_state_or_create_unauthorized::<Chain>(ceremony_id, scope)
+			.on_request(request, result_sender)
+			.with_context(|| {
+				format!(
+					"Invalid preprocessing request with ceremony id {}",
+					ceremony_id_string::<Chain>(ceremony_id)
+				)
+			})
+			.unwrap();
+
+		UNAUTHORIZED_CEREMONY.set(
+			&[Chain::NAME, PREPROCESSING_LABEL],
+			self.preprocessing_states.count_unauthorised_ceremonies(),
+		);
+		AUTHORIZED_CEREMONY.set(
+			&[Chain::NAME, PREPROCESSING_LABEL],
+			self.preprocessing_states.count_authorised_ceremonies(),
+		);
+	}
+
+	fn on_preprocessing_outcome(
+		&mut self,
+		ceremony_id: CeremonyId,
+		outcome: CeremonyOutcome<PreprocessingCeremony<Chain::CryptoScheme>>,
+	) {
+		self.preprocessing_states.remove_authorised_ceremony(&ceremony_id);
+		AUTHORIZED_CEREMONY.set(
+			&[Chain::NAME, PREPROCESSING_LABEL],
+			self.preprocessing_states.count_authorised_ceremonies(),
+		);
+
+		match outcome {
+			Ok(batch) => {
+				debug!(batch_start = batch.batch_start(), "Preprocessed a batch of signing nonces");
+				self.nonce_pool.insert(batch);
+			},
+			Err((reported_parties, reason)) => {
+				reason.log(&reported_parties);
 			},
 		}
 	}
@@ -444,9 +771,20 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 						}
 						Some((id, outcome)) = self.signing_states.outcome_receiver.recv() => {
 							self.signing_states.finalize_authorised_ceremony(id, outcome);
+							AUTHORIZED_CEREMONY.set(
+								&[Chain::NAME, SIGNING_LABEL],
+								self.signing_states.count_authorised_ceremonies(),
+							);
 						}
 						Some((id, outcome)) = self.keygen_states.outcome_receiver.recv() => {
 							self.keygen_states.finalize_authorised_ceremony(id, outcome);
+							AUTHORIZED_CEREMONY.set(
+								&[Chain::NAME, KEYGEN_LABEL],
+								self.keygen_states.count_authorised_ceremonies(),
+							);
+						}
+						Some((id, outcome)) = self.preprocessing_states.outcome_receiver.recv() => {
+							self.on_preprocessing_outcome(id, outcome);
 						}
 					}
 				}
@@ -476,26 +814,29 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 
 		debug!("Processing a key handover request");
 
-		let request =
-			match prepare_key_handover_request(
-				ceremony_id,
-				&self.my_account_id,
-				participants,
-				&self.outgoing_p2p_message_sender,
-				resharing_context,
-				rng,
-			) {
-				Ok(request) => request,
-				Err(failed_outcome) => {
-					let _res = result_sender.send(CeremonyOutcome::<
-						KeygenCeremony<Chain::CryptoScheme>,
-					>::Err((BTreeSet::new(), failed_outcome)));
-
-					// Remove a possible unauthorised ceremony
-					self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id);
-					return
-				},
-			};
+		let request = match prepare_key_handover_request(
+			ceremony_id,
+			&self.my_account_id,
+			participants,
+			&self.outgoing_p2p_message_sender,
+			resharing_context,
+			rng,
+		) {
+			Ok(request) => request,
+			Err(failed_outcome) => {
+				CEREMONY_FAILED.inc(&[Chain::NAME, KEYGEN_LABEL, failed_outcome.metric_label()]);
+				let _res = result_sender.send(
+					CeremonyOutcome::<KeygenCeremony<Chain::CryptoScheme>>::Err((
+						BTreeSet::new(),
+						failed_outcome,
+					)),
+				);
+
+				// Remove a possible unauthorised ceremony
+				self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id);
+				return
+			},
+		};
 
 		let ceremony_handle =
 			self.keygen_states.get_state_or_create_unauthorized::<Chain>(ceremony_id, scope);
@@ -528,25 +869,28 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 
 		debug!("Processing a keygen request");
 
-		let request =
-			match prepare_keygen_request(
-				ceremony_id,
-				&self.my_account_id,
-				participants,
-				&self.outgoing_p2p_message_sender,
-				rng,
-			) {
-				Ok(request) => request,
-				Err(failed_outcome) => {
-					let _res = result_sender.send(CeremonyOutcome::<
-						KeygenCeremony<Chain::CryptoScheme>,
-					>::Err((BTreeSet::new(), failed_outcome)));
-
-					// Remove a possible unauthorised ceremony
-					self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id);
-					return
-				},
-			};
+		let request = match prepare_keygen_request(
+			ceremony_id,
+			&self.my_account_id,
+			participants,
+			&self.outgoing_p2p_message_sender,
+			rng,
+		) {
+			Ok(request) => request,
+			Err(failed_outcome) => {
+				CEREMONY_FAILED.inc(&[Chain::NAME, KEYGEN_LABEL, failed_outcome.metric_label()]);
+				let _res = result_sender.send(
+					CeremonyOutcome::<KeygenCeremony<Chain::CryptoScheme>>::Err((
+						BTreeSet::new(),
+						failed_outcome,
+					)),
+				);
+
+				// Remove a possible unauthorised ceremony
+				self.keygen_states.cleanup_unauthorised_ceremony(&ceremony_id);
+				return
+			},
+		};
 
 		let ceremony_handle =
 			self.keygen_states.get_state_or_create_unauthorized::<Chain>(ceremony_id, scope);
@@ -590,9 +934,11 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 			signing_info,
 			&self.outgoing_p2p_message_sender,
 			rng,
+			Some(&mut self.nonce_pool),
 		) {
 			Ok(request) => request,
 			Err(failed_outcome) => {
+				CEREMONY_FAILED.inc(&[Chain::NAME, SIGNING_LABEL, failed_outcome.metric_label()]);
 				let _res = result_sender.send(CeremonyOutcome::<
 					SigningCeremony<Chain::CryptoScheme>,
 				>::Err((BTreeSet::new(), failed_outcome)));
@@ -638,10 +984,32 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 					sender_id,
 					ceremony_id,
 					data,
-					self.latest_ceremony_id,
+					self.ceremony_id_window(),
 					scope,
 				)
 			},
+			MultisigMessage { ceremony_id, data: MultisigData::Signing(data) }
+				if preprocessing_batch_start(ceremony_id).is_some() =>
+			{
+				let span = info_span!(
+					"Preprocessing Ceremony",
+					ceremony_id = ceremony_id_string::<Chain>(ceremony_id)
+				);
+				let _entered = span.enter();
+
+				if let Some(batch_size) = self.preprocessing_batch_size {
+					self.preprocessing_states.process_data::<Chain>(
+						sender_id,
+						ceremony_id,
+						data,
+						self.preprocessing_ceremony_id_window(batch_size),
+						scope,
+					)
+				} else {
+					CEREMONY_BAD_MSG.inc(&[Chain::NAME, "preprocessing_disabled"]);
+					trace!("Ignoring data: signing preprocessing is disabled");
+				}
+			},
 			MultisigMessage { ceremony_id, data: MultisigData::Signing(data) } => {
 				let span = info_span!(
 					"Signing Ceremony",
@@ -653,13 +1021,18 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 					sender_id,
 					ceremony_id,
 					data,
-					self.latest_ceremony_id,
+					self.ceremony_id_window(),
 					scope,
 				)
 			},
 		}
 	}
 
+	/// The range of ceremony ids for which we allow unauthorised ceremonies to be created
+	fn ceremony_id_window(&self) -> RangeInclusive<CeremonyId> {
+		self.latest_ceremony_id + 1..=self.latest_ceremony_id + Chain::CEREMONY_ID_WINDOW
+	}
+
 	/// Override the latest ceremony id. Used to limit the spamming of unauthorised ceremonies.
 	pub fn update_latest_ceremony_id(&mut self, ceremony_id: CeremonyId) {
 		assert_eq!(self.latest_ceremony_id + 1, ceremony_id);
@@ -667,6 +1040,14 @@ impl<Chain: ChainSigning> CeremonyManager<Chain> {
 	}
 }
 
+/// The start of the first batch of preprocessed nonces that only has slots for ceremony ids after
+/// `latest_ceremony_id`. Batches are aligned to multiples of the batch size, so that all nodes
+/// agree on them.
+fn next_batch_start(latest_ceremony_id: CeremonyId, batch_size: usize) -> CeremonyId {
+	let batch_size = batch_size as CeremonyId;
+	(latest_ceremony_id / batch_size + 1) * batch_size
+}
+
 /// Create unique deterministic context used for generating a ZKP to prevent replay attacks
 fn generate_keygen_context(ceremony_id: CeremonyId, signers: BTreeSet<AccountId>) -> HashContext {
 	use blake2::{Blake2b, Digest};
@@ -706,7 +1087,7 @@ impl<Ceremony: CeremonyTrait> CeremonyStates<Ceremony> {
 		sender_id: AccountId,
 		ceremony_id: CeremonyId,
 		data: Ceremony::Data,
-		latest_ceremony_id: CeremonyId,
+		ceremony_id_window: RangeInclusive<CeremonyId>,
 		scope: &Scope<'_, anyhow::Error>,
 	) where
 		Chain: ChainSigning<CryptoScheme = Ceremony::Crypto>,
@@ -720,11 +1101,11 @@ impl<Ceremony: CeremonyTrait> CeremonyStates<Ceremony> {
 			// Only a ceremony id that is within the ceremony id window can create unauthorised
 			// ceremonies
 			let ceremony_id_string = ceremony_id_string::<Chain>(ceremony_id);
-			if ceremony_id > latest_ceremony_id + Chain::CEREMONY_ID_WINDOW {
+			if ceremony_id > *ceremony_id_window.end() {
 				CEREMONY_BAD_MSG.inc(&[Chain::NAME, "unexpected_future_ceremony_id"]);
 				warn!("Ignoring data: unexpected future ceremony id {ceremony_id_string}",);
 				return
-			} else if ceremony_id <= latest_ceremony_id {
+			} else if ceremony_id < *ceremony_id_window.start() {
 				CEREMONY_BAD_MSG.inc(&[Chain::NAME, "old_ceremony_id"]);
 				trace!("Ignoring data: old ceremony id {ceremony_id_string}",);
 				return
@@ -784,6 +1165,16 @@ impl<Ceremony: CeremonyTrait> CeremonyStates<Ceremony> {
 		}
 	}
 
+	/// Remove the state of an authorised ceremony whose outcome is not sent anywhere
+	fn remove_authorised_ceremony(&mut self, ceremony_id: &CeremonyId) {
+		let ceremony_handle =
+			self.ceremony_handles.remove(ceremony_id).expect("Should have handle");
+		assert!(
+			matches!(ceremony_handle.request_state, CeremonyRequestState::Authorised(_)),
+			"Expected authorised ceremony"
+		);
+	}
+
 	/// Removing any state associated with the unauthorized ceremony and therefore abort its task
 	fn cleanup_unauthorised_ceremony(&mut self, ceremony_id: &CeremonyId) -> bool {
 		// Dropping the ceremony handle will cause any associated task to be aborted
@@ -798,12 +1189,30 @@ impl<Ceremony: CeremonyTrait> CeremonyStates<Ceremony> {
 		}
 	}
 
+	/// Remove all unauthorised ceremonies with ids up to (and including) `ceremony_id`,
+	/// returning the number of ceremonies removed
+	fn cleanup_unauthorised_ceremonies_up_to(&mut self, ceremony_id: CeremonyId) -> usize {
+		let count_before = self.ceremony_handles.len();
+		self.ceremony_handles.retain(|id, handle| {
+			*id > ceremony_id ||
+				!matches!(handle.request_state, CeremonyRequestState::Unauthorised(_))
+		});
+		count_before - self.ceremony_handles.len()
+	}
+
 	fn count_unauthorised_ceremonies(&self) -> usize {
 		self.ceremony_handles
 			.values()
 			.filter(|handle| matches!(handle.request_state, CeremonyRequestState::Unauthorised(_)))
 			.count()
 	}
+
+	fn count_authorised_ceremonies(&self) -> usize {
+		self.ceremony_handles
+			.values()
+			.filter(|handle| matches!(handle.request_state, CeremonyRequestState::Authorised(_)))
+			.count()
+	}
 }
 
 // ==================
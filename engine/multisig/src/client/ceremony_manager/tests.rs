@@ -4,17 +4,19 @@ use crate::{
 	client::{
 		self,
 		ceremony_manager::{
-			CeremonyHandle, CeremonyManager, CeremonyRequestState, SigningCeremony,
+			deserialize_for_version, CeremonyHandle, CeremonyManager, CeremonyRequestState,
+			SigningCeremony,
 		},
 		ceremony_runner::CeremonyRunner,
 		common::{BroadcastFailureReason, SigningFailureReason, SigningStageName},
-		gen_keygen_data_hash_comm1, get_key_data_for_test,
+		gen_keygen_data_hash_comm1, generate_key_data, get_key_data_for_test,
 		helpers::{
 			ACCOUNT_IDS, CEREMONY_TIMEOUT_DURATION, DEFAULT_KEYGEN_SEED, DEFAULT_SIGNING_SEED,
 			INITIAL_LATEST_CEREMONY_ID,
 		},
+		signing::{gen_signing_data_stage1, nonce_pool::preprocessing_ceremony_id},
 		CeremonyRequest, CeremonyRequestDetails, KeygenRequestDetails, MultisigData,
-		SigningRequestDetails,
+		ObservedSigningDetails, SigningRequestDetails,
 	},
 	crypto::{CryptoScheme, Rng},
 	eth::{EthSigning, EvmCryptoScheme},
@@ -458,3 +460,149 @@ async fn should_route_p2p_message() {
 		OutgoingMultisigStageMessages::Broadcast(..)
 	))
 }
+
+#[tokio::test]
+async fn should_ignore_preprocessing_messages_if_disabled() {
+	let mut ceremony_manager =
+		new_ceremony_manager_for_test(ACCOUNT_IDS[0].clone(), INITIAL_LATEST_CEREMONY_ID);
+
+	task_scope(|scope| {
+		let future: Pin<Box<dyn Future<Output = Result<()>> + Send>> = async {
+			ceremony_manager.process_p2p_message(
+				ACCOUNT_IDS[1].clone(),
+				MultisigMessage {
+					ceremony_id: preprocessing_ceremony_id(INITIAL_LATEST_CEREMONY_ID + 1, 0),
+					data: MultisigData::Signing(gen_signing_data_stage1(1)),
+				},
+				scope,
+			);
+
+			// The message must not create an unauthorised ceremony of any kind
+			assert_eq!(ceremony_manager.signing_states.ceremony_handles.len(), 0);
+			assert_eq!(ceremony_manager.preprocessing_states.ceremony_handles.len(), 0);
+
+			anyhow::bail!("End the future so we can complete the test");
+		}
+		.boxed();
+		future
+	})
+	.await
+	.unwrap_err();
+}
+
+#[tokio::test]
+async fn should_start_preprocessing_when_next_batch_is_needed_soon() {
+	const BATCH_SIZE: usize = 4;
+	const LATEST_CEREMONY_ID: CeremonyId = 1;
+
+	let (ceremony_request_sender, ceremony_request_receiver) = mpsc::unbounded_channel();
+	let (_incoming_p2p_sender, incoming_p2p_receiver) = mpsc::unbounded_channel();
+	let (outgoing_p2p_sender, mut outgoing_p2p_receiver) = mpsc::unbounded_channel();
+	let ceremony_manager = CeremonyManager::<EthSigning>::new(
+		ACCOUNT_IDS[0].clone(),
+		outgoing_p2p_sender,
+		LATEST_CEREMONY_ID,
+	)
+	.with_signing_preprocessing(BATCH_SIZE);
+	tokio::spawn(ceremony_manager.run(ceremony_request_receiver, incoming_p2p_receiver));
+
+	// This ceremony is half way through the current batch, so the next one is needed soon
+	let _result_receiver = send_signing_request(
+		&ceremony_request_sender,
+		BTreeSet::from_iter(ACCOUNT_IDS.iter().cloned()),
+		LATEST_CEREMONY_ID + 1,
+	);
+
+	// Small delay to let the ceremonies start
+	tokio::time::sleep(Duration::from_millis(50)).await;
+
+	let mut ceremony_ids = BTreeSet::new();
+	while let Ok(OutgoingMultisigStageMessages::Broadcast(receivers, payload)) =
+		outgoing_p2p_receiver.try_recv()
+	{
+		// Preprocessing is done with the signing set
+		assert_eq!(receivers.len(), ACCOUNT_IDS.len() - 1);
+
+		let message = deserialize_for_version::<EvmCryptoScheme>(VersionedCeremonyMessage {
+			version: CURRENT_PROTOCOL_VERSION,
+			payload,
+		})
+		.unwrap();
+		ceremony_ids.insert(message.ceremony_id);
+	}
+
+	assert_eq!(
+		ceremony_ids,
+		BTreeSet::from_iter([
+			LATEST_CEREMONY_ID + 1,
+			preprocessing_ceremony_id(LATEST_CEREMONY_ID + 3, 0)
+		])
+	);
+}
+
+#[tokio::test]
+async fn should_preprocess_a_batch_for_each_recently_used_key() {
+	const BATCH_SIZE: usize = 4;
+
+	let participants = BTreeSet::from_iter(ACCOUNT_IDS.iter().cloned());
+	let (_, mut key_a) =
+		generate_key_data::<EvmCryptoScheme>(participants.clone(), &mut Rng::from_seed([1; 32]));
+	let (_, mut key_b) =
+		generate_key_data::<EvmCryptoScheme>(participants.clone(), &mut Rng::from_seed([2; 32]));
+	let key_a = key_a.remove(&ACCOUNT_IDS[0]).unwrap();
+	let key_b = key_b.remove(&ACCOUNT_IDS[0]).unwrap();
+
+	let mut ceremony_manager = CeremonyManager::<EthSigning>::new(
+		ACCOUNT_IDS[0].clone(),
+		mpsc::unbounded_channel().0,
+		INITIAL_LATEST_CEREMONY_ID,
+	)
+	.with_signing_preprocessing(BATCH_SIZE);
+
+	task_scope(|scope| {
+		let future: Pin<Box<dyn Future<Output = Result<()>> + Send>> = async {
+			// Alternate between signing with one key (as for ETH) and observing the signing
+			// with another (as for ARB, which shares the ceremony manager)
+			for ceremony_id in INITIAL_LATEST_CEREMONY_ID + 1..=INITIAL_LATEST_CEREMONY_ID + 6 {
+				let details = if ceremony_id % 2 == 1 {
+					CeremonyRequestDetails::Sign(SigningRequestDetails {
+						participants: participants.clone(),
+						signing_info: vec![(
+							key_a.clone(),
+							EvmCryptoScheme::signing_payload_for_test(),
+						)],
+						rng: Rng::from_seed(DEFAULT_SIGNING_SEED),
+						result_sender: oneshot::channel().0,
+					})
+				} else {
+					CeremonyRequestDetails::ObserveSigning(ObservedSigningDetails {
+						participants: participants.clone(),
+						keys: vec![key_b.clone()],
+					})
+				};
+				ceremony_manager
+					.on_request(CeremonyRequest { ceremony_id, details: Some(details) }, scope)
+					.await;
+			}
+
+			// Switching keys must not stop either key from getting its batches
+			let preprocessing_ceremony_ids: BTreeSet<_> =
+				ceremony_manager.preprocessing_states.ceremony_handles.keys().cloned().collect();
+			assert_eq!(
+				preprocessing_ceremony_ids,
+				BTreeSet::from_iter([
+					preprocessing_ceremony_id(4, 0),
+					preprocessing_ceremony_id(4, 1),
+					preprocessing_ceremony_id(8, 0),
+					preprocessing_ceremony_id(8, 1),
+				])
+			);
+
+			anyhow::bail!("End the future so we can complete the test");
+		}
+		.boxed();
+		future
+	})
+	.await
+	.unwrap_err();
+}
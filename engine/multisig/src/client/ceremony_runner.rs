@@ -28,7 +28,7 @@ use state_chain_runtime::{constants::common::MAX_STAGE_DURATION_SECONDS, Account
 
 use super::{
 	ceremony_manager::{CeremonyOutcome, CeremonyTrait, DynStage, PreparedRequest},
-	common::PreProcessStageDataCheck,
+	common::{CeremonyFailureReason, PreProcessStageDataCheck},
 };
 
 const MAX_STAGE_DURATION: Duration = Duration::from_secs(MAX_STAGE_DURATION_SECONDS as u64);
@@ -111,10 +111,18 @@ where
 			}
 		};
 		if let Some(start_instant) = ceremony_start {
-			let duration = start_instant.elapsed().as_millis();
+			let elapsed = start_instant.elapsed();
+			let duration = elapsed.as_millis();
 			runner.metrics.ceremony_duration.set(duration);
+			runner
+				.metrics
+				.ceremony_latency
+				.observe(&[if outcome.is_ok() { "success" } else { "failure" }], elapsed);
 			span.in_scope(|| tracing::info!("Ceremony took {}ms to complete", duration));
 		}
+		if let Err((blamed_parties, reason)) = &outcome {
+			runner.metrics.record_failure(reason.metric_label(), blamed_parties);
+		}
 		let _result = runner.outcome_sender.send((ceremony_id, outcome));
 		Ok(())
 	}
@@ -202,7 +210,7 @@ where
 					}
 				},
 				StageResult::Error(bad_validators, reason) => {
-					self.metrics.stage_failing.inc(&[&stage_name, &format!("{:?}", reason)]);
+					self.metrics.stage_failing.inc(&[&stage_name, reason.metric_label()]);
 					Some(Err((validator_mapping.get_ids(bad_validators), reason)))
 				},
 				StageResult::Done(result) => {
@@ -354,6 +362,7 @@ where
 			self.metrics
 				.missing_messages
 				.set(&[&stage_name], missing_messages_from_accounts.len());
+			self.metrics.stage_timeout.inc(&[&stage_name]);
 			self.finalize_current_stage().await
 		} else {
 			panic!("Unauthorised ceremonies cannot timeout");
@@ -129,6 +129,7 @@ async fn should_delay_stage_1_message_while_unauthorised() {
 		)],
 		&outgoing_p2p_sender,
 		Rng::from_seed(DEFAULT_SIGNING_SEED),
+		None,
 	)
 	.unwrap()
 	.initial_stage;
@@ -224,6 +225,7 @@ async fn gen_stage_1_signing_state(
 		)],
 		&outgoing_p2p_sender,
 		Rng::from_seed(DEFAULT_SIGNING_SEED),
+		None,
 	)
 	.unwrap()
 	.initial_stage;
@@ -378,6 +380,7 @@ async fn should_timeout_authorised_ceremony() {
 			)],
 			&outgoing_p2p_sender,
 			Rng::from_seed(DEFAULT_SIGNING_SEED),
+			None,
 		)
 		.unwrap(),
 	);
@@ -387,3 +390,64 @@ async fn should_timeout_authorised_ceremony() {
 	tokio::time::sleep(CEREMONY_TIMEOUT_DURATION).await;
 	assert!(task_handle.is_finished());
 }
+
+#[tokio::test(start_paused = true)]
+async fn should_record_metrics_for_failed_ceremony() {
+	use utilities::metrics::{CEREMONY_BLAMED_PARTY, CEREMONY_FAILED};
+
+	let failure_count = |reason: &str| {
+		CEREMONY_FAILED
+			.prom_metric
+			.with_label_values(&[
+				EthSigning::NAME,
+				SigningCeremony::<EvmCryptoScheme>::CEREMONY_TYPE,
+				reason,
+			])
+			.get()
+	};
+	let blame_count = |party: &AccountId| {
+		CEREMONY_BLAMED_PARTY
+			.prom_metric
+			.with_label_values(&[
+				EthSigning::NAME,
+				SigningCeremony::<EvmCryptoScheme>::CEREMONY_TYPE,
+				&party.to_string(),
+			])
+			.get()
+	};
+
+	let (_task_handle, (_message_sender, request_sender, mut outcome_receiver)) =
+		spawn_signing_ceremony_runner();
+
+	// Send a signing request, but no messages from the other parties, so the ceremony times out
+	let (outgoing_p2p_sender, _outgoing_p2p_receiver) = tokio::sync::mpsc::unbounded_channel();
+	let _res = request_sender.send(
+		prepare_signing_request(
+			DEFAULT_CEREMONY_ID,
+			&ACCOUNT_IDS[0],
+			BTreeSet::from_iter(ACCOUNT_IDS.iter().cloned()),
+			vec![(
+				get_key_data_for_test::<EvmCryptoScheme>(BTreeSet::from_iter(
+					ACCOUNT_IDS.iter().cloned(),
+				)),
+				EvmCryptoScheme::signing_payload_for_test(),
+			)],
+			&outgoing_p2p_sender,
+			Rng::from_seed(DEFAULT_SIGNING_SEED),
+			None,
+		)
+		.unwrap(),
+	);
+
+	let (blamed_parties, reason) = match outcome_receiver.recv().await.unwrap() {
+		(_, Err(failure)) => failure,
+		(_, Ok(_)) => panic!("Expected the ceremony to fail"),
+	};
+
+	// Other tests may also fail ceremonies, so we only check that the metrics were recorded
+	assert!(!blamed_parties.is_empty());
+	assert!(failure_count(reason.metric_label()) >= 1);
+	for party in &blamed_parties {
+		assert!(blame_count(party) >= 1);
+	}
+}
@@ -216,9 +216,9 @@ where
 		// we insert None for any missing data
 		let stage_name = self.get_stage_name().to_string();
 		if let Some(start_instant) = self.stage_started {
-			metrics
-				.stage_duration
-				.set(&[&stage_name, "receiving"], start_instant.elapsed().as_millis());
+			let elapsed = start_instant.elapsed();
+			metrics.stage_duration.set(&[&stage_name, "receiving"], elapsed.as_millis());
+			metrics.stage_latency.observe(&[&stage_name, "receiving"], elapsed);
 		}
 
 		let process_msg_instant = Instant::now();
@@ -234,9 +234,9 @@ where
 			.collect();
 
 		let result = self.processor.process(messages).await;
-		metrics
-			.stage_duration
-			.set(&[&stage_name, "processing"], process_msg_instant.elapsed().as_millis());
+		let elapsed = process_msg_instant.elapsed();
+		metrics.stage_duration.set(&[&stage_name, "processing"], elapsed.as_millis());
+		metrics.stage_latency.observe(&[&stage_name, "processing"], elapsed);
 		result
 	}
 
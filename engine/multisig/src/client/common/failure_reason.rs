@@ -78,8 +78,22 @@ const KEYGEN_CEREMONY_FAILED_PREFIX: &str = "Keygen ceremony failed";
 const REQUEST_TO_SIGN_IGNORED_PREFIX: &str = "Signing request ignored";
 const KEYGEN_REQUEST_IGNORED_PREFIX: &str = "Keygen request ignored";
 
+impl BroadcastFailureReason {
+	fn metric_label(&self) -> &'static str {
+		match self {
+			BroadcastFailureReason::InsufficientMessages => "broadcast_insufficient_messages",
+			BroadcastFailureReason::InsufficientVerificationMessages =>
+				"broadcast_insufficient_verification_messages",
+			BroadcastFailureReason::Inconsistency => "broadcast_inconsistency",
+		}
+	}
+}
+
 pub trait CeremonyFailureReason {
 	fn log(&self, reported_parties: &BTreeSet<AccountId>);
+
+	/// A label for the reason that is suitable for metrics, i.e. one of a fixed set of values.
+	fn metric_label(&self) -> &'static str;
 }
 
 impl CeremonyFailureReason for SigningFailureReason {
@@ -110,6 +124,21 @@ impl CeremonyFailureReason for SigningFailureReason {
 			},
 		}
 	}
+
+	fn metric_label(&self) -> &'static str {
+		match self {
+			SigningFailureReason::NotParticipatingInUnauthorisedCeremony =>
+				"not_participating_in_unauthorised_ceremony",
+			SigningFailureReason::InvalidParticipants => "invalid_participants",
+			SigningFailureReason::BroadcastFailure(reason, _) => reason.metric_label(),
+			SigningFailureReason::InvalidSigShare => "invalid_sig_share",
+			SigningFailureReason::NotEnoughSigners => "not_enough_signers",
+			SigningFailureReason::UnknownKey => "unknown_key",
+			SigningFailureReason::InvalidNumberOfPayloads => "invalid_number_of_payloads",
+			SigningFailureReason::DeserializationError => "deserialization_error",
+			SigningFailureReason::DeveloperError(_) => "developer_error",
+		}
+	}
 }
 
 impl CeremonyFailureReason for KeygenFailureReason {
@@ -135,4 +164,17 @@ impl CeremonyFailureReason for KeygenFailureReason {
 			},
 		}
 	}
+
+	fn metric_label(&self) -> &'static str {
+		match self {
+			KeygenFailureReason::NotParticipatingInUnauthorisedCeremony =>
+				"not_participating_in_unauthorised_ceremony",
+			KeygenFailureReason::InvalidParticipants => "invalid_participants",
+			KeygenFailureReason::BroadcastFailure(reason, _) => reason.metric_label(),
+			KeygenFailureReason::InvalidCommitment => "invalid_commitment",
+			KeygenFailureReason::DeserializationError => "deserialization_error",
+			KeygenFailureReason::InvalidBlameResponse => "invalid_blame_response",
+			KeygenFailureReason::InvalidComplaint => "invalid_complaint",
+		}
+	}
 }
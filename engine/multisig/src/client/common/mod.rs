@@ -262,6 +262,10 @@ pub enum SigningStageName {
 	LocalSigStage3,
 	#[error("Verify Local Signatures [4]")]
 	VerifyLocalSigsBroadcastStage4,
+	#[error("Preprocessed Commitments [1]")]
+	AwaitPreprocessedCommitments1,
+	#[error("Verify Preprocessed Commitments [2]")]
+	VerifyPreprocessedCommitmentsBroadcast2,
 }
 
 /// Try to deserialize all messages. If at least one fails,
@@ -20,13 +20,17 @@ use utilities::{
 use crate::{
 	client::{
 		ceremony_manager::{
-			prepare_keygen_request, prepare_signing_request, CeremonyOutcome, CeremonyTrait,
-			KeygenCeremony, SigningCeremony,
+			prepare_keygen_request, prepare_preprocessing_request, prepare_signing_request,
+			CeremonyOutcome, CeremonyTrait, KeygenCeremony, PreprocessingCeremony, SigningCeremony,
 		},
 		ceremony_runner::CeremonyRunner,
 		common::CeremonyFailureReason,
 		keygen::{generate_key_data, HashComm1, HashContext},
-		signing, KeygenResultInfo,
+		signing::{
+			self,
+			nonce_pool::{preprocessing_ceremony_id, NoncePool},
+		},
+		KeygenResultInfo,
 	},
 	crypto::{CryptoTag, ECPoint, Rng},
 	eth::{EthSigning, EvmCryptoScheme},
@@ -265,6 +269,7 @@ impl<Chain: ChainSigning> Node<SigningCeremony<Chain::CryptoScheme>, Chain> {
 	async fn request_signing(
 		&mut self,
 		signing_ceremony_details: SigningCeremonyDetails<Chain::CryptoScheme>,
+		nonce_pool: Option<&mut NoncePool<Point<Chain>>>,
 	) {
 		let SigningCeremonyDetails { rng, ceremony_id, signers, payloads } =
 			signing_ceremony_details;
@@ -276,6 +281,37 @@ impl<Chain: ChainSigning> Node<SigningCeremony<Chain::CryptoScheme>, Chain> {
 			payloads.into_iter().map(|p| (p.keygen_result_info, p.payload)).collect(),
 			&self.outgoing_p2p_message_sender,
 			rng,
+			nonce_pool,
+		)
+		.expect("invalid request");
+
+		if let Some(outcome) = self
+			.ceremony_runner
+			.on_ceremony_request(request.initial_stage)
+			.instrument(debug_span!("Node", account_id = self.own_account_id.to_string()))
+			.await
+		{
+			self.on_ceremony_outcome(outcome);
+		}
+	}
+}
+
+impl<Chain: ChainSigning> Node<PreprocessingCeremony<Chain::CryptoScheme>, Chain> {
+	async fn request_preprocessing(
+		&mut self,
+		key_info: &KeygenResultInfo<Chain::CryptoScheme>,
+		batch_start: CeremonyId,
+		batch_size: usize,
+		rng: Rng,
+	) {
+		let request = prepare_preprocessing_request::<Chain::CryptoScheme>(
+			preprocessing_ceremony_id(batch_start, 0),
+			&self.own_account_id,
+			key_info,
+			key_info.validator_mapping.get_all_ids(),
+			batch_size,
+			&self.outgoing_p2p_message_sender,
+			rng,
 		)
 		.expect("invalid request");
 
@@ -651,7 +687,7 @@ where
 			.expect("Failed to get all ceremony outcomes");
 	}
 
-	async fn request_without_gather(&mut self) {
+	pub async fn request_without_gather(&mut self) {
 		for node_id in self.nodes.keys().sorted().cloned().collect::<Vec<_>>() {
 			self.request_ceremony(&node_id).await;
 		}
@@ -777,6 +813,8 @@ impl<C: CryptoScheme> PayloadAndKeyData<C> {
 
 pub struct SigningCeremonyRunnerData<C: CryptoScheme> {
 	pub data: Vec<PayloadAndKeyData<C>>,
+	/// Nodes with a nonce pool will try to sign using preprocessed nonces
+	pub nonce_pools: HashMap<AccountId, NoncePool<C::Point>>,
 }
 pub type SigningCeremonyRunner<Chain> = CeremonyTestRunner<
 	SigningCeremonyRunnerData<<Chain as ChainSigning>::CryptoScheme>,
@@ -818,7 +856,10 @@ impl<Chain: ChainSigning> CeremonyRunnerStrategy for SigningCeremonyRunner<Chain
 		self.nodes
 			.get_mut(node_id)
 			.unwrap()
-			.request_signing(signing_ceremony_details)
+			.request_signing(
+				signing_ceremony_details,
+				self.ceremony_runner_data.nonce_pools.get_mut(node_id),
+			)
 			.await;
 	}
 }
@@ -833,7 +874,7 @@ impl<Chain: ChainSigning> SigningCeremonyRunner<Chain> {
 		Self::inner_new(
 			nodes,
 			ceremony_id,
-			SigningCeremonyRunnerData { data: payloads_and_keys },
+			SigningCeremonyRunnerData { data: payloads_and_keys, nonce_pools: HashMap::new() },
 			rng,
 		)
 	}
@@ -878,6 +919,99 @@ impl<Chain: ChainSigning> SigningCeremonyRunner<Chain> {
 	}
 }
 
+pub struct PreprocessingCeremonyRunnerData<C: CryptoScheme> {
+	pub key_data: HashMap<AccountId, KeygenResultInfo<C>>,
+	pub batch_start: CeremonyId,
+	pub batch_size: usize,
+}
+pub type PreprocessingCeremonyRunner<Chain> = CeremonyTestRunner<
+	PreprocessingCeremonyRunnerData<<Chain as ChainSigning>::CryptoScheme>,
+	PreprocessingCeremony<<Chain as ChainSigning>::CryptoScheme>,
+	Chain,
+>;
+
+#[async_trait]
+impl<Chain: ChainSigning> CeremonyRunnerStrategy for PreprocessingCeremonyRunner<Chain> {
+	type CeremonyType = PreprocessingCeremony<Chain::CryptoScheme>;
+	type CheckedOutput = HashMap<AccountId, StrategyOutput<Self>>;
+	type InitialStageData = signing::PreprocessComm1<Point<Chain>>;
+
+	fn post_successful_complete_check(
+		&self,
+		outputs: HashMap<AccountId, StrategyOutput<Self>>,
+	) -> Self::CheckedOutput {
+		all_same(outputs.values().map(|batch| (*batch.key(), batch.batch_start())))
+			.expect("Preprocessed batches don't match");
+
+		outputs
+	}
+
+	async fn request_ceremony(&mut self, node_id: &AccountId) {
+		use rand::Rng as _;
+
+		let PreprocessingCeremonyRunnerData { key_data, batch_start, batch_size } =
+			&self.ceremony_runner_data;
+
+		self.nodes
+			.get_mut(node_id)
+			.unwrap()
+			.request_preprocessing(
+				&key_data[node_id],
+				*batch_start,
+				*batch_size,
+				Rng::from_seed(self.rng.gen()),
+			)
+			.await;
+	}
+}
+
+impl<Chain: ChainSigning> PreprocessingCeremonyRunner<Chain> {
+	/// Create a preprocessing ceremony with all holders of the key
+	pub fn new(
+		key_data: HashMap<AccountId, KeygenResultInfo<Chain::CryptoScheme>>,
+		batch_start: CeremonyId,
+		batch_size: usize,
+		rng: Rng,
+	) -> Self {
+		Self::inner_new(
+			new_nodes(key_data.keys().cloned()),
+			preprocessing_ceremony_id(batch_start, 0),
+			PreprocessingCeremonyRunnerData { key_data, batch_start, batch_size },
+			rng,
+		)
+	}
+}
+
+/// Run a preprocessing ceremony among all holders of the key, returning the nonce pool of each
+/// node containing the preprocessed batch
+pub async fn run_preprocessing<Chain: ChainSigning>(
+	key_data: HashMap<AccountId, KeygenResultInfo<Chain::CryptoScheme>>,
+	batch_start: CeremonyId,
+	batch_size: usize,
+) -> HashMap<AccountId, NoncePool<Point<Chain>>> {
+	let mut preprocessing_ceremony = PreprocessingCeremonyRunner::<Chain>::new(
+		key_data,
+		batch_start,
+		batch_size,
+		Rng::from_seed(DEFAULT_SIGNING_SEED),
+	);
+	let stage_1_messages = preprocessing_ceremony.request().await;
+	let messages = preprocessing_ceremony
+		.run_stage::<signing::VerifyPreprocessComm2<Point<Chain>>, _, _>(stage_1_messages)
+		.await;
+	preprocessing_ceremony.distribute_messages(messages).await;
+
+	preprocessing_ceremony
+		.complete()
+		.into_iter()
+		.map(|(account_id, batch)| {
+			let mut nonce_pool = NoncePool::default();
+			nonce_pool.insert(batch);
+			(account_id, nonce_pool)
+		})
+		.collect()
+}
+
 pub async fn new_signing_ceremony<Chain: ChainSigning>() -> (
 	SigningCeremonyRunner<Chain>,
 	HashMap<AccountId, Node<SigningCeremony<Chain::CryptoScheme>, Chain>>,
@@ -139,6 +139,16 @@ pub trait MultisigClientApi<C: CryptoScheme> {
 		signing_info: Vec<(KeyId, C::SigningPayload)>,
 	) -> BoxFuture<'_, Result<Vec<C::Signature>, (BTreeSet<AccountId>, SigningFailureReason)>>;
 
+	/// Used instead of `initiate_signing` for signing ceremonies that we are not participating
+	/// in. Besides updating the latest ceremony id, this lets all key holders track the same
+	/// keys and signers, which nonces are preprocessed for.
+	fn observe_signing(
+		&self,
+		ceremony_id: CeremonyId,
+		signers: BTreeSet<AccountId>,
+		key_ids: Vec<KeyId>,
+	);
+
 	fn update_latest_ceremony_id(&self, ceremony_id: CeremonyId);
 }
 
@@ -156,6 +166,8 @@ where
 {
 	Keygen(KeygenRequestDetails<C>),
 	Sign(SigningRequestDetails<C>),
+	/// A signing ceremony that we are not participating in
+	ObserveSigning(ObservedSigningDetails<C>),
 }
 
 #[derive(Debug)]
@@ -179,6 +191,12 @@ where
 	pub result_sender: CeremonyResultSender<SigningCeremony<C>>,
 }
 
+#[derive(Debug)]
+pub struct ObservedSigningDetails<C: CryptoScheme> {
+	pub participants: BTreeSet<AccountId>,
+	pub keys: Vec<KeygenResultInfo<C>>,
+}
+
 /// Multisig client acts as the frontend for the multisig functionality, delegating
 /// the actual signing to "Ceremony Manager". It is additionally responsible for
 /// persistently storing generated keys and providing them to the signing ceremonies.
@@ -383,6 +401,34 @@ impl<C: ChainSigning, KeyStore: KeyStoreAPI<C>> MultisigClientApi<C::CryptoSchem
 		}
 	}
 
+	fn observe_signing(
+		&self,
+		ceremony_id: CeremonyId,
+		signers: BTreeSet<AccountId>,
+		key_ids: Vec<KeyId>,
+	) {
+		let keys = {
+			let key_store = self.key_store.lock().unwrap();
+			key_ids
+				.iter()
+				.map(|key_id| key_store.get_key(key_id))
+				.collect::<Option<Vec<_>>>()
+		};
+
+		// If we don't hold the keys, we can only track the ceremony id
+		self.ceremony_request_sender
+			.send(CeremonyRequest {
+				ceremony_id,
+				details: keys.map(|keys| {
+					CeremonyRequestDetails::ObserveSigning(ObservedSigningDetails {
+						participants: signers,
+						keys,
+					})
+				}),
+			})
+			.unwrap();
+	}
+
 	fn update_latest_ceremony_id(&self, ceremony_id: CeremonyId) {
 		self.ceremony_request_sender
 			.send(CeremonyRequest { ceremony_id, details: None })
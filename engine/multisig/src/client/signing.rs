@@ -1,3 +1,5 @@
+pub mod nonce_pool;
+mod preprocessing_stages;
 mod signing_data;
 mod signing_detail;
 mod signing_stages;
@@ -12,11 +14,13 @@ use crate::CryptoScheme;
 use super::common::KeygenResult;
 
 pub use signing_data::{
-	Comm1, LocalSig3, LocalSig3Inner, SigningCommitment, SigningData, VerifyComm2, VerifyLocalSig4,
+	Comm1, Comm1AndLocalSig, LocalSig3, LocalSig3Inner, PreprocessComm1, SigningCommitment,
+	SigningData, VerifyComm2, VerifyLocalSig4, VerifyPreprocessComm2,
 };
 
 pub use signing_detail::generate_schnorr_response;
 
+pub use preprocessing_stages::AwaitPreprocessedCommitments1;
 pub use signing_stages::AwaitCommitments1;
 
 #[cfg(test)]
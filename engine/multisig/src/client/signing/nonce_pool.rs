@@ -0,0 +1,311 @@
+//! Nonces that are generated and distributed ahead of time (see "Preprocess" in Section 5.3 of
+//! <https://eprint.iacr.org/2020/852.pdf>), which allows signing ceremonies to complete once the
+//! commitments and local signatures have been verified, two rounds instead of four.
+//!
+//! Preprocessing ceremonies are run for every key that has recently been signed with, among the
+//! signers of the recent ceremonies with that key (the signing set), each one producing a batch of
+//! nonces for a fixed range of (future) ceremony ids. Keys that share a ceremony manager (e.g.
+//! Ethereum and Arbitrum keys) each get their own batches, since we can't know in advance which
+//! key a ceremony id will be used with. A signing ceremony may only use the slot that corresponds
+//! to its own ceremony id, and the secret nonce is removed from the pool when it is taken, which
+//! ensures that a nonce is never used twice. Secret nonces are never persisted, so restarting the
+//! node invalidates all of them.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use cf_primitives::{AuthorityCount, CeremonyId};
+use zeroize::Zeroize;
+
+use crate::crypto::ECPoint;
+
+use super::{signing_detail::SecretNoncePair, SigningCommitment};
+
+/// The largest batch of nonces that can be generated by a single preprocessing ceremony
+pub const MAX_PREPROCESSING_BATCH_SIZE: usize = 64;
+
+/// Preprocessing ceremonies are not requested by the State Chain, so we keep their ids
+/// separate from the ids of regular ceremonies by setting the highest bit.
+const PREPROCESSING_CEREMONY_ID_FLAG: CeremonyId = 1 << 63;
+
+/// The lowest bits of a preprocessing ceremony id hold the index of the key (among the keys
+/// preprocessed for at the same time), so that each key's ceremony has its own id
+const PREPROCESSING_KEY_INDEX_BITS: u32 = 3;
+
+/// The most keys that nonces can be preprocessed for at the same time
+pub const MAX_PREPROCESSING_KEYS: usize = 1 << PREPROCESSING_KEY_INDEX_BITS;
+
+/// The id of the ceremony that generates the batch of nonces starting at `batch_start` for
+/// the key with the given index
+pub fn preprocessing_ceremony_id(batch_start: CeremonyId, key_index: usize) -> CeremonyId {
+	assert!(key_index < MAX_PREPROCESSING_KEYS);
+	PREPROCESSING_CEREMONY_ID_FLAG |
+		batch_start << PREPROCESSING_KEY_INDEX_BITS |
+		key_index as CeremonyId
+}
+
+/// Returns the start of the batch if `ceremony_id` is the id of a preprocessing ceremony
+pub fn preprocessing_batch_start(ceremony_id: CeremonyId) -> Option<CeremonyId> {
+	(ceremony_id & PREPROCESSING_CEREMONY_ID_FLAG != 0)
+		.then_some((ceremony_id & !PREPROCESSING_CEREMONY_ID_FLAG) >> PREPROCESSING_KEY_INDEX_BITS)
+}
+
+/// Nonces for the ceremony ids in `batch_start..batch_start + batch_size`, as agreed
+/// on by the signing set during a preprocessing ceremony
+pub struct PreprocessedBatch<P: ECPoint> {
+	/// The aggregate key the nonces can be used with
+	key: P,
+	batch_start: CeremonyId,
+	/// Our secret nonces, which are removed from the batch once used
+	own_nonces: Vec<Option<Box<SecretNoncePair<P>>>>,
+	/// Commitments of the signing set (including our own) for every slot in the batch
+	commitments: BTreeMap<AuthorityCount, Vec<SigningCommitment<P>>>,
+}
+
+impl<P: ECPoint> PreprocessedBatch<P> {
+	pub fn new(
+		key: P,
+		batch_start: CeremonyId,
+		own_nonces: Vec<Box<SecretNoncePair<P>>>,
+		commitments: BTreeMap<AuthorityCount, Vec<SigningCommitment<P>>>,
+	) -> Self {
+		assert!(commitments.values().all(|comms| comms.len() == own_nonces.len()));
+
+		PreprocessedBatch {
+			key,
+			batch_start,
+			own_nonces: own_nonces.into_iter().map(Some).collect(),
+			commitments,
+		}
+	}
+
+	pub fn key(&self) -> &P {
+		&self.key
+	}
+
+	pub fn batch_start(&self) -> CeremonyId {
+		self.batch_start
+	}
+
+	/// The last ceremony id the batch has a slot for
+	fn last_ceremony_id(&self) -> CeremonyId {
+		self.batch_start + self.own_nonces.len() as CeremonyId - 1
+	}
+}
+
+// Don't print the secret nonces
+impl<P: ECPoint> std::fmt::Debug for PreprocessedBatch<P> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("PreprocessedBatch")
+			.field("key", &self.key)
+			.field("batch_start", &self.batch_start)
+			.field("batch_size", &self.own_nonces.len())
+			.finish()
+	}
+}
+
+impl<P: ECPoint> Drop for PreprocessedBatch<P> {
+	fn drop(&mut self) {
+		for nonce in self.own_nonces.iter_mut().flatten() {
+			nonce.zeroize();
+		}
+	}
+}
+
+/// Our secret nonce for a single signing ceremony along with the commitments
+/// of all signers of that ceremony
+pub struct PreprocessedNonce<P: ECPoint> {
+	pub nonce: Box<SecretNoncePair<P>>,
+	pub commitments: BTreeMap<AuthorityCount, SigningCommitment<P>>,
+}
+
+impl<P: ECPoint> Drop for PreprocessedNonce<P> {
+	fn drop(&mut self) {
+		self.nonce.zeroize();
+	}
+}
+
+/// Preprocessed nonces available for upcoming signing ceremonies, indexed by the key they can
+/// be used with and the start of the batch they belong to
+pub struct NoncePool<P: ECPoint> {
+	batches: BTreeMap<P, BTreeMap<CeremonyId, PreprocessedBatch<P>>>,
+}
+
+impl<P: ECPoint> Default for NoncePool<P> {
+	fn default() -> Self {
+		NoncePool { batches: BTreeMap::new() }
+	}
+}
+
+impl<P: ECPoint> NoncePool<P> {
+	pub fn insert(&mut self, batch: PreprocessedBatch<P>) {
+		self.batches.entry(batch.key).or_default().insert(batch.batch_start, batch);
+	}
+
+	pub fn contains(&self, key: &P, batch_start: CeremonyId) -> bool {
+		self.batches
+			.get(key)
+			.map_or(false, |batches| batches.contains_key(&batch_start))
+	}
+
+	/// Take our nonce for the ceremony with the given id, provided that we have the commitments
+	/// of all signers for it. The nonce is removed from the pool either way, so it can never be
+	/// used again.
+	pub fn take(
+		&mut self,
+		key: &P,
+		ceremony_id: CeremonyId,
+		signers: &BTreeSet<AuthorityCount>,
+	) -> Option<PreprocessedNonce<P>> {
+		let (_, batch) = self.batches.get_mut(key)?.range_mut(..=ceremony_id).next_back()?;
+
+		let slot = usize::try_from(ceremony_id - batch.batch_start).ok()?;
+		let mut nonce = batch.own_nonces.get_mut(slot)?.take()?;
+
+		match signers
+			.iter()
+			.map(|idx| batch.commitments.get(idx).map(|comms| (*idx, comms[slot].clone())))
+			.collect::<Option<BTreeMap<_, _>>>()
+		{
+			Some(commitments) => Some(PreprocessedNonce { nonce, commitments }),
+			None => {
+				nonce.zeroize();
+				None
+			},
+		}
+	}
+
+	/// Remove the batches that only have slots for ceremony ids up to
+	/// (and including) `latest_ceremony_id`, as they can no longer be used
+	/// (and including) `latest_ceremony_id`, as they can no longer be used. This also removes
+	/// the batches of keys that are no longer signed with (e.g. after key rotation), as no
+	/// new batches are generated for them.
+	pub fn remove_expired(&mut self, latest_ceremony_id: CeremonyId) {
+		self.batches.retain(|_, batches| {
+			batches.retain(|_, batch| batch.last_ceremony_id() > latest_ceremony_id);
+			!batches.is_empty()
+		});
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.batches.is_empty()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::{crypto::eth::Point, Rng};
+	use rand::SeedableRng;
+
+	const BATCH_SIZE: usize = 4;
+	const BATCH_START: CeremonyId = 8;
+
+	fn new_batch(
+		rng: &mut Rng,
+		key: Point,
+		parties: &BTreeSet<AuthorityCount>,
+	) -> PreprocessedBatch<Point> {
+		let own_nonces: Vec<_> =
+			(0..BATCH_SIZE).map(|_| SecretNoncePair::sample_random(rng)).collect();
+
+		let commitments = parties
+			.iter()
+			.map(|idx| {
+				let comms = own_nonces
+					.iter()
+					.map(|nonce| SigningCommitment { d: nonce.d_pub, e: nonce.e_pub })
+					.collect();
+				(*idx, comms)
+			})
+			.collect();
+
+		PreprocessedBatch::new(key, BATCH_START, own_nonces, commitments)
+	}
+
+	#[test]
+	fn preprocessing_ceremony_ids_are_distinct_from_regular_ids() {
+		assert_eq!(preprocessing_batch_start(BATCH_START), None);
+		for key_index in 0..MAX_PREPROCESSING_KEYS {
+			assert_eq!(
+				preprocessing_batch_start(preprocessing_ceremony_id(BATCH_START, key_index)),
+				Some(BATCH_START)
+			);
+		}
+		assert_ne!(
+			preprocessing_ceremony_id(BATCH_START, 0),
+			preprocessing_ceremony_id(BATCH_START, 1)
+		);
+	}
+
+	#[test]
+	fn nonces_can_only_be_taken_once_and_for_their_own_slot() {
+		let mut rng = Rng::from_seed([0; 32]);
+		let key = Point::random(&mut rng);
+		let parties = BTreeSet::from_iter([1, 2, 3]);
+
+		let mut pool = NoncePool::default();
+		pool.insert(new_batch(&mut rng, key, &parties));
+
+		let signers = BTreeSet::from_iter([1, 3]);
+
+		// Outside of the batch's range
+		assert!(pool.take(&key, BATCH_START - 1, &signers).is_none());
+		assert!(pool.take(&key, BATCH_START + BATCH_SIZE as CeremonyId, &signers).is_none());
+
+		// A different key
+		assert!(pool.take(&Point::random(&mut rng), BATCH_START, &signers).is_none());
+
+		let nonce = pool.take(&key, BATCH_START + 1, &signers).unwrap();
+		assert_eq!(nonce.commitments.keys().cloned().collect::<BTreeSet<_>>(), signers);
+		assert!(pool.take(&key, BATCH_START + 1, &signers).is_none());
+
+		// A signer that didn't take part in preprocessing makes the slot unusable
+		assert!(pool.take(&key, BATCH_START + 2, &BTreeSet::from_iter([1, 4])).is_none());
+		assert!(pool.take(&key, BATCH_START + 2, &signers).is_none());
+
+		assert!(pool.take(&key, BATCH_START + 3, &signers).is_some());
+	}
+
+	#[test]
+	fn batches_are_invalidated_by_expiry() {
+		let mut rng = Rng::from_seed([0; 32]);
+		let key = Point::random(&mut rng);
+		let parties = BTreeSet::from_iter([1, 2, 3]);
+
+		let mut pool = NoncePool::default();
+		pool.insert(new_batch(&mut rng, key, &parties));
+		assert!(pool.contains(&key, BATCH_START));
+		assert!(!pool.contains(&Point::random(&mut rng), BATCH_START));
+
+		// The batch is still useful while there are ceremony ids left in its range
+		pool.remove_expired(BATCH_START + BATCH_SIZE as CeremonyId - 2);
+		assert!(pool.contains(&key, BATCH_START));
+		pool.remove_expired(BATCH_START + BATCH_SIZE as CeremonyId - 1);
+		assert!(pool.is_empty());
+	}
+
+	#[test]
+	fn batches_of_different_keys_are_kept_apart() {
+		let mut rng = Rng::from_seed([0; 32]);
+		let eth_key = Point::random(&mut rng);
+		let arb_key = Point::random(&mut rng);
+		let parties = BTreeSet::from_iter([1, 2, 3]);
+
+		let mut pool = NoncePool::default();
+		pool.insert(new_batch(&mut rng, eth_key, &parties));
+		pool.insert(new_batch(&mut rng, arb_key, &parties));
+		assert!(pool.contains(&eth_key, BATCH_START));
+		assert!(pool.contains(&arb_key, BATCH_START));
+
+		// Ceremonies alternating between the keys each use their own key's batch
+		for slot in 0..BATCH_SIZE as CeremonyId {
+			let key = if slot % 2 == 0 { eth_key } else { arb_key };
+			assert!(pool.take(&key, BATCH_START + slot, &parties).is_some());
+		}
+
+		// A slot is used by one key only, so the other key's nonce for it is left unused
+		assert!(pool.take(&arb_key, BATCH_START, &parties).is_some());
+		assert!(pool.take(&eth_key, BATCH_START, &parties).is_none());
+	}
+}
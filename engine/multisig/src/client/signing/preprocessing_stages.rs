@@ -0,0 +1,166 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use async_trait::async_trait;
+use cf_primitives::{AuthorityCount, CeremonyId};
+use tracing::{debug, warn};
+
+use crate::{
+	client::{
+		ceremony_manager::PreprocessingCeremony,
+		common::{
+			broadcast::{
+				verify_broadcasts_non_blocking, BroadcastStage, BroadcastStageProcessor, DataToSend,
+			},
+			try_deserialize, CeremonyCommon, DelayDeserialization, SigningFailureReason,
+			SigningStageName, StageResult,
+		},
+	},
+	crypto::CryptoScheme,
+};
+
+use super::{
+	nonce_pool::PreprocessedBatch,
+	signing_data::{PreprocessComm1, PreprocessComm1Inner, VerifyPreprocessComm2},
+	signing_detail::SecretNoncePair,
+	SigningCommitment,
+};
+
+type PreprocessingStageResult<Crypto> = StageResult<PreprocessingCeremony<Crypto>>;
+
+/// Stage 1: Generate a batch of secret nonce pairs and broadcast their commitments
+pub struct AwaitPreprocessedCommitments1<Crypto: CryptoScheme> {
+	common: CeremonyCommon,
+	/// The aggregate key the nonces are going to be used with
+	key: Crypto::Point,
+	batch_start: CeremonyId,
+	nonces: Vec<Box<SecretNoncePair<Crypto::Point>>>,
+}
+
+impl<Crypto: CryptoScheme> AwaitPreprocessedCommitments1<Crypto> {
+	pub fn new(
+		mut common: CeremonyCommon,
+		key: Crypto::Point,
+		batch_start: CeremonyId,
+		batch_size: usize,
+	) -> Self {
+		let nonces = (0..batch_size)
+			.map(|_| SecretNoncePair::sample_random(&mut common.rng))
+			.collect();
+
+		AwaitPreprocessedCommitments1 { common, key, batch_start, nonces }
+	}
+}
+
+derive_display_as_type_name!(AwaitPreprocessedCommitments1<Crypto: CryptoScheme>);
+
+#[async_trait]
+impl<Crypto: CryptoScheme> BroadcastStageProcessor<PreprocessingCeremony<Crypto>>
+	for AwaitPreprocessedCommitments1<Crypto>
+{
+	type Message = PreprocessComm1<Crypto::Point>;
+	const NAME: SigningStageName = SigningStageName::AwaitPreprocessedCommitments1;
+
+	fn init(&mut self) -> DataToSend<Self::Message> {
+		let comm1 = PreprocessComm1Inner(
+			self.nonces
+				.iter()
+				.map(|nonce| SigningCommitment::<Crypto::Point> { d: nonce.d_pub, e: nonce.e_pub })
+				.collect(),
+		);
+		DataToSend::Broadcast(DelayDeserialization::new(&comm1))
+	}
+
+	async fn process(
+		self,
+		messages: BTreeMap<AuthorityCount, Option<Self::Message>>,
+	) -> PreprocessingStageResult<Crypto> {
+		let processor = VerifyPreprocessedCommitmentsBroadcast2::<Crypto> {
+			common: self.common.clone(),
+			key: self.key,
+			batch_start: self.batch_start,
+			nonces: self.nonces,
+			commitments: messages,
+		};
+
+		StageResult::NextStage(Box::new(BroadcastStage::new(processor, self.common)))
+	}
+}
+
+/// Stage 2: Verifying data broadcast during stage 1
+struct VerifyPreprocessedCommitmentsBroadcast2<Crypto: CryptoScheme> {
+	common: CeremonyCommon,
+	key: Crypto::Point,
+	batch_start: CeremonyId,
+	nonces: Vec<Box<SecretNoncePair<Crypto::Point>>>,
+	commitments: BTreeMap<AuthorityCount, Option<PreprocessComm1<Crypto::Point>>>,
+}
+
+derive_display_as_type_name!(VerifyPreprocessedCommitmentsBroadcast2<Crypto: CryptoScheme>);
+
+#[async_trait]
+impl<Crypto: CryptoScheme> BroadcastStageProcessor<PreprocessingCeremony<Crypto>>
+	for VerifyPreprocessedCommitmentsBroadcast2<Crypto>
+{
+	type Message = VerifyPreprocessComm2<Crypto::Point>;
+	const NAME: SigningStageName = SigningStageName::VerifyPreprocessedCommitmentsBroadcast2;
+
+	fn init(&mut self) -> DataToSend<Self::Message> {
+		DataToSend::Broadcast(VerifyPreprocessComm2 { data: self.commitments.clone() })
+	}
+
+	/// Verify that all commitments have been broadcast correctly, after which the batch can be
+	/// used by upcoming signing ceremonies. The batch is only produced if every party of the
+	/// signing set took part, so all of us end up with a batch for the same parties (or none).
+	async fn process(
+		self,
+		messages: BTreeMap<AuthorityCount, Option<Self::Message>>,
+	) -> PreprocessingStageResult<Crypto> {
+		let verified_commitments = match verify_broadcasts_non_blocking(messages).await {
+			Ok(comms) => comms,
+			Err((reported_parties, abort_reason)) =>
+				return StageResult::Error(
+					reported_parties,
+					SigningFailureReason::BroadcastFailure(abort_reason, Self::NAME),
+				),
+		};
+
+		let verified_commitments = match try_deserialize(verified_commitments) {
+			Ok(res) => res,
+			Err(bad_parties) =>
+				return StageResult::Error(bad_parties, SigningFailureReason::DeserializationError),
+		};
+
+		let bad_parties: BTreeSet<_> = verified_commitments
+			.iter()
+			.filter_map(|(party_idx, commitments)| {
+				if commitments.0.len() != self.nonces.len() {
+					warn!(
+						from_id = self.common.validator_mapping.get_id(*party_idx).to_string(),
+						"Unexpected number of preprocessed commitments from party: {} (expected: {})",
+						commitments.0.len(),
+						self.nonces.len(),
+					);
+					Some(*party_idx)
+				} else {
+					None
+				}
+			})
+			.collect();
+
+		if !bad_parties.is_empty() {
+			return StageResult::Error(bad_parties, SigningFailureReason::InvalidNumberOfPayloads)
+		}
+
+		debug!("{} is successful", Self::NAME);
+
+		StageResult::Done(PreprocessedBatch::new(
+			self.key,
+			self.batch_start,
+			self.nonces,
+			verified_commitments
+				.into_iter()
+				.map(|(party_idx, commitments)| (party_idx, commitments.0))
+				.collect(),
+		))
+	}
+}
@@ -12,6 +12,8 @@ use crate::{
 	ChainSigning, ChainTag, MAX_BTC_SIGNING_PAYLOADS,
 };
 
+use super::nonce_pool::MAX_PREPROCESSING_BATCH_SIZE;
+
 #[cfg(test)]
 pub use tests::{gen_signing_data_stage1, gen_signing_data_stage2, gen_signing_data_stage4};
 
@@ -101,6 +103,101 @@ pub type VerifyComm2<P> = BroadcastVerificationMessage<Comm1<P>>;
 pub type LocalSig3<P> = DelayDeserialization<LocalSig3Inner<P>>;
 pub type VerifyLocalSig4<P> = BroadcastVerificationMessage<LocalSig3<P>>;
 
+/// Commitments to a batch of nonces that are generated ahead of time, to be
+/// consumed by future signing ceremonies (one commitment per slot in the batch)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PreprocessComm1Inner<P: ECPoint>(#[serde(bound = "")] pub Vec<SigningCommitment<P>>);
+
+pub type PreprocessComm1<P> = DelayDeserialization<PreprocessComm1Inner<P>>;
+pub type VerifyPreprocessComm2<P> = BroadcastVerificationMessage<PreprocessComm1<P>>;
+
+/// Data sent during the first stage of signing: fresh commitments and, if we
+/// hold preprocessed nonces for the ceremony, our local signature generated
+/// with them. Once the local signatures have been verified to be broadcast
+/// consistently, they let everyone finish the ceremony after the second stage,
+/// while the fresh commitments allow falling back to the full protocol if that
+/// is not possible.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Comm1AndLocalSig<P: ECPoint> {
+	#[serde(bound = "")]
+	pub comm1: Comm1<P>,
+	#[serde(bound = "")]
+	pub local_sig: Option<LocalSig3<P>>,
+}
+
+// Without a local signature the data is sent as `CommStage1`, so nodes that
+// don't use preprocessed nonces keep sending exactly what they used to.
+impl<P: ECPoint> From<Comm1AndLocalSig<P>> for SigningData<P> {
+	fn from(data: Comm1AndLocalSig<P>) -> Self {
+		if data.local_sig.is_some() {
+			SigningData::CommAndLocalSigStage1(data)
+		} else {
+			SigningData::CommStage1(data.comm1)
+		}
+	}
+}
+
+impl<P: ECPoint> TryFrom<SigningData<P>> for Comm1AndLocalSig<P> {
+	type Error = SigningData<P>;
+
+	fn try_from(data: SigningData<P>) -> Result<Self, Self::Error> {
+		match data {
+			SigningData::CommStage1(comm1) => Ok(Comm1AndLocalSig { comm1, local_sig: None }),
+			SigningData::CommAndLocalSigStage1(data) => Ok(data),
+			data => Err(data),
+		}
+	}
+}
+
+impl<P: ECPoint> Comm1AndLocalSig<P> {
+	fn is_data_size_valid(&self, num_of_payloads: usize) -> bool {
+		self.comm1.payload.len() <= max_signing_commitments_size(num_of_payloads) &&
+			self.local_sig.as_ref().map_or(true, |local_sig| {
+				local_sig.payload.len() <= max_local_sigs_size(num_of_payloads)
+			})
+	}
+}
+
+pub type VerifyCommAndLocalSig2<P> = BroadcastVerificationMessage<Comm1AndLocalSig<P>>;
+
+// As with the first stage, the data is sent as `BroadcastVerificationStage2`
+// if nobody sent us a local signature.
+impl<P: ECPoint> From<VerifyCommAndLocalSig2<P>> for SigningData<P> {
+	fn from(message: VerifyCommAndLocalSig2<P>) -> Self {
+		if message.data.values().flatten().any(|data| data.local_sig.is_some()) {
+			SigningData::VerifyCommAndLocalSigStage2(message)
+		} else {
+			SigningData::BroadcastVerificationStage2(VerifyComm2 {
+				data: message
+					.data
+					.into_iter()
+					.map(|(idx, data)| (idx, data.map(|data| data.comm1)))
+					.collect(),
+			})
+		}
+	}
+}
+
+impl<P: ECPoint> TryFrom<SigningData<P>> for VerifyCommAndLocalSig2<P> {
+	type Error = SigningData<P>;
+
+	fn try_from(data: SigningData<P>) -> Result<Self, Self::Error> {
+		match data {
+			SigningData::BroadcastVerificationStage2(message) => Ok(VerifyCommAndLocalSig2 {
+				data: message
+					.data
+					.into_iter()
+					.map(|(idx, comm1)| {
+						(idx, comm1.map(|comm1| Comm1AndLocalSig { comm1, local_sig: None }))
+					})
+					.collect(),
+			}),
+			SigningData::VerifyCommAndLocalSigStage2(message) => Ok(message),
+			data => Err(data),
+		}
+	}
+}
+
 /// Signature (the "response" part) shard generated by a single party
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct LocalSig3Inner<P: ECPoint> {
@@ -119,17 +216,33 @@ pub enum SigningData<P: ECPoint> {
 	LocalSigStage3(LocalSig3<P>),
 	#[serde(bound = "")]
 	VerifyLocalSigsStage4(VerifyLocalSig4<P>),
+	// NOTE: new variants must be added at the end to keep
+	// the serialization of the existing ones unchanged
+	#[serde(bound = "")]
+	CommAndLocalSigStage1(Comm1AndLocalSig<P>),
+	#[serde(bound = "")]
+	PreprocessCommStage1(PreprocessComm1<P>),
+	#[serde(bound = "")]
+	PreprocessBroadcastVerificationStage2(VerifyPreprocessComm2<P>),
+	#[serde(bound = "")]
+	VerifyCommAndLocalSigStage2(VerifyCommAndLocalSig2<P>),
 }
 
 derive_impls_for_enum_variants!(impl<P: ECPoint> for Comm1<P>, SigningData::CommStage1, SigningData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyComm2<P>, SigningData::BroadcastVerificationStage2, SigningData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for LocalSig3<P>, SigningData::LocalSigStage3, SigningData<P>);
 derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyLocalSig4<P>, SigningData::VerifyLocalSigsStage4, SigningData<P>);
+derive_impls_for_enum_variants!(impl<P: ECPoint> for PreprocessComm1<P>, SigningData::PreprocessCommStage1, SigningData<P>);
+derive_impls_for_enum_variants!(impl<P: ECPoint> for VerifyPreprocessComm2<P>, SigningData::PreprocessBroadcastVerificationStage2, SigningData<P>);
 
 derive_display_as_type_name!(Comm1<P: ECPoint>);
 derive_display_as_type_name!(VerifyComm2<P: ECPoint>);
 derive_display_as_type_name!(LocalSig3<P: ECPoint>);
 derive_display_as_type_name!(VerifyLocalSig4<P: ECPoint>);
+derive_display_as_type_name!(Comm1AndLocalSig<P: ECPoint>);
+derive_display_as_type_name!(PreprocessComm1<P: ECPoint>);
+derive_display_as_type_name!(VerifyPreprocessComm2<P: ECPoint>);
+derive_display_as_type_name!(VerifyCommAndLocalSig2<P: ECPoint>);
 
 impl<P: ECPoint> Display for SigningData<P> {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -138,6 +251,10 @@ impl<P: ECPoint> Display for SigningData<P> {
 			SigningData::BroadcastVerificationStage2(x) => x.to_string(),
 			SigningData::LocalSigStage3(x) => x.to_string(),
 			SigningData::VerifyLocalSigsStage4(x) => x.to_string(),
+			SigningData::CommAndLocalSigStage1(x) => x.to_string(),
+			SigningData::PreprocessCommStage1(x) => x.to_string(),
+			SigningData::PreprocessBroadcastVerificationStage2(x) => x.to_string(),
+			SigningData::VerifyCommAndLocalSigStage2(x) => x.to_string(),
 		};
 		write!(f, "SigningData({inner})")
 	}
@@ -151,42 +268,70 @@ impl<P: ECPoint> PreProcessStageDataCheck<SigningStageName> for SigningData<P> {
 	) -> bool {
 		let num_of_parties = num_of_parties as usize;
 		match self {
-			SigningData::CommStage1(_) => self.is_initial_stage_data_size_valid::<Chain>(),
+			SigningData::CommStage1(_) | SigningData::CommAndLocalSigStage1(_) =>
+				self.is_initial_stage_data_size_valid::<Chain>(),
 			// It is safe to unwrap after the first stage because the number of payloads is always
 			// known from then on (only for signing ceremonies)
 			SigningData::BroadcastVerificationStage2(message) => message.is_data_size_valid(
 				num_of_parties,
 				max_signing_commitments_size(num_of_payloads.unwrap()),
 			),
+			SigningData::VerifyCommAndLocalSigStage2(message) =>
+				message.data.len() == num_of_parties &&
+					message
+						.data
+						.values()
+						.flatten()
+						.all(|data| data.is_data_size_valid(num_of_payloads.unwrap())),
 			SigningData::LocalSigStage3(message) =>
 				message.payload.len() <= max_local_sigs_size(num_of_payloads.unwrap()),
 
 			SigningData::VerifyLocalSigsStage4(message) => message
 				.is_data_size_valid(num_of_parties, max_local_sigs_size(num_of_payloads.unwrap())),
+			// The number of "payloads" of a preprocessing ceremony is the size of the batch
+			SigningData::PreprocessCommStage1(message) =>
+				message.payload.len() <= max_signing_commitments_size(num_of_payloads.unwrap()),
+			SigningData::PreprocessBroadcastVerificationStage2(message) => message
+				.is_data_size_valid(
+					num_of_parties,
+					max_signing_commitments_size(num_of_payloads.unwrap()),
+				),
 		}
 	}
 
 	fn is_initial_stage_data_size_valid<Chain: ChainSigning>(&self) -> bool {
+		// At this stage we may not know the number of payloads, so we use a maximum
+		let max_payloads = match Chain::CHAIN_TAG {
+			ChainTag::Ethereum | ChainTag::Polkadot | ChainTag::Ed25519 => 1,
+			ChainTag::Bitcoin => MAX_BTC_SIGNING_PAYLOADS,
+		};
 		match self {
-			SigningData::CommStage1(message) => match Chain::CHAIN_TAG {
-				ChainTag::Ethereum | ChainTag::Polkadot | ChainTag::Ed25519 =>
-					message.payload.len() <= max_signing_commitments_size(1),
-				ChainTag::Bitcoin =>
-				// At this stage we may not know the number of payloads, so we use a maximum
-					message.payload.len() <= max_signing_commitments_size(MAX_BTC_SIGNING_PAYLOADS),
-			},
+			SigningData::CommStage1(message) =>
+				message.payload.len() <= max_signing_commitments_size(max_payloads),
+			SigningData::CommAndLocalSigStage1(message) => message.is_data_size_valid(max_payloads),
+			SigningData::PreprocessCommStage1(message) =>
+				message.payload.len() <= max_signing_commitments_size(MAX_PREPROCESSING_BATCH_SIZE),
 			_ => panic!("unexpected stage"),
 		}
 	}
 
 	fn should_delay_unauthorised(&self) -> bool {
-		matches!(self, SigningData::CommStage1(_))
+		matches!(
+			self,
+			SigningData::CommStage1(_) |
+				SigningData::CommAndLocalSigStage1(_) |
+				SigningData::PreprocessCommStage1(_)
+		)
 	}
 
 	fn should_delay(stage_name: SigningStageName, message: &Self) -> bool {
 		match stage_name {
 			SigningStageName::AwaitCommitments1 => {
-				matches!(message, SigningData::BroadcastVerificationStage2(_))
+				matches!(
+					message,
+					SigningData::BroadcastVerificationStage2(_) |
+						SigningData::VerifyCommAndLocalSigStage2(_)
+				)
 			},
 			SigningStageName::VerifyCommitmentsBroadcast2 => {
 				matches!(message, SigningData::LocalSigStage3(_))
@@ -198,6 +343,13 @@ impl<P: ECPoint> PreProcessStageDataCheck<SigningStageName> for SigningData<P> {
 				// Last stage, nothing to delay
 				false
 			},
+			SigningStageName::AwaitPreprocessedCommitments1 => {
+				matches!(message, SigningData::PreprocessBroadcastVerificationStage2(_))
+			},
+			SigningStageName::VerifyPreprocessedCommitmentsBroadcast2 => {
+				// Last stage, nothing to delay
+				false
+			},
 		}
 	}
 }
@@ -215,6 +367,7 @@ mod tests {
 	};
 
 	use rand::SeedableRng;
+	use std::collections::BTreeMap;
 
 	use super::*;
 
@@ -366,6 +519,63 @@ mod tests {
 		}
 	}
 
+	fn gen_preprocessing_data_stage1(number_of_commitments: usize) -> SigningData<Point> {
+		let mut rng = Rng::from_seed([0; 32]);
+		let comm1 = gen_dummy_signing_comm1::<Point>(&mut rng, number_of_commitments as u64)
+			.deserialize()
+			.unwrap();
+		SigningData::<Point>::PreprocessCommStage1(DelayDeserialization::new(
+			&PreprocessComm1Inner(comm1.0),
+		))
+	}
+
+	#[test]
+	fn check_data_size_preprocessing_stage1() {
+		const PARTIES: AuthorityCount = 4;
+		const BATCH_SIZE: usize = 16;
+
+		// Before the batch size is known, anything up to the maximum batch size is accepted
+		assert!(gen_preprocessing_data_stage1(MAX_PREPROCESSING_BATCH_SIZE)
+			.is_initial_stage_data_size_valid::<EthSigning>());
+		assert!(!gen_preprocessing_data_stage1(MAX_PREPROCESSING_BATCH_SIZE + 1)
+			.is_initial_stage_data_size_valid::<EthSigning>());
+
+		assert!(gen_preprocessing_data_stage1(BATCH_SIZE)
+			.is_data_size_valid::<EthSigning>(PARTIES, Some(BATCH_SIZE)));
+		assert!(!gen_preprocessing_data_stage1(BATCH_SIZE + 1)
+			.is_data_size_valid::<EthSigning>(PARTIES, Some(BATCH_SIZE)));
+	}
+
+	#[test]
+	fn should_delay_correct_data_for_preprocessing_stage() {
+		let stage_1_data = gen_preprocessing_data_stage1(1);
+		let stage_2_data = SigningData::<Point>::PreprocessBroadcastVerificationStage2(
+			BroadcastVerificationMessage {
+				data: BTreeMap::from_iter([(1, Some(stage_1_data.clone().try_into().unwrap()))]),
+			},
+		);
+
+		assert!(!SigningData::should_delay(
+			SigningStageName::AwaitPreprocessedCommitments1,
+			&stage_1_data
+		));
+		assert!(SigningData::should_delay(
+			SigningStageName::AwaitPreprocessedCommitments1,
+			&stage_2_data
+		));
+		assert!(!SigningData::should_delay(
+			SigningStageName::VerifyPreprocessedCommitmentsBroadcast2,
+			&stage_1_data
+		));
+		assert!(!SigningData::should_delay(
+			SigningStageName::VerifyPreprocessedCommitmentsBroadcast2,
+			&stage_2_data
+		));
+
+		// Preprocessing data is never processed by a signing ceremony
+		assert!(!SigningData::should_delay(SigningStageName::AwaitCommitments1, &stage_2_data));
+	}
+
 	#[test]
 	/// Check that each chain does not exceed an acceptable limit to the amount of ceremony data
 	/// that a single node can force us to store as delayed initial stage messages.
@@ -1,7 +1,8 @@
 //! The types and operations as discussed in <https://eprint.iacr.org/2020/852.pdf>.
 //! Comments in this file reference sections from this document.
 //! Note that unlike the protocol described in the document, we don't have a
-//! centralised signature aggregator and don't have a preprocessing stage.
+//! centralised signature aggregator, and the preprocessing stage is optional
+//! (see `nonce_pool`).
 use std::collections::{BTreeMap, BTreeSet};
 
 use cf_primitives::AuthorityCount;
@@ -6,6 +6,7 @@ use crate::{
 		ceremony_manager::SigningCeremony,
 		common::{try_deserialize, DelayDeserialization, SigningFailureReason, SigningStageName},
 		signing::{self, signing_data::LocalSig3Inner, PayloadAndKey},
+		KeygenResult,
 	},
 	crypto::CryptoScheme,
 };
@@ -26,7 +27,8 @@ use signing_detail::get_lagrange_coeff;
 use tracing::{debug, warn};
 
 use super::{
-	signing_data::{Comm1, LocalSig3, VerifyComm2, VerifyLocalSig4},
+	nonce_pool::PreprocessedNonce,
+	signing_data::{Comm1AndLocalSig, LocalSig3, VerifyCommAndLocalSig2, VerifyLocalSig4},
 	signing_detail::{NonceBinding, SchnorrCommitment},
 	SigningCommitment,
 };
@@ -45,15 +47,43 @@ pub struct AwaitCommitments1<Crypto: CryptoScheme> {
 	// copies on the stack when the data is moved. We can probably
 	// remove `Box` now that the items are stored in Vec
 	nonces: Vec<Box<SecretNoncePair<Crypto::Point>>>,
+	/// Set if we hold a preprocessed nonce for this ceremony, in which
+	/// case we try to complete the ceremony after the next stage
+	preprocessed: Option<PreprocessedSigningData<Crypto>>,
+}
+
+/// Preprocessed nonce (and the signature data derived from the
+/// commitments of all signers) for a single payload ceremony
+struct PreprocessedSigningData<Crypto: CryptoScheme> {
+	nonce: PreprocessedNonce<Crypto::Point>,
+	signature_data: DerivedSignatureData<Crypto>,
 }
 
 impl<Crypto: CryptoScheme> AwaitCommitments1<Crypto> {
-	pub fn new(mut common: CeremonyCommon, signing_common: SigningStateCommonInfo<Crypto>) -> Self {
+	pub fn new(
+		mut common: CeremonyCommon,
+		signing_common: SigningStateCommonInfo<Crypto>,
+		preprocessed_nonce: Option<PreprocessedNonce<Crypto::Point>>,
+	) -> Self {
 		let nonces = (0..signing_common.payload_count())
 			.map(|_| SecretNoncePair::sample_random(&mut common.rng))
 			.collect();
 
-		AwaitCommitments1 { common, signing_common, nonces }
+		// Only one nonce is preprocessed per ceremony, so ceremonies with
+		// multiple payloads always use the full protocol
+		let preprocessed = match (preprocessed_nonce, &signing_common.payloads_and_keys[..]) {
+			(Some(nonce), [PayloadAndKey { payload, .. }]) => Some(PreprocessedSigningData {
+				signature_data: derive_signature_data(
+					payload,
+					&nonce.commitments,
+					&common.all_idxs,
+				),
+				nonce,
+			}),
+			_ => None,
+		};
+
+		AwaitCommitments1 { common, signing_common, nonces, preprocessed }
 	}
 }
 
@@ -63,7 +93,7 @@ derive_display_as_type_name!(AwaitCommitments1<Crypto: CryptoScheme>);
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 	for AwaitCommitments1<Crypto>
 {
-	type Message = Comm1<Crypto::Point>;
+	type Message = Comm1AndLocalSig<Crypto::Point>;
 	const NAME: SigningStageName = SigningStageName::AwaitCommitments1;
 
 	fn init(&mut self) -> DataToSend<Self::Message> {
@@ -72,19 +102,50 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 			.iter()
 			.map(|nonce| SigningCommitment::<Crypto::Point> { d: nonce.d_pub, e: nonce.e_pub })
 			.collect();
-		DataToSend::Broadcast(DelayDeserialization::new(&comm1))
+
+		let local_sig = self.preprocessed.as_mut().map(|preprocessed| {
+			let PayloadAndKey { payload, key } = &self.signing_common.payloads_and_keys[0];
+			let signature_data = &preprocessed.signature_data;
+
+			let response = signing_detail::generate_local_sig::<Crypto>(
+				payload,
+				&key.key_share,
+				&preprocessed.nonce.nonce,
+				&signature_data.bindings,
+				signature_data.group_commitment,
+				self.common.own_idx,
+				&self.common.all_idxs,
+			);
+
+			use zeroize::Zeroize;
+
+			// The preprocessed nonce must never be used again
+			preprocessed.nonce.nonce.zeroize();
+
+			DelayDeserialization::new(&LocalSig3Inner::<Crypto::Point> {
+				responses: vec![response],
+			})
+		});
+
+		DataToSend::Broadcast(Comm1AndLocalSig {
+			comm1: DelayDeserialization::new(&comm1),
+			local_sig,
+		})
 	}
 
 	async fn process(
 		self,
 		messages: BTreeMap<AuthorityCount, Option<Self::Message>>,
 	) -> SigningStageResult<Crypto> {
-		// No verification is necessary here, just generating new stage
+		// No verification is necessary here, just generating new stage. Local signatures are
+		// only used once they have been verified to be broadcast consistently, so that all
+		// parties agree on whether the ceremony can be completed with them.
 
 		let processor = VerifyCommitmentsBroadcast2::<Crypto> {
 			common: self.common.clone(),
 			signing_common: self.signing_common,
 			nonces: self.nonces,
+			preprocessed: self.preprocessed,
 			commitments: messages,
 		};
 
@@ -102,12 +163,55 @@ struct VerifyCommitmentsBroadcast2<Crypto: CryptoScheme> {
 	signing_common: SigningStateCommonInfo<Crypto>,
 	// Our nonce pair generated in the previous stage
 	nonces: Vec<Box<SecretNoncePair<Crypto::Point>>>,
-	// Public nonce commitments collected in the previous stage
-	commitments: BTreeMap<AuthorityCount, Option<Comm1<Crypto::Point>>>,
+	preprocessed: Option<PreprocessedSigningData<Crypto>>,
+	// Public nonce commitments (and local signatures generated with
+	// preprocessed nonces) collected in the previous stage
+	commitments: BTreeMap<AuthorityCount, Option<Comm1AndLocalSig<Crypto::Point>>>,
 }
 
 derive_display_as_type_name!(VerifyCommitmentsBroadcast2<Crypto: CryptoScheme>);
 
+impl<Crypto: CryptoScheme> VerifyCommitmentsBroadcast2<Crypto> {
+	/// Try to aggregate the verified local signatures generated with preprocessed nonces. This
+	/// is only possible if all signers used the same preprocessed commitments and none of them
+	/// misbehaved. As all parties have the same verified data, they either all complete the
+	/// ceremony here or all continue with the full protocol.
+	fn try_aggregate_preprocessed(
+		&self,
+		verified_messages: &BTreeMap<AuthorityCount, Comm1AndLocalSig<Crypto::Point>>,
+	) -> Option<Crypto::Signature> {
+		let preprocessed = self.preprocessed.as_ref()?;
+
+		let responses = self
+			.common
+			.all_idxs
+			.iter()
+			.map(|idx| {
+				let local_sig = verified_messages.get(idx)?.local_sig.clone()?;
+				match &local_sig.deserialize().ok()?.responses[..] {
+					[response] => Some((*idx, response.clone())),
+					_ => None,
+				}
+			})
+			.collect::<Option<BTreeMap<_, _>>>()?;
+
+		let PayloadAndKey { payload, key } = &self.signing_common.payloads_and_keys[0];
+		let signature_data = &preprocessed.signature_data;
+
+		signing_detail::aggregate_signature::<Crypto>(
+			payload,
+			&self.common.all_idxs,
+			key.get_agg_public_key_point(),
+			&signer_public_keys(key, &self.common),
+			signature_data.group_commitment,
+			&signature_data.bound_commitments,
+			&responses,
+			&lagrange_coefficients::<Crypto>(&self.common.all_idxs),
+		)
+		.ok()
+	}
+}
+
 /// Data derived for a single payload from initial commitments
 pub struct DerivedSignatureData<C: CryptoScheme> {
 	group_commitment: SchnorrCommitment<C>,
@@ -115,11 +219,57 @@ pub struct DerivedSignatureData<C: CryptoScheme> {
 	bound_commitments: BTreeMap<AuthorityCount, SchnorrCommitment<C>>,
 }
 
+fn derive_signature_data<Crypto: CryptoScheme>(
+	payload: &Crypto::SigningPayload,
+	commitments: &BTreeMap<AuthorityCount, SigningCommitment<Crypto::Point>>,
+	all_idxs: &BTreeSet<AuthorityCount>,
+) -> DerivedSignatureData<Crypto> {
+	let bindings = signing_detail::generate_bindings::<Crypto>(payload, commitments, all_idxs);
+
+	let bound_commitments = commitments
+		.iter()
+		.map(|(idx, comm)| (*idx, comm.d + comm.e * bindings[idx].clone()))
+		.collect::<BTreeMap<_, _>>();
+
+	// Combine individual commitments into group (schnorr) commitment.
+	// See "Signing Protocol" in Section 5.2 (page 14).
+	let group_commitment = bound_commitments.values().cloned().sum();
+
+	DerivedSignatureData { group_commitment, bindings, bound_commitments }
+}
+
+fn signer_public_keys<Crypto: CryptoScheme>(
+	key: &KeygenResult<Crypto>,
+	common: &CeremonyCommon,
+) -> BTreeMap<AuthorityCount, Crypto::Point> {
+	common
+		.all_idxs
+		.iter()
+		.map(|idx| {
+			(
+				*idx,
+				*key.party_public_keys
+					.get(common.validator_mapping.get_id(*idx))
+					.expect("should have a public key for this party"),
+			)
+		})
+		.collect()
+}
+
+fn lagrange_coefficients<Crypto: CryptoScheme>(
+	all_idxs: &BTreeSet<AuthorityCount>,
+) -> BTreeMap<AuthorityCount, <Crypto::Point as crate::crypto::ECPoint>::Scalar> {
+	all_idxs
+		.iter()
+		.map(|signer_idx| (*signer_idx, get_lagrange_coeff::<Crypto::Point>(*signer_idx, all_idxs)))
+		.collect()
+}
+
 #[async_trait]
 impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 	for VerifyCommitmentsBroadcast2<Crypto>
 {
-	type Message = VerifyComm2<Crypto::Point>;
+	type Message = VerifyCommAndLocalSig2<Crypto::Point>;
 	const NAME: SigningStageName = SigningStageName::VerifyCommitmentsBroadcast2;
 
 	/// Simply report all data that we have received from
@@ -127,7 +277,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 	fn init(&mut self) -> DataToSend<Self::Message> {
 		let data = self.commitments.clone();
 
-		DataToSend::Broadcast(VerifyComm2 { data })
+		DataToSend::Broadcast(VerifyCommAndLocalSig2 { data })
 	}
 
 	/// Verify that all values have been broadcast correctly during stage 1
@@ -135,8 +285,8 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 		self,
 		messages: BTreeMap<AuthorityCount, Option<Self::Message>>,
 	) -> SigningStageResult<Crypto> {
-		let verified_commitments = match verify_broadcasts_non_blocking(messages).await {
-			Ok(comms) => comms,
+		let verified_messages = match verify_broadcasts_non_blocking(messages).await {
+			Ok(messages) => messages,
 			Err((reported_parties, abort_reason)) =>
 				return SigningStageResult::Error(
 					reported_parties,
@@ -144,8 +294,26 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 				),
 		};
 
+		if self.preprocessed.is_some() {
+			if let Some(signature) = self.try_aggregate_preprocessed(&verified_messages) {
+				debug!("{} is successful, signed using preprocessed nonces", Self::NAME);
+				return StageResult::Done(vec![signature])
+			}
+
+			// We don't report anyone here, as other parties might not have the same
+			// preprocessed commitments as us through no fault of their own. Any missing,
+			// invalid or mismatched local signature makes everyone continue with the full
+			// protocol instead.
+			debug!("Could not sign using preprocessed nonces, continuing with the full protocol");
+		}
+
 		// Deserialize and report any party for which deserialization fails:
-		let verified_commitments = match try_deserialize(verified_commitments) {
+		let verified_commitments = match try_deserialize(
+			verified_messages
+				.into_iter()
+				.map(|(idx, message)| (idx, message.comm1))
+				.collect(),
+		) {
 			Ok(res) => res,
 			Err(bad_parties) =>
 				return SigningStageResult::Error(
@@ -197,22 +365,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 					})
 					.collect::<BTreeMap<_, _>>();
 
-				let bindings = signing_detail::generate_bindings::<Crypto>(
-					payload,
-					&commitments,
-					&self.common.all_idxs,
-				);
-
-				let bound_commitments = commitments
-					.iter()
-					.map(|(idx, comm)| (*idx, comm.d + comm.e * bindings[idx].clone()))
-					.collect::<BTreeMap<_, _>>();
-
-				// Combine individual commitments into group (schnorr) commitment.
-				// See "Signing Protocol" in Section 5.2 (page 14).
-				let group_commitment = bound_commitments.values().cloned().sum();
-
-				DerivedSignatureData { group_commitment, bindings, bound_commitments }
+				derive_signature_data(payload, &commitments, &self.common.all_idxs)
 			})
 			.collect();
 
@@ -382,12 +535,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 
 		let all_idxs = &self.common.all_idxs;
 
-		let lagrange_coefficients: BTreeMap<_, _> = all_idxs
-			.iter()
-			.map(|signer_idx| {
-				(*signer_idx, get_lagrange_coeff::<Crypto::Point>(*signer_idx, all_idxs))
-			})
-			.collect();
+		let lagrange_coefficients = lagrange_coefficients::<Crypto>(all_idxs);
 
 		let signatures_result = (0..self.signing_common.payload_count())
 			.map(|i| {
@@ -404,17 +552,7 @@ impl<Crypto: CryptoScheme> BroadcastStageProcessor<SigningCeremony<Crypto>>
 
 				// NOTE: depending on how many payloads we will need to sign with
 				// the same key, we may want to compute this value once per key
-				let pubkeys: BTreeMap<_, _> = all_idxs
-					.iter()
-					.map(|idx| {
-						(
-							*idx,
-							*key.party_public_keys
-								.get(self.common.validator_mapping.get_id(*idx))
-								.expect("should have a public key for this party"),
-						)
-					})
-					.collect();
+				let pubkeys = signer_public_keys(key, &self.common);
 
 				let payload_data = &self.signature_data[i];
 
@@ -516,3 +516,167 @@ mod timeout {
 		}
 	}
 }
+
+mod preprocessed_nonces {
+	use super::*;
+
+	use crate::client::{
+		helpers::run_preprocessing,
+		signing::{nonce_pool::NoncePool, signing_data::VerifyCommAndLocalSig2, Comm1AndLocalSig},
+	};
+	use std::collections::HashMap;
+
+	const BATCH_START: u64 = 0;
+	const BATCH_SIZE: usize = 4;
+
+	async fn new_signing_ceremony_with_preprocessed_nonces<Chain: ChainSigning>() -> (
+		SigningCeremonyRunner<Chain>,
+		HashMap<AccountId, NoncePool<ChainPoint<Chain>>>,
+		ChainPoint<Chain>,
+	) {
+		let (key, key_data) = generate_key_data::<Chain::CryptoScheme>(
+			BTreeSet::from_iter(ACCOUNT_IDS.iter().cloned()),
+			&mut Rng::from_seed([0; 32]),
+		);
+
+		let key_point = key_data[&ACCOUNT_IDS[0]].key.get_agg_public_key_point();
+		let nonce_pools =
+			run_preprocessing::<Chain>(key_data.clone(), BATCH_START, BATCH_SIZE).await;
+
+		let (signing_ceremony, _non_signing_nodes) =
+			SigningCeremonyRunner::<Chain>::new_with_threshold_subset_of_signers(
+				new_nodes(ACCOUNT_IDS.clone()),
+				DEFAULT_SIGNING_CEREMONY_ID,
+				vec![PayloadAndKeyData::new(
+					<Chain::CryptoScheme as CryptoScheme>::signing_payload_for_test(),
+					key,
+					key_data,
+				)],
+				Rng::from_seed([1; 32]),
+			);
+
+		(signing_ceremony, nonce_pools, key_point)
+	}
+
+	async fn should_sign_after_verifying_the_local_signatures<Chain: ChainSigning>() {
+		let (mut signing_ceremony, nonce_pools, key_point) =
+			new_signing_ceremony_with_preprocessed_nonces::<Chain>().await;
+		signing_ceremony.ceremony_runner_data.nonce_pools = nonce_pools;
+
+		signing_ceremony.request_without_gather().await;
+		let messages = signing_ceremony
+			.gather_outgoing_messages::<Comm1AndLocalSig<ChainPoint<Chain>>, _>()
+			.await;
+		assert!(messages
+			.values()
+			.flat_map(|messages| messages.values())
+			.all(|message| message.local_sig.is_some()));
+
+		// The ceremony completes once the local signatures are verified to be broadcast
+		// consistently
+		let messages = signing_ceremony
+			.run_stage::<VerifyCommAndLocalSig2<ChainPoint<Chain>>, _, _>(messages)
+			.await;
+		signing_ceremony.distribute_messages(messages).await;
+		signing_ceremony.complete();
+
+		// Each nonce can only be used once
+		let signer_idxs = BTreeSet::from_iter([1, 2, 3]);
+		for nonce_pool in signing_ceremony.ceremony_runner_data.nonce_pools.values_mut() {
+			assert!(nonce_pool
+				.take(&key_point, DEFAULT_SIGNING_CEREMONY_ID, &signer_idxs)
+				.is_none());
+		}
+	}
+
+	#[tokio::test]
+	async fn should_sign_after_verifying_the_local_signatures_on_all_schemes() {
+		test_all_crypto_chains_async!(should_sign_after_verifying_the_local_signatures());
+	}
+
+	#[tokio::test]
+	async fn should_fall_back_to_full_protocol_if_a_signer_has_no_preprocessed_nonce() {
+		let (mut signing_ceremony, mut nonce_pools, _) =
+			new_signing_ceremony_with_preprocessed_nonces::<EthSigning>().await;
+
+		let [signer_without_nonces] = signing_ceremony.select_account_ids();
+		nonce_pools.remove(&signer_without_nonces);
+		signing_ceremony.ceremony_runner_data.nonce_pools = nonce_pools;
+
+		signing_ceremony.request_without_gather().await;
+		let messages =
+			signing_ceremony.gather_outgoing_messages::<Comm1AndLocalSig<Point>, _>().await;
+		assert!(messages[&signer_without_nonces]
+			.values()
+			.all(|message| message.local_sig.is_none()));
+
+		// Nobody is reported, the ceremony simply takes the usual number of stages
+		let messages = run_stages!(
+			signing_ceremony,
+			messages,
+			VerifyCommAndLocalSig2<Point>,
+			LocalSig3,
+			VerifyLocalSig4
+		);
+		signing_ceremony.distribute_messages(messages).await;
+		signing_ceremony.complete();
+	}
+
+	#[tokio::test]
+	async fn should_fall_back_to_full_protocol_on_an_invalid_local_signature() {
+		let (mut signing_ceremony, nonce_pools, _) =
+			new_signing_ceremony_with_preprocessed_nonces::<EthSigning>().await;
+		signing_ceremony.ceremony_runner_data.nonce_pools = nonce_pools;
+
+		signing_ceremony.request_without_gather().await;
+		let mut messages =
+			signing_ceremony.gather_outgoing_messages::<Comm1AndLocalSig<Point>, _>().await;
+
+		let [bad_account_id] = signing_ceremony.select_account_ids();
+		let invalid_local_sig = gen_dummy_local_sig(&mut signing_ceremony.rng, 1);
+		for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
+			message.local_sig = Some(invalid_local_sig.clone());
+		}
+
+		// The signature from the full protocol is valid, so nobody is reported
+		let messages = run_stages!(
+			signing_ceremony,
+			messages,
+			VerifyCommAndLocalSig2<Point>,
+			LocalSig3,
+			VerifyLocalSig4
+		);
+		signing_ceremony.distribute_messages(messages).await;
+		signing_ceremony.complete();
+	}
+
+	#[tokio::test]
+	async fn should_report_inconsistently_broadcast_local_signature() {
+		let (mut signing_ceremony, nonce_pools, _) =
+			new_signing_ceremony_with_preprocessed_nonces::<EthSigning>().await;
+		signing_ceremony.ceremony_runner_data.nonce_pools = nonce_pools;
+
+		signing_ceremony.request_without_gather().await;
+		let mut messages =
+			signing_ceremony.gather_outgoing_messages::<Comm1AndLocalSig<Point>, _>().await;
+
+		// The bad account sends a different local signature to each party. This must not let any
+		// party complete the ceremony while the others can't, and only the bad account is reported.
+		let [bad_account_id] = signing_ceremony.select_account_ids();
+		for message in messages.get_mut(&bad_account_id).unwrap().values_mut() {
+			message.local_sig = Some(gen_dummy_local_sig(&mut signing_ceremony.rng, 1));
+		}
+
+		let messages = signing_ceremony
+			.run_stage::<VerifyCommAndLocalSig2<Point>, _, _>(messages)
+			.await;
+		signing_ceremony.distribute_messages(messages).await;
+		signing_ceremony.complete_with_error(
+			&[bad_account_id],
+			SigningFailureReason::BroadcastFailure(
+				BroadcastFailureReason::Inconsistency,
+				SigningStageName::VerifyCommitmentsBroadcast2,
+			),
+		);
+	}
+}
@@ -16,6 +16,28 @@ impl KeyId {
 	pub fn new<Key: CanonicalEncoding>(epoch_index: EpochIndex, key: Key) -> Self {
 		KeyId { epoch_index, public_key_bytes: key.encode_key() }
 	}
+
+	pub fn epoch_index(&self) -> EpochIndex {
+		self.epoch_index
+	}
+}
+
+/// Parses a key id written as `<epoch index>:<hex encoded public key>`.
+impl core::str::FromStr for KeyId {
+	type Err = anyhow::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		use anyhow::Context;
+
+		let (epoch_index, public_key) = s
+			.split_once(':')
+			.context("Expected a key id of the form <epoch index>:<hex encoded public key>")?;
+		Ok(KeyId {
+			epoch_index: epoch_index.parse().context("Invalid epoch index")?,
+			public_key_bytes: hex::decode(public_key.trim_start_matches("0x"))
+				.context("Invalid public key")?,
+		})
+	}
 }
 
 impl CanonicalEncoding for cf_chains::dot::PolkadotPublicKey {
@@ -14,7 +14,7 @@ pub mod client;
 mod crypto;
 
 /// Maximum number of payloads in a single bitcoin signing ceremony
-pub const MAX_BTC_SIGNING_PAYLOADS: usize = 1000;
+pub use cf_chains::btc::MAX_BTC_SIGNING_PAYLOADS;
 
 pub mod p2p {
 	use cf_primitives::AccountId;
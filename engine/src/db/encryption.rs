@@ -0,0 +1,141 @@
+//! Authenticated encryption of the key shares stored in the
+//! [PersistentKeyDB](super::PersistentKeyDB).
+//!
+//! Data is sealed with ChaCha20-Poly1305 under a wrapping key, which is derived with scrypt from
+//! the configured [KeyEncryption] secret and a random salt.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chacha20poly1305::{
+	aead::{Aead, NewAead, Payload},
+	ChaCha20Poly1305, Key, Nonce,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::settings::KeyEncryption;
+
+pub const SALT_SIZE: usize = 16;
+const NONCE_SIZE: usize = 12;
+const WRAPPING_KEY_SIZE: usize = 32;
+
+// scrypt cost parameters. With N = 2^15 and r = 8, each derivation uses 32 MiB of memory.
+#[cfg(not(test))]
+const SCRYPT_LOG_N: u8 = 15;
+// Cheap parameters, so that tests don't spend most of their time deriving keys.
+#[cfg(test)]
+const SCRYPT_LOG_N: u8 = 4;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// Data that has been sealed by a [KeyCipher], along with the nonce it was sealed with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedData {
+	nonce: [u8; NONCE_SIZE],
+	ciphertext: Vec<u8>,
+}
+
+/// Seals and opens data with a wrapping key.
+pub struct KeyCipher {
+	cipher: ChaCha20Poly1305,
+}
+
+impl KeyCipher {
+	/// Derive the wrapping key from the secret described by `key_encryption` and the `salt`.
+	pub fn derive(key_encryption: &KeyEncryption, salt: &[u8]) -> Result<Self> {
+		let secret = load_secret(key_encryption)?;
+
+		let mut wrapping_key = Zeroizing::new([0u8; WRAPPING_KEY_SIZE]);
+		scrypt::scrypt(
+			&secret,
+			salt,
+			&scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)
+				.expect("scrypt parameters are valid"),
+			&mut wrapping_key[..],
+		)
+		.expect("Wrapping key length is valid");
+
+		Ok(Self { cipher: ChaCha20Poly1305::new(Key::from_slice(&wrapping_key[..])) })
+	}
+
+	/// Encrypt `plaintext` with a random nonce. The `associated_data` is authenticated but not
+	/// encrypted, and the same data must be provided to open the result.
+	pub fn seal(&self, plaintext: &[u8], associated_data: &[u8]) -> SealedData {
+		let mut nonce = [0u8; NONCE_SIZE];
+		rand::thread_rng().fill_bytes(&mut nonce);
+
+		let ciphertext = self
+			.cipher
+			.encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: associated_data })
+			.expect("Encryption is not expected to fail");
+
+		SealedData { nonce, ciphertext }
+	}
+
+	pub fn open(&self, sealed: &SealedData, associated_data: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+		self.cipher
+			.decrypt(
+				Nonce::from_slice(&sealed.nonce),
+				Payload { msg: &sealed.ciphertext, aad: associated_data },
+			)
+			.map(Zeroizing::new)
+			.map_err(|_| {
+				anyhow!(
+					"Failed to decrypt data. The wrapping key is wrong or the data is corrupted"
+				)
+			})
+	}
+}
+
+pub fn generate_salt() -> [u8; SALT_SIZE] {
+	let mut salt = [0u8; SALT_SIZE];
+	rand::thread_rng().fill_bytes(&mut salt);
+	salt
+}
+
+fn load_secret(key_encryption: &KeyEncryption) -> Result<Zeroizing<Vec<u8>>> {
+	let secret = Zeroizing::new(match key_encryption {
+		KeyEncryption::Passphrase(passphrase) => passphrase.as_bytes().to_vec(),
+		KeyEncryption::KeyFile(path) => std::fs::read(path)
+			.with_context(|| format!("Failed to read key encryption file {}", path.display()))?,
+	});
+
+	if secret.is_empty() {
+		bail!("The key encryption passphrase or key file must not be empty");
+	}
+
+	Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn can_only_open_with_same_key_and_associated_data() {
+		let salt = generate_salt();
+		let cipher =
+			KeyCipher::derive(&KeyEncryption::Passphrase("correct".to_string()), &salt).unwrap();
+
+		let sealed = cipher.seal(b"key share", b"key id");
+		assert_ne!(sealed.ciphertext, b"key share");
+		assert_eq!(&cipher.open(&sealed, b"key id").unwrap()[..], b"key share");
+
+		assert!(cipher.open(&sealed, b"other key id").is_err());
+		assert!(KeyCipher::derive(&KeyEncryption::Passphrase("wrong".to_string()), &salt)
+			.unwrap()
+			.open(&sealed, b"key id")
+			.is_err());
+		assert!(KeyCipher::derive(&KeyEncryption::Passphrase("correct".to_string()), &[0; 16])
+			.unwrap()
+			.open(&sealed, b"key id")
+			.is_err());
+	}
+
+	#[test]
+	fn empty_secret_is_rejected() {
+		assert!(
+			KeyCipher::derive(&KeyEncryption::Passphrase(String::new()), &generate_salt()).is_err()
+		);
+	}
+}
@@ -0,0 +1,395 @@
+//! Export of key shares into an encrypted backup file, and import of the backup into the database
+//! of a replacement machine.
+//!
+//! A backup file consists of a magic number, the backup format version, the bincode encoded
+//! [KeyBackup], and a SHA-256 checksum of everything before it.
+
+use std::{fs, io::Write, path::Path};
+
+use anyhow::{anyhow, bail, Context, Result};
+use cf_primitives::EpochIndex;
+use multisig::{
+	bitcoin::BtcSigning, client::KeygenResultInfo, eth::EthSigning, polkadot::PolkadotSigning,
+	ChainSigning, ChainTag, KeyId, CHAIN_TAG_SIZE,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use super::{
+	encryption::{generate_salt, KeyCipher, SealedData, SALT_SIZE},
+	PersistentKeyDB,
+};
+use crate::settings::KeyEncryption;
+
+const MAGIC: &[u8; 8] = b"CFKEYBAK";
+/// Must be bumped on any change to the format of the backup file
+const KEY_BACKUP_VERSION: u32 = 1;
+const HEADER_SIZE: usize = MAGIC.len() + std::mem::size_of::<u32>();
+const CHECKSUM_SIZE: usize = 32;
+
+#[derive(Serialize, Deserialize)]
+struct KeyBackup {
+	/// The genesis hash of the network the keys belong to
+	genesis_hash: state_chain_runtime::Hash,
+	salt: [u8; SALT_SIZE],
+	/// The bincode encoded `Vec<BackedUpKey>`, sealed with a key derived from the backup secret.
+	/// The file header and the genesis hash are used as associated data.
+	sealed_keys: SealedData,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BackedUpKey {
+	chain_tag: [u8; CHAIN_TAG_SIZE],
+	key_id: KeyId,
+	/// The bincode encoded `KeygenResultInfo`
+	keygen_result_info: Vec<u8>,
+}
+
+/// Write the keys in `db` to a new backup file at `output`, encrypted with a key derived from
+/// `backup_encryption`. Only the keys of `epoch_index` and with one of `key_ids` are exported, if
+/// given. Returns the number of keys exported.
+pub fn export_keys(
+	db: &PersistentKeyDB,
+	epoch_index: Option<EpochIndex>,
+	key_ids: &[KeyId],
+	backup_encryption: &KeyEncryption,
+	output: &Path,
+) -> Result<usize> {
+	let genesis_hash = db.get_genesis_hash()?.ok_or_else(|| {
+		anyhow!("The database has no genesis hash. Run the engine once to initialise it")
+	})?;
+
+	let selected = |key_id: &KeyId| {
+		epoch_index.map_or(true, |epoch| key_id.epoch_index() == epoch) &&
+			(key_ids.is_empty() || key_ids.contains(key_id))
+	};
+
+	let mut keys = Vec::new();
+	collect_keys::<EthSigning>(db, selected, &mut keys);
+	collect_keys::<PolkadotSigning>(db, selected, &mut keys);
+	collect_keys::<BtcSigning>(db, selected, &mut keys);
+
+	if keys.is_empty() {
+		bail!("No keys found to export");
+	}
+	if let Some(missing) =
+		key_ids.iter().find(|key_id| !keys.iter().any(|key| &key.key_id == *key_id))
+	{
+		bail!("Key {missing} not found in the database");
+	}
+
+	let backup = encode_backup(genesis_hash, &keys, backup_encryption)?;
+
+	// Never overwrite an existing file, which could be an earlier backup
+	fs::OpenOptions::new()
+		.write(true)
+		.create_new(true)
+		.open(output)
+		.and_then(|mut file| file.write_all(&backup))
+		.with_context(|| format!("Failed to write key backup to {}", output.display()))?;
+
+	Ok(keys.len())
+}
+
+/// Import the keys from the backup file at `input` into the database at `db_path`, which is
+/// created if it doesn't exist. The genesis hash of the backup must match the one of the database.
+/// Returns the number of keys imported.
+///
+/// All keys are checked before any are written, and existing keys are never overwritten.
+pub fn import_keys(
+	db_path: &Path,
+	key_encryption: Option<&KeyEncryption>,
+	backup_encryption: &KeyEncryption,
+	input: &Path,
+) -> Result<usize> {
+	let (genesis_hash, keys) = decode_backup(
+		&fs::read(input)
+			.with_context(|| format!("Failed to read key backup from {}", input.display()))?,
+		backup_encryption,
+	)?;
+
+	let db = PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
+		db_path,
+		Some(genesis_hash),
+		key_encryption,
+	)
+	.context("Failed to open database")?;
+
+	for commit in [false, true] {
+		for key in &keys {
+			let chain_tag = num_traits::FromPrimitive::from_u16(u16::from_be_bytes(key.chain_tag))
+				.ok_or_else(|| anyhow!("Unknown chain tag: {:?}", key.chain_tag))?;
+			match chain_tag {
+				ChainTag::Ethereum => import_key::<EthSigning>(&db, key, commit)?,
+				ChainTag::Polkadot => import_key::<PolkadotSigning>(&db, key, commit)?,
+				ChainTag::Bitcoin => import_key::<BtcSigning>(&db, key, commit)?,
+				ChainTag::Ed25519 => bail!("Unsupported chain tag: {chain_tag}"),
+			}
+		}
+	}
+
+	Ok(keys.len())
+}
+
+fn collect_keys<C: ChainSigning>(
+	db: &PersistentKeyDB,
+	selected: impl Fn(&KeyId) -> bool,
+	keys: &mut Vec<BackedUpKey>,
+) {
+	keys.extend(db.load_keys::<C>().into_iter().filter(|(key_id, _)| selected(key_id)).map(
+		|(key_id, keygen_result_info)| {
+			BackedUpKey {
+				chain_tag: C::CHAIN_TAG.to_bytes(),
+				key_id,
+				keygen_result_info: bincode::serialize(&keygen_result_info)
+					.expect("Serialization is not expected to fail"),
+			}
+		},
+	));
+}
+
+/// Checks that the key is valid for the chain, and isn't in the database yet. If `commit` is set,
+/// the key is written to the database.
+fn import_key<C: ChainSigning>(
+	db: &PersistentKeyDB,
+	key: &BackedUpKey,
+	commit: bool,
+) -> Result<()> {
+	let keygen_result_info: KeygenResultInfo<C::CryptoScheme> =
+		bincode::deserialize(&key.keygen_result_info)
+			.with_context(|| format!("Invalid {} key share for {}", C::NAME, key.key_id))?;
+
+	if commit {
+		db.update_key::<C>(&key.key_id, &keygen_result_info);
+	} else if db.load_keys::<C>().contains_key(&key.key_id) {
+		bail!("The database already contains the {} key {}", C::NAME, key.key_id);
+	}
+
+	Ok(())
+}
+
+fn encode_backup(
+	genesis_hash: state_chain_runtime::Hash,
+	keys: &[BackedUpKey],
+	backup_encryption: &KeyEncryption,
+) -> Result<Vec<u8>> {
+	let header = [&MAGIC[..], &KEY_BACKUP_VERSION.to_be_bytes()[..]].concat();
+
+	let salt = generate_salt();
+	let sealed_keys = KeyCipher::derive(backup_encryption, &salt)?.seal(
+		&Zeroizing::new(bincode::serialize(keys).expect("Serialization is not expected to fail")),
+		&associated_data(&header, genesis_hash),
+	);
+
+	let mut backup = [
+		header,
+		bincode::serialize(&KeyBackup { genesis_hash, salt, sealed_keys })
+			.expect("Serialization is not expected to fail"),
+	]
+	.concat();
+	let checksum = Sha256::digest(&backup);
+	backup.extend_from_slice(&checksum);
+
+	Ok(backup)
+}
+
+fn decode_backup(
+	backup: &[u8],
+	backup_encryption: &KeyEncryption,
+) -> Result<(state_chain_runtime::Hash, Vec<BackedUpKey>)> {
+	if backup.len() < HEADER_SIZE + CHECKSUM_SIZE || !backup.starts_with(MAGIC) {
+		bail!("Not a key backup file");
+	}
+
+	let (contents, checksum) = backup.split_at(backup.len() - CHECKSUM_SIZE);
+	if Sha256::digest(contents).as_slice() != checksum {
+		bail!("Key backup checksum mismatch. The file is corrupted");
+	}
+
+	let (header, body) = contents.split_at(HEADER_SIZE);
+	let version = u32::from_be_bytes(header[MAGIC.len()..].try_into().expect("Size is checked"));
+	if version != KEY_BACKUP_VERSION {
+		bail!("Unsupported key backup version {version}, expected {KEY_BACKUP_VERSION}");
+	}
+
+	let KeyBackup { genesis_hash, salt, sealed_keys } =
+		bincode::deserialize(body).context("Failed to deserialize key backup")?;
+
+	let keys = bincode::deserialize(
+		&KeyCipher::derive(backup_encryption, &salt)?
+			.open(&sealed_keys, &associated_data(header, genesis_hash))
+			.context("Failed to decrypt key backup. Is the passphrase correct?")?,
+	)
+	.context("Failed to deserialize backed up keys")?;
+
+	Ok((genesis_hash, keys))
+}
+
+fn associated_data(header: &[u8], genesis_hash: state_chain_runtime::Hash) -> Vec<u8> {
+	[header, &bincode::serialize(&genesis_hash).expect("Serialization is not expected to fail")]
+		.concat()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use cf_primitives::{AccountId, GENESIS_EPOCH};
+	use multisig::client::get_key_data_for_test;
+	use std::collections::BTreeSet;
+	use utilities::testing::new_temp_directory_with_nonexistent_file;
+
+	fn add_key<C: ChainSigning>(db: &PersistentKeyDB, epoch_index: EpochIndex) -> KeyId {
+		let key_id = KeyId::new(epoch_index, rand::random::<[u8; 32]>());
+		db.update_key::<C>(
+			&key_id,
+			&get_key_data_for_test::<C::CryptoScheme>(BTreeSet::from([AccountId::new([0; 32])])),
+		);
+		key_id
+	}
+
+	fn passphrase(passphrase: &str) -> KeyEncryption {
+		KeyEncryption::Passphrase(passphrase.to_string())
+	}
+
+	#[test]
+	fn can_export_and_import_keys() {
+		let (dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let backup_path = dir.path().join("keys.backup");
+		let genesis_hash = sp_core::H256::random();
+
+		let (eth_key, dot_key, btc_key) = {
+			let db =
+				PersistentKeyDB::open_and_migrate_to_latest(&db_path, Some(genesis_hash)).unwrap();
+			add_key::<EthSigning>(&db, GENESIS_EPOCH);
+			(
+				add_key::<EthSigning>(&db, GENESIS_EPOCH + 1),
+				add_key::<PolkadotSigning>(&db, GENESIS_EPOCH + 1),
+				add_key::<BtcSigning>(&db, GENESIS_EPOCH + 1),
+			)
+		};
+
+		{
+			let db = PersistentKeyDB::open_read_only_with_key_encryption(&db_path, None).unwrap();
+			assert_eq!(
+				export_keys(&db, Some(GENESIS_EPOCH + 1), &[], &passphrase("backup"), &backup_path)
+					.unwrap(),
+				3
+			);
+			// Existing files are not overwritten
+			assert!(export_keys(&db, None, &[], &passphrase("backup"), &backup_path).is_err());
+
+			let selected_path = dir.path().join("selected.backup");
+			// Keys that are not in the database can't be selected
+			assert!(export_keys(
+				&db,
+				None,
+				&[dot_key.clone(), KeyId::new(GENESIS_EPOCH, [0; 32])],
+				&passphrase("backup"),
+				&selected_path
+			)
+			.is_err());
+			assert_eq!(
+				export_keys(&db, None, &[dot_key.clone()], &passphrase("backup"), &selected_path)
+					.unwrap(),
+				1
+			);
+		}
+
+		let new_db_path = dir.path().join("new.db");
+		let key_encryption = passphrase("db");
+
+		assert!(import_keys(
+			&new_db_path,
+			Some(&key_encryption),
+			&passphrase("wrong"),
+			&backup_path
+		)
+		.is_err());
+		assert_eq!(
+			import_keys(&new_db_path, Some(&key_encryption), &passphrase("backup"), &backup_path)
+				.unwrap(),
+			3
+		);
+		// Keys are not imported twice
+		assert!(import_keys(
+			&new_db_path,
+			Some(&key_encryption),
+			&passphrase("backup"),
+			&backup_path
+		)
+		.is_err());
+
+		let db = PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
+			&new_db_path,
+			Some(genesis_hash),
+			Some(&key_encryption),
+		)
+		.unwrap();
+		assert_eq!(db.load_keys::<EthSigning>().into_keys().collect::<Vec<_>>(), vec![eth_key]);
+		assert!(db.load_keys::<PolkadotSigning>().contains_key(&dot_key));
+		assert!(db.load_keys::<BtcSigning>().contains_key(&btc_key));
+	}
+
+	#[test]
+	fn import_fails_on_genesis_hash_mismatch() {
+		let (dir, db_path) = new_temp_directory_with_nonexistent_file();
+		let backup_path = dir.path().join("keys.backup");
+
+		{
+			let db = PersistentKeyDB::open_and_migrate_to_latest(
+				&db_path,
+				Some(sp_core::H256::random()),
+			)
+			.unwrap();
+			add_key::<EthSigning>(&db, GENESIS_EPOCH);
+			export_keys(&db, None, &[], &passphrase("backup"), &backup_path).unwrap();
+		}
+
+		let new_db_path = dir.path().join("new.db");
+		drop(
+			PersistentKeyDB::open_and_migrate_to_latest(
+				&new_db_path,
+				Some(sp_core::H256::random()),
+			)
+			.unwrap(),
+		);
+
+		assert!(import_keys(&new_db_path, None, &passphrase("backup"), &backup_path).is_err());
+	}
+
+	#[test]
+	fn export_fails_without_genesis_hash() {
+		let (dir, db_path) = new_temp_directory_with_nonexistent_file();
+
+		let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).unwrap();
+		add_key::<EthSigning>(&db, GENESIS_EPOCH);
+
+		assert!(export_keys(
+			&db,
+			None,
+			&[],
+			&passphrase("backup"),
+			&dir.path().join("keys.backup")
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn corrupted_backup_is_rejected() {
+		let keys = vec![BackedUpKey {
+			chain_tag: ChainTag::Ethereum.to_bytes(),
+			key_id: KeyId::new(GENESIS_EPOCH, [0; 33]),
+			keygen_result_info: vec![1, 2, 3],
+		}];
+		let backup = encode_backup(sp_core::H256::random(), &keys, &passphrase("backup")).unwrap();
+
+		assert_eq!(decode_backup(&backup, &passphrase("backup")).unwrap().1.len(), 1);
+
+		let mut corrupted = backup.clone();
+		corrupted[HEADER_SIZE + 4] ^= 1;
+		assert!(decode_backup(&corrupted, &passphrase("backup")).is_err());
+
+		assert!(decode_backup(&backup[..HEADER_SIZE], &passphrase("backup")).is_err());
+	}
+}
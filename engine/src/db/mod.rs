@@ -1,3 +1,5 @@
+pub mod encryption;
+pub mod key_backup;
 pub mod persistent;
 use std::{collections::HashMap, sync::Arc};
 
@@ -5,23 +5,27 @@ mod tests;
 use std::{cmp::Ordering, collections::HashMap, path::Path};
 
 use cf_primitives::EpochIndex;
-use serde::{de::DeserializeOwned, Serialize};
-use tracing::{debug, info, info_span};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::{debug, info, info_span, warn};
 use utilities::rle_bitmap::RleBitmap;
 
 use multisig::{client::KeygenResultInfo, ChainSigning, KeyId, CHAIN_TAG_SIZE};
 
 use anyhow::{anyhow, bail, Context, Result};
+use zeroize::Zeroizing;
 
 use rocksdb_kv::{RocksDBKeyValueStore, PREFIX_SIZE};
 
+use super::encryption::{generate_salt, KeyCipher, SealedData};
+use crate::settings::KeyEncryption;
+
 /// Name of the directory that the backups will go into (only created before migrations)
 const BACKUPS_DIRECTORY: &str = "backups";
 
 /// This is the version of the data on this current branch
 /// This version *must* be bumped, and appropriate migrations
 /// written on any changes to the persistent application data format
-const LATEST_SCHEMA_VERSION: u32 = 0;
+const LATEST_SCHEMA_VERSION: u32 = 1;
 
 const PARTIAL_PREFIX_SIZE: usize = PREFIX_SIZE - CHAIN_TAG_SIZE;
 
@@ -34,6 +38,22 @@ const PROCESSED_BLOCKS_PARTIAL_PREFIX: &[u8; PARTIAL_PREFIX_SIZE] = b"seen____";
 /// Key used to store the `LATEST_SCHEMA_VERSION` value in the `METADATA_COLUMN`
 const DB_SCHEMA_VERSION_KEY: &[u8; 17] = b"db_schema_version";
 const GENESIS_HASH_KEY: &[u8; 12] = b"genesis_hash";
+/// Keys used to store the key encryption parameters in the `METADATA_COLUMN`. The key records are
+/// encrypted if and only if these are present.
+const KEY_ENCRYPTION_SALT_KEY: &[u8; 19] = b"key_encryption_salt";
+const KEY_ENCRYPTION_CHECK_KEY: &[u8; 20] = b"key_encryption_check";
+/// Sealed with the wrapping key, so that a wrong passphrase or key file is detected on opening
+const KEY_ENCRYPTION_CHECK_PLAINTEXT: &[u8] = b"chainflip key encryption check";
+
+/// The format of the values stored under the keygen data prefix (since schema version 1)
+#[derive(Serialize, Deserialize)]
+enum KeyRecord {
+	/// The bincode encoded `KeygenResultInfo`
+	Plaintext(Vec<u8>),
+	/// The bincode encoded `KeygenResultInfo`, sealed with the wrapping key. The db key of the
+	/// record is used as associated data, so that records can't be swapped.
+	Encrypted(SealedData),
+}
 
 /// Used to specify whether a backup should be created, and if so,
 /// the provided path is used to derive the name of the backup
@@ -46,6 +66,8 @@ enum BackupOption<'a> {
 pub struct PersistentKeyDB {
 	/// Underlying key-value database instance
 	kv_db: RocksDBKeyValueStore,
+	/// Used to encrypt the key records, if key encryption is enabled
+	key_cipher: Option<KeyCipher>,
 }
 
 impl PersistentKeyDB {
@@ -54,11 +76,58 @@ impl PersistentKeyDB {
 	pub fn open_and_migrate_to_latest(
 		db_path: &Path,
 		genesis_hash: Option<state_chain_runtime::Hash>,
+	) -> Result<Self> {
+		Self::open_and_migrate_to_latest_with_key_encryption(db_path, genesis_hash, None)
+	}
+
+	/// As [Self::open_and_migrate_to_latest], but the key records are encrypted at rest with a
+	/// wrapping key derived from `key_encryption`. If the existing key records are not encrypted
+	/// yet, they are encrypted when the database is opened.
+	pub fn open_and_migrate_to_latest_with_key_encryption(
+		db_path: &Path,
+		genesis_hash: Option<state_chain_runtime::Hash>,
+		key_encryption: Option<&KeyEncryption>,
 	) -> Result<Self> {
 		let span = info_span!("PersistentKeyDB");
 		let _entered = span.enter();
 
-		Self::open_and_migrate_to_version(db_path, genesis_hash, LATEST_SCHEMA_VERSION)
+		let mut db =
+			Self::open_and_migrate_to_version(db_path, genesis_hash, LATEST_SCHEMA_VERSION)?;
+		db.init_key_encryption(key_encryption).with_context(|| {
+			format!("Failed to initialise key encryption for database at {}", db_path.display())
+		})?;
+		Ok(db)
+	}
+
+	/// Open an existing key database without writing to it. The database must already be at the
+	/// latest schema version. Key records that are encrypted are decrypted with a wrapping key
+	/// derived from `key_encryption`.
+	pub fn open_read_only_with_key_encryption(
+		db_path: &Path,
+		key_encryption: Option<&KeyEncryption>,
+	) -> Result<Self> {
+		let mut db = PersistentKeyDB {
+			kv_db: RocksDBKeyValueStore::open_read_only(db_path)?,
+			key_cipher: None,
+		};
+
+		let version = db.get_schema_version()?;
+		if version != LATEST_SCHEMA_VERSION {
+			bail!(
+				"The database at {} is at schema version {version} instead of {LATEST_SCHEMA_VERSION}. Run the engine to migrate it first.",
+				db_path.display()
+			);
+		}
+
+		// Plaintext records can be read without a wrapping key.
+		if let Some(salt) = db.kv_db.get_metadata(KEY_ENCRYPTION_SALT_KEY) {
+			let key_encryption = key_encryption.ok_or_else(|| {
+				anyhow!("The key records in the database are encrypted, but no key encryption is configured")
+			})?;
+			db.key_cipher = Some(db.open_key_cipher(key_encryption, &salt)?);
+		}
+
+		Ok(db)
 	}
 
 	/// As [Self::open_and_migrate_to_latest], but allows specifying a specific version
@@ -70,7 +139,7 @@ impl PersistentKeyDB {
 	) -> Result<Self> {
 		let is_existing_db = db_path.exists();
 
-		let db = PersistentKeyDB { kv_db: RocksDBKeyValueStore::open(db_path)? };
+		let db = PersistentKeyDB { kv_db: RocksDBKeyValueStore::open(db_path)?, key_cipher: None };
 
 		// Only create a backup if there is an existing db that we don't
 		// want to accidentally corrupt
@@ -104,8 +173,16 @@ impl PersistentKeyDB {
 		key_id: &KeyId,
 		keygen_result_info: &KeygenResultInfo<C::CryptoScheme>,
 	) {
+		let db_key = keygen_data_key::<C>(key_id);
+		let plaintext = Zeroizing::new(
+			bincode::serialize(keygen_result_info).expect("Serialization is not expected to fail"),
+		);
+
 		self.kv_db
-			.put_data(&keygen_data_prefix::<C>(), &key_id, &keygen_result_info)
+			.put_raw_data(
+				&db_key,
+				&encode_key_record(self.key_cipher.as_ref(), &db_key, &plaintext),
+			)
 			.unwrap_or_else(|e| panic!("Failed to update key {}. Error: {}", &key_id, e));
 	}
 
@@ -113,8 +190,23 @@ impl PersistentKeyDB {
 		let span = info_span!("PersistentKeyDB");
 		let _entered = span.enter();
 
-		let keys: HashMap<_, _> =
-			self.kv_db.get_data_for_prefix(&keygen_data_prefix::<C>()).collect();
+		let prefix = keygen_data_prefix::<C>();
+		let keys: HashMap<KeyId, KeygenResultInfo<C::CryptoScheme>> = self
+			.kv_db
+			.get_raw_data_for_prefix(&prefix)
+			.map(|(db_key, value)| {
+				let key_id: KeyId = bincode::deserialize(&db_key[PREFIX_SIZE..])
+					.expect("Deserialization is not expected to fail");
+				let plaintext = self
+					.decode_key_record(&db_key, &value)
+					.unwrap_or_else(|e| panic!("Failed to load key {key_id}. Error: {e}"));
+				(
+					key_id,
+					bincode::deserialize(&plaintext)
+						.expect("Deserialization is not expected to fail"),
+				)
+			})
+			.collect();
 
 		for key in &keys {
 			tracing::trace!("Loaded {} key from the database: {}", C::NAME, key.0);
@@ -127,6 +219,127 @@ impl PersistentKeyDB {
 		keys
 	}
 
+	/// Re-encrypt all key records with a wrapping key derived from `new_key_encryption`, using a
+	/// fresh salt. If `None`, the key records are decrypted and stored in plaintext.
+	///
+	/// The records are rewritten in a single batch, so an interrupted rotation leaves the database
+	/// as it was. Afterwards the database is compacted, so that the previous records don't remain
+	/// in its files. Backups of the database made before the rotation, such as those made before
+	/// migrations, still contain the previous records, in plaintext if they were not encrypted.
+	pub fn rotate_key_encryption(
+		&mut self,
+		new_key_encryption: Option<&KeyEncryption>,
+	) -> Result<()> {
+		let mut batch = self.kv_db.create_batch();
+
+		let new_key_cipher = match new_key_encryption {
+			Some(key_encryption) => {
+				let salt = generate_salt();
+				let key_cipher = KeyCipher::derive(key_encryption, &salt)?;
+				batch.put_metadata(KEY_ENCRYPTION_SALT_KEY, salt);
+				batch.put_metadata(
+					KEY_ENCRYPTION_CHECK_KEY,
+					bincode::serialize(
+						&key_cipher.seal(KEY_ENCRYPTION_CHECK_PLAINTEXT, KEY_ENCRYPTION_CHECK_KEY),
+					)
+					.expect("Serialization is not expected to fail"),
+				);
+				Some(key_cipher)
+			},
+			None => {
+				batch.delete_metadata(KEY_ENCRYPTION_SALT_KEY);
+				batch.delete_metadata(KEY_ENCRYPTION_CHECK_KEY);
+				None
+			},
+		};
+
+		let mut record_count = 0;
+		for (db_key, value) in self.kv_db.get_raw_data_for_prefix(KEYGEN_DATA_PARTIAL_PREFIX) {
+			let plaintext = self.decode_key_record(&db_key, &value)?;
+			batch.put_value(
+				&db_key,
+				&encode_key_record(new_key_cipher.as_ref(), &db_key, &plaintext),
+			);
+			record_count += 1;
+		}
+
+		batch.write().context("Failed to write re-encrypted key records")?;
+		self.key_cipher = new_key_cipher;
+
+		self.kv_db
+			.purge_overwritten_data()
+			.context("Failed to purge the previous key records from the database files")?;
+
+		info!(
+			"Rewrote {record_count} key records {}",
+			if self.key_cipher.is_some() { "with the new wrapping key" } else { "in plaintext" }
+		);
+
+		Ok(())
+	}
+
+	/// Checks the configured key encryption against the database, and encrypts the key records if
+	/// they are not encrypted yet.
+	fn init_key_encryption(&mut self, key_encryption: Option<&KeyEncryption>) -> Result<()> {
+		match (self.kv_db.get_metadata(KEY_ENCRYPTION_SALT_KEY), key_encryption) {
+			(None, None) => Ok(()),
+			(Some(_), None) => Err(anyhow!(
+				"The key records in the database are encrypted, but no key encryption is configured"
+			)),
+			(Some(salt), Some(key_encryption)) => {
+				self.key_cipher = Some(self.open_key_cipher(key_encryption, &salt)?);
+				Ok(())
+			},
+			(None, Some(key_encryption)) => {
+				let has_key_records =
+					self.kv_db.get_raw_data_for_prefix(KEYGEN_DATA_PARTIAL_PREFIX).next().is_some();
+
+				info!("Enabling encryption of the key records in the database");
+				self.rotate_key_encryption(Some(key_encryption))?;
+
+				if has_key_records {
+					warn!(
+						"Backups of the database made before now, including those in the `{BACKUPS_DIRECTORY}` directory next to it, contain unencrypted key shares and should be removed"
+					);
+				}
+				Ok(())
+			},
+		}
+	}
+
+	/// Derives the wrapping key from `key_encryption` and checks it against the database.
+	fn open_key_cipher(&self, key_encryption: &KeyEncryption, salt: &[u8]) -> Result<KeyCipher> {
+		let key_cipher = KeyCipher::derive(key_encryption, salt)?;
+
+		let check: SealedData = bincode::deserialize(
+			&self
+				.kv_db
+				.get_metadata(KEY_ENCRYPTION_CHECK_KEY)
+				.ok_or_else(|| anyhow!("Could not find key encryption check"))?,
+		)
+		.context("Failed to deserialize key encryption check")?;
+		key_cipher
+			.open(&check, KEY_ENCRYPTION_CHECK_KEY)
+			.ok()
+			.filter(|plaintext| &plaintext[..] == KEY_ENCRYPTION_CHECK_PLAINTEXT)
+			.ok_or_else(|| anyhow!("Incorrect key encryption passphrase or key file"))?;
+
+		Ok(key_cipher)
+	}
+
+	fn decode_key_record(&self, db_key: &[u8], value: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+		match bincode::deserialize(value).context("Failed to deserialize key record")? {
+			KeyRecord::Plaintext(plaintext) => Ok(Zeroizing::new(plaintext)),
+			KeyRecord::Encrypted(sealed) => self
+				.key_cipher
+				.as_ref()
+				.ok_or_else(|| {
+					anyhow!("Key record is encrypted, but key encryption is not enabled")
+				})?
+				.open(&sealed, db_key),
+		}
+	}
+
 	pub fn update_processed_blocks<Index: Ord + Serialize>(
 		&self,
 		witnesser_name: &str,
@@ -192,6 +405,22 @@ fn keygen_data_prefix<C: ChainSigning>() -> Vec<u8> {
 	[&KEYGEN_DATA_PARTIAL_PREFIX[..], &(C::CHAIN_TAG.to_bytes())[..]].concat()
 }
 
+fn keygen_data_key<C: ChainSigning>(key_id: &KeyId) -> Vec<u8> {
+	[
+		keygen_data_prefix::<C>(),
+		bincode::serialize(key_id).expect("Serialization is not expected to fail."),
+	]
+	.concat()
+}
+
+fn encode_key_record(key_cipher: Option<&KeyCipher>, db_key: &[u8], plaintext: &[u8]) -> Vec<u8> {
+	bincode::serialize(&match key_cipher {
+		Some(key_cipher) => KeyRecord::Encrypted(key_cipher.seal(plaintext, db_key)),
+		None => KeyRecord::Plaintext(plaintext.to_vec()),
+	})
+	.expect("Serialization is not expected to fail")
+}
+
 fn processed_blocks_prefix(witnessner_name: &str) -> Vec<u8> {
 	[PROCESSED_BLOCKS_PARTIAL_PREFIX, witnessner_name.as_bytes()].concat()
 }
@@ -248,8 +477,10 @@ fn migrate_db_to_version(
 
 			for version in current_version..target_version {
 				info!("Database is migrating from version {version} to {}", version + 1);
-				// Once we have migrations, we will match on the version here
-				panic!("Unexpected migration from version {version}");
+				match version {
+					0 => migrate_0_to_1(db)?,
+					_ => panic!("Unexpected migration from version {version}"),
+				}
 			}
 
 			Ok(())
@@ -257,6 +488,19 @@ fn migrate_db_to_version(
 	}
 }
 
+/// Key records were stored as the bincode encoded `KeygenResultInfo` in version 0, and are wrapped
+/// in a [KeyRecord] since version 1.
+fn migrate_0_to_1(db: &PersistentKeyDB) -> Result<()> {
+	let mut batch = db.kv_db.create_batch();
+
+	for (db_key, value) in db.kv_db.get_raw_data_for_prefix(KEYGEN_DATA_PARTIAL_PREFIX) {
+		batch.put_value(&db_key, &encode_key_record(None, &db_key, &value));
+	}
+	batch.put_metadata(DB_SCHEMA_VERSION_KEY, 1u32.to_be_bytes());
+
+	batch.write().context("Failed to migrate key records to version 1")
+}
+
 // Creates a backup of the database folder to BACKUPS_DIRECTORY/backup_vx_xx_xx
 fn create_backup(path: &Path, schema_version: u32) -> Result<String, anyhow::Error> {
 	// Build the name for the new backup using the schema version and a timestamp
@@ -1,6 +1,9 @@
 use std::path::Path;
 
-use rocksdb::{ColumnFamily, ColumnFamilyDescriptor, Options, WriteBatch, DB};
+use rocksdb::{
+	BottommostLevelCompaction, ColumnFamily, ColumnFamilyDescriptor, CompactOptions, Direction,
+	IteratorMode, Options, ReadOptions, WriteBatch, DB,
+};
 use serde::{de::DeserializeOwned, Serialize};
 
 use anyhow::{Context, Result};
@@ -48,6 +51,19 @@ impl RocksDBKeyValueStore {
 		Ok(RocksDBKeyValueStore { db })
 	}
 
+	/// Open an existing database without the ability to write to it.
+	pub fn open_read_only(db_path: &Path) -> Result<Self> {
+		let db = DB::open_cf_for_read_only(
+			&Options::default(),
+			db_path,
+			[METADATA_COLUMN, DATA_COLUMN],
+			false,
+		)
+		.with_context(|| format!("Failed to open database at: {}", db_path.display()))?;
+
+		Ok(RocksDBKeyValueStore { db })
+	}
+
 	pub fn put_data<T: Serialize, K: Serialize>(
 		&self,
 		prefix: &[u8],
@@ -81,20 +97,31 @@ impl RocksDBKeyValueStore {
 			.transpose()
 	}
 
-	pub fn get_data_for_prefix<'a, K: DeserializeOwned, V: DeserializeOwned>(
+	pub fn put_raw_data(&self, key: &[u8], value: &[u8]) -> Result<()> {
+		self.db
+			.put_cf(get_data_column_handle(&self.db), key, value)
+			.context("Failed to write data to database.")
+	}
+
+	/// Returns the undecoded keys and values of all entries whose key starts with `prefix`, which
+	/// does not have to be `PREFIX_SIZE` long.
+	pub fn get_raw_data_for_prefix<'a>(
 		&'a self,
-		prefix: &[u8],
-	) -> impl Iterator<Item = (K, V)> + 'a {
+		prefix: &'a [u8],
+	) -> impl Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a {
+		// The prefix extractor can't be used for prefixes of a different length, so we must
+		// iterate in total order.
+		let mut read_options = ReadOptions::default();
+		read_options.set_total_order_seek(true);
+
 		self.db
-			.prefix_iterator_cf(get_data_column_handle(&self.db), prefix)
-			.map(|result| result.expect("prefix iterator should not fail"))
-			.map(|(key, value)| (Vec::from(&key[PREFIX_SIZE..]), value))
-			.map(|(key, value)| {
-				(
-					bincode::deserialize(&key).expect("Deserialization is not expected to fail"),
-					bincode::deserialize(&value).expect("Deserialization is not expected to fail"),
-				)
-			})
+			.iterator_cf_opt(
+				get_data_column_handle(&self.db),
+				read_options,
+				IteratorMode::From(prefix, Direction::Forward),
+			)
+			.map(|result| result.expect("iterator should not fail"))
+			.take_while(move |(key, _)| key.starts_with(prefix))
 	}
 
 	pub fn put_metadata<V>(&self, key: &[u8], value: V) -> Result<()>
@@ -112,6 +139,22 @@ impl RocksDBKeyValueStore {
 			.expect("metadata column must exist")
 	}
 
+	/// Flushes the write-ahead log and the memtables, and compacts every file of the database, so
+	/// that overwritten and deleted values no longer remain on disk.
+	pub fn purge_overwritten_data(&self) -> Result<()> {
+		self.db.flush_wal(true).context("Failed to flush the write-ahead log")?;
+
+		let mut compact_options = CompactOptions::default();
+		compact_options.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+		for column in [get_metadata_column_handle(&self.db), get_data_column_handle(&self.db)] {
+			self.db.flush_cf(column).context("Failed to flush database")?;
+			self.db
+				.compact_range_cf_opt::<&[u8], &[u8]>(column, None, None, &compact_options);
+		}
+
+		Ok(())
+	}
+
 	pub fn create_batch(&self) -> KVWriteBatch<'_> {
 		KVWriteBatch { db: &self.db, batch: WriteBatch::default() }
 	}
@@ -123,7 +166,6 @@ pub struct KVWriteBatch<'a> {
 }
 
 impl<'a> KVWriteBatch<'a> {
-	#[allow(dead_code)]
 	pub fn put_value(&mut self, key: &[u8], value: &[u8]) {
 		self.batch.put_cf(get_data_column_handle(self.db), key, value);
 	}
@@ -140,6 +182,10 @@ impl<'a> KVWriteBatch<'a> {
 		self.batch.put_cf(get_metadata_column_handle(self.db), key, value);
 	}
 
+	pub fn delete_metadata(&mut self, key: &[u8]) {
+		self.batch.delete_cf(get_metadata_column_handle(self.db), key);
+	}
+
 	pub fn write(self) -> anyhow::Result<()> {
 		self.db.write(self.batch).context("failed to write batch")
 	}
@@ -311,3 +311,160 @@ fn test_migration_to_latest_from_0() {
 
 	assert_eq!(db.get_schema_version().unwrap(), LATEST_SCHEMA_VERSION);
 }
+
+fn get_key_record<C: ChainSigning>(db: &PersistentKeyDB, key_id: &KeyId) -> KeyRecord {
+	db.kv_db
+		.get_data(&keygen_data_prefix::<C>(), key_id)
+		.unwrap()
+		.expect("Should find key record")
+}
+
+#[test]
+fn test_migration_from_0_keeps_keys() {
+	type Scheme = EthSigning;
+
+	let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+
+	// Write the key in the version 0 format
+	{
+		let db = PersistentKeyDB::open_and_migrate_to_version(&db_path, None, 0).unwrap();
+		db.kv_db
+			.put_data(
+				&keygen_data_prefix::<Scheme>(),
+				&key_id,
+				&get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>(),
+			)
+			.unwrap();
+	}
+
+	let db = PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).unwrap();
+
+	assert!(matches!(get_key_record::<Scheme>(&db, &key_id), KeyRecord::Plaintext(_)));
+	assert!(db.load_keys::<Scheme>().contains_key(&key_id));
+}
+
+#[test]
+fn key_records_are_encrypted_at_rest() {
+	type Scheme = EthSigning;
+
+	let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+	let key_encryption = KeyEncryption::Passphrase("passphrase".to_string());
+
+	{
+		let db = PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
+			&db_path,
+			None,
+			Some(&key_encryption),
+		)
+		.unwrap();
+		db.update_key::<Scheme>(
+			&key_id,
+			&get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>(),
+		);
+		assert!(matches!(get_key_record::<Scheme>(&db, &key_id), KeyRecord::Encrypted(_)));
+	}
+
+	// Can't open the db without the correct passphrase
+	assert!(PersistentKeyDB::open_and_migrate_to_latest(&db_path, None).is_err());
+	assert!(PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
+		&db_path,
+		None,
+		Some(&KeyEncryption::Passphrase("wrong passphrase".to_string())),
+	)
+	.is_err());
+
+	let db = PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
+		&db_path,
+		None,
+		Some(&key_encryption),
+	)
+	.unwrap();
+	assert!(db.load_keys::<Scheme>().contains_key(&key_id));
+}
+
+#[test]
+fn existing_key_records_are_encrypted_when_encryption_is_enabled() {
+	type Scheme = EthSigning;
+
+	let (_dir, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_id = KeyId::new(GENESIS_EPOCH, [0; 33]);
+	let key_encryption = KeyEncryption::Passphrase("passphrase".to_string());
+
+	// Write the key in the version 0 format
+	{
+		let db = PersistentKeyDB::open_and_migrate_to_version(&db_path, None, 0).unwrap();
+		db.kv_db
+			.put_data(
+				&keygen_data_prefix::<Scheme>(),
+				&key_id,
+				&get_single_key_data::<<Scheme as ChainSigning>::CryptoScheme>(),
+			)
+			.unwrap();
+	}
+
+	let db = PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
+		&db_path,
+		None,
+		Some(&key_encryption),
+	)
+	.unwrap();
+
+	assert_eq!(db.get_schema_version().unwrap(), LATEST_SCHEMA_VERSION);
+	assert!(matches!(get_key_record::<Scheme>(&db, &key_id), KeyRecord::Encrypted(_)));
+	assert!(db.load_keys::<Scheme>().contains_key(&key_id));
+}
+
+#[test]
+fn can_rotate_key_encryption() {
+	type Scheme1 = EthSigning;
+	type Scheme2 = BtcSigning;
+
+	let (dir, db_path) = new_temp_directory_with_nonexistent_file();
+	let key_id_1 = KeyId::new(GENESIS_EPOCH, [1; 33]);
+	let key_id_2 = KeyId::new(GENESIS_EPOCH, [2; 32]);
+	let old_key_encryption = KeyEncryption::Passphrase("old passphrase".to_string());
+	let new_key_encryption = {
+		let key_file = dir.path().join("key_file");
+		fs::write(&key_file, rand::random::<[u8; 32]>()).unwrap();
+		KeyEncryption::KeyFile(key_file)
+	};
+
+	let open_db = |key_encryption: Option<&KeyEncryption>| {
+		PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
+			&db_path,
+			None,
+			key_encryption,
+		)
+	};
+
+	{
+		let mut db = open_db(Some(&old_key_encryption)).unwrap();
+		db.update_key::<Scheme1>(
+			&key_id_1,
+			&get_single_key_data::<<Scheme1 as ChainSigning>::CryptoScheme>(),
+		);
+		db.update_key::<Scheme2>(
+			&key_id_2,
+			&get_single_key_data::<<Scheme2 as ChainSigning>::CryptoScheme>(),
+		);
+
+		db.rotate_key_encryption(Some(&new_key_encryption)).unwrap();
+	}
+
+	assert!(open_db(Some(&old_key_encryption)).is_err());
+	{
+		let mut db = open_db(Some(&new_key_encryption)).unwrap();
+		assert!(db.load_keys::<Scheme1>().contains_key(&key_id_1));
+		assert!(db.load_keys::<Scheme2>().contains_key(&key_id_2));
+
+		// Disable encryption
+		db.rotate_key_encryption(None).unwrap();
+		assert!(matches!(get_key_record::<Scheme1>(&db, &key_id_1), KeyRecord::Plaintext(_)));
+	}
+
+	let db = open_db(None).unwrap();
+	assert!(db.load_keys::<Scheme1>().contains_key(&key_id_1));
+	assert!(db.load_keys::<Scheme2>().contains_key(&key_id_2));
+}
@@ -49,6 +49,15 @@ impl DotRetryRpcClient {
 		Self::new_inner(scope, nodes, Some(expected_genesis_hash))
 	}
 
+	/// Creates a client for the Asset Hub parachain. Its genesis hash isn't recorded on the State
+	/// Chain, so unlike the relay chain's it isn't checked.
+	pub fn new_asset_hub(
+		scope: &Scope<'_, anyhow::Error>,
+		nodes: NodeContainer<WsHttpEndpoints>,
+	) -> Result<Self> {
+		Self::new_inner(scope, nodes, None)
+	}
+
 	fn new_inner(
 		scope: &Scope<'_, anyhow::Error>,
 		nodes: NodeContainer<WsHttpEndpoints>,
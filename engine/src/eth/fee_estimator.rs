@@ -0,0 +1,165 @@
+use ethers::types::{FeeHistory, U256};
+use pallet_cf_broadcast::AttemptCount;
+
+use crate::settings::EthFeeEstimation;
+
+/// The number of recent blocks whose priority fees are sampled.
+pub const FEE_HISTORY_BLOCK_COUNT: u64 = 10;
+
+/// Each rebroadcast attempt offers this much more than the previous one, so that transactions
+/// don't remain pending during a gas spike.
+const REBROADCAST_FEE_INCREASE_PERCENT: u64 = 20;
+
+/// The maximum number of times the fees are escalated, after which they stay constant.
+const MAX_FEE_ESCALATIONS: AttemptCount = 10;
+
+const WEI_PER_GWEI: u64 = 1_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip1559Fees {
+	pub max_fee_per_gas: U256,
+	pub max_priority_fee_per_gas: U256,
+}
+
+/// Chooses EIP-1559 fees based on the fees paid in recent blocks.
+#[derive(Debug, Clone)]
+pub struct FeeEstimator {
+	settings: EthFeeEstimation,
+}
+
+impl FeeEstimator {
+	pub fn new(settings: EthFeeEstimation) -> Self {
+		Self { settings }
+	}
+
+	/// The reward percentiles to request from `eth_feeHistory`.
+	pub fn reward_percentiles(&self) -> Vec<f64> {
+		vec![self.settings.priority_fee_percentile as f64]
+	}
+
+	/// Estimates the fees for a transaction from the result of an `eth_feeHistory` request made
+	/// with [Self::reward_percentiles]. The fees are increased for each previous attempt at
+	/// broadcasting the transaction, and never exceed the given upper bounds.
+	pub fn estimate(
+		&self,
+		fee_history: &FeeHistory,
+		attempt_count: AttemptCount,
+		max_fee_per_gas_bound: Option<U256>,
+		max_priority_fee_per_gas_bound: Option<U256>,
+	) -> Eip1559Fees {
+		// The history contains the base fee of the next block as its last entry.
+		let next_base_fee = fee_history.base_fee_per_gas.last().copied().unwrap_or_default();
+
+		let mut priority_fees = fee_history
+			.reward
+			.iter()
+			.filter_map(|rewards| rewards.first().copied())
+			.collect::<Vec<_>>();
+		priority_fees.sort();
+		let median_priority_fee =
+			priority_fees.get(priority_fees.len() / 2).copied().unwrap_or_default();
+
+		let escalation_percent =
+			100 + REBROADCAST_FEE_INCREASE_PERCENT * attempt_count.min(MAX_FEE_ESCALATIONS) as u64;
+
+		let max_priority_fee_per_gas = apply_percent(median_priority_fee, escalation_percent).min(
+			U256::from(self.settings.max_priority_fee_gwei)
+				.saturating_mul(U256::from(WEI_PER_GWEI)),
+		);
+		let max_fee_per_gas = apply_percent(
+			apply_percent(next_base_fee, self.settings.base_fee_multiplier_percent),
+			escalation_percent,
+		)
+		.saturating_add(max_priority_fee_per_gas);
+
+		let max_fee_per_gas =
+			max_fee_per_gas_bound.map_or(max_fee_per_gas, |bound| max_fee_per_gas.min(bound));
+		let max_priority_fee_per_gas = max_priority_fee_per_gas_bound
+			.map_or(max_priority_fee_per_gas, |bound| max_priority_fee_per_gas.min(bound))
+			// The priority fee is part of the max fee, so it can't be larger.
+			.min(max_fee_per_gas);
+
+		Eip1559Fees { max_fee_per_gas, max_priority_fee_per_gas }
+	}
+}
+
+fn apply_percent(value: U256, percent: u64) -> U256 {
+	value.saturating_mul(U256::from(percent)) / 100
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn fee_history(base_fees: &[u64], priority_fees: &[u64]) -> FeeHistory {
+		FeeHistory {
+			base_fee_per_gas: base_fees.iter().copied().map(U256::from).collect(),
+			gas_used_ratio: vec![0.5; priority_fees.len()],
+			oldest_block: U256::zero(),
+			reward: priority_fees.iter().map(|fee| vec![U256::from(*fee)]).collect(),
+		}
+	}
+
+	fn estimator() -> FeeEstimator {
+		FeeEstimator::new(EthFeeEstimation {
+			priority_fee_percentile: 50,
+			max_priority_fee_gwei: 1,
+			base_fee_multiplier_percent: 200,
+		})
+	}
+
+	#[test]
+	fn uses_median_priority_fee_and_next_base_fee() {
+		assert_eq!(
+			estimator().estimate(&fee_history(&[10, 20, 100], &[5, 1, 3]), 0, None, None),
+			Eip1559Fees {
+				max_fee_per_gas: U256::from(2 * 100 + 3),
+				max_priority_fee_per_gas: U256::from(3),
+			}
+		);
+	}
+
+	#[test]
+	fn fees_escalate_with_each_attempt() {
+		let history = fee_history(&[100], &[10]);
+
+		let fees = (0..3)
+			.map(|attempt| estimator().estimate(&history, attempt, None, None))
+			.collect::<Vec<_>>();
+
+		assert_eq!(
+			fees.iter().map(|fees| fees.max_priority_fee_per_gas).collect::<Vec<_>>(),
+			vec![U256::from(10), U256::from(12), U256::from(14)]
+		);
+		assert_eq!(
+			fees.iter().map(|fees| fees.max_fee_per_gas).collect::<Vec<_>>(),
+			vec![U256::from(210), U256::from(252), U256::from(294)]
+		);
+
+		// Escalation is limited.
+		assert_eq!(
+			estimator().estimate(&history, MAX_FEE_ESCALATIONS + 100, None, None),
+			estimator().estimate(&history, MAX_FEE_ESCALATIONS, None, None),
+		);
+	}
+
+	#[test]
+	fn fees_are_capped() {
+		let history = fee_history(&[100], &[10 * WEI_PER_GWEI]);
+
+		// The priority fee is capped by the settings.
+		assert_eq!(
+			estimator().estimate(&history, 0, None, None).max_priority_fee_per_gas,
+			U256::from(WEI_PER_GWEI)
+		);
+
+		// And by the given bounds.
+		assert_eq!(
+			estimator().estimate(&history, 0, Some(U256::from(50)), Some(U256::from(80))),
+			Eip1559Fees {
+				max_fee_per_gas: U256::from(50),
+				max_priority_fee_per_gas: U256::from(50),
+			}
+		);
+	}
+}
@@ -1,4 +1,5 @@
 pub mod event;
+pub mod fee_estimator;
 pub mod retry_rpc;
 pub mod rpc;
 
@@ -1,4 +1,5 @@
 pub mod address_checker;
+pub mod call_tracer;
 
 use ethers::{
 	prelude::*,
@@ -11,17 +12,19 @@ use crate::{
 	common::option_inner,
 	eth::rpc::EthRpcApi,
 	retrier::{Attempt, RequestLog, RetrierClient},
-	settings::{NodeContainer, WsHttpEndpoints},
+	settings::{EthFeeEstimation, NodeContainer, WsHttpEndpoints},
 	witness::common::chain_source::{ChainClient, Header},
 };
 use std::{path::PathBuf, time::Duration};
 
 use super::{
+	fee_estimator::{FeeEstimator, FEE_HISTORY_BLOCK_COUNT},
 	rpc::{EthRpcClient, ReconnectSubscriptionClient},
 	ConscientiousEthWebsocketBlockHeaderStream,
 };
 use crate::eth::rpc::ReconnectSubscribeApi;
 use cf_chains::Ethereum;
+use pallet_cf_broadcast::AttemptCount;
 
 use anyhow::{Context, Result};
 
@@ -29,6 +32,7 @@ use anyhow::{Context, Result};
 pub struct EthersRetryRpcClient {
 	rpc_retry_client: RetrierClient<EthRpcClient>,
 	sub_retry_client: RetrierClient<ReconnectSubscriptionClient>,
+	fee_estimator: FeeEstimator,
 }
 
 const ETHERS_RPC_TIMEOUT: Duration = Duration::from_millis(4 * 1000);
@@ -42,6 +46,7 @@ impl EthersRetryRpcClient {
 		private_key_file: PathBuf,
 		nodes: NodeContainer<WsHttpEndpoints>,
 		expected_chain_id: U256,
+		fee_estimation: EthFeeEstimation,
 	) -> Result<Self> {
 		let f_create_clients = |endpoints: WsHttpEndpoints| {
 			Result::<_, anyhow::Error>::Ok((
@@ -75,6 +80,7 @@ impl EthersRetryRpcClient {
 				ETHERS_RPC_TIMEOUT,
 				MAX_CONCURRENT_SUBMISSIONS,
 			),
+			fee_estimator: FeeEstimator::new(fee_estimation),
 		})
 	}
 }
@@ -84,6 +90,7 @@ pub trait EthersRetryRpcApi: Clone {
 	async fn broadcast_transaction(
 		&self,
 		tx: cf_chains::evm::Transaction,
+		attempt_count: AttemptCount,
 	) -> anyhow::Result<TxHash>;
 
 	async fn get_logs(&self, block_hash: H256, contract_address: H160) -> Vec<Log>;
@@ -108,25 +115,49 @@ pub trait EthersRetryRpcApi: Clone {
 
 #[async_trait::async_trait]
 impl EthersRetryRpcApi for EthersRetryRpcClient {
-	/// Estimates gas and then sends the transaction to the network.
+	/// Estimates fees and gas and then sends the transaction to the network.
+	///
+	/// The fees set on the transaction are treated as upper bounds. Fees are escalated with each
+	/// attempt so rebroadcasts are more likely to be included.
 	async fn broadcast_transaction(
 		&self,
 		tx: cf_chains::evm::Transaction,
+		attempt_count: AttemptCount,
 	) -> anyhow::Result<TxHash> {
-		let log = RequestLog::new("broadcast_transaction".to_string(), Some(format!("{tx:?}")));
+		let log = RequestLog::new(
+			"broadcast_transaction".to_string(),
+			Some(format!("{tx:?}, attempt: {attempt_count}")),
+		);
+		let fee_estimator = self.fee_estimator.clone();
 		self.rpc_retry_client
 			.request_with_limit(
 				Box::pin(move |client| {
 					let tx = tx.clone();
+					let fee_estimator = fee_estimator.clone();
 					#[allow(clippy::redundant_async_block)]
 					Box::pin(async move {
+						let fee_history = client
+							.fee_history(
+								FEE_HISTORY_BLOCK_COUNT.into(),
+								BlockNumber::Latest,
+								&fee_estimator.reward_percentiles(),
+							)
+							.await
+							.context("Failed to get fee history")?;
+						let fees = fee_estimator.estimate(
+							&fee_history,
+							attempt_count,
+							tx.max_fee_per_gas,
+							tx.max_priority_fee_per_gas,
+						);
+
 						let mut transaction_request = Eip1559TransactionRequest {
 							to: Some(NameOrAddress::Address(tx.contract)),
 							data: Some(tx.data.into()),
 							chain_id: Some(tx.chain_id.into()),
 							value: Some(tx.value),
-							max_fee_per_gas: tx.max_fee_per_gas,
-							max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+							max_fee_per_gas: Some(fees.max_fee_per_gas),
+							max_priority_fee_per_gas: Some(fees.max_priority_fee_per_gas),
 							// geth uses the latest block gas limit as an upper bound
 							gas: None,
 							access_list: AccessList::default(),
@@ -353,6 +384,7 @@ pub mod mocks {
 			async fn broadcast_transaction(
 				&self,
 				tx: cf_chains::evm::Transaction,
+				attempt_count: AttemptCount,
 			) -> anyhow::Result<TxHash>;
 
 			async fn get_logs(&self, block_hash: H256, contract_address: H160) -> Vec<Log>;
@@ -397,6 +429,7 @@ mod tests {
 					settings.eth.private_key_file,
 					settings.eth.nodes,
 					U256::from(1337u64),
+					settings.eth.fee_estimation,
 				)
 				.unwrap();
 
@@ -0,0 +1,27 @@
+use ethers::prelude::*;
+
+use crate::eth::rpc::call_tracer::{CallFrame, CallTracerRpcApi};
+
+use super::EthersRetryRpcClient;
+
+use crate::eth::retry_rpc::RequestLog;
+
+#[async_trait::async_trait]
+pub trait CallTracerRetryRpcApi {
+	async fn trace_block_calls(&self, block_hash: H256) -> Vec<CallFrame>;
+}
+
+#[async_trait::async_trait]
+impl CallTracerRetryRpcApi for EthersRetryRpcClient {
+	async fn trace_block_calls(&self, block_hash: H256) -> Vec<CallFrame> {
+		self.rpc_retry_client
+			.request(
+				Box::pin(move |client| {
+					#[allow(clippy::redundant_async_block)]
+					Box::pin(async move { client.trace_block_calls(block_hash).await })
+				}),
+				RequestLog::new("trace_block_calls".to_string(), Some(format!("{block_hash:?}"))),
+			)
+			.await
+	}
+}
@@ -1,4 +1,5 @@
 pub mod address_checker;
+pub mod call_tracer;
 
 use anyhow::bail;
 use ethers::{prelude::*, signers::Signer, types::transaction::eip2718::TypedTransaction};
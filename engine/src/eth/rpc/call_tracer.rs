@@ -0,0 +1,105 @@
+use ethers::prelude::*;
+use serde::Deserialize;
+
+use anyhow::Result;
+
+use super::EthRpcClient;
+
+/// A call made during the execution of a transaction, as reported by geth's `callTracer`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+	/// The type of the call, e.g. `CALL`, `DELEGATECALL`, `CREATE2` or `SELFDESTRUCT`.
+	#[serde(rename = "type")]
+	pub typ: String,
+	pub from: H160,
+	#[serde(default)]
+	pub to: Option<H160>,
+	#[serde(default)]
+	pub value: Option<U256>,
+	/// Set if the call reverted, in which case neither it nor any of its sub-calls had any effect.
+	#[serde(default)]
+	pub error: Option<String>,
+	#[serde(default)]
+	pub calls: Vec<CallFrame>,
+}
+
+#[derive(Deserialize)]
+struct TransactionTrace {
+	result: CallFrame,
+}
+
+#[async_trait::async_trait]
+pub trait CallTracerRpcApi {
+	/// Returns the top-level call frame of each transaction in the block.
+	async fn trace_block_calls(&self, block_hash: H256) -> Result<Vec<CallFrame>>;
+}
+
+#[async_trait::async_trait]
+impl CallTracerRpcApi for EthRpcClient {
+	async fn trace_block_calls(&self, block_hash: H256) -> Result<Vec<CallFrame>> {
+		let traces: Vec<TransactionTrace> = self
+			.signer
+			.provider()
+			.request(
+				"debug_traceBlockByHash",
+				(block_hash, serde_json::json!({ "tracer": "callTracer" })),
+			)
+			.await?;
+
+		Ok(traces.into_iter().map(|trace| trace.result).collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn deserialize_call_frames() {
+		let traces: Vec<TransactionTrace> = serde_json::from_str(
+			r#"[{
+				"txHash": "0x7f4a3b8b2b8a1b0d8b0d8b0d8b0d8b0d8b0d8b0d8b0d8b0d8b0d8b0d8b0d8b0d",
+				"result": {
+					"type": "CALL",
+					"from": "0x0000000000000000000000000000000000000001",
+					"to": "0x0000000000000000000000000000000000000002",
+					"value": "0x10",
+					"gas": "0x5208",
+					"gasUsed": "0x5208",
+					"input": "0x",
+					"calls": [{
+						"type": "CALL",
+						"from": "0x0000000000000000000000000000000000000002",
+						"to": "0x0000000000000000000000000000000000000003",
+						"value": "0x8",
+						"gas": "0x5208",
+						"gasUsed": "0x5208",
+						"input": "0x",
+						"error": "execution reverted"
+					}]
+				}
+			}]"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			traces.into_iter().map(|trace| trace.result).collect::<Vec<_>>(),
+			vec![CallFrame {
+				typ: "CALL".to_string(),
+				from: H160::from_low_u64_be(1),
+				to: Some(H160::from_low_u64_be(2)),
+				value: Some(U256::from(0x10)),
+				error: None,
+				calls: vec![CallFrame {
+					typ: "CALL".to_string(),
+					from: H160::from_low_u64_be(2),
+					to: Some(H160::from_low_u64_be(3)),
+					value: Some(U256::from(0x8)),
+					error: Some("execution reverted".to_string()),
+					calls: vec![],
+				}],
+			}]
+		);
+	}
+}
@@ -1,40 +1,367 @@
 //! Health monitor for the CFE
 //! allowing external services to query, ensuring it's online
-//! Returns a HTTP 200 response to any request on {hostname}:{port}/health
-//! Method returns a Sender, allowing graceful termination of the infinite loop
+//! Serves the following paths on {hostname}:{port}:
+//! - `/health`: Always returns a HTTP 200 response, with either INITIALISING or RUNNING
+//! - `/health/live`: Liveness probe. Fails if one of the engine's own tasks has stopped sending
+//!   heartbeats, and so the engine should be restarted. State Chain progress doesn't affect
+//!   liveness, as a syncing node or a stalled chain would otherwise restart every engine at once
+//! - `/health/ready`: Readiness probe. Fails while the engine is initialising, while the State
+//!   Chain node is syncing, and while the engine is not keeping up with the State Chain
+//! - `/health/status`: Returns the status of each of the engine's subsystems as JSON
 
-use std::{net::IpAddr, sync::Arc};
+use std::{
+	collections::BTreeMap,
+	net::IpAddr,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc, Mutex,
+	},
+	time::Duration,
+};
 
-use tracing::info;
-use utilities::task_scope;
-use warp::Filter;
+use futures::StreamExt;
+use serde::Serialize;
+use tokio::time::Instant;
+use tracing::{info, warn};
+use utilities::{
+	metrics::{AUTHORIZED_CEREMONY, P2P_ACTIVE_CONNECTIONS},
+	task_scope,
+};
+use warp::{http::StatusCode, Filter};
 
-use crate::settings;
+use crate::{
+	settings,
+	state_chain_observer::client::{base_rpc_api::BaseRpcApi, StateChainStreamApi},
+};
 
 const INITIALISING: &str = "INITIALISING";
 const RUNNING: &str = "RUNNING";
 
+const LIVE: &str = "LIVE";
+const STALLED: &str = "STALLED";
+const READY: &str = "READY";
+const NOT_READY: &str = "NOT_READY";
+
+/// The engine is not ready if it hasn't processed a State Chain block for this long.
+const STATE_CHAIN_STALE_AFTER: Duration = Duration::from_secs(60);
+
+const NODE_HEALTH_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// The task polling the State Chain node's health sends a heartbeat on each poll.
+const HEALTH_MONITOR_TASK: &str = "health_monitor";
+const HEALTH_MONITOR_STALLED_AFTER: Duration = Duration::from_secs(60);
+const NODE_HEALTH_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StateChainNodeHealth {
+	pub is_syncing: bool,
+	pub peers: usize,
+}
+
+struct ProcessedStateChainBlock {
+	number: state_chain_runtime::BlockNumber,
+	hash: state_chain_runtime::Hash,
+	processed_at: Instant,
+}
+
+struct TaskHeartbeat {
+	stalled_after: Duration,
+	last: Instant,
+}
+
+struct ExternalChainHead {
+	unreachable_after: Duration,
+	latest: Option<(u64, Instant)>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Status {
+	pub status: &'static str,
+	pub live: bool,
+	pub ready: bool,
+	/// The engine's tasks that send heartbeats, which determine whether the engine is live.
+	pub tasks: BTreeMap<&'static str, TaskStatus>,
+	pub state_chain: StateChainStatus,
+	pub external_chains: BTreeMap<&'static str, ExternalChainStatus>,
+	pub p2p: P2PStatus,
+	/// The number of authorised ceremonies in progress, by chain and then by ceremony type.
+	pub ceremonies: BTreeMap<String, BTreeMap<String, i64>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskStatus {
+	pub live: bool,
+	pub secs_since_last_heartbeat: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StateChainStatus {
+	/// `None` if the State Chain node could not be queried.
+	pub node: Option<StateChainNodeHealth>,
+	pub last_processed_block: Option<state_chain_runtime::BlockNumber>,
+	pub last_processed_block_hash: Option<state_chain_runtime::Hash>,
+	pub secs_since_last_processed_block: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExternalChainStatus {
+	/// Whether a new head has been received from the chain's RPC recently.
+	pub reachable: bool,
+	pub head: Option<u64>,
+	/// The time since the latest head was received from the chain's RPC.
+	pub secs_since_last_head: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct P2PStatus {
+	pub connected_peers: i64,
+}
+
+/// Collects the health of the engine's subsystems, as reported by the subsystems themselves.
+#[derive(Default)]
+pub struct HealthMonitor {
+	has_completed_initialising: AtomicBool,
+	tasks: Mutex<BTreeMap<&'static str, TaskHeartbeat>>,
+	last_state_chain_block: Mutex<Option<ProcessedStateChainBlock>>,
+	state_chain_node: Mutex<Option<StateChainNodeHealth>>,
+	external_chains: Mutex<BTreeMap<&'static str, ExternalChainHead>>,
+}
+
+impl HealthMonitor {
+	pub fn set_initialised(&self) {
+		self.has_completed_initialising.store(true, Ordering::Relaxed);
+	}
+
+	pub fn has_completed_initialising(&self) -> bool {
+		self.has_completed_initialising.load(Ordering::Relaxed)
+	}
+
+	/// Adds the task to the liveness check. The engine is not live if the task doesn't send a
+	/// heartbeat within `stalled_after` of the previous one, or of being registered.
+	pub fn register_task(&self, task: &'static str, stalled_after: Duration) {
+		self.tasks
+			.lock()
+			.unwrap()
+			.insert(task, TaskHeartbeat { stalled_after, last: Instant::now() });
+	}
+
+	pub fn heartbeat(&self, task: &'static str) {
+		if let Some(heartbeat) = self.tasks.lock().unwrap().get_mut(task) {
+			heartbeat.last = Instant::now();
+		}
+	}
+
+	pub fn record_state_chain_block(
+		&self,
+		number: state_chain_runtime::BlockNumber,
+		hash: state_chain_runtime::Hash,
+	) {
+		*self.last_state_chain_block.lock().unwrap() =
+			Some(ProcessedStateChainBlock { number, hash, processed_at: Instant::now() });
+	}
+
+	pub fn record_state_chain_node_health(&self, node_health: Option<StateChainNodeHealth>) {
+		*self.state_chain_node.lock().unwrap() = node_health;
+	}
+
+	/// Adds the chain to the reported status. The chain is considered unreachable if no new head
+	/// is received within `unreachable_after`.
+	pub fn register_external_chain(&self, chain: &'static str, unreachable_after: Duration) {
+		self.external_chains
+			.lock()
+			.unwrap()
+			.insert(chain, ExternalChainHead { unreachable_after, latest: None });
+	}
+
+	pub fn record_external_chain_head(&self, chain: &'static str, head: u64) {
+		if let Some(chain_head) = self.external_chains.lock().unwrap().get_mut(chain) {
+			chain_head.latest = Some((head, Instant::now()));
+		}
+	}
+
+	fn time_since_last_state_chain_block(&self) -> Option<Duration> {
+		self.last_state_chain_block
+			.lock()
+			.unwrap()
+			.as_ref()
+			.map(|block| block.processed_at.elapsed())
+	}
+
+	/// Whether all of the engine's registered tasks are still sending heartbeats.
+	pub fn is_live(&self) -> bool {
+		self.tasks
+			.lock()
+			.unwrap()
+			.values()
+			.all(|heartbeat| heartbeat.last.elapsed() < heartbeat.stalled_after)
+	}
+
+	/// Whether the engine has initialised and is keeping up with the State Chain.
+	pub fn is_ready(&self) -> bool {
+		self.has_completed_initialising() &&
+			self.time_since_last_state_chain_block()
+				.map_or(false, |elapsed| elapsed < STATE_CHAIN_STALE_AFTER) &&
+			!self.state_chain_node.lock().unwrap().map_or(false, |node| node.is_syncing)
+	}
+
+	pub fn status(&self) -> Status {
+		let tasks = self
+			.tasks
+			.lock()
+			.unwrap()
+			.iter()
+			.map(|(task, heartbeat)| {
+				let elapsed = heartbeat.last.elapsed();
+				(
+					*task,
+					TaskStatus {
+						live: elapsed < heartbeat.stalled_after,
+						secs_since_last_heartbeat: elapsed.as_secs(),
+					},
+				)
+			})
+			.collect();
+
+		let state_chain = {
+			let last_block = self.last_state_chain_block.lock().unwrap();
+			StateChainStatus {
+				node: *self.state_chain_node.lock().unwrap(),
+				last_processed_block: last_block.as_ref().map(|block| block.number),
+				last_processed_block_hash: last_block.as_ref().map(|block| block.hash),
+				secs_since_last_processed_block: last_block
+					.as_ref()
+					.map(|block| block.processed_at.elapsed().as_secs()),
+			}
+		};
+
+		let external_chains = self
+			.external_chains
+			.lock()
+			.unwrap()
+			.iter()
+			.map(|(chain, chain_head)| {
+				let since_last_head =
+					chain_head.latest.map(|(_, received_at)| received_at.elapsed());
+				(
+					*chain,
+					ExternalChainStatus {
+						reachable: since_last_head
+							.map_or(false, |elapsed| elapsed < chain_head.unreachable_after),
+						head: chain_head.latest.map(|(head, _)| head),
+						secs_since_last_head: since_last_head.map(|elapsed| elapsed.as_secs()),
+					},
+				)
+			})
+			.collect();
+
+		let mut ceremonies = BTreeMap::<String, BTreeMap<String, i64>>::new();
+		for (labels, count) in AUTHORIZED_CEREMONY.values() {
+			if let (Some(chain), Some(ceremony_type)) = (labels.get("chain"), labels.get("type")) {
+				ceremonies
+					.entry(chain.clone())
+					.or_default()
+					.insert(ceremony_type.clone(), count);
+			}
+		}
+
+		Status {
+			status: if self.has_completed_initialising() { RUNNING } else { INITIALISING },
+			live: self.is_live(),
+			ready: self.is_ready(),
+			tasks,
+			state_chain,
+			external_chains,
+			p2p: P2PStatus { connected_peers: P2P_ACTIVE_CONNECTIONS.prom_metric.get() },
+			ceremonies,
+		}
+	}
+
+	/// Records each State Chain block the engine processes, and periodically polls the health of
+	/// the State Chain node, sending a heartbeat on each poll.
+	pub async fn monitor_state_chain<BaseRpcClient: BaseRpcApi + Send + Sync>(
+		self: Arc<Self>,
+		mut state_chain_stream: impl StateChainStreamApi,
+		base_rpc_client: Arc<BaseRpcClient>,
+	) -> anyhow::Result<()> {
+		self.register_task(HEALTH_MONITOR_TASK, HEALTH_MONITOR_STALLED_AFTER);
+
+		let block = *state_chain_stream.cache();
+		self.record_state_chain_block(block.number, block.hash);
+
+		let mut poll_interval = tokio::time::interval(NODE_HEALTH_POLL_INTERVAL);
+		loop {
+			tokio::select! {
+				block = state_chain_stream.next() => match block {
+					Some(block) => self.record_state_chain_block(block.number, block.hash),
+					None => break Ok(()),
+				},
+				_ = poll_interval.tick() => {
+					self.heartbeat(HEALTH_MONITOR_TASK);
+					self.record_state_chain_node_health(
+						match tokio::time::timeout(NODE_HEALTH_TIMEOUT, base_rpc_client.health()).await {
+							Ok(Ok(health)) => Some(StateChainNodeHealth {
+								is_syncing: health.is_syncing,
+								peers: health.peers,
+							}),
+							Ok(Err(error)) => {
+								warn!("Failed to query the State Chain node's health: {error}");
+								None
+							},
+							Err(_) => {
+								warn!("Timed out querying the State Chain node's health");
+								None
+							},
+						},
+					);
+				}
+			}
+		}
+	}
+}
+
+fn probe_reply(
+	passing: bool,
+	passing_text: &'static str,
+	failing_text: &'static str,
+) -> warp::reply::WithStatus<&'static str> {
+	if passing {
+		warp::reply::with_status(passing_text, StatusCode::OK)
+	} else {
+		warp::reply::with_status(failing_text, StatusCode::SERVICE_UNAVAILABLE)
+	}
+}
+
 #[tracing::instrument(name = "health-check", skip_all)]
 pub async fn start<'a, 'env>(
 	scope: &'a task_scope::Scope<'env, anyhow::Error>,
 	health_check_settings: &'a settings::HealthCheck,
-	has_completed_initialising: Arc<std::sync::atomic::AtomicBool>,
+	health_monitor: Arc<HealthMonitor>,
 ) -> Result<(), anyhow::Error> {
 	info!("Starting");
 
-	const PATH: &str = "health";
-
-	let future =
-		warp::serve(warp::any().and(warp::path(PATH)).and(warp::path::end()).map(move || {
+	let health = warp::path!("health").map({
+		let health_monitor = health_monitor.clone();
+		move || {
 			warp::reply::with_status(
-				if has_completed_initialising.load(std::sync::atomic::Ordering::Relaxed) {
-					RUNNING
-				} else {
-					INITIALISING
-				},
-				warp::http::StatusCode::OK,
+				if health_monitor.has_completed_initialising() { RUNNING } else { INITIALISING },
+				StatusCode::OK,
 			)
-		}))
+		}
+	});
+
+	let live = warp::path!("health" / "live").map({
+		let health_monitor = health_monitor.clone();
+		move || probe_reply(health_monitor.is_live(), LIVE, STALLED)
+	});
+
+	let ready = warp::path!("health" / "ready").map({
+		let health_monitor = health_monitor.clone();
+		move || probe_reply(health_monitor.is_ready(), READY, NOT_READY)
+	});
+
+	let status =
+		warp::path!("health" / "status").map(move || warp::reply::json(&health_monitor.status()));
+
+	let future = warp::serve(health.or(live).or(ready).or(status))
 		.bind((health_check_settings.hostname.parse::<IpAddr>()?, health_check_settings.port));
 
 	scope.spawn_weak(async move {
@@ -60,22 +387,30 @@ mod tests {
 
 		task_scope::task_scope(|scope| {
 			async {
-				let has_completed_initialising =
-					Arc::new(std::sync::atomic::AtomicBool::new(false));
-				start(scope, &health_check, has_completed_initialising.clone()).await.unwrap();
+				let health_monitor = Arc::new(HealthMonitor::default());
+				health_monitor.register_external_chain("Ethereum", Duration::from_secs(60));
+				start(scope, &health_check, health_monitor.clone()).await.unwrap();
 
-				let request_test = |path: &'static str,
-				                    expected_status: reqwest::StatusCode,
-				                    expected_text: &'static str| {
+				let request = |path: &'static str| {
 					let health_check = health_check.clone();
 
 					async move {
-						let resp = reqwest::get(&format!(
+						reqwest::get(&format!(
 							"http://{}:{}/{}",
 							&health_check.hostname, &health_check.port, path
 						))
 						.await
-						.unwrap();
+						.unwrap()
+					}
+				};
+
+				let request_test = |path: &'static str,
+				                    expected_status: reqwest::StatusCode,
+				                    expected_text: &'static str| {
+					let request = request.clone();
+
+					async move {
+						let resp = request(path).await;
 
 						assert_eq!(expected_status, resp.status());
 						assert_eq!(resp.text().await.unwrap(), expected_text);
@@ -84,11 +419,47 @@ mod tests {
 
 				// starts with `has_completed_initialising` set to false
 				request_test("health", reqwest::StatusCode::OK, INITIALISING).await;
+				request_test("health/live", reqwest::StatusCode::OK, LIVE).await;
+				request_test("health/ready", reqwest::StatusCode::SERVICE_UNAVAILABLE, NOT_READY)
+					.await;
 				request_test("invalid", reqwest::StatusCode::NOT_FOUND, "").await;
 
-				has_completed_initialising.store(true, std::sync::atomic::Ordering::Relaxed);
+				health_monitor.set_initialised();
+				health_monitor.register_task("p2p", Duration::from_secs(60));
 
 				request_test("health", reqwest::StatusCode::OK, RUNNING).await;
+				// No State Chain block has been processed yet
+				request_test("health/live", reqwest::StatusCode::OK, LIVE).await;
+				request_test("health/ready", reqwest::StatusCode::SERVICE_UNAVAILABLE, NOT_READY)
+					.await;
+
+				health_monitor.record_state_chain_block(10, Default::default());
+				health_monitor.record_external_chain_head("Ethereum", 100);
+
+				request_test("health/live", reqwest::StatusCode::OK, LIVE).await;
+				request_test("health/ready", reqwest::StatusCode::OK, READY).await;
+
+				health_monitor.record_state_chain_node_health(Some(StateChainNodeHealth {
+					is_syncing: true,
+					peers: 3,
+				}));
+
+				request_test("health/ready", reqwest::StatusCode::SERVICE_UNAVAILABLE, NOT_READY)
+					.await;
+
+				let resp = request("health/status").await;
+				assert_eq!(reqwest::StatusCode::OK, resp.status());
+				let status: serde_json::Value = resp.json().await.unwrap();
+				assert_eq!(status["status"], RUNNING);
+				assert_eq!(status["live"], true);
+				assert_eq!(status["ready"], false);
+				assert_eq!(status["tasks"]["p2p"]["live"], true);
+				assert_eq!(status["state_chain"]["node"]["is_syncing"], true);
+				assert_eq!(status["state_chain"]["node"]["peers"], 3);
+				assert_eq!(status["state_chain"]["last_processed_block"], 10);
+				assert_eq!(status["external_chains"]["Ethereum"]["reachable"], true);
+				assert_eq!(status["external_chains"]["Ethereum"]["head"], 100);
+				assert!(status["p2p"]["connected_peers"].is_number());
 
 				Ok(())
 			}
@@ -97,4 +468,51 @@ mod tests {
 		.await
 		.unwrap();
 	}
+
+	#[tokio::test(start_paused = true)]
+	async fn liveness_depends_only_on_task_heartbeats() {
+		let health_monitor = HealthMonitor::default();
+		health_monitor.register_task("p2p", Duration::from_secs(180));
+		health_monitor.register_external_chain("Bitcoin", Duration::from_secs(3600));
+		health_monitor.set_initialised();
+		health_monitor.record_state_chain_block(1, Default::default());
+		health_monitor.record_external_chain_head("Bitcoin", 800_000);
+
+		assert!(health_monitor.is_live());
+		assert!(health_monitor.is_ready());
+
+		tokio::time::advance(STATE_CHAIN_STALE_AFTER).await;
+
+		assert!(health_monitor.is_live());
+		assert!(!health_monitor.is_ready());
+		assert!(health_monitor.status().external_chains["Bitcoin"].reachable);
+
+		// The State Chain not making progress doesn't affect liveness while the tasks send
+		// heartbeats
+		for _ in 0..5 {
+			tokio::time::advance(Duration::from_secs(120)).await;
+			health_monitor.heartbeat("p2p");
+			assert!(health_monitor.is_live());
+		}
+		assert!(!health_monitor.is_ready());
+
+		tokio::time::advance(Duration::from_secs(180)).await;
+
+		assert!(!health_monitor.is_live());
+		assert!(!health_monitor.status().tasks["p2p"].live);
+
+		tokio::time::advance(Duration::from_secs(3600)).await;
+
+		let status = health_monitor.status();
+		assert!(!status.external_chains["Bitcoin"].reachable);
+		assert_eq!(status.external_chains["Bitcoin"].head, Some(800_000));
+		assert_eq!(status.external_chains["Bitcoin"].secs_since_last_head, Some(4440));
+
+		health_monitor.heartbeat("p2p");
+		assert!(health_monitor.is_live());
+
+		// Processing a new block makes the engine ready again
+		health_monitor.record_state_chain_block(2, Default::default());
+		assert!(health_monitor.is_ready());
+	}
 }
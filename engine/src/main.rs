@@ -1,13 +1,14 @@
-use anyhow::Context;
+use anyhow::{bail, Context};
 use cf_chains::dot::PolkadotHash;
 use cf_primitives::{AccountRole, SemVer};
 use chainflip_engine::{
 	btc::retry_rpc::BtcRetryRpcClient,
-	db::{KeyStore, PersistentKeyDB},
+	db::{key_backup, KeyStore, PersistentKeyDB},
 	dot::retry_rpc::DotRetryRpcClient,
 	eth::retry_rpc::EthersRetryRpcClient,
-	health, p2p,
-	settings::{CommandLineOptions, Settings, DEFAULT_SETTINGS_DIR},
+	health::{self, HealthMonitor},
+	p2p,
+	settings::{CommandLineOptions, EngineCommand, KeyEncryption, Settings, DEFAULT_SETTINGS_DIR},
 	state_chain_observer::{
 		self,
 		client::{
@@ -20,10 +21,7 @@ use chainflip_node::chain_spec::use_chainflip_account_id_encoding;
 use clap::Parser;
 use futures::FutureExt;
 use multisig::{self, bitcoin::BtcSigning, eth::EthSigning, polkadot::PolkadotSigning};
-use std::{
-	sync::{atomic::AtomicBool, Arc},
-	time::Duration,
-};
+use std::{sync::Arc, time::Duration};
 use utilities::{metrics, task_scope::task_scope, CachedStream};
 
 lazy_static::lazy_static! {
@@ -39,11 +37,16 @@ async fn main() -> anyhow::Result<()> {
 	use_chainflip_account_id_encoding();
 
 	let opts = CommandLineOptions::parse();
+	let command = opts.cmd.clone();
 
 	// the settings directory from opts.config_root that we'll use to read the settings file
 	let settings = Settings::new_with_settings_dir(DEFAULT_SETTINGS_DIR, opts)
 		.context("Error reading settings")?;
 
+	if let Some(command) = command {
+		return run_command(command, settings)
+	}
+
 	// Note: the greeting should only be printed in normal mode (i.e. not for short-lived commands
 	// like `--version`), so we execute it only after the settings have been parsed.
 	utilities::print_start_and_end!(async run_main(settings));
@@ -51,13 +54,73 @@ async fn main() -> anyhow::Result<()> {
 	Ok(())
 }
 
+fn run_command(command: EngineCommand, settings: Settings) -> anyhow::Result<()> {
+	match command {
+		EngineCommand::RotateKeyEncryption { new_passphrase, new_key_file, disable } => {
+			let new_key_encryption = match (new_passphrase, new_key_file, disable) {
+				(Some(passphrase), None, false) => Some(KeyEncryption::Passphrase(passphrase)),
+				(None, Some(key_file), false) => Some(KeyEncryption::KeyFile(key_file)),
+				(None, None, true) => None,
+				_ => bail!(
+					"Exactly one of --new-passphrase, --new-key-file or --disable is required"
+				),
+			};
+
+			PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
+				&settings.signing.db_file,
+				None,
+				settings.signing.key_encryption.as_ref(),
+			)
+			.context("Failed to open database")?
+			.rotate_key_encryption(new_key_encryption.as_ref())
+			.context("Failed to rotate key encryption")?;
+
+			println!(
+				"Rotated the key encryption of {}. Update the signing.key_encryption setting before restarting the engine.",
+				settings.signing.db_file.display()
+			);
+		},
+		EngineCommand::ExportKeys { output, epoch, key_ids, passphrase } => {
+			let db = PersistentKeyDB::open_read_only_with_key_encryption(
+				&settings.signing.db_file,
+				settings.signing.key_encryption.as_ref(),
+			)
+			.context("Failed to open database")?;
+
+			let key_count = key_backup::export_keys(
+				&db,
+				epoch,
+				&key_ids,
+				&KeyEncryption::Passphrase(passphrase),
+				&output,
+			)
+			.context("Failed to export keys")?;
+
+			println!("Exported {key_count} keys to {}", output.display());
+		},
+		EngineCommand::ImportKeys { input, passphrase } => {
+			let key_count = key_backup::import_keys(
+				&settings.signing.db_file,
+				settings.signing.key_encryption.as_ref(),
+				&KeyEncryption::Passphrase(passphrase),
+				&input,
+			)
+			.context("Failed to import keys")?;
+
+			println!("Imported {key_count} keys into {}", settings.signing.db_file.display());
+		},
+	}
+
+	Ok(())
+}
+
 async fn run_main(settings: Settings) -> anyhow::Result<()> {
 	task_scope(|scope| {
 		async move {
 			let mut start_logger_server_fn =
 				Some(utilities::logging::init_json_logger(settings.logging.clone()).await);
 
-			let has_completed_initialising = Arc::new(AtomicBool::new(false));
+			let health_monitor = Arc::new(HealthMonitor::default());
 
 			let (state_chain_stream, unfinalised_state_chain_stream, state_chain_client) =
 				state_chain_observer::client::StateChainClient::connect_with_account(
@@ -78,8 +141,11 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 			start_logger_server_fn.take().expect("only called once")(scope);
 
 			if let Some(health_check_settings) = &settings.health_check {
-				health::start(scope, health_check_settings, has_completed_initialising.clone())
-					.await?;
+				health::start(scope, health_check_settings, health_monitor.clone()).await?;
+				scope.spawn_weak(health_monitor.clone().monitor_state_chain(
+					state_chain_stream.clone(),
+					state_chain_client.base_rpc_client.clone(),
+				));
 			}
 
 			if let Some(prometheus_settings) = &settings.prometheus {
@@ -87,9 +153,10 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 			}
 
 			let db = Arc::new(
-				PersistentKeyDB::open_and_migrate_to_latest(
+				PersistentKeyDB::open_and_migrate_to_latest_with_key_encryption(
 					&settings.signing.db_file,
 					Some(state_chain_client.genesis_hash()),
+					settings.signing.key_encryption.as_ref(),
 				)
 				.context("Failed to open database")?,
 			);
@@ -108,12 +175,19 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 				state_chain_client.clone(),
 				settings.node_p2p.clone(),
 				state_chain_stream.cache().hash,
+				health_monitor.clone(),
 			)
 			.await
 			.context("Failed to start p2p")?;
 
 			scope.spawn(p2p_fut);
 
+			let nonce_preprocessing_batch_size = settings
+				.signing
+				.nonce_preprocessing
+				.as_ref()
+				.map(|nonce_preprocessing| nonce_preprocessing.batch_size);
+
 			let (eth_multisig_client, eth_multisig_client_backend_future) =
 				chainflip_engine::multisig::start_client::<EthSigning>(
 					state_chain_client.account_id(),
@@ -127,6 +201,7 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 						>>(state_chain_stream.cache().hash)
 						.await
 						.context("Failed to get Ethereum CeremonyIdCounter from SC")?,
+					nonce_preprocessing_batch_size,
 				);
 
 			scope.spawn(eth_multisig_client_backend_future);
@@ -144,6 +219,7 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 						>>(state_chain_stream.cache().hash)
 						.await
 						.context("Failed to get Polkadot CeremonyIdCounter from SC")?,
+					nonce_preprocessing_batch_size,
 				);
 
 			scope.spawn(dot_multisig_client_backend_future);
@@ -161,6 +237,7 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 						>>(state_chain_stream.cache().hash)
 						.await
 						.context("Failed to get Bitcoin CeremonyIdCounter from SC")?,
+					nonce_preprocessing_batch_size,
 				);
 
 			scope.spawn(btc_multisig_client_backend_future);
@@ -180,6 +257,7 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 					settings.eth.private_key_file,
 					settings.eth.nodes,
 					expected_eth_chain_id,
+					settings.eth.fee_estimation,
 				)?
 			};
 			let btc_client = {
@@ -204,16 +282,59 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 				);
 				DotRetryRpcClient::new(scope, settings.dot.nodes, expected_dot_genesis_hash)?
 			};
+			let asset_hub_client = match settings.dot.asset_hub {
+				Some(asset_hub_nodes) =>
+					Some(DotRetryRpcClient::new_asset_hub(scope, asset_hub_nodes)?),
+				None => {
+					tracing::warn!(
+						"No Asset Hub node is configured. Assets held on Asset Hub won't be witnessed."
+					);
+					None
+				},
+			};
+
+			let arb_client = match settings.arb {
+				Some(arb_settings) => {
+					let expected_arb_chain_id = web3::types::U256::from(
+						state_chain_client
+							.storage_value::<pallet_cf_environment::ArbitrumChainId<state_chain_runtime::Runtime>>(
+								state_chain_client.latest_finalized_block().hash,
+							)
+							.await
+							.expect(STATE_CHAIN_CONNECTION),
+					);
+					Some((
+						EthersRetryRpcClient::new(
+							scope,
+							arb_settings.private_key_file,
+							arb_settings.nodes,
+							expected_arb_chain_id,
+							arb_settings.fee_estimation,
+						)?,
+						arb_settings.native_deposit_witnessing,
+					))
+				},
+				None => {
+					tracing::warn!(
+						"No Arbitrum node is configured, Arbitrum will not be witnessed or broadcast to"
+					);
+					None
+				},
+			};
 
 			witness::start::start(
 				scope,
 				eth_client.clone(),
+				settings.eth.native_deposit_witnessing,
 				btc_client.clone(),
 				dot_client.clone(),
+				asset_hub_client,
+				arb_client.clone(),
 				state_chain_client.clone(),
 				state_chain_stream.clone(),
 				unfinalised_state_chain_stream.clone(),
 				db.clone(),
+				health_monitor.clone(),
 			)
 			.await?;
 
@@ -223,15 +344,17 @@ async fn run_main(settings: Settings) -> anyhow::Result<()> {
 				eth_client,
 				dot_client,
 				btc_client,
+				arb_client.map(|(arb_client, _)| arb_client),
 				eth_multisig_client,
 				dot_multisig_client,
 				btc_multisig_client,
 				peer_update_sender,
+				health_monitor.clone(),
 			));
 
 			p2p_ready_receiver.await.unwrap();
 
-			has_completed_initialising.store(true, std::sync::atomic::Ordering::Relaxed);
+			health_monitor.set_initialised();
 
 			Ok(())
 		}
@@ -17,6 +17,7 @@ pub fn start_client<C: ChainSigning>(
 	incoming_p2p_message_receiver: MultisigMessageReceiver<<C as ChainSigning>::ChainCrypto>,
 	outgoing_p2p_message_sender: MultisigMessageSender<<C as ChainSigning>::ChainCrypto>,
 	latest_ceremony_id: CeremonyId,
+	nonce_preprocessing_batch_size: Option<usize>,
 ) -> (MultisigClient<C, KeyStore<C>>, impl futures::Future<Output = Result<()>> + Send) {
 	info!("Starting {} MultisigClient", C::NAME);
 
@@ -29,12 +30,16 @@ pub fn start_client<C: ChainSigning>(
 	let multisig_client_backend_future = {
 		use multisig::client::ceremony_manager::CeremonyManager;
 
-		let ceremony_manager = CeremonyManager::<C>::new(
+		let mut ceremony_manager = CeremonyManager::<C>::new(
 			my_account_id,
 			outgoing_p2p_message_sender.0,
 			latest_ceremony_id,
 		);
 
+		if let Some(batch_size) = nonce_preprocessing_batch_size {
+			ceremony_manager = ceremony_manager.with_signing_preprocessing(batch_size);
+		}
+
 		ceremony_manager
 			.run(ceremony_request_receiver, incoming_p2p_message_receiver.0)
 			.instrument(info_span!("MultisigClient", chain = C::NAME))
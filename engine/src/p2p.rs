@@ -9,6 +9,7 @@ use std::{
 };
 
 use crate::{
+	health::HealthMonitor,
 	p2p::core::ed25519_secret_key_to_x25519_secret_key,
 	settings::P2P as P2PSettings,
 	state_chain_observer::client::{
@@ -91,6 +92,7 @@ pub async fn start<StateChainClient>(
 	state_chain_client: Arc<StateChainClient>,
 	settings: P2PSettings,
 	initial_block_hash: H256,
+	health_monitor: Arc<HealthMonitor>,
 ) -> anyhow::Result<(
 	MultisigMessageSender<EvmCrypto>,
 	MultisigMessageReceiver<EvmCrypto>,
@@ -177,6 +179,7 @@ where
 					incoming_message_sender,
 					outgoing_message_receiver,
 					peer_update_receiver,
+					health_monitor,
 				)
 				.await;
 
@@ -26,7 +26,10 @@ use utilities::{
 };
 use x25519_dalek::StaticSecret;
 
-use crate::p2p::{pk_to_string, OutgoingMultisigStageMessages};
+use crate::{
+	health::HealthMonitor,
+	p2p::{pk_to_string, OutgoingMultisigStageMessages},
+};
 use monitor::MonitorEvent;
 
 use socket::{ConnectedOutgoingSocket, OutgoingSocket, RECONNECT_INTERVAL, RECONNECT_INTERVAL_MAX};
@@ -44,6 +47,8 @@ const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(3);
 pub const MAX_INACTIVITY_THRESHOLD: Duration = Duration::from_secs(60 * 60);
 /// How often to check for "stale" connections
 pub const ACTIVITY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// The control loop sends a heartbeat to the health monitor on each activity check
+const HEALTH_TASK: &str = "p2p";
 
 #[derive(Clone)]
 pub struct X25519KeyPair {
@@ -247,6 +252,7 @@ struct P2PContext {
 	/// This is how we communicate with the "monitor" thread
 	monitor_handle: monitor::MonitorHandle,
 	our_account_id: AccountId,
+	health_monitor: Arc<HealthMonitor>,
 	/// NOTE: zmq context is intentionally declared at the bottom of the struct
 	/// to ensure its destructor is called after that of any zmq sockets
 	zmq_context: zmq::Context,
@@ -260,6 +266,7 @@ pub(super) async fn start(
 	incoming_message_sender: UnboundedSender<(AccountId, Vec<u8>)>,
 	outgoing_message_receiver: UnboundedReceiver<OutgoingMultisigStageMessages>,
 	peer_update_receiver: UnboundedReceiver<PeerUpdate>,
+	health_monitor: Arc<HealthMonitor>,
 ) {
 	debug!("Our derived x25519 pubkey: {}", pk_to_string(&p2p_key.encryption_key.public_key));
 
@@ -284,6 +291,7 @@ pub(super) async fn start(
 		reconnect_context: ReconnectContext::new(reconnect_sender),
 		incoming_message_sender,
 		our_account_id,
+		health_monitor,
 	};
 
 	debug!("Registering peer info for {} peers", current_peers.len());
@@ -319,6 +327,7 @@ impl P2PContext {
 		mut reconnect_receiver: UnboundedReceiver<AccountId>,
 	) {
 		let mut check_activity_interval = make_periodic_tick(ACTIVITY_CHECK_INTERVAL, false);
+		self.health_monitor.register_task(HEALTH_TASK, 3 * ACTIVITY_CHECK_INTERVAL);
 
 		loop {
 			tokio::select! {
@@ -341,6 +350,7 @@ impl P2PContext {
 				}
 				_ = check_activity_interval.tick() => {
 					self.check_activity();
+					self.health_monitor.heartbeat(HEALTH_TASK);
 				}
 			}
 		}
@@ -7,7 +7,9 @@ use std::{
 };
 
 use anyhow::{bail, Context};
+use cf_primitives::EpochIndex;
 use config::{Config, ConfigBuilder, ConfigError, Environment, File, Map, Source, Value};
+use multisig::KeyId;
 use serde::{de, Deserialize, Deserializer};
 
 pub use anyhow::Result;
@@ -15,7 +17,7 @@ use regex::Regex;
 use sp_runtime::DeserializeOwned;
 use url::Url;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use utilities::{
 	logging::LoggingSettings, metrics::Prometheus, redact_endpoint_secret::SecretUrl, Port,
 };
@@ -93,11 +95,70 @@ pub struct Eth {
 	pub nodes: NodeContainer<WsHttpEndpoints>,
 	#[serde(deserialize_with = "deser_path")]
 	pub private_key_file: PathBuf,
+	#[serde(default)]
+	pub fee_estimation: EthFeeEstimation,
+	#[serde(default)]
+	pub native_deposit_witnessing: NativeDepositWitnessing,
+}
+
+/// How deposits of the native asset to deposit channels are detected. Both produce the same
+/// deposit witnesses, so each node can choose either.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NativeDepositWitnessing {
+	/// Compare the balances of deposit channels between blocks, using the AddressChecker
+	/// contract, and account for the `FetchedNative` events emitted by the Vault.
+	#[default]
+	AddressChecker,
+	/// Trace all transactions with the node's call tracer, and witness every transfer to a deposit
+	/// channel. Requires the node to expose the `debug` namespace.
+	CallTracer,
 }
 
 impl Eth {
 	pub fn validate_settings(&self) -> Result<(), ConfigError> {
-		self.nodes.validate()
+		self.nodes.validate()?;
+		self.fee_estimation.validate()
+	}
+}
+
+/// Determines the EIP-1559 fees the engine offers when broadcasting transactions.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct EthFeeEstimation {
+	/// The percentile of priority fees paid in recent blocks that we aim to match.
+	pub priority_fee_percentile: u8,
+	/// Upper bound on the priority fee we are willing to pay, in gwei.
+	pub max_priority_fee_gwei: u64,
+	/// Applied to the next block's base fee to allow for base fee increases before inclusion.
+	pub base_fee_multiplier_percent: u64,
+}
+
+impl Default for EthFeeEstimation {
+	fn default() -> Self {
+		Self {
+			priority_fee_percentile: 50,
+			max_priority_fee_gwei: 100,
+			base_fee_multiplier_percent: 150,
+		}
+	}
+}
+
+impl EthFeeEstimation {
+	pub fn validate(&self) -> Result<(), ConfigError> {
+		if self.priority_fee_percentile > 100 {
+			return Err(ConfigError::Message(format!(
+				"priority_fee_percentile must be at most 100, got {}",
+				self.priority_fee_percentile
+			)))
+		}
+		if self.base_fee_multiplier_percent < 100 {
+			return Err(ConfigError::Message(format!(
+				"base_fee_multiplier_percent must be at least 100, got {}",
+				self.base_fee_multiplier_percent
+			)))
+		}
+		Ok(())
 	}
 }
 
@@ -105,11 +166,17 @@ impl Eth {
 pub struct Dot {
 	#[serde(flatten)]
 	pub nodes: NodeContainer<WsHttpEndpoints>,
+	/// Nodes of the Asset Hub parachain. Without these, deposits of assets held on Asset Hub are
+	/// not witnessed, and neither is the outcome of egresses of these assets.
+	pub asset_hub: Option<NodeContainer<WsHttpEndpoints>>,
 }
 
 impl Dot {
 	pub fn validate_settings(&self) -> Result<(), ConfigError> {
 		self.nodes.validate()?;
+		if let Some(asset_hub_nodes) = &self.asset_hub {
+			asset_hub_nodes.validate()?;
+		}
 
 		// Check that all endpoints have a port number
 		let validate_dot_endpoints = |endpoints: &WsHttpEndpoints| -> Result<(), ConfigError> {
@@ -121,9 +188,11 @@ impl Dot {
 					))
 				})
 		};
-		validate_dot_endpoints(&self.nodes.primary)?;
-		if let Some(backup) = &self.nodes.backup {
-			validate_dot_endpoints(backup)?;
+		for nodes in std::iter::once(&self.nodes).chain(&self.asset_hub) {
+			validate_dot_endpoints(&nodes.primary)?;
+			if let Some(backup) = &nodes.backup {
+				validate_dot_endpoints(backup)?;
+			}
 		}
 		Ok(())
 	}
@@ -178,6 +247,38 @@ pub struct HealthCheck {
 pub struct Signing {
 	#[serde(deserialize_with = "deser_path")]
 	pub db_file: PathBuf,
+	/// If set, the key shares in the database are encrypted at rest.
+	#[serde(default)]
+	pub key_encryption: Option<KeyEncryption>,
+	/// If set, signing nonces are generated ahead of time, which allows most signing ceremonies
+	/// to complete in two rounds instead of four.
+	#[serde(default)]
+	pub nonce_preprocessing: Option<NoncePreprocessing>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct NoncePreprocessing {
+	/// The number of nonces generated by each preprocessing ceremony. Nonces are only used if
+	/// all signers have preprocessing enabled with the same batch size.
+	pub batch_size: usize,
+}
+
+/// The secret that the key used to encrypt the key shares in the database is derived from.
+#[derive(Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyEncryption {
+	Passphrase(String),
+	KeyFile(#[serde(deserialize_with = "deser_path")] PathBuf),
+}
+
+impl fmt::Debug for KeyEncryption {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			KeyEncryption::Passphrase(_) =>
+				f.debug_tuple("Passphrase").field(&"<redacted>").finish(),
+			KeyEncryption::KeyFile(path) => f.debug_tuple("KeyFile").field(path).finish(),
+		}
+	}
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
@@ -188,6 +289,8 @@ pub struct Settings {
 	pub eth: Eth,
 	pub dot: Dot,
 	pub btc: Btc,
+	/// Arbitrum is optional: it's only witnessed and broadcast to if it's configured.
+	pub arb: Option<Eth>,
 
 	pub health_check: Option<HealthCheck>,
 	pub prometheus: Option<Prometheus>,
@@ -219,6 +322,22 @@ pub struct EthOptions {
 	pub eth_private_key_file: Option<PathBuf>,
 }
 
+#[derive(Parser, Debug, Clone, Default)]
+pub struct ArbOptions {
+	#[clap(long = "arb.rpc.ws_endpoint")]
+	pub arb_ws_endpoint: Option<String>,
+	#[clap(long = "arb.rpc.http_endpoint")]
+	pub arb_http_endpoint: Option<String>,
+
+	#[clap(long = "arb.backup_rpc.ws_endpoint")]
+	pub arb_backup_ws_endpoint: Option<String>,
+	#[clap(long = "arb.backup_rpc.http_endpoint")]
+	pub arb_backup_http_endpoint: Option<String>,
+
+	#[clap(long = "arb.private_key_file")]
+	pub arb_private_key_file: Option<PathBuf>,
+}
+
 #[derive(Parser, Debug, Clone, Default)]
 pub struct DotOptions {
 	#[clap(long = "dot.rpc.ws_endpoint")]
@@ -230,6 +349,11 @@ pub struct DotOptions {
 	pub dot_backup_ws_endpoint: Option<String>,
 	#[clap(long = "dot.backup_rpc.http_endpoint")]
 	pub dot_backup_http_endpoint: Option<String>,
+
+	#[clap(long = "dot.asset_hub.rpc.ws_endpoint")]
+	pub dot_asset_hub_ws_endpoint: Option<String>,
+	#[clap(long = "dot.asset_hub.rpc.http_endpoint")]
+	pub dot_asset_hub_http_endpoint: Option<String>,
 }
 
 #[derive(Parser, Debug, Clone, Default)]
@@ -283,6 +407,9 @@ pub struct CommandLineOptions {
 	#[clap(flatten)]
 	pub btc_opts: BtcOptions,
 
+	#[clap(flatten)]
+	pub arb_opts: ArbOptions,
+
 	// Health Check Settings
 	#[clap(long = "health_check.hostname")]
 	pub health_check_hostname: Option<String>,
@@ -298,6 +425,8 @@ pub struct CommandLineOptions {
 	// Signing Settings
 	#[clap(long = "signing.db_file", parse(from_os_str))]
 	pub signing_db_file: Option<PathBuf>,
+	#[clap(long = "signing.key_encryption.key_file", parse(from_os_str))]
+	pub signing_key_encryption_key_file: Option<PathBuf>,
 
 	// Logging settings
 	#[clap(long = "logging.span_lifecycle")]
@@ -305,6 +434,54 @@ pub struct CommandLineOptions {
 
 	#[clap(long = "logging.command_server_port")]
 	pub logging_command_server_port: Option<Port>,
+
+	#[clap(subcommand)]
+	pub cmd: Option<EngineCommand>,
+}
+
+/// Maintenance commands. These operate on the local database and exit, instead of starting the
+/// engine, so the engine must not be running at the same time.
+#[derive(Subcommand, Debug, Clone)]
+pub enum EngineCommand {
+	/// Re-encrypt the key shares in the database with a new wrapping key. The current wrapping
+	/// key is derived from the `signing.key_encryption` setting.
+	RotateKeyEncryption {
+		/// Derive the new wrapping key from this passphrase.
+		#[clap(long, env = "NEW_KEY_ENCRYPTION_PASSPHRASE", hide_env_values = true)]
+		new_passphrase: Option<String>,
+		/// Derive the new wrapping key from the contents of this file.
+		#[clap(long, parse(from_os_str), conflicts_with = "new_passphrase")]
+		new_key_file: Option<PathBuf>,
+		/// Decrypt the key shares and store them in plaintext.
+		#[clap(long, conflicts_with_all = &["new_passphrase", "new_key_file"])]
+		disable: bool,
+	},
+	/// Export key shares from the database into an encrypted backup file.
+	ExportKeys {
+		/// The file to write the backup to. It must not exist yet.
+		#[clap(long, parse(from_os_str))]
+		output: PathBuf,
+		/// Only export the keys of this epoch.
+		#[clap(long)]
+		epoch: Option<EpochIndex>,
+		/// Only export the keys with these ids, written as `<epoch index>:<hex encoded public
+		/// key>`. Can be given more than once.
+		#[clap(long = "key-id")]
+		key_ids: Vec<KeyId>,
+		/// The passphrase to encrypt the backup with.
+		#[clap(long, env = "KEY_BACKUP_PASSPHRASE", hide_env_values = true)]
+		passphrase: String,
+	},
+	/// Import the key shares from a backup file into the database, which is created if it doesn't
+	/// exist.
+	ImportKeys {
+		/// The backup file to import.
+		#[clap(long, parse(from_os_str))]
+		input: PathBuf,
+		/// The passphrase the backup was encrypted with.
+		#[clap(long, env = "KEY_BACKUP_PASSPHRASE", hide_env_values = true)]
+		passphrase: String,
+	},
 }
 
 impl Default for CommandLineOptions {
@@ -319,13 +496,16 @@ impl Default for CommandLineOptions {
 			eth_opts: EthOptions::default(),
 			dot_opts: DotOptions::default(),
 			btc_opts: BtcOptions::default(),
+			arb_opts: ArbOptions::default(),
 			health_check_hostname: None,
 			health_check_port: None,
 			prometheus_hostname: None,
 			prometheus_port: None,
 			signing_db_file: None,
+			signing_key_encryption_key_file: None,
 			logging_span_lifecycle: false,
 			logging_command_server_port: None,
+			cmd: None,
 		}
 	}
 }
@@ -338,8 +518,10 @@ const STATE_CHAIN_WS_ENDPOINT: &str = "state_chain.ws_endpoint";
 const STATE_CHAIN_SIGNING_KEY_FILE: &str = "state_chain.signing_key_file";
 
 const ETH_PRIVATE_KEY_FILE: &str = "eth.private_key_file";
+const ARB_PRIVATE_KEY_FILE: &str = "arb.private_key_file";
 
 const SIGNING_DB_FILE: &str = "signing.db_file";
+const SIGNING_KEY_ENCRYPTION_KEY_FILE: &str = "signing.key_encryption.key_file";
 
 const LOGGING_SPAN_LIFECYCLE: &str = "logging.span_lifecycle";
 const LOGGING_COMMAND_SERVER_PORT: &str = "logging.command_server_port";
@@ -500,10 +682,24 @@ impl CfSettings for Settings {
 
 		self.btc.validate_settings()?;
 
+		if let Some(arb) = &self.arb {
+			arb.validate_settings()?;
+		}
+
 		self.state_chain.validate_settings()?;
 
 		is_valid_db_path(&self.signing.db_file).map_err(|e| ConfigError::Message(e.to_string()))?;
 
+		if let Some(NoncePreprocessing { batch_size }) = &self.signing.nonce_preprocessing {
+			use multisig::client::signing::nonce_pool::MAX_PREPROCESSING_BATCH_SIZE;
+
+			if !(1..=MAX_PREPROCESSING_BATCH_SIZE).contains(batch_size) {
+				return Err(ConfigError::Message(format!(
+					"signing.nonce_preprocessing.batch_size must be between 1 and {MAX_PREPROCESSING_BATCH_SIZE}"
+				)))
+			}
+		}
+
 		self.state_chain.signing_key_file = resolve_settings_path(
 			config_root,
 			&self.state_chain.signing_key_file,
@@ -514,7 +710,21 @@ impl CfSettings for Settings {
 			&self.eth.private_key_file,
 			Some(PathResolutionExpectation::ExistingFile),
 		)?;
+		if let Some(arb) = &mut self.arb {
+			arb.private_key_file = resolve_settings_path(
+				config_root,
+				&arb.private_key_file,
+				Some(PathResolutionExpectation::ExistingFile),
+			)?;
+		}
 		self.signing.db_file = resolve_settings_path(config_root, &self.signing.db_file, None)?;
+		if let Some(KeyEncryption::KeyFile(key_file)) = &mut self.signing.key_encryption {
+			*key_file = resolve_settings_path(
+				config_root,
+				key_file,
+				Some(PathResolutionExpectation::ExistingFile),
+			)?;
+		}
 		self.node_p2p.node_key_file = resolve_settings_path(
 			config_root,
 			&self.node_p2p.node_key_file,
@@ -583,6 +793,8 @@ impl Source for CommandLineOptions {
 
 		self.btc_opts.insert_all(&mut map);
 
+		self.arb_opts.insert_all(&mut map);
+
 		insert_command_line_option(&mut map, "health_check.hostname", &self.health_check_hostname);
 		insert_command_line_option(&mut map, "health_check.port", &self.health_check_port);
 
@@ -590,6 +802,11 @@ impl Source for CommandLineOptions {
 		insert_command_line_option(&mut map, "prometheus.port", &self.prometheus_port);
 
 		insert_command_line_option_path(&mut map, SIGNING_DB_FILE, &self.signing_db_file);
+		insert_command_line_option_path(
+			&mut map,
+			SIGNING_KEY_ENCRYPTION_KEY_FILE,
+			&self.signing_key_encryption_key_file,
+		);
 		insert_command_line_option(
 			&mut map,
 			LOGGING_SPAN_LIFECYCLE,
@@ -660,6 +877,23 @@ impl EthOptions {
 	}
 }
 
+impl ArbOptions {
+	/// Inserts all the Arb Options into the given map (if Some)
+	pub fn insert_all(&self, map: &mut HashMap<String, Value>) {
+		insert_command_line_option(map, "arb.rpc.ws_endpoint", &self.arb_ws_endpoint);
+		insert_command_line_option(map, "arb.rpc.http_endpoint", &self.arb_http_endpoint);
+
+		insert_command_line_option(map, "arb.backup_rpc.ws_endpoint", &self.arb_backup_ws_endpoint);
+		insert_command_line_option(
+			map,
+			"arb.backup_rpc.http_endpoint",
+			&self.arb_backup_http_endpoint,
+		);
+
+		insert_command_line_option_path(map, ARB_PRIVATE_KEY_FILE, &self.arb_private_key_file);
+	}
+}
+
 impl P2POptions {
 	/// Inserts all the P2P Options into the given map (if Some)
 	pub fn insert_all(&self, map: &mut HashMap<String, Value>) {
@@ -713,6 +947,17 @@ impl DotOptions {
 			"dot.backup_rpc.http_endpoint",
 			&self.dot_backup_http_endpoint,
 		);
+
+		insert_command_line_option(
+			map,
+			"dot.asset_hub.rpc.ws_endpoint",
+			&self.dot_asset_hub_ws_endpoint,
+		);
+		insert_command_line_option(
+			map,
+			"dot.asset_hub.rpc.http_endpoint",
+			&self.dot_asset_hub_http_endpoint,
+		);
 	}
 }
 
@@ -847,6 +1092,17 @@ pub mod tests {
 		test_all_command_line_options();
 	}
 
+	#[test]
+	fn fee_estimation_settings_are_validated() {
+		assert_ok!(EthFeeEstimation::default().validate());
+		assert!(EthFeeEstimation { priority_fee_percentile: 101, ..Default::default() }
+			.validate()
+			.is_err());
+		assert!(EthFeeEstimation { base_fee_multiplier_percent: 99, ..Default::default() }
+			.validate()
+			.is_err());
+	}
+
 	fn settings_valid_if_only_all_the_environment_set() {
 		let _guard = TestEnvironment::default();
 
@@ -854,6 +1110,8 @@ pub mod tests {
 			.expect("Check that the test environment is set correctly");
 		assert_eq!(settings.state_chain.ws_endpoint, "ws://localhost:9944");
 		assert_eq!(settings.eth.nodes.primary.http_endpoint.as_ref(), "http://localhost:8545");
+		assert_eq!(settings.eth.fee_estimation, EthFeeEstimation::default());
+		assert_eq!(settings.eth.native_deposit_witnessing, NativeDepositWitnessing::AddressChecker);
 		assert_eq!(
 			settings.dot.nodes.primary.ws_endpoint.as_ref(),
 			"wss://my_fake_polkadot_rpc:443/<secret_key>"
@@ -866,6 +1124,10 @@ pub mod tests {
 			settings.dot.nodes.backup.unwrap().ws_endpoint.as_ref(),
 			"wss://second.my_fake_polkadot_rpc:443/<secret_key>"
 		);
+		assert_eq!(
+			settings.arb.unwrap().nodes.primary.http_endpoint.as_ref(),
+			"http://localhost:8547"
+		);
 	}
 
 	fn test_init_config_with_testing_config() {
@@ -942,6 +1204,9 @@ pub mod tests {
 
 				dot_backup_ws_endpoint: Some("ws://second.endpoint:4321".to_owned()),
 				dot_backup_http_endpoint: Some("http://second.endpoint:4321".to_owned()),
+
+				dot_asset_hub_ws_endpoint: Some("ws://asset-hub.endpoint:4321".to_owned()),
+				dot_asset_hub_http_endpoint: Some("http://asset-hub.endpoint:4321".to_owned()),
 			},
 			btc_opts: BtcOptions {
 				btc_http_endpoint: Some("http://btc-endpoint:4321".to_owned()),
@@ -952,13 +1217,24 @@ pub mod tests {
 				btc_backup_basic_auth_user: Some("second.my_username".to_owned()),
 				btc_backup_basic_auth_password: Some("second.my_password".to_owned()),
 			},
+			arb_opts: ArbOptions {
+				arb_ws_endpoint: Some("ws://arb-endpoint:4321".to_owned()),
+				arb_http_endpoint: Some("http://arb-endpoint:4321".to_owned()),
+				arb_backup_ws_endpoint: Some("ws://second.arb-endpoint:4321".to_owned()),
+				arb_backup_http_endpoint: Some("http://second.arb-endpoint:4321".to_owned()),
+				arb_private_key_file: Some(PathBuf::from_str("keys/eth_private_key_2").unwrap()),
+			},
 			health_check_hostname: Some("health_check_hostname".to_owned()),
 			health_check_port: Some(1337),
 			prometheus_hostname: Some(("prometheus_hostname").to_owned()),
 			prometheus_port: Some(9999),
 			signing_db_file: Some(PathBuf::from_str("also/not/real.db").unwrap()),
+			signing_key_encryption_key_file: Some(
+				PathBuf::from_str("keys/eth_private_key_2").unwrap(),
+			),
 			logging_span_lifecycle: true,
 			logging_command_server_port: Some(6969),
+			cmd: None,
 		};
 
 		// Load the test opts into the settings
@@ -1018,6 +1294,16 @@ pub mod tests {
 			dot_backup_node.http_endpoint.as_ref()
 		);
 
+		let dot_asset_hub_node = settings.dot.asset_hub.unwrap().primary;
+		assert_eq!(
+			opts.dot_opts.dot_asset_hub_ws_endpoint.unwrap(),
+			dot_asset_hub_node.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.dot_opts.dot_asset_hub_http_endpoint.unwrap(),
+			dot_asset_hub_node.http_endpoint.as_ref()
+		);
+
 		assert_eq!(
 			opts.btc_opts.btc_http_endpoint.unwrap(),
 			settings.btc.nodes.primary.http_endpoint.as_ref()
@@ -1041,6 +1327,25 @@ pub mod tests {
 			btc_backup_node.basic_auth_password
 		);
 
+		let arb = settings.arb.unwrap();
+		assert_eq!(opts.arb_opts.arb_ws_endpoint.unwrap(), arb.nodes.primary.ws_endpoint.as_ref());
+		assert_eq!(
+			opts.arb_opts.arb_http_endpoint.unwrap(),
+			arb.nodes.primary.http_endpoint.as_ref()
+		);
+
+		let arb_backup_node = arb.nodes.backup.unwrap();
+		assert_eq!(
+			opts.arb_opts.arb_backup_ws_endpoint.unwrap(),
+			arb_backup_node.ws_endpoint.as_ref()
+		);
+		assert_eq!(
+			opts.arb_opts.arb_backup_http_endpoint.unwrap(),
+			arb_backup_node.http_endpoint.as_ref()
+		);
+
+		assert!(arb.private_key_file.ends_with("eth_private_key_2"));
+
 		assert_eq!(
 			opts.health_check_hostname.unwrap(),
 			settings.health_check.as_ref().unwrap().hostname
@@ -1054,6 +1359,10 @@ pub mod tests {
 		assert_eq!(opts.prometheus_port.unwrap(), settings.prometheus.as_ref().unwrap().port);
 
 		assert!(settings.signing.db_file.ends_with("not/real.db"));
+		assert!(matches!(
+			settings.signing.key_encryption,
+			Some(KeyEncryption::KeyFile(key_file)) if key_file.ends_with("eth_private_key_2")
+		));
 	}
 
 	#[test]
@@ -1123,6 +1432,13 @@ pub mod tests {
 					http_endpoint: "http://valid.endpoint_with_port:6969".into(),
 				}),
 			},
+			asset_hub: Some(NodeContainer {
+				primary: WsHttpEndpoints {
+					ws_endpoint: "ws://valid.endpoint_with_port:9955".into(),
+					http_endpoint: "http://valid.endpoint_with_port:9955".into(),
+				},
+				backup: None,
+			}),
 		};
 		assert_ok!(valid_settings.validate_settings());
 
@@ -1137,6 +1453,11 @@ pub mod tests {
 			http_endpoint: "http://invalid.no_port_in_url/secret_key".into(),
 		});
 		assert!(invalid_backup_settings.validate_settings().is_err());
+
+		let mut invalid_asset_hub_settings = valid_settings.clone();
+		invalid_asset_hub_settings.asset_hub.as_mut().unwrap().primary.http_endpoint =
+			"http://invalid.no_port_in_url/secret_key".into();
+		assert!(invalid_asset_hub_settings.validate_settings().is_err());
 	}
 
 	#[test]
@@ -7,7 +7,7 @@ use cf_chains::{
 use multisig::{
 	bitcoin::BtcSigning, eth::EthSigning, polkadot::PolkadotSigning, ChainSigning, CryptoScheme,
 };
-use state_chain_runtime::{BitcoinInstance, EthereumInstance, PolkadotInstance};
+use state_chain_runtime::{ArbitrumInstance, BitcoinInstance, EthereumInstance, PolkadotInstance};
 
 /// Compatibility layer for converting between public keys generated using the [CryptoScheme] types
 /// and the on-chain representation as defined by [ChainCrypto].
@@ -25,6 +25,14 @@ impl CryptoCompat<EthSigning, EvmCrypto> for EthereumInstance {
 	}
 }
 
+impl CryptoCompat<EthSigning, EvmCrypto> for ArbitrumInstance {
+	fn pubkey_to_aggkey(
+		pubkey: <<EthSigning as ChainSigning>::CryptoScheme as CryptoScheme>::PublicKey,
+	) -> <EvmCrypto as ChainCrypto>::AggKey {
+		pubkey
+	}
+}
+
 impl CryptoCompat<BtcSigning, BitcoinCrypto> for BitcoinInstance {
 	fn pubkey_to_aggkey(
 		pubkey: <<BtcSigning as ChainSigning>::CryptoScheme as CryptoScheme>::PublicKey,
@@ -11,7 +11,9 @@ use cf_primitives::{BlockNumber, CeremonyId, EpochIndex};
 use crypto_compat::CryptoCompat;
 use futures::{FutureExt, StreamExt};
 use sp_runtime::AccountId32;
-use state_chain_runtime::{AccountId, BitcoinInstance, EthereumInstance, PolkadotInstance};
+use state_chain_runtime::{
+	AccountId, ArbitrumInstance, BitcoinInstance, EthereumInstance, PolkadotInstance,
+};
 use std::{
 	collections::BTreeSet,
 	sync::{
@@ -27,6 +29,7 @@ use crate::{
 	btc::retry_rpc::BtcRetryRpcApi,
 	dot::retry_rpc::DotRetryRpcApi,
 	eth::retry_rpc::EthersRetryRpcApi,
+	health::HealthMonitor,
 	p2p::{PeerInfo, PeerUpdate},
 	state_chain_observer::client::{
 		extrinsic_api::{
@@ -42,7 +45,14 @@ use multisig::{
 	polkadot::PolkadotCryptoScheme, ChainSigning, CryptoScheme, KeyId,
 	SignatureToThresholdSignature,
 };
-use utilities::task_scope::{task_scope, Scope};
+use utilities::{
+	make_periodic_tick,
+	task_scope::{task_scope, Scope},
+};
+
+/// The block loop sends a heartbeat to the health monitor at this interval.
+const HEALTH_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+const HEALTH_TASK: &str = "sc_observer";
 
 async fn handle_keygen_request<'a, StateChainClient, MultisigClient, C, I>(
 	scope: &Scope<'a, anyhow::Error>,
@@ -189,7 +199,11 @@ async fn handle_signing_request<'a, StateChainClient, MultisigClient, C, I>(
 			Ok(())
 		});
 	} else {
-		multisig_client.update_latest_ceremony_id(ceremony_id);
+		multisig_client.observe_signing(
+			ceremony_id,
+			signers,
+			signing_info.into_iter().map(|(key_id, _)| key_id).collect(),
+		);
 	}
 }
 
@@ -222,6 +236,7 @@ pub async fn start<
 	EthRpc,
 	DotRpc,
 	BtcRpc,
+	ArbRpc,
 	EthMultisigClient,
 	PolkadotMultisigClient,
 	BitcoinMultisigClient,
@@ -231,16 +246,19 @@ pub async fn start<
 	eth_rpc: EthRpc,
 	dot_rpc: DotRpc,
 	btc_rpc: BtcRpc,
+	arb_rpc: Option<ArbRpc>,
 	eth_multisig_client: EthMultisigClient,
 	dot_multisig_client: PolkadotMultisigClient,
 	btc_multisig_client: BitcoinMultisigClient,
 	peer_update_sender: UnboundedSender<PeerUpdate>,
+	health_monitor: Arc<HealthMonitor>,
 ) -> Result<(), anyhow::Error>
 where
 	BlockStream: StateChainStreamApi,
 	EthRpc: EthersRetryRpcApi + Send + Sync + 'static,
 	DotRpc: DotRetryRpcApi + Send + Sync + 'static,
 	BtcRpc: BtcRetryRpcApi + Send + Sync + 'static,
+	ArbRpc: EthersRetryRpcApi + Send + Sync + 'static,
 	EthMultisigClient: MultisigClientApi<EvmCryptoScheme> + Send + Sync + 'static,
 	PolkadotMultisigClient: MultisigClientApi<PolkadotCryptoScheme> + Send + Sync + 'static,
 	BitcoinMultisigClient: MultisigClientApi<BtcCryptoScheme> + Send + Sync + 'static,
@@ -286,9 +304,21 @@ where
 
         info!("Sending heartbeat every {blocks_per_heartbeat} blocks");
 
+        // The heartbeat is sent while waiting for the next block, so it stops if processing a block
+        // gets stuck, but not if the State Chain stops producing blocks.
+        let mut heartbeat_interval = make_periodic_tick(HEALTH_HEARTBEAT_INTERVAL, false);
+        health_monitor.register_task(HEALTH_TASK, 3 * HEALTH_HEARTBEAT_INTERVAL);
+
         let mut sc_block_stream = Box::pin(sc_block_stream);
         loop {
-            match sc_block_stream.next().await {
+            let current_block = tokio::select! {
+                current_block = sc_block_stream.next() => current_block,
+                _ = heartbeat_interval.tick() => {
+                    health_monitor.heartbeat(HEALTH_TASK);
+                    continue
+                }
+            };
+            match current_block {
                 Some(current_block) => {
                     debug!("Processing SC block {} with block hash: {:#x}", current_block.number, current_block.hash);
 
@@ -369,6 +399,24 @@ where
                                             participants,
                                         ).await;
                                     }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumVault(
+                                        pallet_cf_vaults::Event::KeygenRequest {
+                                            ceremony_id,
+                                            participants,
+                                            epoch_index
+                                        }
+                                    ) => {
+                                        // Arbitrum uses the same crypto as Ethereum, so its keys are
+                                        // generated and used by the Ethereum multisig client.
+                                        handle_keygen_request::<_, _, _, ArbitrumInstance>(
+                                            scope,
+                                            &eth_multisig_client,
+                                            state_chain_client.clone(),
+                                            ceremony_id,
+                                            epoch_index,
+                                            participants,
+                                        ).await;
+                                    }
                                     state_chain_runtime::RuntimeEvent::EthereumThresholdSigner(
                                         pallet_cf_threshold_signature::Event::ThresholdSignatureRequest{
                                             request_id: _,
@@ -392,6 +440,29 @@ where
                                         ).await;
                                     }
 
+                                    state_chain_runtime::RuntimeEvent::ArbitrumThresholdSigner(
+                                        pallet_cf_threshold_signature::Event::ThresholdSignatureRequest{
+                                            request_id: _,
+                                            ceremony_id,
+                                            epoch,
+                                            key,
+                                            signatories,
+                                            payload,
+                                        },
+                                    ) => {
+                                        handle_signing_request::<_, _, _, ArbitrumInstance>(
+                                                scope,
+                                                &eth_multisig_client,
+                                            state_chain_client.clone(),
+                                            ceremony_id,
+                                            signatories,
+                                            vec![(
+                                                KeyId::new(epoch, key),
+                                                multisig::eth::SigningPayload(payload.0)
+                                            )],
+                                        ).await;
+                                    }
+
                                     state_chain_runtime::RuntimeEvent::PolkadotThresholdSigner(
                                         pallet_cf_threshold_signature::Event::ThresholdSignatureRequest{
                                             request_id: _,
@@ -493,6 +564,13 @@ where
                                     ) => {
                                         panic!("There should be no key handover requests made for Polkadot")
                                     }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumVault(
+                                        pallet_cf_vaults::Event::KeyHandoverRequest {
+                                           ..
+                                        },
+                                    ) => {
+                                        panic!("There should be no key handover requests made for Arbitrum")
+                                    }
 
                                     state_chain_runtime::RuntimeEvent::EthereumBroadcaster(
                                         pallet_cf_broadcast::Event::TransactionBroadcastRequest {
@@ -507,7 +585,7 @@ where
                                             let eth_rpc = eth_rpc.clone();
                                             let state_chain_client = state_chain_client.clone();
                                             scope.spawn(async move {
-                                                match eth_rpc.broadcast_transaction(transaction_payload).await {
+                                                match eth_rpc.broadcast_transaction(transaction_payload, broadcast_attempt_id.attempt_count).await {
                                                     Ok(tx_hash) => info!("Ethereum TransactionBroadcastRequest {broadcast_attempt_id:?} success: tx_hash: {tx_hash:#x}"),
                                                     Err(error) => {
                                                         // Note: this error can indicate that we failed to estimate gas, or that there is
@@ -528,6 +606,40 @@ where
                                             })
                                         }
                                     }
+                                    state_chain_runtime::RuntimeEvent::ArbitrumBroadcaster(
+                                        pallet_cf_broadcast::Event::TransactionBroadcastRequest {
+                                            broadcast_attempt_id,
+                                            nominee,
+                                            transaction_payload,
+                                            transaction_out_id: _,
+                                        },
+                                    ) => {
+                                        if nominee == account_id {
+                                            let arb_rpc = arb_rpc.clone();
+                                            let state_chain_client = state_chain_client.clone();
+                                            scope.spawn(async move {
+                                                let result = match arb_rpc {
+                                                    Some(arb_rpc) => arb_rpc.broadcast_transaction(transaction_payload, broadcast_attempt_id.attempt_count).await,
+                                                    None => Err(anyhow!("No Arbitrum node is configured")),
+                                                };
+                                                match result {
+                                                    Ok(tx_hash) => info!("Arbitrum TransactionBroadcastRequest {broadcast_attempt_id:?} success: tx_hash: {tx_hash:#x}"),
+                                                    Err(error) => {
+                                                        error!("Error on Arbitrum TransactionBroadcastRequest {broadcast_attempt_id:?}: {error:?}");
+                                                        state_chain_client.finalize_signed_extrinsic(
+                                                            state_chain_runtime::RuntimeCall::ArbitrumBroadcaster(
+                                                                pallet_cf_broadcast::Call::transaction_signing_failure {
+                                                                    broadcast_attempt_id,
+                                                                },
+                                                            ),
+                                                        )
+                                                        .await;
+                                                    }
+                                                }
+                                                Ok(())
+                                            })
+                                        }
+                                    }
                                     state_chain_runtime::RuntimeEvent::PolkadotBroadcaster(
                                         pallet_cf_broadcast::Event::TransactionBroadcastRequest {
                                             broadcast_attempt_id,
@@ -60,10 +60,12 @@ async fn start_sc_observer<
 		eth_rpc,
 		MockDotHttpRpcClient::new(),
 		MockBtcRetryRpcClient::new(),
+		None::<MockEthRetryRpcClient>,
 		MockMultisigClientApi::new(),
 		MockMultisigClientApi::new(),
 		MockMultisigClientApi::new(),
 		account_peer_mapping_change_sender,
+		Default::default(),
 	)
 	.await
 	.unwrap_err();
@@ -123,7 +125,7 @@ async fn only_encodes_and_signs_when_specified() {
 
 	// This doesn't always get called since the test can finish without the scope that spwans the
 	// broadcast task finishing.
-	eth_rpc_mock_broadcast.expect_broadcast_transaction().return_once(|_| {
+	eth_rpc_mock_broadcast.expect_broadcast_transaction().return_once(|_, _| {
 		// return some hash
 		Ok(H256::from([1; 32]))
 	});
@@ -173,13 +175,17 @@ ChainCrypto>::ThresholdSignature: std::convert::From<<C as CryptoScheme>::Signat
 		.times(3)
 		.return_const(our_account_id.clone());
 
-	// ceremony_id_1 is a non-participating ceremony and should update the latest ceremony id
+	// ceremony_id_1 is a non-participating ceremony and should only be observed
 	let ceremony_id_1 = 1;
 	multisig_client
-		.expect_update_latest_ceremony_id()
-		.with(eq(ceremony_id_1))
+		.expect_observe_signing()
+		.with(
+			eq(ceremony_id_1),
+			eq(BTreeSet::from_iter([not_our_account_id.clone()])),
+			eq(vec![key_id.clone()]),
+		)
 		.once()
-		.returning(|_| ());
+		.returning(|_, _, _| ());
 
 	// ceremony_id_2 is a failure and should submit a signed extrinsic
 	let ceremony_id_2 = ceremony_id_1 + 1;
@@ -525,10 +531,12 @@ async fn run_the_sc_observer() {
 				MockEthRetryRpcClient::new(),
 				MockDotHttpRpcClient::new(),
 				MockBtcRetryRpcClient::new(),
+				None::<MockEthRetryRpcClient>,
 				MockMultisigClientApi::new(),
 				MockMultisigClientApi::new(),
 				MockMultisigClientApi::new(),
 				account_peer_mapping_change_sender,
+				Default::default(),
 			)
 			.await
 			.unwrap_err();
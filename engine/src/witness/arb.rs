@@ -0,0 +1,247 @@
+mod arb_chain_tracking;
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use cf_chains::Arbitrum;
+use cf_primitives::{chains::assets::arb, EpochIndex};
+use futures_core::Future;
+use sp_core::H160;
+use utilities::task_scope::Scope;
+
+use crate::{
+	db::PersistentKeyDB,
+	eth::retry_rpc::EthersRetryRpcClient,
+	health::HealthMonitor,
+	settings::NativeDepositWitnessing,
+	state_chain_observer::client::{
+		chain_api::ChainApi, extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi,
+		StateChainStreamApi,
+	},
+	witness::eth::erc20_deposits::erc20_token::Erc20TokenEvents,
+};
+
+use super::{
+	common::{chain_source::extension::ChainSourceExt, epoch_source::EpochSourceBuilder},
+	eth::EvmSource,
+};
+
+use anyhow::{Context, Result};
+
+const SAFETY_MARGIN: usize = 1;
+
+/// Witnesses Arbitrum using the same contract witnessers as Ethereum, since the contracts deployed
+/// on Arbitrum are the same. There is no StateChainGateway on Arbitrum.
+pub async fn start<
+	StateChainClient,
+	StateChainStream,
+	ProcessCall,
+	ProcessingFut,
+	PrewitnessCall,
+	PrewitnessFut,
+>(
+	scope: &Scope<'_, anyhow::Error>,
+	arb_client: EthersRetryRpcClient,
+	native_deposit_witnessing: NativeDepositWitnessing,
+	process_call: ProcessCall,
+	prewitness_call: PrewitnessCall,
+	state_chain_client: Arc<StateChainClient>,
+	state_chain_stream: StateChainStream,
+	unfinalized_state_chain_stream: impl StateChainStreamApi<false>,
+	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
+	db: Arc<PersistentKeyDB>,
+	health_monitor: Arc<HealthMonitor>,
+) -> Result<()>
+where
+	StateChainClient: StorageApi + ChainApi + SignedExtrinsicApi + 'static + Send + Sync,
+	StateChainStream: StateChainStreamApi + Clone,
+	ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	ProcessingFut: Future<Output = ()> + Send + 'static,
+	PrewitnessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> PrewitnessFut
+		+ Send
+		+ Sync
+		+ Clone
+		+ 'static,
+	PrewitnessFut: Future<Output = ()> + Send + 'static,
+{
+	let key_manager_address = state_chain_client
+		.storage_value::<pallet_cf_environment::ArbitrumKeyManagerAddress<state_chain_runtime::Runtime>>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.context("Failed to get Arbitrum KeyManager address from SC")?;
+
+	let vault_address = state_chain_client
+		.storage_value::<pallet_cf_environment::ArbitrumVaultAddress<state_chain_runtime::Runtime>>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.context("Failed to get Arbitrum Vault contract address from SC")?;
+
+	let address_checker_address = state_chain_client
+		.storage_value::<pallet_cf_environment::ArbitrumAddressCheckerAddress<state_chain_runtime::Runtime>>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.context("Failed to get Arbitrum AddressChecker address from SC")?;
+
+	let supported_arb_erc20_tokens: HashMap<arb::Asset, H160> = state_chain_client
+		.storage_map::<pallet_cf_environment::ArbitrumSupportedAssets<state_chain_runtime::Runtime>, _>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.context("Failed to fetch Arbitrum supported assets")?;
+
+	let usdc_contract_address = *supported_arb_erc20_tokens
+		.get(&arb::Asset::ArbUsdc)
+		.context("Arbitrum USDC not supported")?;
+
+	let supported_arb_erc20_tokens: HashMap<H160, cf_primitives::Asset> =
+		supported_arb_erc20_tokens
+			.into_iter()
+			.map(|(asset, address)| (address, asset.into()))
+			.collect();
+
+	let arb_source = EvmSource::<_, Arbitrum>::new(arb_client.clone())
+		.report_health(health_monitor.clone(), Duration::from_secs(60))
+		.shared_with_heartbeat(scope, health_monitor, "arbitrum_witnessing");
+
+	arb_source
+		.clone()
+		.chunk_by_time(epoch_source.clone(), scope)
+		.chain_tracking(state_chain_client.clone(), arb_client.clone())
+		.logging("chain tracking")
+		.spawn(scope);
+
+	let vaults = epoch_source.vaults().await;
+
+	// ===== Prewitnessing stream =====
+	let prewitness_source =
+		arb_source.clone().strictly_monotonic().chunk_by_vault(vaults.clone(), scope);
+
+	let prewitness_source_deposit_addresses = prewitness_source
+		.clone()
+		.deposit_addresses(scope, unfinalized_state_chain_stream, state_chain_client.clone())
+		.await;
+
+	prewitness_source_deposit_addresses
+		.clone()
+		.erc20_deposits::<_, _, _, Erc20TokenEvents>(
+			prewitness_call.clone(),
+			arb_client.clone(),
+			arb::Asset::ArbUsdc,
+			usdc_contract_address,
+		)
+		.await?
+		.logging("pre-witnessing ArbitrumUSDCDeposits")
+		.spawn(scope);
+
+	match native_deposit_witnessing {
+		NativeDepositWitnessing::AddressChecker => prewitness_source_deposit_addresses
+			.clone()
+			.ethereum_deposits(
+				prewitness_call.clone(),
+				arb_client.clone(),
+				arb::Asset::ArbEth,
+				address_checker_address,
+				vault_address,
+			)
+			.await
+			.logging("pre-witnessing ArbitrumDeposits")
+			.spawn(scope),
+		NativeDepositWitnessing::CallTracer => prewitness_source_deposit_addresses
+			.clone()
+			.traced_ethereum_deposits(
+				prewitness_call.clone(),
+				arb_client.clone(),
+				arb::Asset::ArbEth,
+			)
+			.logging("pre-witnessing ArbitrumDeposits")
+			.spawn(scope),
+	}
+
+	prewitness_source
+		.vault_witnessing(
+			prewitness_call,
+			arb_client.clone(),
+			vault_address,
+			cf_primitives::Asset::ArbEth,
+			cf_primitives::ForeignChain::Arbitrum,
+			supported_arb_erc20_tokens.clone(),
+		)
+		.logging("pre-witnessing ArbitrumVault")
+		.spawn(scope);
+
+	// ===== Full witnessing stream =====
+
+	let arb_safe_vault_source = arb_source
+		.strictly_monotonic()
+		.lag_safety(SAFETY_MARGIN)
+		.logging("safe block produced")
+		.chunk_by_vault(vaults, scope);
+
+	let arb_safe_vault_source_deposit_addresses = arb_safe_vault_source
+		.clone()
+		.deposit_addresses(scope, state_chain_stream.clone(), state_chain_client.clone())
+		.await;
+
+	arb_safe_vault_source
+		.clone()
+		.key_manager_witnessing(process_call.clone(), arb_client.clone(), key_manager_address)
+		.continuous("ArbitrumKeyManager".to_string(), db.clone())
+		.logging("ArbitrumKeyManager")
+		.spawn(scope);
+
+	arb_safe_vault_source_deposit_addresses
+		.clone()
+		.erc20_deposits::<_, _, _, Erc20TokenEvents>(
+			process_call.clone(),
+			arb_client.clone(),
+			arb::Asset::ArbUsdc,
+			usdc_contract_address,
+		)
+		.await?
+		.continuous("ArbitrumUSDCDeposits".to_string(), db.clone())
+		.logging("ArbitrumUSDCDeposits")
+		.spawn(scope);
+
+	match native_deposit_witnessing {
+		NativeDepositWitnessing::AddressChecker => arb_safe_vault_source_deposit_addresses
+			.clone()
+			.ethereum_deposits(
+				process_call.clone(),
+				arb_client.clone(),
+				arb::Asset::ArbEth,
+				address_checker_address,
+				vault_address,
+			)
+			.await
+			.continuous("ArbitrumDeposits".to_string(), db.clone())
+			.logging("ArbitrumDeposits")
+			.spawn(scope),
+		NativeDepositWitnessing::CallTracer => arb_safe_vault_source_deposit_addresses
+			.clone()
+			.traced_ethereum_deposits(process_call.clone(), arb_client.clone(), arb::Asset::ArbEth)
+			.continuous("ArbitrumDeposits".to_string(), db.clone())
+			.logging("ArbitrumDeposits")
+			.spawn(scope),
+	}
+
+	arb_safe_vault_source
+		.vault_witnessing(
+			process_call,
+			arb_client.clone(),
+			vault_address,
+			cf_primitives::Asset::ArbEth,
+			cf_primitives::ForeignChain::Arbitrum,
+			supported_arb_erc20_tokens,
+		)
+		.continuous("ArbitrumVault".to_string(), db)
+		.logging("ArbitrumVault")
+		.spawn(scope);
+
+	Ok(())
+}
@@ -2,7 +2,7 @@ mod btc_chain_tracking;
 mod btc_deposits;
 pub mod btc_source;
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use bitcoin::{BlockHash, Transaction};
 use cf_chains::btc::{self, deposit_address::DepositAddress, BlockNumber, CHANGE_ADDRESS_SALT};
@@ -14,6 +14,7 @@ use utilities::task_scope::Scope;
 use crate::{
 	btc::retry_rpc::{BtcRetryRpcApi, BtcRetryRpcClient},
 	db::PersistentKeyDB,
+	health::HealthMonitor,
 	state_chain_observer::client::{
 		extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi, StateChainStreamApi,
 	},
@@ -83,6 +84,7 @@ pub async fn start<
 	unfinalised_state_chain_stream: impl StateChainStreamApi<false>,
 	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
 	db: Arc<PersistentKeyDB>,
+	health_monitor: Arc<HealthMonitor>,
 ) -> Result<()>
 where
 	StateChainClient: StorageApi + SignedExtrinsicApi + 'static + Send + Sync,
@@ -100,7 +102,10 @@ where
 		+ 'static,
 	PrewitnessFut: Future<Output = ()> + Send + 'static,
 {
-	let btc_source = BtcSource::new(btc_client.clone()).shared(scope);
+	let btc_source = BtcSource::new(btc_client.clone())
+		// Bitcoin blocks are produced every 10 minutes on average, but can be much slower
+		.report_health(health_monitor.clone(), Duration::from_secs(3600))
+		.shared_with_heartbeat(scope, health_monitor, "bitcoin_witnessing");
 
 	btc_source
 		.clone()
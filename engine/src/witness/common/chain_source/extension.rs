@@ -1,18 +1,24 @@
+use std::{sync::Arc, time::Duration};
+
 use futures_core::Future;
 use utilities::task_scope::Scope;
 
-use crate::witness::common::{
-	chunked_chain_source::{
-		chunked_by_time::{builder::ChunkedByTimeBuilder, ChunkByTime},
-		chunked_by_vault::{builder::ChunkedByVaultBuilder, ChunkByVault},
+use crate::{
+	health::HealthMonitor,
+	witness::common::{
+		chunked_chain_source::{
+			chunked_by_time::{builder::ChunkedByTimeBuilder, ChunkByTime},
+			chunked_by_vault::{builder::ChunkedByVaultBuilder, ChunkByVault},
+		},
+		epoch_source::{EpochSource, VaultSource},
+		ExternalChainSource, RuntimeHasChain,
 	},
-	epoch_source::{EpochSource, VaultSource},
-	ExternalChainSource, RuntimeHasChain,
 };
 
 use super::{
-	aliases, and_then::AndThen, lag_safety::LagSafety, logging::Logging, shared::SharedSource,
-	strictly_monotonic::StrictlyMonotonic, then::Then, ChainSource, Header,
+	aliases, and_then::AndThen, lag_safety::LagSafety, logging::Logging,
+	report_health::ReportHealth, shared::SharedSource, strictly_monotonic::StrictlyMonotonic,
+	then::Then, ChainSource, Header,
 };
 
 #[async_trait::async_trait]
@@ -59,7 +65,24 @@ pub trait ChainSourceExt: ChainSource {
 		Self::Client: Clone,
 		Self::Data: Clone,
 	{
-		SharedSource::new(self, scope)
+		SharedSource::new(self, scope, None)
+	}
+
+	/// Like [Self::shared], but the task sharing the stream also sends heartbeats to the health
+	/// monitor as `health_task`, so the engine is no longer live if a consumer of the stream gets
+	/// stuck.
+	fn shared_with_heartbeat<'env>(
+		self,
+		scope: &Scope<'env, anyhow::Error>,
+		health_monitor: Arc<HealthMonitor>,
+		health_task: &'static str,
+	) -> SharedSource<Self>
+	where
+		Self: 'env + Sized,
+		Self::Client: Clone,
+		Self::Data: Clone,
+	{
+		SharedSource::new(self, scope, Some((health_monitor, health_task)))
 	}
 
 	/// Logs when a header is produced by the underlying stream the hash and index of the header.
@@ -71,6 +94,20 @@ pub trait ChainSourceExt: ChainSource {
 		Logging::new(self, log_prefix)
 	}
 
+	/// Reports the index of each header produced by the underlying stream to the health monitor, as
+	/// the head of the chain. The chain is reported as unreachable if no header is produced within
+	/// `unreachable_after`.
+	fn report_health(
+		self,
+		health_monitor: Arc<HealthMonitor>,
+		unreachable_after: Duration,
+	) -> ReportHealth<Self>
+	where
+		Self: ExternalChainSource + Sized,
+	{
+		ReportHealth::new(self, health_monitor, unreachable_after)
+	}
+
 	/// Ensures the stream is always increasing with respect to the header index (normally the block
 	/// number). We don't assume the root chain source is strictly increasing, since we could
 	/// encounter reorgs.
@@ -0,0 +1,55 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_util::StreamExt;
+
+use crate::{health::HealthMonitor, witness::common::ExternalChainSource};
+
+use super::{BoxChainStream, ChainSource};
+
+#[derive(Clone)]
+pub struct ReportHealth<InnerSource: ChainSource> {
+	inner_source: InnerSource,
+	health_monitor: Arc<HealthMonitor>,
+}
+impl<InnerSource: ExternalChainSource> ReportHealth<InnerSource> {
+	pub fn new(
+		inner_source: InnerSource,
+		health_monitor: Arc<HealthMonitor>,
+		unreachable_after: Duration,
+	) -> Self {
+		health_monitor.register_external_chain(
+			<InnerSource::Chain as cf_chains::Chain>::NAME,
+			unreachable_after,
+		);
+		Self { inner_source, health_monitor }
+	}
+}
+
+#[async_trait::async_trait]
+impl<InnerSource: ChainSource + ExternalChainSource> ChainSource for ReportHealth<InnerSource> {
+	type Index = InnerSource::Index;
+	type Hash = InnerSource::Hash;
+	type Data = InnerSource::Data;
+
+	type Client = InnerSource::Client;
+
+	async fn stream_and_client(
+		&self,
+	) -> (BoxChainStream<'_, Self::Index, Self::Hash, Self::Data>, Self::Client) {
+		let (chain_stream, chain_client) = self.inner_source.stream_and_client().await;
+		(
+			Box::pin(chain_stream.map(move |header| {
+				self.health_monitor.record_external_chain_head(
+					<InnerSource::Chain as cf_chains::Chain>::NAME,
+					header.index.into(),
+				);
+				header
+			})),
+			chain_client,
+		)
+	}
+}
+
+impl<InnerSource: ExternalChainSource> ExternalChainSource for ReportHealth<InnerSource> {
+	type Chain = InnerSource::Chain;
+}
@@ -1,11 +1,13 @@
+use std::{sync::Arc, time::Duration};
+
 use futures_util::StreamExt;
 use tokio::sync::oneshot;
 use utilities::{
-	loop_select, spmc,
+	loop_select, make_periodic_tick, spmc,
 	task_scope::{Scope, OR_CANCEL},
 };
 
-use crate::witness::common::ExternalChainSource;
+use crate::{health::HealthMonitor, witness::common::ExternalChainSource};
 
 use super::{BoxChainStream, ChainSource, ChainStream, Header};
 
@@ -22,6 +24,10 @@ type Request<InnerSource> = tokio::sync::oneshot::Sender<(
 	<InnerSource as ChainSource>::Client,
 )>;
 
+/// The task sharing the stream sends a heartbeat to the health monitor at this interval, if it
+/// was given a health monitor.
+const HEALTH_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Clone)]
 pub struct SharedSource<InnerSource: ChainSource> {
 	request_sender: tokio::sync::mpsc::Sender<Request<InnerSource>>,
@@ -31,7 +37,14 @@ where
 	InnerSource::Client: Clone,
 	InnerSource::Data: Clone,
 {
-	pub fn new<'a, 'env>(inner_source: InnerSource, scope: &'a Scope<'env, anyhow::Error>) -> Self
+	/// If a health monitor is given, the task sharing the stream is registered with it as
+	/// `health_task`. The heartbeat is sent while waiting for the next header (or subscriber), so
+	/// it stops if a consumer stops taking headers, but not if the chain stops producing them.
+	pub fn new<'a, 'env>(
+		inner_source: InnerSource,
+		scope: &'a Scope<'env, anyhow::Error>,
+		health: Option<(Arc<HealthMonitor>, &'static str)>,
+	) -> Self
 	where
 		InnerSource: 'env,
 	{
@@ -42,8 +55,24 @@ where
 			let mut request_receiver =
 				tokio_stream::wrappers::ReceiverStream::new(request_receiver);
 
+			let mut heartbeat_interval = make_periodic_tick(HEALTH_HEARTBEAT_INTERVAL, false);
+			if let Some((health_monitor, health_task)) = &health {
+				health_monitor.register_task(health_task, 3 * HEALTH_HEARTBEAT_INTERVAL);
+			}
+			let heartbeat = || {
+				if let Some((health_monitor, health_task)) = &health {
+					health_monitor.heartbeat(health_task);
+				}
+			};
+
 			loop {
-				let Some(response_sender) = request_receiver.next().await else { break };
+				let response_sender = loop {
+					tokio::select! {
+						response_sender = request_receiver.next() => break response_sender,
+						_ = heartbeat_interval.tick() => heartbeat(),
+					}
+				};
+				let Some(response_sender) = response_sender else { break };
 
 				let (mut inner_stream, inner_client) = inner_source.stream_and_client().await;
 				let (mut sender, receiver) = spmc::channel(1);
@@ -58,6 +87,7 @@ where
 						sender.send(item).await;
 					},
 					let _ = sender.closed() => { break },
+					let _ = heartbeat_interval.tick() => { heartbeat() },
 				)
 			}
 			Ok(())
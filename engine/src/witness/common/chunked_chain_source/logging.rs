@@ -4,20 +4,27 @@ use super::{
 };
 
 impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
-	pub fn logging(self, log_prefix: &'static str) -> ChunkedByVaultBuilder<impl ChunkedByVault>
+	pub fn logging(
+		self,
+		log_prefix: impl Into<String>,
+	) -> ChunkedByVaultBuilder<impl ChunkedByVault>
 	where
 		Inner: ChunkedByVault,
 	{
-		self.then(move |epoch, header| async move {
-			tracing::info!(
-				"{} | {} processed: epoch index: {:?}, block index {:?}, hash {:?}",
-				<Inner::Chain as cf_chains::Chain>::NAME,
-				log_prefix,
-				epoch.index,
-				header.index,
-				header.hash
-			);
-			Ok::<_, anyhow::Error>(header.data)
+		let log_prefix: String = log_prefix.into();
+		self.then(move |epoch, header| {
+			let log_prefix = log_prefix.clone();
+			async move {
+				tracing::info!(
+					"{} | {} processed: epoch index: {:?}, block index {:?}, hash {:?}",
+					<Inner::Chain as cf_chains::Chain>::NAME,
+					log_prefix,
+					epoch.index,
+					header.index,
+					header.hash
+				);
+				Ok::<_, anyhow::Error>(header.data)
+			}
 		})
 	}
 }
@@ -3,6 +3,7 @@ mod dot_deposits;
 mod dot_source;
 
 use cf_chains::dot::{
+	xcm::{sent_asset_hub_message_topic, XcmTopic, ASSET_HUB_PARA_ID},
 	PolkadotAccountId, PolkadotBalance, PolkadotExtrinsicIndex, PolkadotHash, PolkadotSignature,
 	PolkadotUncheckedExtrinsic,
 };
@@ -15,15 +16,16 @@ use subxt::{
 	utils::AccountId32,
 };
 
-use tracing::error;
+use tracing::{error, warn};
 
-use std::{collections::BTreeSet, sync::Arc};
+use std::{collections::BTreeSet, sync::Arc, time::Duration};
 
 use utilities::task_scope::Scope;
 
 use crate::{
 	db::PersistentKeyDB,
 	dot::retry_rpc::{DotRetryRpcApi, DotRetryRpcClient},
+	health::HealthMonitor,
 	state_chain_observer::client::{
 		extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi, StateChainStreamApi,
 	},
@@ -46,10 +48,37 @@ pub mod polkadot {}
 
 #[derive(Debug, Clone)]
 pub enum EventWrapper {
-	ProxyAdded { delegator: AccountId32, delegatee: AccountId32 },
-	Transfer { to: AccountId32, from: AccountId32, amount: PolkadotBalance },
-	TransactionFeePaid { actual_fee: PolkadotBalance, tip: PolkadotBalance },
+	ProxyAdded {
+		delegator: AccountId32,
+		delegatee: AccountId32,
+	},
+	Transfer {
+		to: AccountId32,
+		from: AccountId32,
+		amount: PolkadotBalance,
+	},
+	/// A transfer of an asset held in the Assets pallet, such as USDT on Asset Hub.
+	AssetTransferred {
+		asset_id: u32,
+		to: AccountId32,
+		from: AccountId32,
+		amount: PolkadotBalance,
+	},
+	TransactionFeePaid {
+		actual_fee: PolkadotBalance,
+		tip: PolkadotBalance,
+	},
 	ExtrinsicSuccess,
+	/// A block of the given parachain was included in the relay chain.
+	CandidateIncluded {
+		para_id: u32,
+		para_head: PolkadotHash,
+	},
+	/// An XCM message sent by the relay chain was executed on Asset Hub.
+	XcmProcessed {
+		id: XcmTopic,
+		success: bool,
+	},
 }
 
 use polkadot::{
@@ -57,6 +86,85 @@ use polkadot::{
 	transaction_payment::events::TransactionFeePaid,
 };
 
+// The following events aren't part of the relay chain metadata, so they're decoded directly from
+// the event's field bytes.
+
+/// The `Transferred` event of the Assets pallet on Asset Hub.
+#[derive(codec::Decode)]
+struct AssetsTransferred {
+	asset_id: u32,
+	from: AccountId32,
+	to: AccountId32,
+	amount: PolkadotBalance,
+}
+
+impl AssetsTransferred {
+	const PALLET: &'static str = "Assets";
+	const EVENT: &'static str = "Transferred";
+}
+
+/// The `CandidateIncluded` event of the relay chain's ParaInclusion pallet. Only the leading fields
+/// of the candidate's descriptor, up to the hash of the parachain block, are decoded.
+#[derive(codec::Decode)]
+struct CandidateIncluded {
+	para_id: u32,
+	_relay_parent: PolkadotHash,
+	_collator: [u8; 32],
+	_persisted_validation_data_hash: PolkadotHash,
+	_pov_hash: PolkadotHash,
+	_erasure_root: PolkadotHash,
+	_signature: [u8; 64],
+	para_head: PolkadotHash,
+}
+
+impl CandidateIncluded {
+	const PALLET: &'static str = "ParaInclusion";
+	const EVENT: &'static str = "CandidateIncluded";
+}
+
+/// The `Processed` event of the MessageQueue pallet on Asset Hub.
+#[derive(codec::Decode)]
+struct MessageQueueProcessed {
+	id: XcmTopic,
+	origin: AggregateMessageOrigin,
+	_weight_used: Weight,
+	success: bool,
+}
+
+impl MessageQueueProcessed {
+	const PALLET: &'static str = "MessageQueue";
+	const EVENT: &'static str = "Processed";
+}
+
+#[derive(codec::Decode, PartialEq, Eq)]
+enum AggregateMessageOrigin {
+	Here,
+	Parent,
+	Sibling(u32),
+}
+
+#[derive(codec::Decode)]
+struct Weight {
+	#[codec(compact)]
+	_ref_time: u64,
+	#[codec(compact)]
+	_proof_size: u64,
+}
+
+fn decode_event_fields<Event: codec::Decode>(
+	event_details: &EventDetails<PolkadotConfig>,
+) -> Option<Event> {
+	<Event as codec::Decode>::decode(&mut event_details.field_bytes())
+		.map_err(|err| {
+			error!(
+				"Failed to decode {}.{} event: {err}",
+				event_details.pallet_name(),
+				event_details.variant_name()
+			)
+		})
+		.ok()
+}
+
 pub fn filter_map_events(
 	res_event_details: Result<EventDetails<PolkadotConfig>, subxt::Error>,
 ) -> Option<(Phase, EventWrapper)> {
@@ -72,6 +180,25 @@ pub fn filter_map_events(
 					event_details.as_event::<Transfer>().unwrap().unwrap();
 				Some(EventWrapper::Transfer { to, amount, from })
 			},
+			(AssetsTransferred::PALLET, AssetsTransferred::EVENT) => decode_event_fields::<
+				AssetsTransferred,
+			>(&event_details)
+			.map(|AssetsTransferred { asset_id, from, to, amount }| {
+				EventWrapper::AssetTransferred { asset_id, to, from, amount }
+			}),
+			(CandidateIncluded::PALLET, CandidateIncluded::EVENT) =>
+				decode_event_fields::<CandidateIncluded>(&event_details).map(
+					|CandidateIncluded { para_id, para_head, .. }| {
+						EventWrapper::CandidateIncluded { para_id, para_head }
+					},
+				),
+			(MessageQueueProcessed::PALLET, MessageQueueProcessed::EVENT) => decode_event_fields::<
+				MessageQueueProcessed,
+			>(&event_details)
+			.and_then(|MessageQueueProcessed { id, origin, success, .. }| {
+				(origin == AggregateMessageOrigin::Parent)
+					.then_some(EventWrapper::XcmProcessed { id, success })
+			}),
 			(TransactionFeePaid::PALLET, TransactionFeePaid::EVENT) => {
 				let TransactionFeePaid { actual_fee, tip, .. } =
 					event_details.as_event::<TransactionFeePaid>().unwrap().unwrap();
@@ -92,6 +219,58 @@ pub fn filter_map_events(
 	}
 }
 
+/// How long to wait before fetching an Asset Hub block again when the Asset Hub node doesn't have
+/// it yet.
+const ASSET_HUB_BLOCK_RETRY_INTERVAL: Duration = Duration::from_secs(6);
+
+/// How many relay chain blocks before the one in which a message's outcome on Asset Hub is
+/// witnessed, the extrinsic that sent the message is looked for.
+const ASSET_HUB_OUTCOME_LOOKBACK: PolkadotBlockNumber = 20;
+
+/// Adds the events of the Asset Hub blocks included in a relay chain block to that block's events.
+/// Asset Hub is witnessed this way so that its events are processed at the relay chain's block
+/// numbers, which are what the State Chain tracks for Polkadot.
+///
+/// Only asset transfers and the outcomes of messages from the relay chain are added, so that Asset
+/// Hub's extrinsics aren't mistaken for the relay chain's.
+pub async fn with_asset_hub_events(
+	asset_hub_client: Option<DotRetryRpcClient>,
+	mut events: Vec<(Phase, EventWrapper)>,
+) -> Vec<(Phase, EventWrapper)> {
+	let Some(asset_hub_client) = asset_hub_client else { return events };
+
+	let included_asset_hub_blocks = events
+		.iter()
+		.filter_map(|(_phase, wrapped_event)| match wrapped_event {
+			EventWrapper::CandidateIncluded { para_id: ASSET_HUB_PARA_ID, para_head } =>
+				Some(*para_head),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+
+	for block_hash in included_asset_hub_blocks {
+		let asset_hub_events = loop {
+			match asset_hub_client.events(block_hash).await {
+				Some(asset_hub_events) => break asset_hub_events,
+				None => {
+					warn!("Asset Hub block {block_hash:?} not found. Retrying in {ASSET_HUB_BLOCK_RETRY_INTERVAL:?}...");
+					tokio::time::sleep(ASSET_HUB_BLOCK_RETRY_INTERVAL).await;
+				},
+			}
+		};
+		events.extend(asset_hub_events.iter().filter_map(filter_map_events).filter(
+			|(_phase, wrapped_event)| {
+				matches!(
+					wrapped_event,
+					EventWrapper::AssetTransferred { .. } | EventWrapper::XcmProcessed { .. }
+				)
+			},
+		));
+	}
+
+	events
+}
+
 pub async fn proxy_added_witnessing(
 	epoch: Vault<cf_chains::Polkadot, PolkadotAccountId, ()>,
 	header: Header<PolkadotBlockNumber, PolkadotHash, Vec<(Phase, EventWrapper)>>,
@@ -130,13 +309,96 @@ pub async fn process_egress<ProcessCall, ProcessingFut>(
 		.map(|(signature, _)| signature)
 		.collect::<BTreeSet<_>>();
 
+	let (signer_id, epoch_index) = (epoch.info.1, epoch.index);
+	let report_success = move |signature: PolkadotSignature, tx_fee: PolkadotBalance| {
+		let process_call = process_call.clone();
+		async move {
+			tracing::info!("Witnessing transaction_succeeded. signature: {signature:?}");
+			process_call(
+				pallet_cf_broadcast::Call::<_, PolkadotInstance>::transaction_succeeded {
+					tx_out_id: signature,
+					signer_id,
+					tx_fee,
+					tx_metadata: (),
+				}
+				.into(),
+				epoch_index,
+			)
+			.await;
+		}
+	};
+
 	// To guarantee witnessing egress, we are interested in all extrinsics that were successful
 	extrinsic_indices.extend(extrinsic_success_indices(&events));
 
+	for (signature, tx_fee, asset_hub_message_topic) in monitored_extrinsics(
+		header.hash,
+		&extrinsic_indices,
+		&events,
+		&monitored_egress_ids,
+		&dot_client,
+	)
+	.await
+	{
+		if asset_hub_message_topic.is_some() {
+			// The egress only succeeds once the message it sent has been executed on Asset Hub,
+			// which happens in a later block.
+			tracing::info!(
+				"Waiting for the outcome on Asset Hub of extrinsic with signature: {signature:?}"
+			);
+		} else {
+			report_success(signature, tx_fee).await;
+		}
+	}
+
+	let executed_asset_hub_messages = events
+		.iter()
+		.filter_map(|(_phase, wrapped_event)| match wrapped_event {
+			EventWrapper::XcmProcessed { id, success: true } => Some(*id),
+			_ => None,
+		})
+		.collect::<BTreeSet<_>>();
+
+	if !executed_asset_hub_messages.is_empty() {
+		for block_number in header.index.saturating_sub(ASSET_HUB_OUTCOME_LOOKBACK)..header.index {
+			let Some(block_hash) = dot_client.block_hash(block_number).await else { continue };
+			let Some(block_events) = dot_client.events(block_hash).await else { continue };
+			let block_events =
+				block_events.iter().filter_map(filter_map_events).collect::<Vec<_>>();
+
+			for (signature, tx_fee, asset_hub_message_topic) in monitored_extrinsics(
+				block_hash,
+				&extrinsic_success_indices(&block_events),
+				&block_events,
+				&monitored_egress_ids,
+				&dot_client,
+			)
+			.await
+			{
+				if asset_hub_message_topic
+					.is_some_and(|topic| executed_asset_hub_messages.contains(&topic))
+				{
+					report_success(signature, tx_fee).await;
+				}
+			}
+		}
+	}
+}
+
+/// The signature, fee and the topic of any message sent to Asset Hub, of each of the extrinsics at
+/// the given indices that we are monitoring.
+async fn monitored_extrinsics(
+	block_hash: PolkadotHash,
+	extrinsic_indices: &BTreeSet<PolkadotExtrinsicIndex>,
+	events: &[(Phase, EventWrapper)],
+	monitored_egress_ids: &BTreeSet<PolkadotSignature>,
+	dot_client: &DotRetryRpcClient,
+) -> Vec<(PolkadotSignature, PolkadotBalance, Option<XcmTopic>)> {
 	let extrinsics: Vec<subxt::rpc::types::ChainBlockExtrinsic> =
-		dot_client.extrinsics(header.hash).await;
+		dot_client.extrinsics(block_hash).await;
 
-	for (extrinsic_index, tx_fee) in transaction_fee_paids(&extrinsic_indices, &events) {
+	let mut monitored_extrinsics = vec![];
+	for (extrinsic_index, tx_fee) in transaction_fee_paids(extrinsic_indices, events) {
 		let xt = extrinsics.get(extrinsic_index as usize).expect(
 			"We know this exists since we got
 	this index from the event, from the block we are querying.",
@@ -147,18 +409,11 @@ pub async fn process_egress<ProcessCall, ProcessingFut>(
 		if let Ok(unchecked) = unchecked {
 			if let Some(signature) = unchecked.signature() {
 				if monitored_egress_ids.contains(&signature) {
-					tracing::info!("Witnessing transaction_succeeded. signature: {signature:?}");
-					process_call(
-						pallet_cf_broadcast::Call::<_, PolkadotInstance>::transaction_succeeded {
-							tx_out_id: signature,
-							signer_id: epoch.info.1,
-							tx_fee,
-							tx_metadata: (),
-						}
-						.into(),
-						epoch.index,
-					)
-					.await;
+					monitored_extrinsics.push((
+						signature,
+						tx_fee,
+						sent_asset_hub_message_topic(unchecked.call()),
+					));
 				}
 			}
 		} else {
@@ -168,11 +423,13 @@ pub async fn process_egress<ProcessCall, ProcessingFut>(
 			tracing::debug!("Failed to decode UncheckedExtrinsic {unchecked:?}");
 		}
 	}
+	monitored_extrinsics
 }
 
 pub async fn start<StateChainClient, ProcessCall, ProcessingFut, PrewitnessCall, PrewitnessFut>(
 	scope: &Scope<'_, anyhow::Error>,
 	dot_client: DotRetryRpcClient,
+	asset_hub_client: Option<DotRetryRpcClient>,
 	process_call: ProcessCall,
 	prewitness_call: PrewitnessCall,
 	state_chain_client: Arc<StateChainClient>,
@@ -180,6 +437,7 @@ pub async fn start<StateChainClient, ProcessCall, ProcessingFut, PrewitnessCall,
 	unfinalized_state_chain_stream: impl StateChainStreamApi<false>,
 	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
 	db: Arc<PersistentKeyDB>,
+	health_monitor: Arc<HealthMonitor>,
 ) -> Result<()>
 where
 	StateChainClient: StorageApi + SignedExtrinsicApi + 'static + Send + Sync,
@@ -197,8 +455,10 @@ where
 	PrewitnessFut: Future<Output = ()> + Send + 'static,
 {
 	let unfinalised_source = DotUnfinalisedSource::new(dot_client.clone())
+		// Polkadot produces a block every 6 seconds
+		.report_health(health_monitor.clone(), Duration::from_secs(60))
 		.then(|header| async move { header.data.iter().filter_map(filter_map_events).collect() })
-		.shared(scope);
+		.shared_with_heartbeat(scope, health_monitor, "polkadot_witnessing");
 
 	unfinalised_source
 		.clone()
@@ -226,6 +486,10 @@ where
 	// Pre-witnessing
 	unfinalised_source
 		.strictly_monotonic()
+		.then({
+			let asset_hub_client = asset_hub_client.clone();
+			move |header| with_asset_hub_events(asset_hub_client.clone(), header.data)
+		})
 		.chunk_by_vault(vaults.clone(), scope)
 		.deposit_addresses(scope, unfinalized_state_chain_stream, state_chain_client.clone())
 		.await
@@ -237,8 +501,11 @@ where
 	DotFinalisedSource::new(dot_client.clone())
 		.strictly_monotonic()
 		.logging("finalised block produced")
-		.then(|header| async move {
-			header.data.iter().filter_map(filter_map_events).collect::<Vec<_>>()
+		.then(move |header| {
+			with_asset_hub_events(
+				asset_hub_client.clone(),
+				header.data.iter().filter_map(filter_map_events).collect(),
+			)
 		})
 		.chunk_by_vault(vaults, scope)
 		.deposit_addresses(scope, state_chain_stream.clone(), state_chain_client.clone())
@@ -361,6 +628,31 @@ pub mod test {
 		assert!(extrinsic_indices.contains(&our_proxy_added_index));
 	}
 
+	#[test]
+	fn candidate_included_is_decoded_up_to_the_para_head() {
+		use codec::{Decode, Encode};
+
+		let para_head = PolkadotHash::repeat_byte(0xaa);
+		let encoded = (
+			ASSET_HUB_PARA_ID,
+			PolkadotHash::repeat_byte(1),
+			[2u8; 32],
+			PolkadotHash::repeat_byte(3),
+			PolkadotHash::repeat_byte(4),
+			PolkadotHash::repeat_byte(5),
+			[6u8; 64],
+			para_head,
+			// The remaining fields of the event.
+			PolkadotHash::repeat_byte(7),
+			vec![8u8; 10],
+		)
+			.encode();
+
+		let decoded = CandidateIncluded::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(decoded.para_id, ASSET_HUB_PARA_ID);
+		assert_eq!(decoded.para_head, para_head);
+	}
+
 	#[tokio::test]
 	async fn test_extrinsic_success_filtering() {
 		let events = phase_and_events(vec![
@@ -15,7 +15,7 @@ use crate::witness::{
 };
 use cf_chains::{
 	assets::dot::Asset,
-	dot::{PolkadotAccountId, PolkadotHash},
+	dot::{xcm::USDT_ASSET_ID, PolkadotAccountId, PolkadotHash},
 	Polkadot,
 };
 use subxt::events::Phase;
@@ -82,12 +82,14 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 
 fn address_and_details_to_addresses(
 	address_and_details: Vec<DepositChannelDetails<state_chain_runtime::Runtime, PolkadotInstance>>,
-) -> Vec<PolkadotAccountId> {
+) -> Vec<(PolkadotAccountId, Asset)> {
 	address_and_details
 		.into_iter()
 		.map(|deposit_channel_details| {
-			assert_eq!(deposit_channel_details.deposit_channel.asset, Asset::Dot);
-			deposit_channel_details.deposit_channel.address
+			(
+				deposit_channel_details.deposit_channel.address,
+				deposit_channel_details.deposit_channel.asset,
+			)
 		})
 		.collect()
 }
@@ -95,22 +97,26 @@ fn address_and_details_to_addresses(
 // Return the deposit witnesses and the extrinsic indices of transfers we want
 // to confirm the broadcast of.
 fn deposit_witnesses(
-	monitored_addresses: Vec<PolkadotAccountId>,
+	monitored_addresses: Vec<(PolkadotAccountId, Asset)>,
 	events: &Vec<(Phase, EventWrapper)>,
 ) -> Vec<DepositWitness<Polkadot>> {
 	let mut deposit_witnesses = vec![];
 	for (phase, wrapped_event) in events {
 		if let Phase::ApplyExtrinsic(_extrinsic_index) = phase {
-			if let EventWrapper::Transfer { to, amount, from: _ } = wrapped_event {
-				let deposit_address = PolkadotAccountId::from_aliased(to.0);
-				if monitored_addresses.contains(&deposit_address) {
-					deposit_witnesses.push(DepositWitness {
-						deposit_address,
-						asset: Asset::Dot,
-						amount: *amount,
-						deposit_details: (),
-					});
-				}
+			let (to, amount, asset) = match wrapped_event {
+				EventWrapper::Transfer { to, amount, from: _ } => (to, amount, Asset::Dot),
+				EventWrapper::AssetTransferred { asset_id: USDT_ASSET_ID, to, amount, from: _ } =>
+					(to, amount, Asset::DotUsdt),
+				_ => continue,
+			};
+			let deposit_address = PolkadotAccountId::from_aliased(to.0);
+			if monitored_addresses.contains(&(deposit_address, asset)) {
+				deposit_witnesses.push(DepositWitness {
+					deposit_address,
+					asset,
+					amount: *amount,
+					deposit_details: (),
+				});
 			}
 		}
 	}
@@ -203,7 +209,10 @@ mod test {
 		]);
 
 		let deposit_witnesses = deposit_witnesses(
-			vec![transfer_1_deposit_address, transfer_2_deposit_address],
+			vec![
+				(transfer_1_deposit_address, Asset::Dot),
+				(transfer_2_deposit_address, Asset::Dot),
+			],
 			&block_event_details,
 		);
 
@@ -212,4 +221,51 @@ mod test {
 		assert_eq!(deposit_witnesses.get(1).unwrap().amount, TRANSFER_2_AMOUNT);
 		assert_eq!(deposit_witnesses.get(2).unwrap().amount, TRANSFER_TO_SELF_AMOUNT);
 	}
+
+	#[test]
+	fn witness_usdt_deposits_for_addresses_we_monitor() {
+		let dot_deposit_address = PolkadotAccountId::from_aliased([1; 32]);
+		let usdt_deposit_address = PolkadotAccountId::from_aliased([2; 32]);
+		const USDT_AMOUNT: PolkadotBalance = 10000;
+
+		let mock_asset_transfer =
+			|asset_id, to: &PolkadotAccountId, amount| EventWrapper::AssetTransferred {
+				asset_id,
+				from: PolkadotAccountId::from_aliased([7; 32]).aliased_ref().to_owned().into(),
+				to: to.aliased_ref().to_owned().into(),
+				amount,
+			};
+
+		let block_event_details = phase_and_events(vec![
+			(1, mock_asset_transfer(USDT_ASSET_ID, &usdt_deposit_address, USDT_AMOUNT)),
+			// Not USDT.
+			(2, mock_asset_transfer(USDT_ASSET_ID + 1, &usdt_deposit_address, 20000)),
+			// USDT sent to a channel that was opened for DOT.
+			(3, mock_asset_transfer(USDT_ASSET_ID, &dot_deposit_address, 30000)),
+			// DOT sent to a channel that was opened for USDT.
+			(
+				4,
+				mock_transfer(
+					&PolkadotAccountId::from_aliased([7; 32]),
+					&usdt_deposit_address,
+					40000,
+				),
+			),
+		]);
+
+		let deposit_witnesses = deposit_witnesses(
+			vec![(dot_deposit_address, Asset::Dot), (usdt_deposit_address, Asset::DotUsdt)],
+			&block_event_details,
+		);
+
+		assert_eq!(
+			deposit_witnesses,
+			vec![DepositWitness {
+				deposit_address: usdt_deposit_address,
+				asset: Asset::DotUsdt,
+				amount: USDT_AMOUNT,
+				deposit_details: (),
+			}]
+		);
+	}
 }
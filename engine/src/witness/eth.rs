@@ -5,9 +5,10 @@ mod eth_source;
 mod ethereum_deposits;
 mod key_manager;
 mod state_chain_gateway;
+mod traced_deposits;
 pub mod vault;
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use cf_primitives::{chains::assets::eth, EpochIndex};
 use futures_core::Future;
@@ -17,23 +18,34 @@ use utilities::task_scope::Scope;
 use crate::{
 	db::PersistentKeyDB,
 	eth::retry_rpc::EthersRetryRpcClient,
+	health::HealthMonitor,
+	settings::NativeDepositWitnessing,
 	state_chain_observer::client::{
 		chain_api::ChainApi, extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi,
 		StateChainStreamApi,
 	},
-	witness::eth::erc20_deposits::{flip::FlipEvents, usdc::UsdcEvents},
+	witness::eth::erc20_deposits::erc20_token::Erc20TokenEvents,
 };
 
 use super::common::{
 	chain_source::extension::ChainSourceExt, epoch_source::EpochSourceBuilder,
 	STATE_CHAIN_CONNECTION,
 };
-pub use eth_source::EthSource;
+pub use eth_source::{EthSource, EvmSource};
 
 use anyhow::{Context, Result};
 
 const SAFETY_MARGIN: usize = 6;
 
+/// The name of the witnesser for deposits of an ERC-20 token. This is also used as the key of the
+/// witnesser's progress in the db, so the names of existing witnessers must not change.
+fn erc20_deposits_name(asset: eth::Asset) -> String {
+	match asset {
+		eth::Asset::Usdc => "USDCDeposits".to_string(),
+		asset => format!("{asset:?}Deposits"),
+	}
+}
+
 pub async fn start<
 	StateChainClient,
 	StateChainStream,
@@ -44,6 +56,7 @@ pub async fn start<
 >(
 	scope: &Scope<'_, anyhow::Error>,
 	eth_client: EthersRetryRpcClient,
+	native_deposit_witnessing: NativeDepositWitnessing,
 	process_call: ProcessCall,
 	prewitness_call: PrewitnessCall,
 	state_chain_client: Arc<StateChainClient>,
@@ -51,6 +64,7 @@ pub async fn start<
 	unfinalized_state_chain_stream: impl StateChainStreamApi<false>,
 	epoch_source: EpochSourceBuilder<'_, '_, StateChainClient, (), ()>,
 	db: Arc<PersistentKeyDB>,
+	health_monitor: Arc<HealthMonitor>,
 ) -> Result<()>
 where
 	StateChainClient: StorageApi + ChainApi + SignedExtrinsicApi + 'static + Send + Sync,
@@ -96,26 +110,24 @@ where
 		.await
 		.expect(STATE_CHAIN_CONNECTION);
 
-	let supported_erc20_tokens: HashMap<cf_primitives::chains::assets::eth::Asset, H160> =
-		state_chain_client
-			.storage_map::<pallet_cf_environment::EthereumSupportedAssets<state_chain_runtime::Runtime>, _>(
-				state_chain_client.latest_finalized_block().hash,
-			)
-			.await
-			.context("Failed to fetch Ethereum supported assets")?;
-
-	let usdc_contract_address =
-		*supported_erc20_tokens.get(&eth::Asset::Usdc).context("USDC not supported")?;
-
-	let flip_contract_address =
-		*supported_erc20_tokens.get(&eth::Asset::Flip).context("FLIP not supported")?;
+	// The registry is only read on startup, so tokens registered later are witnessed once the
+	// engine is restarted.
+	let erc20_token_registry: HashMap<H160, eth::Asset> = state_chain_client
+		.storage_map::<pallet_cf_environment::EthereumErc20Tokens<state_chain_runtime::Runtime>, _>(
+			state_chain_client.latest_finalized_block().hash,
+		)
+		.await
+		.context("Failed to fetch the Ethereum ERC-20 token registry")?;
 
-	let supported_erc20_tokens: HashMap<H160, cf_primitives::Asset> = supported_erc20_tokens
-		.into_iter()
-		.map(|(asset, address)| (address, asset.into()))
+	let supported_erc20_tokens: HashMap<H160, cf_primitives::Asset> = erc20_token_registry
+		.iter()
+		.map(|(address, asset)| (*address, (*asset).into()))
 		.collect();
 
-	let eth_source = EthSource::new(eth_client.clone()).shared(scope);
+	let eth_source = EthSource::new(eth_client.clone())
+		// Ethereum produces a block every 12 seconds
+		.report_health(health_monitor.clone(), Duration::from_secs(120))
+		.shared_with_heartbeat(scope, health_monitor, "ethereum_witnessing");
 
 	eth_source
 		.clone()
@@ -135,42 +147,39 @@ where
 		.deposit_addresses(scope, unfinalized_state_chain_stream, state_chain_client.clone())
 		.await;
 
-	prewitness_source_deposit_addresses
-		.clone()
-		.erc20_deposits::<_, _, _, UsdcEvents>(
-			prewitness_call.clone(),
-			eth_client.clone(),
-			cf_primitives::chains::assets::eth::Asset::Usdc,
-			usdc_contract_address,
-		)
-		.await?
-		.logging("pre-witnessing USDCDeposits")
-		.spawn(scope);
-
-	prewitness_source_deposit_addresses
-		.clone()
-		.erc20_deposits::<_, _, _, FlipEvents>(
-			prewitness_call.clone(),
-			eth_client.clone(),
-			cf_primitives::chains::assets::eth::Asset::Flip,
-			flip_contract_address,
-		)
-		.await?
-		.logging("pre-witnessing FlipDeposits")
-		.spawn(scope);
-
-	prewitness_source_deposit_addresses
-		.clone()
-		.ethereum_deposits(
-			prewitness_call.clone(),
-			eth_client.clone(),
-			eth::Asset::Eth,
-			address_checker_address,
-			vault_address,
-		)
-		.await
-		.logging("pre-witnessing EthereumDeposits")
-		.spawn(scope);
+	for (address, asset) in erc20_token_registry.iter() {
+		prewitness_source_deposit_addresses
+			.clone()
+			.erc20_deposits::<_, _, _, Erc20TokenEvents>(
+				prewitness_call.clone(),
+				eth_client.clone(),
+				*asset,
+				*address,
+			)
+			.await?
+			.logging(format!("pre-witnessing {}", erc20_deposits_name(*asset)))
+			.spawn(scope);
+	}
+
+	match native_deposit_witnessing {
+		NativeDepositWitnessing::AddressChecker => prewitness_source_deposit_addresses
+			.clone()
+			.ethereum_deposits(
+				prewitness_call.clone(),
+				eth_client.clone(),
+				eth::Asset::Eth,
+				address_checker_address,
+				vault_address,
+			)
+			.await
+			.logging("pre-witnessing EthereumDeposits")
+			.spawn(scope),
+		NativeDepositWitnessing::CallTracer => prewitness_source_deposit_addresses
+			.clone()
+			.traced_ethereum_deposits(prewitness_call.clone(), eth_client.clone(), eth::Asset::Eth)
+			.logging("pre-witnessing EthereumDeposits")
+			.spawn(scope),
+	}
 
 	prewitness_source
 		.vault_witnessing(
@@ -215,45 +224,42 @@ where
 		.logging("StateChainGateway")
 		.spawn(scope);
 
-	eth_safe_vault_source_deposit_addresses
-		.clone()
-		.erc20_deposits::<_, _, _, UsdcEvents>(
-			process_call.clone(),
-			eth_client.clone(),
-			cf_primitives::chains::assets::eth::Asset::Usdc,
-			usdc_contract_address,
-		)
-		.await?
-		.continuous("USDCDeposits".to_string(), db.clone())
-		.logging("USDCDeposits")
-		.spawn(scope);
-
-	eth_safe_vault_source_deposit_addresses
-		.clone()
-		.erc20_deposits::<_, _, _, FlipEvents>(
-			process_call.clone(),
-			eth_client.clone(),
-			cf_primitives::chains::assets::eth::Asset::Flip,
-			flip_contract_address,
-		)
-		.await?
-		.continuous("FlipDeposits".to_string(), db.clone())
-		.logging("FlipDeposits")
-		.spawn(scope);
-
-	eth_safe_vault_source_deposit_addresses
-		.clone()
-		.ethereum_deposits(
-			process_call.clone(),
-			eth_client.clone(),
-			eth::Asset::Eth,
-			address_checker_address,
-			vault_address,
-		)
-		.await
-		.continuous("EthereumDeposits".to_string(), db.clone())
-		.logging("EthereumDeposits")
-		.spawn(scope);
+	for (address, asset) in erc20_token_registry {
+		eth_safe_vault_source_deposit_addresses
+			.clone()
+			.erc20_deposits::<_, _, _, Erc20TokenEvents>(
+				process_call.clone(),
+				eth_client.clone(),
+				asset,
+				address,
+			)
+			.await?
+			.continuous(erc20_deposits_name(asset), db.clone())
+			.logging(erc20_deposits_name(asset))
+			.spawn(scope);
+	}
+
+	match native_deposit_witnessing {
+		NativeDepositWitnessing::AddressChecker => eth_safe_vault_source_deposit_addresses
+			.clone()
+			.ethereum_deposits(
+				process_call.clone(),
+				eth_client.clone(),
+				eth::Asset::Eth,
+				address_checker_address,
+				vault_address,
+			)
+			.await
+			.continuous("EthereumDeposits".to_string(), db.clone())
+			.logging("EthereumDeposits")
+			.spawn(scope),
+		NativeDepositWitnessing::CallTracer => eth_safe_vault_source_deposit_addresses
+			.clone()
+			.traced_ethereum_deposits(process_call.clone(), eth_client.clone(), eth::Asset::Eth)
+			.continuous("EthereumDeposits".to_string(), db.clone())
+			.logging("EthereumDeposits")
+			.spawn(scope),
+	}
 
 	eth_safe_vault_source
 		.vault_witnessing(
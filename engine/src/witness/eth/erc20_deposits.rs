@@ -52,13 +52,8 @@ macro_rules! define_erc20 {
 	};
 }
 
-define_erc20!(
-	flip,
-	Flip,
-	FlipEvents,
-	"$CF_ETH_CONTRACT_ABI_ROOT/$CF_ETH_CONTRACT_ABI_TAG/IFLIP.json"
-);
-define_erc20!(usdc, Usdc, UsdcEvents, "$CF_ETH_CONTRACT_ABI_ROOT/IUSDC.json");
+// Only the standard `Transfer` event is used, so any ERC-20 token can be witnessed with this ABI.
+define_erc20!(erc20_token, Erc20Token, Erc20TokenEvents, "$CF_ETH_CONTRACT_ABI_ROOT/IERC20.json");
 
 impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 	pub async fn erc20_deposits<ProcessCall, ProcessingFut, EthRetryRpcClient, Events>(
@@ -7,23 +7,29 @@ use crate::{
 	},
 	witness::common::{
 		chain_source::{BoxChainStream, ChainClient, ChainSource, Header},
-		ExternalChainSource,
+		ExternalChain, ExternalChainSource,
 	},
 };
 use futures::stream::StreamExt;
 use futures_util::stream;
-use std::time::Duration;
+use std::{marker::PhantomData, time::Duration};
 
+/// A source of block headers for any EVM chain, the `Chain` parameter determines which chain the
+/// headers are attributed to.
 #[derive(Clone)]
-pub struct EthSource<C> {
+pub struct EvmSource<C, Chain> {
 	client: C,
+	_phantom: PhantomData<Chain>,
 }
 
-impl<C: EthersRetrySubscribeApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone>
-	EthSource<C>
+pub type EthSource<C> = EvmSource<C, cf_chains::Ethereum>;
+
+impl<C, Chain> EvmSource<C, Chain>
+where
+	C: EthersRetrySubscribeApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
 {
 	pub fn new(client: C) -> Self {
-		Self { client }
+		Self { client, _phantom: PhantomData }
 	}
 }
 
@@ -31,9 +37,10 @@ const TIMEOUT: Duration = Duration::from_secs(60);
 const RESTART_STREAM_DELAY: Duration = Duration::from_secs(6);
 
 #[async_trait::async_trait]
-impl<C> ChainSource for EthSource<C>
+impl<C, Chain> ChainSource for EvmSource<C, Chain>
 where
 	C: EthersRetrySubscribeApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
+	Chain: Send + Sync,
 {
 	type Index = <C as ChainClient>::Index;
 	type Hash = <C as ChainClient>::Hash;
@@ -85,9 +92,10 @@ where
 	}
 }
 
-impl<C> ExternalChainSource for EthSource<C>
+impl<C, Chain> ExternalChainSource for EvmSource<C, Chain>
 where
 	C: EthersRetrySubscribeApi + ChainClient<Index = u64, Hash = H256, Data = Bloom> + Clone,
+	Chain: ExternalChain<ChainBlockNumber = u64> + Send + Sync,
 {
-	type Chain = cf_chains::Ethereum;
+	type Chain = Chain;
 }
@@ -14,7 +14,7 @@ use crate::witness::{
 	eth::vault::VaultEvents,
 };
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use ethers::prelude::*;
 use itertools::Itertools;
@@ -76,7 +76,7 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 								deposit_channel.deposit_channel.asset == native_asset
 							})
 							.map(|deposit_channel| deposit_channel.deposit_channel.address)
-							.collect::<Vec<_>>();
+							.collect::<BTreeSet<_>>();
 
 						let ingresses = eth_ingresses_at_block(
 							address_states(
@@ -146,11 +146,15 @@ async fn address_states<EthRetryRpcClient>(
 	address_checker_address: H160,
 	parent_hash: H256,
 	hash: H256,
-	addresses: Vec<H160>,
+	addresses: BTreeSet<H160>,
 ) -> Result<impl Iterator<Item = (H160, (AddressState, AddressState))>, anyhow::Error>
 where
 	EthRetryRpcClient: AddressCheckerRetryRpcApi + Send + Sync + Clone,
 {
+	// Each address is only checked (and witnessed) once, in address order, so the witnesses are
+	// the same as the ones produced by `traced_ethereum_deposits`.
+	let addresses = Vec::from_iter(addresses);
+
 	let previous_address_states = eth_rpc
 		.address_states(parent_hash, address_checker_address, addresses.clone())
 		.await;
@@ -182,6 +186,8 @@ where
 /// by the Deposit contract upon deployment or after it.
 /// Note that when we have a contract deployed already we substrate the balance at the previous
 /// block, since we will have already witnessed the deposits at the time the deposit was made.
+///
+/// The ingresses are returned in the order of the given addresses.
 pub fn eth_ingresses_at_block<
 	Addresses: IntoIterator<Item = (H160, (AddressState, AddressState))>,
 >(
@@ -320,13 +326,14 @@ mod tests {
 					settings.eth.private_key_file,
 					settings.eth.nodes,
 					U256::from(1337u64),
+					settings.eth.fee_estimation,
 				)
 				.unwrap();
 
-				let addresses = vec![
+				let addresses = BTreeSet::from([
 					"41aD2bc63A2059f9b623533d87fe99887D794847".parse().unwrap(),
 					"c2774b2f1972f50ac6113e81721cc7214388434d".parse().unwrap(),
-				];
+				]);
 
 				let block_number = 138;
 				let block = client.block(block_number.into()).await;
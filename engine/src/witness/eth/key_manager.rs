@@ -206,6 +206,8 @@ mod tests {
 						backup: None,
 					},
 					private_key_file: PathBuf::from_str("/some/key/file").unwrap(),
+					fee_estimation: Default::default(),
+					native_deposit_witnessing: Default::default(),
 				};
 
 				let retry_client = EthersRetryRpcClient::new(
@@ -213,6 +215,7 @@ mod tests {
 					eth_settings.private_key_file,
 					eth_settings.nodes,
 					U256::from(1337u64),
+					eth_settings.fee_estimation,
 				)
 				.unwrap();
 
@@ -0,0 +1,279 @@
+use crate::{
+	eth::{retry_rpc::call_tracer::CallTracerRetryRpcApi, rpc::call_tracer::CallFrame},
+	witness::common::{RuntimeCallHasChain, RuntimeHasChain},
+};
+use cf_primitives::EpochIndex;
+use ethers::types::Bloom;
+use futures_core::Future;
+use sp_core::{H160, H256, U256};
+use state_chain_runtime::PalletInstanceAlias;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::witness::common::chunked_chain_source::chunked_by_vault::deposit_addresses::Addresses;
+
+use super::super::common::chunked_chain_source::chunked_by_vault::{
+	builder::ChunkedByVaultBuilder, ChunkedByVault,
+};
+
+/// Call types that can move ether to their target.
+const VALUE_TRANSFERRING_CALL_TYPES: [&str; 4] = ["CALL", "CREATE", "CREATE2", "SELFDESTRUCT"];
+
+impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
+	/// An alternative to [Self::ethereum_deposits] which traces every transaction in the block,
+	/// using the node's `debug_traceBlockByHash` call tracer, and witnesses all successful
+	/// transfers of native ETH to deposit channels, including internal transfers made by smart
+	/// contract wallets.
+	///
+	/// The witnesses are the same as the ones produced by [Self::ethereum_deposits]: one per
+	/// deposit channel address, in address order, with the amount received in the block. Ether
+	/// that a deposit contract forwards to the Vault is witnessed when it is received by the
+	/// contract, which is when the Vault emits `FetchedNative` for it. So nodes using either
+	/// mode vote for the same `process_deposits` calls.
+	///
+	/// This requires the node to expose the `debug` namespace.
+	pub fn traced_ethereum_deposits<ProcessCall, ProcessingFut, EthRetryRpcClient>(
+		self,
+		process_call: ProcessCall,
+		eth_rpc: EthRetryRpcClient,
+		native_asset: <Inner::Chain as cf_chains::Chain>::ChainAsset,
+	) -> ChunkedByVaultBuilder<impl ChunkedByVault>
+	where
+		Inner::Chain:
+			cf_chains::Chain<ChainAmount = u128, DepositDetails = (), ChainAccount = H160>,
+		Inner: ChunkedByVault<Index = u64, Hash = H256, Data = (Bloom, Addresses<Inner>)>,
+		ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
+			+ Send
+			+ Sync
+			+ Clone
+			+ 'static,
+		ProcessingFut: Future<Output = ()> + Send + 'static,
+		EthRetryRpcClient: CallTracerRetryRpcApi + Send + Sync + Clone,
+		state_chain_runtime::Runtime: RuntimeHasChain<Inner::Chain>,
+		state_chain_runtime::RuntimeCall:
+			RuntimeCallHasChain<state_chain_runtime::Runtime, Inner::Chain>,
+	{
+		self.then(move |epoch, header| {
+			let eth_rpc = eth_rpc.clone();
+			let process_call = process_call.clone();
+			async move {
+				let (_bloom, deposit_channels) = header.data;
+
+				let addresses = deposit_channels
+					.into_iter()
+					.filter(|deposit_channel| deposit_channel.deposit_channel.asset == native_asset)
+					.map(|deposit_channel| deposit_channel.deposit_channel.address)
+					.collect::<BTreeSet<_>>();
+
+				if !addresses.is_empty() {
+					let ingresses =
+						traced_ingresses(&eth_rpc.trace_block_calls(header.hash).await, &addresses);
+
+					if !ingresses.is_empty() {
+						process_call(
+							pallet_cf_ingress_egress::Call::<
+								_,
+								<Inner::Chain as PalletInstanceAlias>::Instance,
+							>::process_deposits {
+								deposit_witnesses: ingresses
+									.into_iter()
+									.map(|(to_addr, value)| {
+										pallet_cf_ingress_egress::DepositWitness {
+											deposit_address: to_addr,
+											asset: native_asset,
+											amount: value.try_into().expect(
+												"Ingress witness transfer value should fit u128",
+											),
+											deposit_details: (),
+										}
+									})
+									.collect(),
+								block_height: header.index,
+							}
+							.into(),
+							epoch.index,
+						)
+						.await;
+					}
+				}
+			}
+		})
+	}
+}
+
+/// Sums up the ether transferred to each of the given addresses by the traced calls, including
+/// all nested calls. Calls that reverted are ignored, along with all of their nested calls, since
+/// none of their transfers took effect.
+///
+/// Once a deposit contract is deployed it forwards any ether it receives to the Vault, but since
+/// those calls are made *from* the deposit address they are not counted again.
+fn traced_ingresses(calls: &[CallFrame], addresses: &BTreeSet<H160>) -> Vec<(H160, U256)> {
+	fn visit(call: &CallFrame, addresses: &BTreeSet<H160>, totals: &mut BTreeMap<H160, U256>) {
+		if call.error.is_some() {
+			return
+		}
+		if let (Some(to), Some(value)) = (call.to, call.value) {
+			if !value.is_zero() &&
+				addresses.contains(&to) &&
+				VALUE_TRANSFERRING_CALL_TYPES.contains(&call.typ.as_str())
+			{
+				let total = totals.entry(to).or_default();
+				*total = total.saturating_add(value);
+			}
+		}
+		for nested_call in &call.calls {
+			visit(nested_call, addresses, totals);
+		}
+	}
+
+	let mut totals = BTreeMap::new();
+	for call in calls {
+		visit(call, addresses, &mut totals);
+	}
+	totals.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		eth::rpc::address_checker::AddressState,
+		witness::eth::{ethereum_deposits::eth_ingresses_at_block, vault::FetchedNativeFilter},
+	};
+
+	fn call(typ: &str, to: H160, value: u64, calls: Vec<CallFrame>) -> CallFrame {
+		CallFrame {
+			typ: typ.to_string(),
+			from: H160::repeat_byte(0xff),
+			to: Some(to),
+			value: Some(U256::from(value)),
+			error: None,
+			calls,
+		}
+	}
+
+	#[test]
+	fn witnesses_direct_and_internal_transfers() {
+		let channel_1 = H160::repeat_byte(1);
+		let channel_2 = H160::repeat_byte(2);
+		let smart_wallet = H160::repeat_byte(3);
+		let addresses = BTreeSet::from([channel_1, channel_2]);
+
+		let ingresses = traced_ingresses(
+			&[
+				// A direct transfer.
+				call("CALL", channel_1, 100, vec![]),
+				// A smart contract wallet forwarding to both channels.
+				call(
+					"CALL",
+					smart_wallet,
+					0,
+					vec![
+						call("CALL", channel_1, 20, vec![]),
+						call("CALL", smart_wallet, 0, vec![call("CALL", channel_2, 30, vec![])]),
+					],
+				),
+				// Not a channel.
+				call("CALL", smart_wallet, 1000, vec![]),
+			],
+			&addresses,
+		);
+
+		assert_eq!(ingresses, vec![(channel_1, U256::from(120)), (channel_2, U256::from(30))]);
+	}
+
+	#[test]
+	fn ignores_reverted_calls_and_calls_that_do_not_move_value() {
+		let channel = H160::repeat_byte(1);
+		let addresses = BTreeSet::from([channel]);
+
+		let reverted = |calls| CallFrame {
+			error: Some("execution reverted".to_string()),
+			..call("CALL", H160::repeat_byte(3), 0, calls)
+		};
+
+		let ingresses = traced_ingresses(
+			&[
+				reverted(vec![call("CALL", channel, 100, vec![])]),
+				call("CALL", H160::repeat_byte(3), 0, vec![reverted(vec![])]),
+				// Delegate calls report the value of the parent call, but don't transfer it.
+				call("DELEGATECALL", channel, 200, vec![]),
+				call("STATICCALL", channel, 0, vec![]),
+			],
+			&addresses,
+		);
+
+		assert!(ingresses.is_empty());
+	}
+
+	#[test]
+	fn witnesses_match_the_address_checker() {
+		let vault = H160::repeat_byte(0xee);
+		let smart_wallet = H160::repeat_byte(0xdd);
+		// Not deployed yet, receives a direct and an internal transfer.
+		let undeployed_channel = H160::repeat_byte(4);
+		// Deployed already, forwards what it receives to the Vault.
+		let deployed_channel = H160::repeat_byte(3);
+		// Deployed in this block, sweeping what it received before.
+		let deploying_channel = H160::repeat_byte(2);
+		// Nothing happens to it.
+		let idle_channel = H160::repeat_byte(1);
+
+		let forward_to_vault = |value| call("CALL", vault, value, vec![]);
+
+		let traced = traced_ingresses(
+			&[
+				call("CALL", undeployed_channel, 100, vec![]),
+				call("CALL", smart_wallet, 0, vec![call("CALL", undeployed_channel, 20, vec![])]),
+				call("CALL", deployed_channel, 30, vec![forward_to_vault(30)]),
+				call(
+					"CALL",
+					vault,
+					0,
+					vec![call("CREATE2", deploying_channel, 0, vec![forward_to_vault(50)])],
+				),
+			],
+			&BTreeSet::from([
+				undeployed_channel,
+				deployed_channel,
+				deploying_channel,
+				idle_channel,
+			]),
+		);
+
+		let state = |balance: u64, has_contract| AddressState {
+			balance: U256::from(balance),
+			has_contract,
+		};
+		let address_checker = eth_ingresses_at_block(
+			// Checked once each, in address order, as in `ethereum_deposits`.
+			BTreeSet::from([undeployed_channel, deployed_channel, deploying_channel, idle_channel])
+				.into_iter()
+				.map(|address| {
+					(
+						address,
+						match address {
+							address if address == undeployed_channel =>
+								(state(0, false), state(120, false)),
+							address if address == deployed_channel =>
+								(state(0, true), state(0, true)),
+							address if address == deploying_channel =>
+								(state(50, false), state(0, true)),
+							_ => (state(0, false), state(0, false)),
+						},
+					)
+				}),
+			vec![
+				FetchedNativeFilter { sender: deployed_channel, amount: U256::from(30) },
+				FetchedNativeFilter { sender: deploying_channel, amount: U256::from(50) },
+			],
+		)
+		.unwrap();
+
+		assert_eq!(
+			traced,
+			vec![(deployed_channel, U256::from(30)), (undeployed_channel, U256::from(120))]
+		);
+		assert_eq!(traced, address_checker);
+	}
+}
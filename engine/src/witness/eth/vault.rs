@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use ethers::types::Bloom;
 use sp_core::{H160, H256};
 
-use crate::eth::retry_rpc::EthersRetryRpcApi;
+use crate::{
+	eth::retry_rpc::EthersRetryRpcApi,
+	witness::common::{ExternalChain, RuntimeCallHasChain, RuntimeHasChain},
+};
 
 use super::{
 	super::common::{
@@ -20,24 +23,39 @@ use cf_chains::{
 	address::EncodedAddress, eth::Address as EthereumAddress, CcmChannelMetadata,
 	CcmDepositMetadata,
 };
-use cf_primitives::{chains::assets::eth::Asset as EthereumAsset, Asset, ForeignChain};
+use cf_primitives::{Asset, ForeignChain};
 use ethers::prelude::*;
-use state_chain_runtime::{EthereumInstance, Runtime, RuntimeCall};
+use state_chain_runtime::{PalletInstanceAlias, Runtime, RuntimeCall};
 
 abigen!(Vault, "$CF_ETH_CONTRACT_ABI_ROOT/$CF_ETH_CONTRACT_ABI_TAG/IVault.json");
 
-pub fn call_from_event(
+pub fn call_from_event<C: ExternalChain>(
 	event: Event<VaultEvents>,
 	// can be different for different EVM chains
 	native_asset: Asset,
 	source_chain: ForeignChain,
 	supported_assets: &HashMap<EthereumAddress, Asset>,
-) -> Result<Option<RuntimeCall>> {
+) -> Result<Option<RuntimeCall>>
+where
+	C: cf_chains::Chain<ChainAmount = u128, ChainAccount = H160>,
+	C::ChainAsset: TryFrom<Asset>,
+	Runtime: RuntimeHasChain<C>,
+	RuntimeCall: RuntimeCallHasChain<Runtime, C>,
+{
 	fn try_into_encoded_address(chain: ForeignChain, bytes: Vec<u8>) -> Result<EncodedAddress> {
 		EncodedAddress::from_chain_bytes(chain, bytes)
 			.map_err(|e| anyhow!("Failed to convert into EncodedAddress: {e}"))
 	}
 
+	fn try_into_chain_asset<C: cf_chains::Chain>(asset: Asset) -> Result<C::ChainAsset>
+	where
+		C::ChainAsset: TryFrom<Asset>,
+	{
+		asset
+			.try_into()
+			.map_err(|_| anyhow!("Asset {asset:?} is not supported by the {} chain", C::NAME))
+	}
+
 	fn try_into_primitive<Primitive: std::fmt::Debug + TryInto<CfType> + Copy, CfType>(
 		from: Primitive,
 	) -> Result<CfType>
@@ -66,6 +84,7 @@ pub fn call_from_event(
 				dst_address.to_vec(),
 			)?,
 			tx_hash: event.tx_hash.into(),
+			refund_parameters: None,
 		})),
 		VaultEvents::SwapTokenFilter(SwapTokenFilter {
 			dst_chain,
@@ -86,6 +105,7 @@ pub fn call_from_event(
 				dst_address.to_vec(),
 			)?,
 			tx_hash: event.tx_hash.into(),
+			refund_parameters: None,
 		})),
 		VaultEvents::XcallNativeFilter(XcallNativeFilter {
 			dst_chain,
@@ -161,28 +181,29 @@ pub fn call_from_event(
 		VaultEvents::TransferNativeFailedFilter(TransferNativeFailedFilter {
 			recipient,
 			amount,
-		}) => Some(RuntimeCall::EthereumIngressEgress(
-			pallet_cf_ingress_egress::Call::vault_transfer_failed {
-				asset: EthereumAsset::Eth,
+		}) => Some(
+			pallet_cf_ingress_egress::Call::<Runtime, <C as PalletInstanceAlias>::Instance>::vault_transfer_failed {
+				asset: try_into_chain_asset::<C>(native_asset)?,
 				amount: try_into_primitive(amount)?,
 				destination_address: recipient,
-			},
-		)),
+			}
+			.into(),
+		),
 		VaultEvents::TransferTokenFailedFilter(TransferTokenFailedFilter {
 			recipient,
 			amount,
 			token,
 			reason: _,
-		}) => Some(RuntimeCall::EthereumIngressEgress(pallet_cf_ingress_egress::Call::<
-			Runtime,
-			EthereumInstance,
-		>::vault_transfer_failed {
-			asset: (*(supported_assets.get(&token).ok_or(anyhow!("Asset {token:?} not found"))?))
-				.try_into()
-				.expect("Asset translated from EthereumAddress must be supported by the chain."),
-			amount: try_into_primitive(amount)?,
-			destination_address: recipient,
-		})),
+		}) => Some(
+			pallet_cf_ingress_egress::Call::<Runtime, <C as PalletInstanceAlias>::Instance>::vault_transfer_failed {
+				asset: try_into_chain_asset::<C>(
+					*(supported_assets.get(&token).ok_or(anyhow!("Asset {token:?} not found"))?),
+				)?,
+				amount: try_into_primitive(amount)?,
+				destination_address: recipient,
+			}
+			.into(),
+		),
 		_ => None,
 	})
 }
@@ -204,6 +225,7 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 	where
 		Inner::Chain:
 			cf_chains::Chain<ChainAmount = u128, DepositDetails = (), ChainAccount = H160>,
+		<Inner::Chain as cf_chains::Chain>::ChainAsset: TryFrom<Asset>,
 		Inner: ChunkedByVault<Index = u64, Hash = H256, Data = Bloom>,
 		ProcessCall: Fn(state_chain_runtime::RuntimeCall, EpochIndex) -> ProcessingFut
 			+ Send
@@ -211,6 +233,8 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 			+ Clone
 			+ 'static,
 		ProcessingFut: Future<Output = ()> + Send + 'static,
+		Runtime: RuntimeHasChain<Inner::Chain>,
+		RuntimeCall: RuntimeCallHasChain<Runtime, Inner::Chain>,
 	{
 		self.then::<Result<Bloom>, _, _>(move |epoch, header| {
 			let process_call = process_call.clone();
@@ -220,7 +244,12 @@ impl<Inner: ChunkedByVault> ChunkedByVaultBuilder<Inner> {
 				for event in
 					events_at_block::<VaultEvents, _>(header, contract_address, &eth_rpc).await?
 				{
-					match call_from_event(event, native_asset, source_chain, &supported_assets) {
+					match call_from_event::<Inner::Chain>(
+						event,
+						native_asset,
+						source_chain,
+						&supported_assets,
+					) {
 						Ok(option_call) =>
 							if let Some(call) = option_call {
 								process_call(call, epoch.index).await;
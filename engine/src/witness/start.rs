@@ -7,6 +7,8 @@ use crate::{
 	db::PersistentKeyDB,
 	dot::retry_rpc::DotRetryRpcClient,
 	eth::retry_rpc::EthersRetryRpcClient,
+	health::HealthMonitor,
+	settings::NativeDepositWitnessing,
 	state_chain_observer::client::{
 		extrinsic_api::signed::SignedExtrinsicApi, storage_api::StorageApi, StateChainStreamApi,
 	},
@@ -26,12 +28,16 @@ use anyhow::Result;
 pub async fn start<StateChainClient>(
 	scope: &Scope<'_, anyhow::Error>,
 	eth_client: EthersRetryRpcClient,
+	eth_native_deposit_witnessing: NativeDepositWitnessing,
 	btc_client: BtcRetryRpcClient,
 	dot_client: DotRetryRpcClient,
+	asset_hub_client: Option<DotRetryRpcClient>,
+	arb_client: Option<(EthersRetryRpcClient, NativeDepositWitnessing)>,
 	state_chain_client: Arc<StateChainClient>,
 	state_chain_stream: impl StateChainStreamApi + Clone,
 	unfinalised_state_chain_stream: impl StateChainStreamApi<false> + Clone,
 	db: Arc<PersistentKeyDB>,
+	health_monitor: Arc<HealthMonitor>,
 ) -> Result<()>
 where
 	StateChainClient: StorageApi + ChainApi + SignedExtrinsicApi + 'static + Send + Sync,
@@ -77,6 +83,7 @@ where
 	let start_eth = super::eth::start(
 		scope,
 		eth_client,
+		eth_native_deposit_witnessing,
 		witness_call.clone(),
 		prewitness_call.clone(),
 		state_chain_client.clone(),
@@ -84,6 +91,7 @@ where
 		unfinalised_state_chain_stream.clone(),
 		epoch_source.clone(),
 		db.clone(),
+		health_monitor.clone(),
 	);
 
 	let start_btc = super::btc::start(
@@ -96,21 +104,46 @@ where
 		unfinalised_state_chain_stream.clone(),
 		epoch_source.clone(),
 		db.clone(),
+		health_monitor.clone(),
 	);
 
 	let start_dot = super::dot::start(
 		scope,
 		dot_client,
-		witness_call,
-		prewitness_call,
-		state_chain_client,
-		state_chain_stream,
-		unfinalised_state_chain_stream,
-		epoch_source,
-		db,
+		asset_hub_client,
+		witness_call.clone(),
+		prewitness_call.clone(),
+		state_chain_client.clone(),
+		state_chain_stream.clone(),
+		unfinalised_state_chain_stream.clone(),
+		epoch_source.clone(),
+		db.clone(),
+		health_monitor.clone(),
 	);
 
-	futures::future::try_join3(start_eth, start_btc, start_dot).await?;
+	// Arbitrum is optional, so it's only witnessed if a node is configured.
+	let start_arb = async move {
+		match arb_client {
+			Some((arb_client, arb_native_deposit_witnessing)) =>
+				super::arb::start(
+					scope,
+					arb_client,
+					arb_native_deposit_witnessing,
+					witness_call,
+					prewitness_call,
+					state_chain_client,
+					state_chain_stream,
+					unfinalised_state_chain_stream,
+					epoch_source,
+					db,
+					health_monitor,
+				)
+				.await,
+			None => Ok(()),
+		}
+	};
+
+	futures::future::try_join4(start_eth, start_btc, start_dot, start_arb).await?;
 
 	Ok(())
 }
@@ -7,8 +7,8 @@ use core::convert::Infallible;
 
 use codec::{Decode, Encode};
 use common::{
-	price_to_sqrt_price, sqrt_price_to_price, Amount, OneToZero, Order, Price, SetFeesError, Side,
-	SideMap, SqrtPriceQ64F96, Tick, ZeroToOne,
+	mul_div_floor, price_to_sqrt_price, sqrt_price_to_price, Amount, OneToZero, Order, Price,
+	SetFeesError, Side, SideMap, SqrtPriceQ64F96, Tick, ZeroToOne, ONE_IN_HUNDREDTH_PIPS,
 };
 use limit_orders::{Collected, PositionInfo};
 use range_orders::Liquidity;
@@ -83,6 +83,20 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 	///
 	/// This function never panics.
 	pub fn swap(&mut self, side: Side, order: Order, amount: Amount) -> (Amount, Amount) {
+		let (output_amount, remaining_amount, _fees) = self.swap_with_fees(side, order, amount);
+		(output_amount, remaining_amount)
+	}
+
+	/// Performs a swap like [Self::swap], and also returns the fees paid to the limit and range
+	/// orders the swap was executed against, in the input asset.
+	///
+	/// This function never panics.
+	pub fn swap_with_fees(
+		&mut self,
+		side: Side,
+		order: Order,
+		amount: Amount,
+	) -> (Amount, Amount, Amount) {
 		match (side, order) {
 			(Side::Zero, Order::Sell) => self.inner_swap::<ZeroToOne>(amount, None),
 			(Side::One, Order::Sell) => self.inner_swap::<OneToZero>(amount, None),
@@ -96,11 +110,12 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 		&mut self,
 		mut amount: Amount,
 		sqrt_price_limit: Option<SqrtPriceQ64F96>,
-	) -> (Amount, Amount) {
+	) -> (Amount, Amount, Amount) {
 		let mut total_output_amount = Amount::zero();
+		let mut total_fees = Amount::zero();
 
 		while !amount.is_zero() {
-			let (output_amount, remaining_amount) = match (
+			let (fee_hundredth_pips, (output_amount, remaining_amount)) = match (
 				self.limit_orders.current_sqrt_price::<SD>().filter(|sqrt_price| {
 					sqrt_price_limit.map_or(true, |sqrt_price_limit| {
 						!SD::sqrt_price_op_more_than(*sqrt_price, sqrt_price_limit)
@@ -114,7 +129,10 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 			) {
 				(Some(limit_order_sqrt_price), Some(range_order_sqrt_price)) => {
 					if SD::sqrt_price_op_more_than(limit_order_sqrt_price, range_order_sqrt_price) {
-						self.range_orders.swap::<SD>(amount, Some(limit_order_sqrt_price))
+						(
+							self.range_orders.fee_hundredth_pips,
+							self.range_orders.swap::<SD>(amount, Some(limit_order_sqrt_price)),
+						)
 					} else {
 						// Note it is important that in the equal price case we prefer to swap limit
 						// orders as if we do a swap with range_orders where the sqrt_price_limit is
@@ -122,19 +140,34 @@ impl<LiquidityProvider: Clone + Ord> PoolState<LiquidityProvider> {
 						// price or use any of the input amount, therefore we would loop forever
 
 						// Also we prefer limit orders as they don't immediately incur slippage
-						self.limit_orders.swap::<SD>(amount, Some(range_order_sqrt_price))
+						(
+							self.limit_orders.fee_hundredth_pips,
+							self.limit_orders.swap::<SD>(amount, Some(range_order_sqrt_price)),
+						)
 					}
 				},
-				(Some(_), None) => self.limit_orders.swap::<SD>(amount, sqrt_price_limit),
-				(None, Some(_)) => self.range_orders.swap::<SD>(amount, sqrt_price_limit),
+				(Some(_), None) => (
+					self.limit_orders.fee_hundredth_pips,
+					self.limit_orders.swap::<SD>(amount, sqrt_price_limit),
+				),
+				(None, Some(_)) => (
+					self.range_orders.fee_hundredth_pips,
+					self.range_orders.swap::<SD>(amount, sqrt_price_limit),
+				),
 				(None, None) => break,
 			};
 
+			// The fee is taken from the part of the input that was swapped
+			total_fees = total_fees.saturating_add(mul_div_floor(
+				amount.saturating_sub(remaining_amount),
+				Amount::from(fee_hundredth_pips),
+				Amount::from(ONE_IN_HUNDREDTH_PIPS),
+			));
 			amount = remaining_amount;
 			total_output_amount = total_output_amount.saturating_add(output_amount);
 		}
 
-		(total_output_amount, amount)
+		(total_output_amount, amount, total_fees)
 	}
 
 	pub fn collect_and_mint_limit_order(
@@ -12,8 +12,8 @@ use cf_traits::{AsyncResult, EpochInfo, SafeMode, VaultRotator, VaultStatus};
 use pallet_cf_environment::SafeModeUpdate;
 use pallet_cf_validator::{CurrentRotationPhase, RotationPhase};
 use state_chain_runtime::{
-	safe_mode::RuntimeSafeMode, BitcoinVault, Environment, EthereumInstance, EthereumVault, Flip,
-	PolkadotInstance, PolkadotVault, Runtime, RuntimeOrigin, Validator,
+	safe_mode::RuntimeSafeMode, ArbitrumVault, BitcoinVault, Environment, EthereumInstance,
+	EthereumVault, Flip, PolkadotInstance, PolkadotVault, Runtime, RuntimeOrigin, Validator,
 };
 
 // Helper function that creates a network, funds backup nodes, and have them join the auction.
@@ -381,6 +381,11 @@ fn authority_rotation_can_recover_after_keygen_fails() {
 					BitcoinVault::ceremony_id_counter(),
 					Err(BTreeSet::default()),
 				));
+				assert_ok!(ArbitrumVault::report_keygen_outcome(
+					RuntimeOrigin::signed(validator.clone()),
+					ArbitrumVault::ceremony_id_counter(),
+					Err(BTreeSet::default()),
+				));
 			});
 
 			// Authority rotation can recover and succeed.
@@ -8,7 +8,7 @@ use crate::mock_runtime::{
 use super::*;
 use cf_primitives::AccountRole;
 use cf_traits::{AccountInfo, EpochInfo, QualifyNode};
-use state_chain_runtime::{BitcoinVault, EthereumVault, PolkadotVault};
+use state_chain_runtime::{ArbitrumVault, BitcoinVault, EthereumVault, PolkadotVault};
 pub const GENESIS_BALANCE: FlipBalance = TOTAL_ISSUANCE / 100;
 
 const BLOCKS_PER_EPOCH: u32 = 1000;
@@ -69,6 +69,7 @@ fn state_of_genesis_is_as_expected() {
 		assert_eq!(EthereumVault::ceremony_id_counter(), 0, "no key generation requests");
 		assert_eq!(PolkadotVault::ceremony_id_counter(), 0, "no key generation requests");
 		assert_eq!(BitcoinVault::ceremony_id_counter(), 0, "no key generation requests");
+		assert_eq!(ArbitrumVault::ceremony_id_counter(), 0, "no key generation requests");
 
 		assert_eq!(
 			pallet_cf_environment::EthereumSignatureNonce::<Runtime>::get(),
@@ -13,13 +13,15 @@ use state_chain_runtime::{
 };
 
 use cf_chains::{
+	arb::ArbitrumTrackedData,
 	btc::{BitcoinFeeInfo, BitcoinTrackedData},
 	dot::{PolkadotTrackedData, RuntimeVersion},
 	eth::EthereumTrackedData,
-	Bitcoin, ChainState, Ethereum, Polkadot,
+	Arbitrum, Bitcoin, ChainState, Ethereum, Polkadot,
 };
 use state_chain_runtime::{
-	BitcoinChainTrackingConfig, EthereumChainTrackingConfig, PolkadotChainTrackingConfig,
+	ArbitrumChainTrackingConfig, BitcoinChainTrackingConfig, EthereumChainTrackingConfig,
+	PolkadotChainTrackingConfig,
 };
 
 pub const CURRENT_AUTHORITY_EMISSION_INFLATION_PERBILL: u32 = 28;
@@ -221,11 +223,19 @@ impl ExtBuilder {
 					tracked_data: BitcoinTrackedData { btc_fee_info: BitcoinFeeInfo::new(1000) },
 				},
 			},
+			arbitrum_chain_tracking: ArbitrumChainTrackingConfig {
+				init_chain_state: ChainState::<Arbitrum> {
+					block_height: 0,
+					tracked_data: ArbitrumTrackedData { base_fee: 100000000u32.into() },
+				},
+			},
 			bitcoin_threshold_signer: Default::default(),
 			ethereum_threshold_signer: Default::default(),
 			polkadot_threshold_signer: Default::default(),
+			arbitrum_threshold_signer: Default::default(),
 			bitcoin_vault: Default::default(),
 			polkadot_vault: Default::default(),
+			arbitrum_vault: Default::default(),
 			environment: Default::default(),
 			liquidity_pools: Default::default(),
 			swapping: Default::default(),
@@ -234,6 +244,7 @@ impl ExtBuilder {
 			bitcoin_ingress_egress: Default::default(),
 			polkadot_ingress_egress: Default::default(),
 			ethereum_ingress_egress: Default::default(),
+			arbitrum_ingress_egress: Default::default(),
 		})
 	}
 }
@@ -17,8 +17,8 @@ use pallet_cf_validator::RotationPhase;
 use sp_consensus_aura::SlotDuration;
 use sp_std::collections::btree_set::BTreeSet;
 use state_chain_runtime::{
-	AccountRoles, AllPalletsWithSystem, BitcoinInstance, EthereumInstance, PolkadotInstance,
-	Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Weight,
+	AccountRoles, AllPalletsWithSystem, ArbitrumInstance, BitcoinInstance, EthereumInstance,
+	PolkadotInstance, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin, Weight,
 };
 use std::{
 	cell::RefCell,
@@ -149,6 +149,7 @@ pub struct Engine {
 	pub eth_threshold_signer: Rc<RefCell<EthThresholdSigner>>,
 	pub dot_threshold_signer: Rc<RefCell<DotThresholdSigner>>,
 	pub btc_threshold_signer: Rc<RefCell<BtcThresholdSigner>>,
+	pub arb_threshold_signer: Rc<RefCell<EthThresholdSigner>>,
 }
 
 impl Engine {
@@ -157,6 +158,7 @@ impl Engine {
 		eth_threshold_signer: Rc<RefCell<EthThresholdSigner>>,
 		dot_threshold_signer: Rc<RefCell<DotThresholdSigner>>,
 		btc_threshold_signer: Rc<RefCell<BtcThresholdSigner>>,
+		arb_threshold_signer: Rc<RefCell<EthThresholdSigner>>,
 	) -> Self {
 		Engine {
 			node_id,
@@ -164,6 +166,7 @@ impl Engine {
 			eth_threshold_signer,
 			dot_threshold_signer,
 			btc_threshold_signer,
+			arb_threshold_signer,
 			auto_submit_heartbeat: true,
 			last_heartbeat: Default::default(),
 		}
@@ -227,6 +230,7 @@ impl Engine {
 							self.eth_threshold_signer.borrow_mut().use_proposed_key();
 							self.dot_threshold_signer.borrow_mut().use_proposed_key();
 							self.btc_threshold_signer.borrow_mut().use_proposed_key();
+							self.arb_threshold_signer.borrow_mut().use_proposed_key();
 					}
 					RuntimeEvent::EthereumThresholdSigner(
 						// A signature request
@@ -281,6 +285,24 @@ impl Engine {
 								), RuntimeOrigin::none()
 							);
 					}
+
+					RuntimeEvent::ArbitrumThresholdSigner(
+						pallet_cf_threshold_signature::Event::ThresholdSignatureRequest{
+							ceremony_id,
+							key,
+							payload,
+							..
+						}) => {
+							queue_dispatch_extrinsic(
+								RuntimeCall::ArbitrumThresholdSigner(
+									pallet_cf_threshold_signature::Call::signature_success{
+										ceremony_id: *ceremony_id,
+										signature: self.arb_threshold_signer.borrow().sign_with_key(*key, payload.as_fixed_bytes()),
+									}
+								),
+								RuntimeOrigin::none()
+							);
+					}
 					RuntimeEvent::Validator(pallet_cf_validator::Event::RotationPhaseUpdated { new_phase: RotationPhase::ActivatingKeys(_) }) => {
 						// NOTE: This is a little inaccurate a representation of how it actually works. An event is emitted
 						// which contains the transaction to broadcast for the rotation tx, which the CFE then broadcasts.
@@ -299,6 +321,17 @@ impl Engine {
 								}),
 							RuntimeOrigin::signed(self.node_id.clone())
 						);
+						queue_dispatch_extrinsic(
+							RuntimeCall::Witnesser(
+								pallet_cf_witnesser::Call::witness_at_epoch {
+									call: Box::new(pallet_cf_vaults::Call::<_, ArbitrumInstance>::vault_key_rotated {
+										block_number: 100,
+										tx_id: [1u8; 32].into(),
+									}.into()),
+									epoch_index: Validator::epoch_index(),
+								}),
+							RuntimeOrigin::signed(self.node_id.clone())
+						);
 					}
 
 					RuntimeEvent::PolkadotVault(pallet_cf_vaults::Event::<_, PolkadotInstance>::AwaitingGovernanceActivation { .. }) => {
@@ -322,6 +355,17 @@ impl Engine {
 							pallet_cf_governance::RawOrigin::GovernanceApproval.into()
 						);
 					}
+					RuntimeEvent::ArbitrumVault(pallet_cf_vaults::Event::<_, ArbitrumInstance>::AwaitingGovernanceActivation { .. }) => {
+						queue_dispatch_extrinsic(
+							RuntimeCall::Environment(pallet_cf_environment::Call::witness_arbitrum_vault_deployment {
+								key_manager_address: Default::default(),
+								vault_address: Default::default(),
+								address_checker_address: Default::default(),
+								deployment_block: 0,
+							}),
+							pallet_cf_governance::RawOrigin::GovernanceApproval.into()
+						);
+					}
 				};
 			}
 
@@ -362,6 +406,17 @@ impl Engine {
 							), RuntimeOrigin::signed(self.node_id.clone()));
 						}
 				}
+				RuntimeEvent::ArbitrumVault(
+					pallet_cf_vaults::Event::KeygenRequest {ceremony_id, participants, .. }) => {
+						if participants.contains(&self.node_id) {
+							queue_dispatch_extrinsic(RuntimeCall::ArbitrumVault(
+								pallet_cf_vaults::Call::report_keygen_outcome {
+									ceremony_id: *ceremony_id,
+									reported_outcome: Ok(self.arb_threshold_signer.borrow_mut().propose_new_key()),
+								}
+							), RuntimeOrigin::signed(self.node_id.clone()));
+						}
+				}
 				RuntimeEvent::BitcoinVault(
 					pallet_cf_vaults::Event::KeyHandoverRequest {ceremony_id, sharing_participants, receiving_participants, .. }) => {
 						let all_participants = sharing_participants.union(receiving_participants).cloned().collect::<BTreeSet<_>>();
@@ -422,6 +477,7 @@ pub struct Network {
 	pub eth_threshold_signer: Rc<RefCell<EthThresholdSigner>>,
 	pub dot_threshold_signer: Rc<RefCell<DotThresholdSigner>>,
 	pub btc_threshold_signer: Rc<RefCell<BtcThresholdSigner>>,
+	pub arb_threshold_signer: Rc<RefCell<EthThresholdSigner>>,
 }
 
 thread_local! {
@@ -443,6 +499,7 @@ pub fn dispatch_all_pending_extrinsics() {
 				RuntimeCall::EthereumThresholdSigner(..) |
 				RuntimeCall::PolkadotThresholdSigner(..) |
 				RuntimeCall::BitcoinThresholdSigner(..) |
+				RuntimeCall::ArbitrumThresholdSigner(..) |
 				RuntimeCall::Environment(..) => {
 					// These are allowed to fail, since it is possible to sign things
 					// that have already succeeded
@@ -469,6 +526,11 @@ pub fn dispatch_all_pending_extrinsics() {
 						Validator::current_rotation_phase(),
 						BitcoinVault::pending_vault_rotations()
 					),
+					RuntimeCall::ArbitrumVault(..) => log::info!(
+						"Validator status: {:?}\nVault Status: {:?}",
+						Validator::current_rotation_phase(),
+						ArbitrumVault::pending_vault_rotations()
+					),
 					RuntimeCall::Validator(..) => log::info!(
 						"Validator status: {:?}\nAllVaults Status: {:?}",
 						Validator::current_rotation_phase(),
@@ -549,6 +611,7 @@ impl Network {
 				self.eth_threshold_signer.clone(),
 				self.dot_threshold_signer.clone(),
 				self.btc_threshold_signer.clone(),
+				self.arb_threshold_signer.clone(),
 			),
 		);
 	}
@@ -161,6 +161,7 @@ fn set_limit_order(
 		id,
 		tick,
 		sell_amount,
+		None,
 	));
 	let new_sell_balance =
 		pallet_cf_lp::FreeBalances::<Runtime>::get(account_id, sell_asset).unwrap_or_default();
@@ -225,6 +226,7 @@ fn basic_pool_setup_provision_and_swap() {
 			EncodedAddress::Eth([1u8; 20]),
 			0u16,
 			None,
+			None,
 		));
 
 		let deposit_address = <AddressDerivation as AddressDerivationApi<Ethereum>>::generate_address(
@@ -329,6 +331,7 @@ fn can_process_ccm_via_swap_deposit_address() {
 			EncodedAddress::Eth([0x02; 20]),
 			0u16,
 			Some(message),
+			None,
 		));
 
 		// Deposit funds for the ccm.
@@ -533,6 +536,7 @@ fn failed_swaps_are_rolled_back() {
 						deposit_amount: amount,
 						destination_address,
 						tx_hash: Default::default(),
+						refund_parameters: None,
 					},
 				));
 				let current_epoch = Validator::current_epoch();
@@ -652,6 +656,50 @@ fn failed_swaps_are_rolled_back() {
 	});
 }
 
+#[test]
+fn network_fees_are_recorded_in_pool_stats() {
+	super::genesis::default().build().execute_with(|| {
+		setup_pool_and_accounts(vec![Asset::Eth, Asset::Flip]);
+
+		for (from, to) in [(Asset::Eth, Asset::Flip), (Asset::Usdc, Asset::Eth)] {
+			let swap_call = Box::new(RuntimeCall::Swapping(
+				pallet_cf_swapping::Call::schedule_swap_from_contract {
+					from,
+					to,
+					deposit_amount: 10_000,
+					destination_address: EncodedAddress::Eth(Default::default()),
+					tx_hash: Default::default(),
+					refund_parameters: None,
+				},
+			));
+			let current_epoch = Validator::current_epoch();
+			for node in Validator::current_authorities() {
+				assert_ok!(Witnesser::witness_at_epoch(
+					RuntimeOrigin::signed(node),
+					swap_call.clone(),
+					current_epoch
+				));
+			}
+		}
+		Swapping::on_finalize(1);
+
+		let network_fees = |asset| {
+			let stats = LiquidityPools::pool_stats(asset, STABLE_ASSET, None).unwrap();
+			(stats.swaps.base.network_fees, stats.swaps.pair.network_fees)
+		};
+		// The Eth -> Flip swap pays its fee on the Eth -> Usdc leg, and the Usdc -> Eth swap on
+		// its only leg.
+		let (eth_sold_fees, usdc_sold_fees) = network_fees(Asset::Eth);
+		assert!(!eth_sold_fees.is_zero());
+		assert!(!usdc_sold_fees.is_zero());
+		assert_eq!(network_fees(Asset::Flip), Default::default());
+		assert_eq!(
+			eth_sold_fees + usdc_sold_fees,
+			pallet_cf_pools::CollectedNetworkFee::<Runtime>::get().into()
+		);
+	});
+}
+
 #[test]
 fn ethereum_ccm_can_calculate_gas_limits() {
 	super::genesis::default().build().execute_with(|| {
@@ -49,6 +49,7 @@ pub enum ForeignChainAddress {
 	Eth(EthereumAddress),
 	Dot(PolkadotAccountId),
 	Btc(ScriptPubkey),
+	Arb(EthereumAddress),
 }
 
 impl ForeignChainAddress {
@@ -57,6 +58,7 @@ impl ForeignChainAddress {
 			ForeignChainAddress::Eth(_) => ForeignChain::Ethereum,
 			ForeignChainAddress::Dot(_) => ForeignChain::Polkadot,
 			ForeignChainAddress::Btc(_) => ForeignChain::Bitcoin,
+			ForeignChainAddress::Arb(_) => ForeignChain::Arbitrum,
 		}
 	}
 }
@@ -68,6 +70,7 @@ pub enum EncodedAddress {
 	Eth([u8; 20]),
 	Dot([u8; 32]),
 	Btc(Vec<u8>),
+	Arb([u8; 20]),
 }
 
 pub trait AddressConverter: Sized {
@@ -81,7 +84,7 @@ pub trait AddressConverter: Sized {
 impl core::fmt::Display for EncodedAddress {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		match self {
-			EncodedAddress::Eth(addr) => {
+			EncodedAddress::Eth(addr) | EncodedAddress::Arb(addr) => {
 				write!(f, "0x{}", hex::encode(&addr[..]))
 			},
 			EncodedAddress::Dot(addr) => {
@@ -104,6 +107,8 @@ pub enum AddressError {
 	InvalidAddress,
 }
 
+/// Only accepts Ethereum addresses. Arbitrum shares the account type, so its addresses are
+/// converted with [Chain::try_from_foreign_chain_address](crate::Chain) instead.
 impl TryFrom<ForeignChainAddress> for H160 {
 	type Error = AddressError;
 
@@ -131,6 +136,9 @@ impl TryFrom<ForeignChainAddress> for ScriptPubkey {
 
 	fn try_from(foreign_chain_address: ForeignChainAddress) -> Result<Self, Self::Error> {
 		match foreign_chain_address {
+			// Data outputs are unspendable, so they can never be a destination.
+			ForeignChainAddress::Btc(ScriptPubkey::OpReturn(_)) =>
+				Err(AddressError::InvalidAddress),
 			ForeignChainAddress::Btc(script_pubkey) => Ok(script_pubkey),
 			_ => Err(AddressError::InvalidAddress),
 		}
@@ -175,6 +183,14 @@ impl EncodedAddress {
 				Ok(EncodedAddress::Dot(address))
 			},
 			ForeignChain::Bitcoin => Ok(EncodedAddress::Btc(bytes)),
+			ForeignChain::Arbitrum => {
+				if bytes.len() != 20 {
+					return Err("Invalid Arbitrum address length")
+				}
+				let mut address = [0u8; 20];
+				address.copy_from_slice(&bytes);
+				Ok(EncodedAddress::Arb(address))
+			},
 		}
 	}
 }
@@ -189,6 +205,7 @@ pub fn to_encoded_address<GetNetwork: FnOnce() -> NetworkEnvironment>(
 		ForeignChainAddress::Btc(script_pubkey) => EncodedAddress::Btc(
 			script_pubkey.to_address(&network_environment().into()).as_bytes().to_vec(),
 		),
+		ForeignChainAddress::Arb(address) => EncodedAddress::Arb(address.0),
 	}
 }
 
@@ -208,6 +225,7 @@ pub fn try_from_encoded_address<GetNetwork: FnOnce() -> NetworkEnvironment>(
 			)
 			.map_err(|_| ())?,
 		)),
+		EncodedAddress::Arb(address_bytes) => Ok(ForeignChainAddress::Arb(address_bytes.into())),
 	}
 }
 
@@ -262,6 +280,7 @@ pub enum ForeignChainAddressHumanreadable {
 	Eth(<EthereumAddress as ToHumanreadableAddress>::Humanreadable),
 	Dot(<PolkadotAccountId as ToHumanreadableAddress>::Humanreadable),
 	Btc(<ScriptPubkey as ToHumanreadableAddress>::Humanreadable),
+	Arb(<EthereumAddress as ToHumanreadableAddress>::Humanreadable),
 }
 
 #[cfg(feature = "std")]
@@ -287,6 +306,8 @@ impl ToHumanreadableAddress for ForeignChainAddress {
 				ForeignChainAddressHumanreadable::Dot(address.to_humanreadable(network_environment)),
 			ForeignChainAddress::Btc(address) =>
 				ForeignChainAddressHumanreadable::Btc(address.to_humanreadable(network_environment)),
+			ForeignChainAddress::Arb(address) =>
+				ForeignChainAddressHumanreadable::Arb(address.to_humanreadable(network_environment)),
 		}
 	}
 }
@@ -329,3 +350,14 @@ fn encode_and_decode_address() {
 		test(addr, true);
 	}
 }
+
+#[test]
+fn encode_and_decode_arbitrum_address() {
+	let network = || NetworkEnvironment::Mainnet;
+	let encoded_addr =
+		EncodedAddress::from_chain_bytes(ForeignChain::Arbitrum, vec![0xcf; 20]).unwrap();
+	let foreign_chain_addr = try_from_encoded_address(encoded_addr.clone(), network).unwrap();
+	assert_eq!(foreign_chain_addr, ForeignChainAddress::Arb(H160::repeat_byte(0xcf)));
+	assert_eq!(to_encoded_address(foreign_chain_addr, network), encoded_addr);
+	assert!(EncodedAddress::from_chain_bytes(ForeignChain::Arbitrum, vec![0xcf; 32]).is_err());
+}
@@ -0,0 +1,172 @@
+//! Types and functions that are common to Arbitrum.
+pub mod api;
+
+pub mod benchmarking;
+
+use crate::{
+	evm::{DeploymentStatus, EvmFetchId, EvmTransactionMetadata, Transaction},
+	*,
+};
+use cf_primitives::chains::assets;
+pub use cf_primitives::chains::Arbitrum;
+use codec::{Decode, Encode, MaxEncodedLen};
+use evm::api::EvmReplayProtection;
+use frame_support::sp_runtime::RuntimeDebug;
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_std::{cmp::min, convert::TryInto};
+
+// Reference constants for the chain spec
+pub const CHAIN_ID_MAINNET: u64 = 42161;
+pub const CHAIN_ID_SEPOLIA: u64 = 421614;
+
+impl Chain for Arbitrum {
+	const NAME: &'static str = "Arbitrum";
+	type ChainCrypto = evm::EvmCrypto;
+
+	type ChainBlockNumber = u64;
+	type ChainAmount = EthAmount;
+	type TransactionFee = evm::TransactionFee;
+	type TrackedData = ArbitrumTrackedData;
+	type ChainAccount = evm::Address;
+	type ChainAsset = assets::arb::Asset;
+	type EpochStartData = ();
+	type DepositFetchId = EvmFetchId;
+	type DepositChannelState = DeploymentStatus;
+	type DepositDetails = ();
+	type Transaction = Transaction;
+	type TransactionMetadata = EvmTransactionMetadata;
+	type ReplayProtectionParams = Self::ChainAccount;
+	type ReplayProtection = EvmReplayProtection;
+
+	fn into_foreign_chain_address(address: Self::ChainAccount) -> ForeignChainAddress {
+		ForeignChainAddress::Arb(address)
+	}
+
+	fn try_from_foreign_chain_address(
+		address: ForeignChainAddress,
+	) -> Result<Self::ChainAccount, address::AddressError> {
+		match address {
+			ForeignChainAddress::Arb(address) => Ok(address),
+			_ => Err(address::AddressError::InvalidAddress),
+		}
+	}
+}
+
+#[derive(
+	Copy,
+	Clone,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+)]
+#[codec(mel_bound())]
+pub struct ArbitrumTrackedData {
+	/// Arbitrum doesn't use priority fees, so the base fee is the effective gas price.
+	pub base_fee: <Arbitrum as Chain>::ChainAmount,
+}
+
+impl FeeEstimationApi<Arbitrum> for ArbitrumTrackedData {
+	fn estimate_fee(&self, gas: u64) -> EthAmount {
+		self.base_fee.saturating_mul(gas.into())
+	}
+}
+
+impl Default for ArbitrumTrackedData {
+	#[track_caller]
+	fn default() -> Self {
+		panic!("You should not use the default chain tracking, as it's meaningless.")
+	}
+}
+
+impl FeeRefundCalculator<Arbitrum> for Transaction {
+	fn return_fee_refund(
+		&self,
+		fee_paid: <Arbitrum as Chain>::TransactionFee,
+	) -> <Arbitrum as Chain>::ChainAmount {
+		min(
+			self.max_fee_per_gas
+				.unwrap_or_default()
+				.try_into()
+				.expect("In practice `max_fee_per_gas` is always less than u128::MAX"),
+			fee_paid.effective_gas_price,
+		)
+		.saturating_mul(fee_paid.gas_used)
+	}
+}
+
+impl From<&DepositChannel<Arbitrum>> for EvmFetchId {
+	fn from(channel: &DepositChannel<Arbitrum>) -> Self {
+		match channel.state {
+			DeploymentStatus::Undeployed => EvmFetchId::DeployAndFetch(channel.channel_id),
+			DeploymentStatus::Pending | DeploymentStatus::Deployed =>
+				if channel.asset == assets::arb::Asset::ArbEth {
+					EvmFetchId::NotRequired
+				} else {
+					EvmFetchId::Fetch(channel.address)
+				},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn arbitrum_addresses_are_tagged_with_their_chain() {
+		let address = evm::Address::repeat_byte(0xcf);
+		assert_eq!(
+			Arbitrum::into_foreign_chain_address(address),
+			ForeignChainAddress::Arb(address)
+		);
+		assert_eq!(
+			Ethereum::into_foreign_chain_address(address),
+			ForeignChainAddress::Eth(address)
+		);
+		assert_eq!(Arbitrum::into_foreign_chain_address(address).chain(), ForeignChain::Arbitrum);
+
+		assert_eq!(
+			Arbitrum::try_from_foreign_chain_address(ForeignChainAddress::Arb(address)),
+			Ok(address)
+		);
+		assert!(
+			Arbitrum::try_from_foreign_chain_address(ForeignChainAddress::Eth(address)).is_err()
+		);
+		assert_eq!(
+			Ethereum::try_from_foreign_chain_address(ForeignChainAddress::Eth(address)),
+			Ok(address)
+		);
+		assert!(
+			Ethereum::try_from_foreign_chain_address(ForeignChainAddress::Arb(address)).is_err()
+		);
+	}
+
+	#[test]
+	fn arb_eth_is_not_fetched_from_deployed_channels() {
+		let channel = |asset, state| DepositChannel::<Arbitrum> {
+			channel_id: 1,
+			address: evm::Address::repeat_byte(0xcf),
+			asset,
+			state,
+		};
+		assert_eq!(
+			EvmFetchId::from(&channel(assets::arb::Asset::ArbEth, DeploymentStatus::Undeployed)),
+			EvmFetchId::DeployAndFetch(1)
+		);
+		assert_eq!(
+			EvmFetchId::from(&channel(assets::arb::Asset::ArbEth, DeploymentStatus::Deployed)),
+			EvmFetchId::NotRequired
+		);
+		assert_eq!(
+			EvmFetchId::from(&channel(assets::arb::Asset::ArbUsdc, DeploymentStatus::Deployed)),
+			EvmFetchId::Fetch(evm::Address::repeat_byte(0xcf))
+		);
+	}
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{
+	benchmarking_value::BenchmarkValue,
+	evm::{
+		api::{
+			set_agg_key_with_agg_key::SetAggKeyWithAggKey, EvmReplayProtection,
+			EvmTransactionBuilder,
+		},
+		AggKey,
+	},
+};
+
+use super::{api::ArbitrumApi, ArbitrumTrackedData};
+
+impl<E> BenchmarkValue for ArbitrumApi<E> {
+	fn benchmark_value() -> Self {
+		EvmTransactionBuilder::new_unsigned(
+			EvmReplayProtection::default(),
+			SetAggKeyWithAggKey::new(AggKey::benchmark_value()),
+		)
+		.into()
+	}
+}
+
+impl BenchmarkValue for ArbitrumTrackedData {
+	fn benchmark_value() -> Self {
+		Self { base_fee: 100_000_000 }
+	}
+}
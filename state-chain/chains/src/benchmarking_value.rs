@@ -1,6 +1,6 @@
 #[cfg(feature = "runtime-benchmarks")]
 use cf_primitives::{
-	chains::assets::{btc, dot, eth},
+	chains::assets::{arb, btc, dot, eth},
 	Asset,
 };
 
@@ -82,6 +82,13 @@ impl BenchmarkValue for btc::Asset {
 	}
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkValue for arb::Asset {
+	fn benchmark_value() -> Self {
+		arb::Asset::ArbEth
+	}
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 impl BenchmarkValue for ForeignChainAddress {
 	fn benchmark_value() -> Self {
@@ -21,6 +21,7 @@ use cf_utilities::SliceToArray;
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	sp_io::hashing::sha2_256,
+	sp_runtime::Percent,
 	traits::{ConstBool, ConstU32},
 	BoundedVec, RuntimeDebug,
 };
@@ -37,6 +38,10 @@ pub const CHANGE_ADDRESS_SALT: u32 = 0;
 // our construction
 pub const MAX_BITCOIN_SCRIPT_LENGTH: u32 = 128;
 
+/// Maximum number of payloads in a single bitcoin signing ceremony, ie. the maximum number of
+/// inputs in a transaction.
+pub const MAX_BTC_SIGNING_PAYLOADS: usize = 1000;
+
 pub type BlockNumber = u64;
 
 #[derive(Encode, Decode, TypeInfo, Clone, RuntimeDebug, PartialEq, Eq, Copy)]
@@ -105,6 +110,12 @@ pub struct BitcoinTrackedData {
 	pub btc_fee_info: BitcoinFeeInfo,
 }
 
+impl FeeEstimationApi<Bitcoin> for BitcoinTrackedData {
+	fn estimate_fee(&self, vbytes: u64) -> BtcAmount {
+		self.btc_fee_info.fee_per_output_utxo.saturating_mul(vbytes) / OUTPUT_UTXO_SIZE_IN_BYTES
+	}
+}
+
 impl Default for BitcoinTrackedData {
 	#[track_caller]
 	fn default() -> Self {
@@ -133,6 +144,58 @@ pub struct BitcoinFeeInfo {
 
 const BYTES_PER_KILOBYTE: BtcAmount = 1024;
 
+/// Governance-set parameters that determine when and how the vault's utxos are consolidated.
+#[derive(Copy, Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct UtxoConsolidationParameters {
+	/// Consolidate once the vault holds at least this many utxos.
+	pub utxo_count_threshold: u32,
+	/// Utxos holding less than this amount are considered dust.
+	pub dust_amount: BtcAmount,
+	/// Consolidate once at least this fraction of the vault's utxos are dust.
+	pub dust_fraction_threshold: Percent,
+	/// Only consolidate while spending an input costs at most this much.
+	pub max_fee_per_input_utxo: BtcAmount,
+	/// The maximum number of utxos swept into a single consolidation transaction.
+	pub consolidation_size: u32,
+}
+
+impl Default for UtxoConsolidationParameters {
+	fn default() -> Self {
+		Self {
+			utxo_count_threshold: 200,
+			dust_amount: 10_000,
+			dust_fraction_threshold: Percent::from_percent(25),
+			// Equivalent to a fee rate of 10 sats per vbyte.
+			max_fee_per_input_utxo: 10 * INPUT_UTXO_SIZE_IN_BYTES,
+			consolidation_size: 100,
+		}
+	}
+}
+
+impl UtxoConsolidationParameters {
+	/// A consolidation must sweep at least two utxos and fit into a single signing ceremony.
+	pub fn are_valid(&self) -> bool {
+		self.consolidation_size > 1 && self.consolidation_size as usize <= MAX_BTC_SIGNING_PAYLOADS
+	}
+
+	/// Whether the given utxos are numerous or fragmented enough to warrant a consolidation. Only
+	/// utxos that are worth spending at the given fee are counted, since no others would be swept.
+	pub fn consolidation_required(&self, utxos: &[Utxo], fee_per_input_utxo: BtcAmount) -> bool {
+		let (spendable_count, dust_count) = utxos
+			.iter()
+			.filter(|utxo| utxo.is_worth_spending(fee_per_input_utxo))
+			.fold((0usize, 0usize), |(spendable_count, dust_count), utxo| {
+				(spendable_count + 1, dust_count + (utxo.amount < self.dust_amount) as usize)
+			});
+		// A consolidation needs at least two utxos to sweep.
+		if spendable_count < 2 {
+			return false
+		}
+		spendable_count >= self.utxo_count_threshold as usize ||
+			Percent::from_rational(dust_count, spendable_count) >= self.dust_fraction_threshold
+	}
+}
+
 impl Default for BitcoinFeeInfo {
 	fn default() -> Self {
 		Self {
@@ -324,6 +387,13 @@ pub struct Utxo {
 	pub deposit_address: DepositAddress,
 }
 
+impl Utxo {
+	/// Whether the utxo holds more than it costs to spend it at the given fee.
+	pub fn is_worth_spending(&self, fee_per_input_utxo: BtcAmount) -> bool {
+		self.amount > fee_per_input_utxo
+	}
+}
+
 pub trait GetUtxoAmount {
 	fn amount(&self) -> u64;
 }
@@ -458,6 +528,11 @@ const SEGWIT_VERSION_TAPROOT: u8 = 1;
 const SEGWIT_VERSION_MAX: u8 = 16;
 const MIN_SEGWIT_PROGRAM_BYTES: u32 = 2;
 const MAX_SEGWIT_PROGRAM_BYTES: u32 = 40;
+/// The maximum size of data that can be carried by a standard OP_RETURN output.
+pub const MAX_OP_RETURN_DATA_BYTES: u32 = 80;
+
+/// Arbitrary data to be embedded in a transaction via an OP_RETURN output.
+pub type OpReturnData = BoundedVec<u8, ConstU32<MAX_OP_RETURN_DATA_BYTES>>;
 
 #[derive(
 	Clone,
@@ -479,7 +554,13 @@ pub enum ScriptPubkey {
 	P2WPKH([u8; 20]),
 	P2WSH([u8; 32]),
 	Taproot([u8; 32]),
-	OtherSegwit { version: u8, program: BoundedVec<u8, ConstU32<MAX_SEGWIT_PROGRAM_BYTES>> },
+	OtherSegwit {
+		version: u8,
+		program: BoundedVec<u8, ConstU32<MAX_SEGWIT_PROGRAM_BYTES>>,
+	},
+	/// A provably unspendable data output. This has no address and can't be used as a
+	/// destination, it's only used to attach a memo to our own transactions.
+	OpReturn(OpReturnData),
 }
 
 impl SerializeBtc for ScriptPubkey {
@@ -521,7 +602,11 @@ impl ScriptPubkey {
 			]),
 			ScriptPubkey::OtherSegwit { version, program } => BitcoinScript::new(&[
 				BitcoinOp::PushVersion { version: *version },
-				BitcoinOp::PushBytes { bytes: program.clone() },
+				BitcoinOp::PushBytes { bytes: BoundedVec::truncate_from(program.to_vec()) },
+			]),
+			ScriptPubkey::OpReturn(data) => BitcoinScript::new(&[
+				BitcoinOp::Return,
+				BitcoinOp::PushBytes { bytes: data.clone() },
 			]),
 		}
 	}
@@ -532,6 +617,12 @@ impl ScriptPubkey {
 
 	pub fn to_address(&self, network: &BitcoinNetwork) -> String {
 		let (data, maybe_bech, version) = match self {
+			// There is no address format for data outputs, so we display the raw data instead.
+			ScriptPubkey::OpReturn(data) =>
+				return alloc::format!(
+					"OP_RETURN:{}",
+					data.iter().map(|byte| alloc::format!("{byte:02x}")).collect::<String>()
+				),
 			ScriptPubkey::P2PKH(data) => (&data[..], None, network.p2pkh_address_version()),
 			ScriptPubkey::P2SH(data) => (&data[..], None, network.p2sh_address_version()),
 			ScriptPubkey::P2WPKH(data) => (&data[..], Some(Variant::Bech32), SEGWIT_VERSION_ZERO),
@@ -561,6 +652,8 @@ impl ScriptPubkey {
 		}
 	}
 
+	/// Parses a Bitcoin address. Data outputs have no address, so this never returns
+	/// [ScriptPubkey::OpReturn].
 	pub fn try_from_address(address: &str, network: &BitcoinNetwork) -> Result<Self, Error> {
 		// See https://en.bitcoin.it/wiki/Base58Check_encoding
 		fn try_decode_as_base58(address: &str, network: &BitcoinNetwork) -> Option<ScriptPubkey> {
@@ -695,6 +788,25 @@ impl BitcoinTransaction {
 			!self.signatures.iter().any(|signature| signature == &[0u8; 64])
 	}
 
+	/// The fee paid by this transaction, ie. the amount by which its inputs exceed its outputs.
+	pub fn fee(&self) -> BtcAmount {
+		self.inputs
+			.iter()
+			.map(|input| input.amount)
+			.sum::<BtcAmount>()
+			.saturating_sub(self.outputs.iter().map(|output| output.amount).sum())
+	}
+
+	/// The fee this transaction would need to pay to be accepted at the given fee rate.
+	pub fn required_fee(&self, fee_info: &BitcoinFeeInfo) -> BtcAmount {
+		(self.inputs.len() as BtcAmount)
+			.saturating_mul(fee_info.fee_per_input_utxo)
+			.saturating_add(
+				(self.outputs.len() as BtcAmount).saturating_mul(fee_info.fee_per_output_utxo),
+			)
+			.saturating_add(fee_info.min_fee_required_per_tx)
+	}
+
 	pub fn txid(&self) -> [u8; 32] {
 		let mut id_bytes = Vec::default();
 		id_bytes.extend(VERSION);
@@ -857,8 +969,9 @@ impl<T: SerializeBtc> SerializeBtc for &[T] {
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, RuntimeDebug, PartialEq, Eq)]
 enum BitcoinOp {
 	PushUint { value: u32 },
-	PushBytes { bytes: BoundedVec<u8, ConstU32<MAX_SEGWIT_PROGRAM_BYTES>> },
+	PushBytes { bytes: OpReturnData },
 	Drop,
+	Return,
 	CheckSig,
 	Dup,
 	Hash160,
@@ -941,6 +1054,7 @@ impl SerializeBtc for BitcoinOp {
 				buf.extend(bytes);
 			},
 			BitcoinOp::Drop => buf.push(0x75),
+			BitcoinOp::Return => buf.push(0x6a),
 			BitcoinOp::CheckSig => buf.push(0xac),
 			BitcoinOp::Dup => buf.push(0x76),
 			BitcoinOp::Hash160 => buf.push(0xa9),
@@ -984,6 +1098,7 @@ impl SerializeBtc for BitcoinOp {
 					}
 			},
 			BitcoinOp::Drop |
+			BitcoinOp::Return |
 			BitcoinOp::CheckSig |
 			BitcoinOp::Dup |
 			BitcoinOp::Hash160 |
@@ -1195,6 +1310,97 @@ mod test {
 		}
 	}
 
+	#[test]
+	fn test_scriptpubkey_network_mismatch() {
+		let addresses = [
+			("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", BitcoinNetwork::Mainnet),
+			(
+				"bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+				BitcoinNetwork::Mainnet,
+			),
+			("1AKDDsfTh8uY4X3ppy1m7jw1fVMBSMkzjP", BitcoinNetwork::Mainnet),
+			("34nSkinWC9rDDJiUY438qQN1JHmGqBHGW7", BitcoinNetwork::Mainnet),
+			(
+				"tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+				BitcoinNetwork::Testnet,
+			),
+			(
+				"tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+				BitcoinNetwork::Testnet,
+			),
+		];
+		for (address, network) in addresses {
+			for other_network in
+				[BitcoinNetwork::Mainnet, BitcoinNetwork::Testnet, BitcoinNetwork::Regtest]
+			{
+				assert_eq!(
+					ScriptPubkey::try_from_address(address, &other_network).is_ok(),
+					network == other_network,
+					"Address {address} on {other_network:?}",
+				);
+			}
+		}
+
+		// Regtest segwit addresses have their own hrp, but base58 addresses are shared with
+		// testnet.
+		let script_pubkey = ScriptPubkey::Taproot([0x5a; 32]);
+		let regtest_address = script_pubkey.to_address(&BitcoinNetwork::Regtest);
+		assert!(regtest_address.starts_with("bcrt1p"));
+		assert_eq!(
+			ScriptPubkey::try_from_address(&regtest_address, &BitcoinNetwork::Regtest).unwrap(),
+			script_pubkey
+		);
+		assert!(ScriptPubkey::try_from_address(&regtest_address, &BitcoinNetwork::Testnet).is_err());
+		assert!(ScriptPubkey::try_from_address(&regtest_address, &BitcoinNetwork::Mainnet).is_err());
+
+		let script_pubkey = ScriptPubkey::P2PKH([0x5a; 20]);
+		assert_eq!(
+			ScriptPubkey::try_from_address(
+				&script_pubkey.to_address(&BitcoinNetwork::Testnet),
+				&BitcoinNetwork::Regtest
+			)
+			.unwrap(),
+			script_pubkey
+		);
+	}
+
+	#[test]
+	fn test_op_return_scriptpubkey() {
+		let script_pubkey = ScriptPubkey::OpReturn(b"hello world".to_vec().try_into().unwrap());
+		assert_eq!(script_pubkey.bytes(), hex_literal::hex!("6a0b68656c6c6f20776f726c64"));
+		assert_eq!(
+			BitcoinOutput { amount: 0, script_pubkey: script_pubkey.clone() }.btc_serialize(),
+			hex_literal::hex!("00000000000000000d6a0b68656c6c6f20776f726c64")
+		);
+		assert_eq!(
+			script_pubkey.to_address(&BitcoinNetwork::Mainnet),
+			"OP_RETURN:68656c6c6f20776f726c64"
+		);
+
+		// Pushes of more than 75 bytes need an explicit OP_PUSHDATA1.
+		let script_pubkey = ScriptPubkey::OpReturn(
+			vec![0xcf; MAX_OP_RETURN_DATA_BYTES as usize].try_into().unwrap(),
+		);
+		assert_eq!(
+			script_pubkey.bytes(),
+			itertools::chain!(hex_literal::hex!("6a4c50"), [0xcf; 80]).collect::<Vec<_>>()
+		);
+		assert_eq!(
+			BitcoinOutput { amount: 0, script_pubkey: script_pubkey.clone() }.size(),
+			8 + 1 + 3 + MAX_OP_RETURN_DATA_BYTES as usize
+		);
+
+		assert!(OpReturnData::try_from(vec![0xcf; MAX_OP_RETURN_DATA_BYTES as usize + 1]).is_err());
+
+		// Data outputs can't be used as a destination.
+		assert!(ScriptPubkey::try_from_address(
+			&script_pubkey.to_address(&BitcoinNetwork::Mainnet),
+			&BitcoinNetwork::Mainnet
+		)
+		.is_err());
+		assert!(ScriptPubkey::try_from(crate::ForeignChainAddress::Btc(script_pubkey)).is_err());
+	}
+
 	fn create_test_unsigned_transaction(sign_with: PreviousOrCurrent) -> BitcoinTransaction {
 		let pubkey_x =
 			hex_literal::hex!("78C79A2B436DA5575A03CDE40197775C656FFF9F0F59FC1466E09C20A81A9CDB");
@@ -1,10 +1,12 @@
 pub mod batch_transfer;
 
 use super::{
-	deposit_address::DepositAddress, AggKey, Bitcoin, BitcoinCrypto, BitcoinOutput, BtcAmount,
-	Utxo, CHANGE_ADDRESS_SALT,
+	deposit_address::DepositAddress, AggKey, Bitcoin, BitcoinCrypto, BitcoinFeeInfo, BitcoinOutput,
+	BtcAmount, ScriptPubkey, SerializeBtc, Utxo, UtxoId, CHANGE_ADDRESS_SALT,
+	MAX_OP_RETURN_DATA_BYTES, OUTPUT_UTXO_SIZE_IN_BYTES,
 };
 use crate::*;
+use cf_primitives::EgressCounter;
 use frame_support::{CloneNoBound, DebugNoBound, EqNoBound, Never, PartialEqNoBound};
 use sp_std::marker::PhantomData;
 
@@ -19,44 +21,162 @@ pub enum BitcoinApi<Environment: 'static> {
 
 pub type SelectedUtxosAndChangeAmount = (Vec<Utxo>, BtcAmount);
 
-#[derive(Copy, Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
 pub enum UtxoSelectionType {
 	SelectAllForRotation,
-	Some { output_amount: BtcAmount, number_of_outputs: u64 },
+	Some {
+		output_amount: BtcAmount,
+		number_of_outputs: u64,
+	},
+	/// Select the given change utxo, reserving enough of it to cover both the fee of spending it
+	/// to a single output and the fee the parent transaction is short of. If it's too small, other
+	/// available utxos are selected alongside it to make up the difference.
+	ChildPaysForParent {
+		change_utxo: UtxoId,
+		parent_fee_deficit: BtcAmount,
+	},
+	/// Select a bounded number of the smallest utxos that are still worth spending.
+	SelectForConsolidation,
 }
 
-impl<E> AllBatch<Bitcoin> for BitcoinApi<E>
+/// Looks up whether batch transfers should be tagged with the ids of the egresses they pay out.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct EgressMemos;
+
+/// The number of egress counters that fit in a single OP_RETURN memo.
+pub const MAX_EGRESS_IDS_PER_MEMO: usize =
+	MAX_OP_RETURN_DATA_BYTES as usize / core::mem::size_of::<EgressCounter>();
+
+impl<E> BitcoinApi<E>
 where
 	E: ChainEnvironment<UtxoSelectionType, SelectedUtxosAndChangeAmount>
 		+ ChainEnvironment<(), AggKey>,
 {
+	/// Builds a child-pays-for-parent transaction that spends the change output of `parent` back
+	/// to the vault, paying enough fee to bring the parent up to the given fee rate.
+	///
+	/// If the change output is too small to cover the bump, other available vault utxos are spent
+	/// alongside it. Returns `None` if the parent already pays enough, if its change output has
+	/// already been spent, or if the available utxos can't cover the bump.
+	pub fn new_child_pays_for_parent(
+		parent: &Self,
+		fee_info: &BitcoinFeeInfo,
+	) -> Option<FeeBump<Bitcoin, Self>> {
+		let parent = match parent {
+			BitcoinApi::BatchTransfer(batch_transfer) => batch_transfer,
+			BitcoinApi::_Phantom(..) => unreachable!(),
+		};
+		let parent_transaction = &parent.bitcoin_transaction;
+		let parent_fee_deficit = parent_transaction
+			.required_fee(fee_info)
+			.checked_sub(parent_transaction.fee())
+			.filter(|deficit| *deficit > 0)?;
+
+		let agg_key @ AggKey { current, previous } =
+			<E as ChainEnvironment<(), AggKey>>::lookup(())?;
+		// The change output can only be signed for if it belongs to one of our two latest keys.
+		if parent.change_utxo_key != current && Some(parent.change_utxo_key) != previous {
+			return None
+		}
+
+		let (input_utxos, change_amount) = E::lookup(UtxoSelectionType::ChildPaysForParent {
+			change_utxo: UtxoId {
+				tx_id: parent_transaction.txid(),
+				vout: parent_transaction.outputs.len().checked_sub(1)? as u32,
+			},
+			parent_fee_deficit,
+		})?;
+		let total_input_amount = input_utxos.iter().map(|utxo| utxo.amount).sum::<BtcAmount>();
+		let vault_change_script = DepositAddress::new(current, CHANGE_ADDRESS_SALT).script_pubkey();
+
+		Some(FeeBump {
+			call: Self::BatchTransfer(batch_transfer::BatchTransfer::new_unsigned(
+				&agg_key,
+				current,
+				input_utxos,
+				vec![BitcoinOutput { amount: change_amount, script_pubkey: vault_change_script }],
+			)),
+			extra_fee: total_input_amount.saturating_sub(change_amount),
+		})
+	}
+}
+
+impl<E> AllBatch<Bitcoin> for BitcoinApi<E>
+where
+	E: ChainEnvironment<UtxoSelectionType, SelectedUtxosAndChangeAmount>
+		+ ChainEnvironment<(), AggKey>
+		+ ChainEnvironment<EgressMemos, bool>,
+{
+	/// When egress memos are enabled, a batch can only pay out as many egresses as fit in a memo.
+	fn max_transfers_per_batch() -> Option<usize> {
+		egress_memos_enabled::<E>().then_some(MAX_EGRESS_IDS_PER_MEMO)
+	}
+
 	fn new_unsigned(
 		_fetch_params: Vec<FetchAssetParams<Bitcoin>>,
-		transfer_params: Vec<TransferAssetParams<Bitcoin>>,
+		transfer_params: Vec<(TransferAssetParams<Bitcoin>, EgressId)>,
+	) -> Result<Self, AllBatchError> {
+		let (btc_outputs, egress_ids): (Vec<_>, Vec<_>) = transfer_params
+			.into_iter()
+			.map(|(transfer_param, egress_id)| {
+				(
+					BitcoinOutput {
+						amount: transfer_param.amount,
+						script_pubkey: transfer_param.to,
+					},
+					egress_id,
+				)
+			})
+			.unzip();
+		let memo = if egress_memos_enabled::<E>() && !egress_ids.is_empty() {
+			Some(egress_memo(&egress_ids).ok_or(AllBatchError::Other)?)
+		} else {
+			None
+		};
+		Self::new_batch_transfer(btc_outputs, memo)
+	}
+}
+
+fn egress_memos_enabled<E: ChainEnvironment<EgressMemos, bool>>() -> bool {
+	E::lookup(EgressMemos).unwrap_or_default()
+}
+
+impl<E> BitcoinApi<E>
+where
+	E: ChainEnvironment<UtxoSelectionType, SelectedUtxosAndChangeAmount>
+		+ ChainEnvironment<(), AggKey>,
+{
+	/// Builds a transaction paying out the given outputs from the vault, followed by the memo, if
+	/// any, and the change output.
+	fn new_batch_transfer(
+		mut btc_outputs: Vec<BitcoinOutput>,
+		memo: Option<BitcoinOutput>,
 	) -> Result<Self, AllBatchError> {
 		let agg_key @ AggKey { current, .. } =
 			<E as ChainEnvironment<(), AggKey>>::lookup(()).ok_or(AllBatchError::Other)?;
 		let bitcoin_change_script =
 			DepositAddress::new(current, CHANGE_ADDRESS_SALT).script_pubkey();
-		let mut total_output_amount: u64 = 0;
-		let mut btc_outputs = vec![];
-		for transfer_param in transfer_params {
-			btc_outputs.push(BitcoinOutput {
-				amount: transfer_param.amount,
-				script_pubkey: transfer_param.to,
-			});
-			total_output_amount += transfer_param.amount;
+		let total_output_amount = btc_outputs.iter().map(|output| output.amount).sum::<BtcAmount>();
+		// +1 for the change output
+		let mut number_of_outputs = btc_outputs.len() as u64 + 1;
+		// The memo can be larger than a regular output, so its fee is accounted for by its size.
+		if let Some(memo) = memo {
+			number_of_outputs +=
+				(memo.size() as u64 + OUTPUT_UTXO_SIZE_IN_BYTES - 1) / OUTPUT_UTXO_SIZE_IN_BYTES;
+			btc_outputs.push(memo);
 		}
+
 		// Looks up all available Utxos and selects and takes them for the transaction depending on
 		// the amount that needs to be output. If the output amount is 0,
 		let (selected_input_utxos, change_amount) = E::lookup(UtxoSelectionType::Some {
 			output_amount: (total_output_amount > 0)
 				.then_some(total_output_amount)
 				.ok_or(AllBatchError::NotRequired)?,
-			number_of_outputs: (btc_outputs.len() + 1) as u64, // +1 for the change output
+			number_of_outputs,
 		})
 		.ok_or(AllBatchError::Other)?;
 
+		// The change output must remain the last output, since it's the one we track as our utxo.
 		btc_outputs
 			.push(BitcoinOutput { amount: change_amount, script_pubkey: bitcoin_change_script });
 
@@ -69,6 +189,23 @@ where
 	}
 }
 
+/// Builds a zero-value OP_RETURN output containing the big-endian egress counter of each of the
+/// given egress ids, so that the transaction can be matched to its egresses on-chain. Returns
+/// `None` if there are more than [MAX_EGRESS_IDS_PER_MEMO] egress ids.
+fn egress_memo(egress_ids: &[EgressId]) -> Option<BitcoinOutput> {
+	Some(BitcoinOutput {
+		amount: 0,
+		script_pubkey: ScriptPubkey::OpReturn(
+			egress_ids
+				.iter()
+				.flat_map(|(_chain, egress_counter)| egress_counter.to_be_bytes())
+				.collect::<Vec<_>>()
+				.try_into()
+				.ok()?,
+		),
+	})
+}
+
 impl<E> SetAggKeyWithAggKey<BitcoinCrypto> for BitcoinApi<E>
 where
 	E: ChainEnvironment<UtxoSelectionType, SelectedUtxosAndChangeAmount>,
@@ -96,23 +233,69 @@ where
 	}
 }
 
+impl<E> ConsolidateCall<Bitcoin> for BitcoinApi<E>
+where
+	E: ChainEnvironment<UtxoSelectionType, SelectedUtxosAndChangeAmount>
+		+ ChainEnvironment<(), AggKey>,
+{
+	fn consolidate_utxos() -> Result<Self, ConsolidationError> {
+		let agg_key @ AggKey { current, .. } =
+			<E as ChainEnvironment<(), AggKey>>::lookup(()).ok_or(ConsolidationError::Other)?;
+		let vault_change_script = DepositAddress::new(current, CHANGE_ADDRESS_SALT).script_pubkey();
+
+		let (selected_input_utxos, change_amount) =
+			E::lookup(UtxoSelectionType::SelectForConsolidation)
+				.ok_or(ConsolidationError::NotRequired)?;
+
+		Ok(Self::BatchTransfer(batch_transfer::BatchTransfer::new_unsigned(
+			&agg_key,
+			current,
+			selected_input_utxos,
+			vec![BitcoinOutput { amount: change_amount, script_pubkey: vault_change_script }],
+		)))
+	}
+}
+
 impl<E> From<batch_transfer::BatchTransfer> for BitcoinApi<E> {
 	fn from(tx: batch_transfer::BatchTransfer) -> Self {
 		Self::BatchTransfer(tx)
 	}
 }
 
-// TODO: Implement transfer / transfer and call for Bitcoin.
-impl<E: ReplayProtectionProvider<Bitcoin>> ExecutexSwapAndCall<Bitcoin> for BitcoinApi<E> {
+impl<E> ExecutexSwapAndCall<Bitcoin> for BitcoinApi<E>
+where
+	E: ChainEnvironment<UtxoSelectionType, SelectedUtxosAndChangeAmount>
+		+ ChainEnvironment<(), AggKey>,
+{
+	/// Bitcoin has no contracts to call, so the message is attached to the egress as an
+	/// OP_RETURN output and the source and gas budget are not used.
 	fn new_unsigned(
 		_egress_id: EgressId,
-		_transfer_param: TransferAssetParams<Bitcoin>,
+		transfer_param: TransferAssetParams<Bitcoin>,
 		_source_chain: ForeignChain,
 		_source_address: Option<ForeignChainAddress>,
 		_gas_budget: <Bitcoin as Chain>::ChainAmount,
-		_message: Vec<u8>,
-	) -> Result<Self, DispatchError> {
-		Err(DispatchError::Other("Bitcoin's ExecutexSwapAndCall is not supported."))
+		message: Vec<u8>,
+	) -> Result<Self, ExecutexSwapAndCallError> {
+		let memo = BitcoinOutput {
+			amount: 0,
+			script_pubkey: ScriptPubkey::OpReturn(message.try_into().map_err(|_| {
+				ExecutexSwapAndCallError::Invalid(DispatchError::Other(
+					"CCM message does not fit in OP_RETURN",
+				))
+			})?),
+		};
+		Self::new_batch_transfer(
+			vec![BitcoinOutput { amount: transfer_param.amount, script_pubkey: transfer_param.to }],
+			Some(memo),
+		)
+		.map_err(|e| match e {
+			// There are not enough utxos to pay for the egress yet.
+			AllBatchError::Other => ExecutexSwapAndCallError::Unavailable,
+			AllBatchError::NotRequired => ExecutexSwapAndCallError::Invalid(DispatchError::Other(
+				"Nothing to transfer in Bitcoin CCM",
+			)),
+		})
 	}
 }
 
@@ -4,6 +4,8 @@ pub mod api;
 
 pub mod benchmarking;
 
+pub mod xcm;
+
 #[cfg(feature = "std")]
 pub mod serializable_address;
 
@@ -183,6 +185,10 @@ impl PolkadotUncheckedExtrinsic {
 		Ok(Self(UncheckedExtrinsic::decode(input)?))
 	}
 
+	pub fn call(&self) -> &PolkadotRuntimeCall {
+		&self.0.function
+	}
+
 	pub fn signature(&self) -> Option<PolkadotSignature> {
 		self.0.signature.as_ref().and_then(|signature| {
 			if let MultiSignature::Sr25519(signature) = &signature.1 {
@@ -245,6 +251,12 @@ pub struct PolkadotTrackedData {
 	pub runtime_version: RuntimeVersion,
 }
 
+impl FeeEstimationApi<Polkadot> for PolkadotTrackedData {
+	fn estimate_fee(&self, inclusion_fee: u64) -> PolkadotBalance {
+		PolkadotBalance::from(inclusion_fee).saturating_add(self.median_tip)
+	}
+}
+
 impl Default for PolkadotTrackedData {
 	#[track_caller]
 	fn default() -> Self {
@@ -431,11 +443,20 @@ pub enum PolkadotRuntimeCall {
 	Utility(UtilityCall),
 	#[codec(index = 29u8)] // INDEX FOR WESTEND: 22, FOR POLKADOT: 29
 	Proxy(ProxyCall),
+	#[codec(index = 99u8)] // INDEX FOR WESTEND: 99, FOR POLKADOT: 99
+	XcmPallet(xcm::XcmCall),
 }
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
-pub enum SystemCall {}
+pub enum SystemCall {
+	/// Make some on-chain remark and emit an event.
+	#[codec(index = 7u8)]
+	remark_with_event {
+		#[allow(missing_docs)]
+		remark: Vec<u8>,
+	},
+}
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
@@ -1,4 +1,5 @@
 pub mod batch_fetch_and_transfer;
+pub mod execute_x_swap_and_call;
 pub mod rotate_vault_proxy;
 
 use super::{
@@ -51,12 +52,15 @@ where
 {
 	fn new_unsigned(
 		fetch_params: Vec<FetchAssetParams<Polkadot>>,
-		transfer_params: Vec<TransferAssetParams<Polkadot>>,
+		transfer_params: Vec<(TransferAssetParams<Polkadot>, EgressId)>,
 	) -> Result<Self, AllBatchError> {
 		Ok(Self::BatchFetchAndTransfer(batch_fetch_and_transfer::extrinsic_builder(
 			E::replay_protection(false),
 			fetch_params,
-			transfer_params,
+			transfer_params
+				.into_iter()
+				.map(|(transfer_param, _egress_id)| transfer_param)
+				.collect(),
 			E::try_vault_account().ok_or(AllBatchError::Other)?,
 		)))
 	}
@@ -105,15 +109,22 @@ impl<E> ExecutexSwapAndCall<Polkadot> for PolkadotApi<E>
 where
 	E: PolkadotEnvironment + ReplayProtectionProvider<Polkadot>,
 {
+	/// Polkadot has no contracts to call, so the message is attached to the transfer as a remark
+	/// and the source and gas budget are not used.
 	fn new_unsigned(
 		_egress_id: EgressId,
-		_transfer_param: TransferAssetParams<Polkadot>,
+		transfer_param: TransferAssetParams<Polkadot>,
 		_source_chain: ForeignChain,
 		_source_address: Option<ForeignChainAddress>,
 		_gas_budget: <Polkadot as Chain>::ChainAmount,
-		_message: Vec<u8>,
-	) -> Result<Self, DispatchError> {
-		Err(DispatchError::Other("Not implemented"))
+		message: Vec<u8>,
+	) -> Result<Self, ExecutexSwapAndCallError> {
+		Ok(Self::ExecuteXSwapAndCall(execute_x_swap_and_call::extrinsic_builder(
+			E::replay_protection(false),
+			transfer_param,
+			message,
+			E::try_vault_account().ok_or(ExecutexSwapAndCallError::Unavailable)?,
+		)))
 	}
 }
 
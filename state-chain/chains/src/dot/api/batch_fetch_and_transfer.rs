@@ -1,14 +1,18 @@
 use crate::{
 	dot::{
+		xcm::{
+			asset_hub_account_of, asset_hub_message_topic, send_to_asset_hub, AssetHubRuntimeCall,
+			AssetHubUtilityCall, AssetsCall, USDT_ASSET_ID,
+		},
 		BalancesCall, Polkadot, PolkadotAccountId, PolkadotAccountIdLookup,
 		PolkadotExtrinsicBuilder, PolkadotProxyType, PolkadotReplayProtection, PolkadotRuntimeCall,
 		ProxyCall, UtilityCall,
 	},
 	FetchAssetParams, TransferAssetParams,
 };
-use cf_primitives::ChannelId;
+use cf_primitives::{chains::assets::dot::Asset, ChannelId};
 use cf_utilities::SliceToArray;
-use sp_std::{boxed::Box, vec::Vec};
+use sp_std::{boxed::Box, vec, vec::Vec};
 
 pub fn extrinsic_builder(
 	replay_protection: PolkadotReplayProtection,
@@ -16,6 +20,34 @@ pub fn extrinsic_builder(
 	transfer_params: Vec<TransferAssetParams<Polkadot>>,
 	vault_account: PolkadotAccountId,
 ) -> PolkadotExtrinsicBuilder {
+	let (usdt_fetch_params, dot_fetch_params): (Vec<_>, Vec<_>) = fetch_params
+		.into_iter()
+		.partition(|fetch_param| fetch_param.asset == Asset::DotUsdt);
+	let (usdt_transfer_params, dot_transfer_params): (Vec<_>, Vec<_>) = transfer_params
+		.into_iter()
+		.partition(|transfer_param| transfer_param.asset == Asset::DotUsdt);
+
+	// USDT lives on Asset Hub, so all USDT fetches and transfers are bundled into a single XCM
+	// message sent from the vault.
+	let asset_hub_calls = [
+		usdt_fetch_params
+			.into_iter()
+			.map(|fetch_param| asset_hub_fetch(fetch_param.deposit_fetch_id, vault_account))
+			.collect::<Vec<AssetHubRuntimeCall>>(),
+		usdt_transfer_params
+			.into_iter()
+			.map(|transfer_param| {
+				AssetHubRuntimeCall::Assets(AssetsCall::transfer {
+					id: USDT_ASSET_ID,
+					target: PolkadotAccountIdLookup::from(transfer_param.to),
+					amount: transfer_param.amount,
+				})
+			})
+			.collect::<Vec<AssetHubRuntimeCall>>(),
+	]
+	.concat();
+	let asset_hub_message_topic = asset_hub_message_topic(&replay_protection);
+
 	PolkadotExtrinsicBuilder::new(
 		replay_protection,
 		PolkadotRuntimeCall::Proxy(ProxyCall::proxy {
@@ -23,13 +55,13 @@ pub fn extrinsic_builder(
 			force_proxy_type: Some(PolkadotProxyType::Any),
 			call: Box::new(PolkadotRuntimeCall::Utility(UtilityCall::force_batch {
 				calls: [
-					fetch_params
+					dot_fetch_params
 						.into_iter()
 						.map(|fetch_param| {
 							utility_fetch(fetch_param.deposit_fetch_id, vault_account)
 						})
 						.collect::<Vec<PolkadotRuntimeCall>>(),
-					transfer_params
+					dot_transfer_params
 						.into_iter()
 						.map(|transfer_param| {
 							PolkadotRuntimeCall::Balances(BalancesCall::transfer {
@@ -38,6 +70,15 @@ pub fn extrinsic_builder(
 							})
 						})
 						.collect::<Vec<PolkadotRuntimeCall>>(),
+					if asset_hub_calls.is_empty() {
+						vec![]
+					} else {
+						vec![send_to_asset_hub(
+							asset_hub_calls,
+							vault_account,
+							asset_hub_message_topic,
+						)]
+					},
 				]
 				.concat(),
 			})),
@@ -45,15 +86,18 @@ pub fn extrinsic_builder(
 	)
 }
 
-fn utility_fetch(channel_id: ChannelId, vault_account: PolkadotAccountId) -> PolkadotRuntimeCall {
-	let layers = channel_id
+/// The `as_derivative` indices leading from the vault account to the channel's account.
+fn derivation_layers(channel_id: ChannelId) -> Vec<u16> {
+	channel_id
 		.to_be_bytes()
 		.chunks(2)
 		.map(|chunk| u16::from_be_bytes(chunk.as_array::<2>()))
 		.skip_while(|layer| *layer == 0u16)
-		.collect::<Vec<u16>>();
+		.collect::<Vec<u16>>()
+}
 
-	layers.into_iter().fold(
+fn utility_fetch(channel_id: ChannelId, vault_account: PolkadotAccountId) -> PolkadotRuntimeCall {
+	derivation_layers(channel_id).into_iter().fold(
 		PolkadotRuntimeCall::Balances(BalancesCall::transfer_all {
 			dest: PolkadotAccountIdLookup::from(vault_account),
 			keep_alive: false,
@@ -64,6 +108,24 @@ fn utility_fetch(channel_id: ChannelId, vault_account: PolkadotAccountId) -> Pol
 	)
 }
 
+/// Same as [utility_fetch], but for USDT held on Asset Hub. The call is dispatched from the
+/// vault's Asset Hub account, so the channel's account is a derivative of that account.
+fn asset_hub_fetch(channel_id: ChannelId, vault_account: PolkadotAccountId) -> AssetHubRuntimeCall {
+	derivation_layers(channel_id).into_iter().fold(
+		AssetHubRuntimeCall::Assets(AssetsCall::transfer_all {
+			id: USDT_ASSET_ID,
+			dest: PolkadotAccountIdLookup::from(asset_hub_account_of(vault_account)),
+			keep_alive: false,
+		}),
+		|call, index| {
+			AssetHubRuntimeCall::Utility(AssetHubUtilityCall::as_derivative {
+				index,
+				call: Box::new(call),
+			})
+		},
+	)
+}
+
 #[cfg(test)]
 mod test_batch_fetch {
 
@@ -179,4 +241,65 @@ mod test_batch_fetch {
 
 		assert_ne!(call_1, call_2);
 	}
+
+	#[test]
+	fn usdt_is_fetched_and_transferred_on_asset_hub() {
+		let vault_account = PolkadotAccountId::from_aliased([1u8; 32]);
+		let usdt_destination = PolkadotAccountId::from_aliased([8u8; 32]);
+		let replay_protection = PolkadotReplayProtection {
+			nonce: NONCE_1,
+			signer: PolkadotAccountId::from_aliased([2u8; 32]),
+			genesis_hash: Default::default(),
+		};
+		let topic = asset_hub_message_topic(&replay_protection);
+
+		let builder = super::extrinsic_builder(
+			replay_protection,
+			vec![
+				FetchAssetParams::<Polkadot> {
+					deposit_fetch_id: 1,
+					asset: assets::dot::Asset::Dot,
+				},
+				FetchAssetParams::<Polkadot> {
+					deposit_fetch_id: 2,
+					asset: assets::dot::Asset::DotUsdt,
+				},
+			],
+			vec![TransferAssetParams::<Polkadot> {
+				to: usdt_destination,
+				amount: 5,
+				asset: assets::dot::Asset::DotUsdt,
+			}],
+			vault_account,
+		);
+
+		assert_eq!(
+			builder.extrinsic_call,
+			PolkadotRuntimeCall::Proxy(ProxyCall::proxy {
+				real: PolkadotAccountIdLookup::from(vault_account),
+				force_proxy_type: Some(PolkadotProxyType::Any),
+				call: Box::new(PolkadotRuntimeCall::Utility(UtilityCall::force_batch {
+					calls: vec![
+						utility_fetch(1, vault_account),
+						send_to_asset_hub(
+							vec![
+								asset_hub_fetch(2, vault_account),
+								AssetHubRuntimeCall::Assets(AssetsCall::transfer {
+									id: USDT_ASSET_ID,
+									target: PolkadotAccountIdLookup::from(usdt_destination),
+									amount: 5,
+								}),
+							],
+							vault_account,
+							topic,
+						),
+					],
+				})),
+			})
+		);
+		assert_eq!(
+			crate::dot::xcm::sent_asset_hub_message_topic(&builder.extrinsic_call),
+			Some(topic)
+		);
+	}
 }
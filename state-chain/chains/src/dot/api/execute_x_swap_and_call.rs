@@ -0,0 +1,106 @@
+use crate::{
+	dot::{
+		xcm::{
+			asset_hub_message_topic, send_to_asset_hub, AssetHubRuntimeCall, AssetsCall,
+			USDT_ASSET_ID,
+		},
+		BalancesCall, Polkadot, PolkadotAccountId, PolkadotAccountIdLookup,
+		PolkadotExtrinsicBuilder, PolkadotProxyType, PolkadotReplayProtection, PolkadotRuntimeCall,
+		ProxyCall, SystemCall, UtilityCall,
+	},
+	TransferAssetParams,
+};
+use cf_primitives::chains::assets::dot::Asset;
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+/// Builds an extrinsic that transfers the egressed asset from the vault and records the CCM
+/// message on-chain as a remark made by the vault, so that the recipient can match the message to
+/// the transfer.
+///
+/// Both calls are dispatched atomically: the remark is only made if the transfer succeeds.
+pub fn extrinsic_builder(
+	replay_protection: PolkadotReplayProtection,
+	transfer_param: TransferAssetParams<Polkadot>,
+	message: Vec<u8>,
+	vault_account: PolkadotAccountId,
+) -> PolkadotExtrinsicBuilder {
+	let transfer_call = match transfer_param.asset {
+		Asset::Dot => PolkadotRuntimeCall::Balances(BalancesCall::transfer {
+			dest: PolkadotAccountIdLookup::from(transfer_param.to),
+			value: transfer_param.amount,
+		}),
+		Asset::DotUsdt => send_to_asset_hub(
+			vec![AssetHubRuntimeCall::Assets(AssetsCall::transfer {
+				id: USDT_ASSET_ID,
+				target: PolkadotAccountIdLookup::from(transfer_param.to),
+				amount: transfer_param.amount,
+			})],
+			vault_account,
+			asset_hub_message_topic(&replay_protection),
+		),
+	};
+
+	PolkadotExtrinsicBuilder::new(
+		replay_protection,
+		PolkadotRuntimeCall::Proxy(ProxyCall::proxy {
+			real: PolkadotAccountIdLookup::from(vault_account),
+			force_proxy_type: Some(PolkadotProxyType::Any),
+			call: Box::new(PolkadotRuntimeCall::Utility(UtilityCall::batch_all {
+				calls: vec![
+					transfer_call,
+					PolkadotRuntimeCall::System(SystemCall::remark_with_event { remark: message }),
+				],
+			})),
+		}),
+	)
+}
+
+#[cfg(test)]
+mod test_execute_x_swap_and_call {
+	use super::*;
+	use crate::dot::{PolkadotPair, NONCE_1, RAW_SEED_1, RAW_SEED_2, TEST_RUNTIME_VERSION};
+
+	#[test]
+	fn transfer_and_remark_are_batched_atomically() {
+		let vault_account = PolkadotPair::from_seed(&RAW_SEED_1).public_key();
+		let keypair_proxy = PolkadotPair::from_seed(&RAW_SEED_2);
+		let destination = PolkadotAccountId::from_aliased([7u8; 32]);
+
+		let mut builder = super::extrinsic_builder(
+			PolkadotReplayProtection {
+				nonce: NONCE_1,
+				signer: keypair_proxy.public_key(),
+				genesis_hash: Default::default(),
+			},
+			TransferAssetParams::<Polkadot> { to: destination, amount: 4, asset: Asset::Dot },
+			b"hello".to_vec(),
+			vault_account,
+		);
+
+		assert_eq!(
+			builder.extrinsic_call,
+			PolkadotRuntimeCall::Proxy(ProxyCall::proxy {
+				real: PolkadotAccountIdLookup::from(vault_account),
+				force_proxy_type: Some(PolkadotProxyType::Any),
+				call: Box::new(PolkadotRuntimeCall::Utility(UtilityCall::batch_all {
+					calls: vec![
+						PolkadotRuntimeCall::Balances(BalancesCall::transfer {
+							dest: PolkadotAccountIdLookup::from(destination),
+							value: 4,
+						}),
+						PolkadotRuntimeCall::System(SystemCall::remark_with_event {
+							remark: b"hello".to_vec(),
+						}),
+					],
+				})),
+			})
+		);
+
+		let payload = builder.get_signature_payload(
+			TEST_RUNTIME_VERSION.spec_version,
+			TEST_RUNTIME_VERSION.transaction_version,
+		);
+		builder.insert_signature(keypair_proxy.sign(&payload));
+		assert!(builder.is_signed());
+	}
+}
@@ -0,0 +1,306 @@
+//! A minimal subset of the XCM (v3) and Asset Hub call types, sufficient for the Polkadot vault to
+//! move assets held on Asset Hub by sending XCM messages from the relay chain.
+//!
+//! Only the variants we actually use are defined, with their codec indices matching the upstream
+//! types so that the encoding is identical.
+use super::{
+	PolkadotAccountId, PolkadotAccountIdLookup, PolkadotBalance, PolkadotReplayProtection,
+	PolkadotRuntimeCall, ProxyCall, UtilityCall,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	sp_runtime::traits::{BlakeTwo256, Hash},
+	weights::Weight,
+};
+use scale_info::TypeInfo;
+use sp_std::{boxed::Box, vec, vec::Vec};
+
+/// The parachain id of Asset Hub on Polkadot.
+pub const ASSET_HUB_PARA_ID: u32 = 1000;
+
+/// The id of USDT in the Assets pallet on Asset Hub.
+pub const USDT_ASSET_ID: u32 = 1984;
+
+/// The amount of DOT withdrawn from the vault's Asset Hub account to pay for the execution of an
+/// XCM message. Any surplus is refunded to the same account.
+pub const ASSET_HUB_EXECUTION_FEE: PolkadotBalance = 1_000_000_000;
+
+/// Identifies an XCM message. Asset Hub reports the outcome of executing a message under its topic.
+pub type XcmTopic = [u8; 32];
+
+/// Upper bound on the weight of each call dispatched on Asset Hub.
+const ASSET_HUB_WEIGHT_PER_CALL: Weight = Weight::from_parts(1_000_000_000, 50_000);
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum XcmCall {
+	/// Send an XCM message to the given destination. The message is executed with the origin of
+	/// the sender descended from the relay chain.
+	#[codec(index = 0u8)]
+	send {
+		#[allow(missing_docs)]
+		dest: Box<VersionedMultiLocation>,
+		#[allow(missing_docs)]
+		message: Box<VersionedXcm>,
+	},
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum VersionedMultiLocation {
+	#[codec(index = 3u8)]
+	V3(MultiLocation),
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum VersionedXcm {
+	#[codec(index = 3u8)]
+	V3(Vec<Instruction>),
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub struct MultiLocation {
+	pub parents: u8,
+	pub interior: Junctions,
+}
+
+impl MultiLocation {
+	/// The location of the relay chain, as seen from a parachain. Also used to identify DOT.
+	pub fn parent() -> Self {
+		Self { parents: 1, interior: Junctions::Here }
+	}
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum Junctions {
+	#[codec(index = 0u8)]
+	Here,
+	#[codec(index = 1u8)]
+	X1(Junction),
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum Junction {
+	#[codec(index = 0u8)]
+	Parachain(#[codec(compact)] u32),
+	#[codec(index = 1u8)]
+	AccountId32 { network: Option<NetworkId>, id: [u8; 32] },
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum NetworkId {
+	#[codec(index = 2u8)]
+	Polkadot,
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum Instruction {
+	#[codec(index = 0u8)]
+	WithdrawAsset(Vec<MultiAsset>),
+	#[codec(index = 6u8)]
+	Transact { origin_kind: OriginKind, require_weight_at_most: Weight, call: Vec<u8> },
+	#[codec(index = 13u8)]
+	DepositAsset { assets: MultiAssetFilter, beneficiary: MultiLocation },
+	#[codec(index = 19u8)]
+	BuyExecution { fees: MultiAsset, weight_limit: WeightLimit },
+	#[codec(index = 20u8)]
+	RefundSurplus,
+	#[codec(index = 32u8)]
+	ExpectTransactStatus(MaybeErrorCode),
+	#[codec(index = 44u8)]
+	SetTopic(XcmTopic),
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum MaybeErrorCode {
+	#[codec(index = 0u8)]
+	Success,
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub struct MultiAsset {
+	pub id: AssetId,
+	pub fun: Fungibility,
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum AssetId {
+	#[codec(index = 0u8)]
+	Concrete(MultiLocation),
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum Fungibility {
+	#[codec(index = 0u8)]
+	Fungible(#[codec(compact)] u128),
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum MultiAssetFilter {
+	#[codec(index = 1u8)]
+	Wild(WildMultiAsset),
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum WildMultiAsset {
+	#[codec(index = 2u8)]
+	AllCounted(#[codec(compact)] u32),
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum OriginKind {
+	#[codec(index = 1u8)]
+	SovereignAccount,
+}
+
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum WeightLimit {
+	#[codec(index = 0u8)]
+	Unlimited,
+}
+
+// FROM: https://github.com/paritytech/polkadot-sdk/blob/master/cumulus/parachains/runtimes/assets/asset-hub-polkadot/src/lib.rs
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum AssetHubRuntimeCall {
+	#[codec(index = 40u8)]
+	Utility(AssetHubUtilityCall),
+	#[codec(index = 50u8)]
+	Assets(AssetsCall),
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum AssetHubUtilityCall {
+	/// Send a call through an indexed pseudonym of the sender.
+	#[codec(index = 1u8)]
+	as_derivative {
+		#[allow(missing_docs)]
+		index: u16,
+		#[allow(missing_docs)]
+		call: Box<AssetHubRuntimeCall>,
+	},
+	/// Send a batch of dispatch calls and atomically execute them.
+	#[codec(index = 2u8)]
+	batch_all {
+		#[allow(missing_docs)]
+		calls: Vec<AssetHubRuntimeCall>,
+	},
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+pub enum AssetsCall {
+	/// Move some assets from the sender account to another.
+	#[codec(index = 8u8)]
+	transfer {
+		#[allow(missing_docs)]
+		#[codec(compact)]
+		id: u32,
+		#[allow(missing_docs)]
+		target: PolkadotAccountIdLookup,
+		#[allow(missing_docs)]
+		#[codec(compact)]
+		amount: PolkadotBalance,
+	},
+	/// Transfer the entire transferable balance of an asset from the caller account.
+	#[codec(index = 32u8)]
+	transfer_all {
+		#[allow(missing_docs)]
+		#[codec(compact)]
+		id: u32,
+		#[allow(missing_docs)]
+		dest: PolkadotAccountIdLookup,
+		#[allow(missing_docs)]
+		keep_alive: bool,
+	},
+}
+
+/// The account on Asset Hub that is controlled by the given relay chain account via XCM.
+///
+/// Mirrors Asset Hub's `HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>`
+/// location-to-account conversion.
+pub fn asset_hub_account_of(relay_account: PolkadotAccountId) -> PolkadotAccountId {
+	let interior = (b"AccountId32", relay_account.aliased_ref()).encode();
+	PolkadotAccountId::from_aliased(
+		BlakeTwo256::hash(&(b"ParentChain", interior).encode()).to_fixed_bytes(),
+	)
+}
+
+/// The topic of the XCM message sent to Asset Hub by the extrinsic with the given replay
+/// protection. Each extrinsic uses a new nonce, so topics are unique.
+pub fn asset_hub_message_topic(replay_protection: &PolkadotReplayProtection) -> XcmTopic {
+	BlakeTwo256::hash(
+		&(b"chainflip/asset-hub", replay_protection.signer, replay_protection.nonce).encode(),
+	)
+	.to_fixed_bytes()
+}
+
+/// The topic of the message sent to Asset Hub by the given call, if it sends one.
+pub fn sent_asset_hub_message_topic(call: &PolkadotRuntimeCall) -> Option<XcmTopic> {
+	match call {
+		PolkadotRuntimeCall::Proxy(ProxyCall::proxy { call, .. }) |
+		PolkadotRuntimeCall::Utility(UtilityCall::as_derivative { call, .. }) =>
+			sent_asset_hub_message_topic(call),
+		PolkadotRuntimeCall::Utility(
+			UtilityCall::batch { calls } |
+			UtilityCall::batch_all { calls } |
+			UtilityCall::force_batch { calls },
+		) => calls.iter().find_map(sent_asset_hub_message_topic),
+		PolkadotRuntimeCall::XcmPallet(XcmCall::send { message, .. }) => {
+			let VersionedXcm::V3(instructions) = message.as_ref();
+			instructions.iter().find_map(|instruction| match instruction {
+				Instruction::SetTopic(topic) => Some(*topic),
+				_ => None,
+			})
+		},
+		_ => None,
+	}
+}
+
+/// Builds a relay chain call that dispatches the given calls on Asset Hub on behalf of the vault.
+///
+/// Execution is paid for in DOT from the vault's Asset Hub account, and any surplus is returned to
+/// it. The calls are dispatched atomically, and the message only executes successfully if they
+/// all succeed. Its outcome is reported on Asset Hub under the given topic.
+pub fn send_to_asset_hub(
+	calls: Vec<AssetHubRuntimeCall>,
+	vault_account: PolkadotAccountId,
+	topic: XcmTopic,
+) -> PolkadotRuntimeCall {
+	let fees = MultiAsset {
+		id: AssetId::Concrete(MultiLocation::parent()),
+		fun: Fungibility::Fungible(ASSET_HUB_EXECUTION_FEE),
+	};
+	let require_weight_at_most = ASSET_HUB_WEIGHT_PER_CALL.saturating_mul(calls.len() as u64);
+
+	PolkadotRuntimeCall::XcmPallet(XcmCall::send {
+		dest: Box::new(VersionedMultiLocation::V3(MultiLocation {
+			parents: 0,
+			interior: Junctions::X1(Junction::Parachain(ASSET_HUB_PARA_ID)),
+		})),
+		message: Box::new(VersionedXcm::V3(vec![
+			Instruction::WithdrawAsset(vec![fees.clone()]),
+			Instruction::BuyExecution { fees, weight_limit: WeightLimit::Unlimited },
+			Instruction::Transact {
+				origin_kind: OriginKind::SovereignAccount,
+				require_weight_at_most,
+				call: AssetHubRuntimeCall::Utility(AssetHubUtilityCall::batch_all { calls })
+					.encode(),
+			},
+			Instruction::RefundSurplus,
+			Instruction::DepositAsset {
+				assets: MultiAssetFilter::Wild(WildMultiAsset::AllCounted(1)),
+				beneficiary: MultiLocation {
+					parents: 0,
+					interior: Junctions::X1(Junction::AccountId32 {
+						network: None,
+						id: *asset_hub_account_of(vault_account).aliased_ref(),
+					}),
+				},
+			},
+			// Transact succeeds even if the dispatched call fails, so the message has to check
+			// the call's outcome for it to be reflected in the message's outcome.
+			Instruction::ExpectTransactStatus(MaybeErrorCode::Success),
+			Instruction::SetTopic(topic),
+		])),
+	})
+}
@@ -77,6 +77,12 @@ impl EthereumTrackedData {
 	}
 }
 
+impl FeeEstimationApi<Ethereum> for EthereumTrackedData {
+	fn estimate_fee(&self, gas: u64) -> EthAmount {
+		self.base_fee.saturating_add(self.priority_fee).saturating_mul(gas.into())
+	}
+}
+
 impl Default for EthereumTrackedData {
 	#[track_caller]
 	fn default() -> Self {
@@ -200,7 +200,7 @@ where
 {
 	fn new_unsigned(
 		fetch_params: Vec<FetchAssetParams<Ethereum>>,
-		transfer_params: Vec<TransferAssetParams<Ethereum>>,
+		transfer_params: Vec<(TransferAssetParams<Ethereum>, EgressId)>,
 	) -> Result<Self, AllBatchError> {
 		let mut fetch_only_params = vec![];
 		let mut fetch_deploy_params = vec![];
@@ -238,7 +238,7 @@ where
 					fetch_only_params,
 					transfer_params
 						.into_iter()
-						.map(|TransferAssetParams { asset, to, amount }| {
+						.map(|(TransferAssetParams { asset, to, amount }, _egress_id)| {
 							E::token_address(asset)
 								.map(|address| EncodableTransferAssetParams {
 									to,
@@ -265,9 +265,10 @@ where
 		source_address: Option<ForeignChainAddress>,
 		gas_budget: <Ethereum as Chain>::ChainAmount,
 		message: Vec<u8>,
-	) -> Result<Self, DispatchError> {
+	) -> Result<Self, ExecutexSwapAndCallError> {
 		let transfer_param = EncodableTransferAssetParams {
-			asset: E::token_address(transfer_param.asset).ok_or(DispatchError::CannotLookup)?,
+			asset: E::token_address(transfer_param.asset)
+				.ok_or(ExecutexSwapAndCallError::Invalid(DispatchError::CannotLookup))?,
 			to: transfer_param.to,
 			amount: transfer_param.amount,
 		};
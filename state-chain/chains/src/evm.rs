@@ -378,9 +378,21 @@ impl<C: Chain<Transaction = Transaction>> TransactionMetadata<C> for EvmTransact
 			};
 		}
 
+		// The broadcaster is free to choose lower fees than the ones we set, these are only upper
+		// bounds.
+		macro_rules! check_optional_bound {
+			($field:ident) => {
+				match (expected_metadata.$field, self.$field) {
+					(None, _) => true,
+					(Some(expected), Some(actual)) => actual <= expected,
+					(Some(_), None) => false,
+				}
+			};
+		}
+
 		self.contract == expected_metadata.contract &&
-			check_optional!(max_fee_per_gas) &&
-			check_optional!(max_priority_fee_per_gas) &&
+			check_optional_bound!(max_fee_per_gas) &&
+			check_optional_bound!(max_priority_fee_per_gas) &&
 			check_optional!(gas_limit)
 	}
 }
@@ -756,7 +768,7 @@ fn metadata_verification() {
 		&EvmTransactionMetadata { max_priority_fee_per_gas: None, ..submitted_metadata }
 	));
 
-	// If we expect something else it fails.
+	// If we expect a lower fee it fails.
 	assert!(!<EvmTransactionMetadata as TransactionMetadata<Ethereum>>::verify_metadata(
 		&submitted_metadata,
 		&EvmTransactionMetadata {
@@ -765,6 +777,21 @@ fn metadata_verification() {
 		}
 	));
 
+	// Fees are upper bounds, so paying less than expected is ok.
+	assert!(<EvmTransactionMetadata as TransactionMetadata<Ethereum>>::verify_metadata(
+		&submitted_metadata,
+		&EvmTransactionMetadata {
+			max_priority_fee_per_gas: Some(U256::from(2)),
+			..submitted_metadata
+		}
+	));
+
+	// A different gas limit fails.
+	assert!(!<EvmTransactionMetadata as TransactionMetadata<Ethereum>>::verify_metadata(
+		&submitted_metadata,
+		&EvmTransactionMetadata { gas_limit: Some(U256::one()), ..submitted_metadata }
+	));
+
 	// If we witness `None` instead of `Some`, it fails.
 	assert!(!<EvmTransactionMetadata as TransactionMetadata<Ethereum>>::verify_metadata(
 		&submitted_metadata,
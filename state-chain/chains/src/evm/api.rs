@@ -194,3 +194,13 @@ pub trait EthEnvironmentProvider {
 		Self::contract_address(EthereumContract::Vault)
 	}
 }
+
+/// Provides the environment data for EVM chains other than Ethereum. These only have the Key
+/// Manager and Vault contracts deployed.
+pub trait EvmEnvironmentProvider<C: Chain> {
+	fn token_address(asset: C::ChainAsset) -> Option<Address>;
+	fn key_manager_address() -> Address;
+	fn vault_address() -> Address;
+	fn chain_id() -> EvmChainId;
+	fn next_nonce() -> u64;
+}
@@ -34,6 +34,7 @@ pub use frame_support::traits::Get;
 pub mod benchmarking_value;
 
 pub mod any;
+pub mod arb;
 pub mod btc;
 pub mod dot;
 pub mod eth;
@@ -138,6 +139,22 @@ pub trait Chain: Member + Parameter {
 	/// Passed in to construct the replay protection.
 	type ReplayProtectionParams: Member + Parameter;
 	type ReplayProtection: Member + Parameter;
+
+	/// Converts an account on this chain into a [ForeignChainAddress]. Chains that share an
+	/// account type with another chain (eg. EVM chains) must override this so that the address is
+	/// tagged with the correct chain.
+	fn into_foreign_chain_address(address: Self::ChainAccount) -> ForeignChainAddress {
+		address.into()
+	}
+
+	/// Converts a [ForeignChainAddress] into an account on this chain, failing if the address
+	/// belongs to another chain. Chains that share an account type with another chain must
+	/// override this along with [Chain::into_foreign_chain_address].
+	fn try_from_foreign_chain_address(
+		address: ForeignChainAddress,
+	) -> Result<Self::ChainAccount, address::AddressError> {
+		address.try_into().map_err(|_| address::AddressError::InvalidAddress)
+	}
 }
 
 /// Common crypto-related types and operations for some external chain.
@@ -258,6 +275,22 @@ where
 	fn calculate_gas_limit(_call: &Call) -> Option<U256> {
 		Default::default()
 	}
+
+	/// Build a new call that speeds up confirmation of a transaction that has been stuck for too
+	/// long, for example because the network fee rate rose after it was signed.
+	///
+	/// Returns `None` if the chain doesn't support fee bumping or if no bump is possible right now.
+	fn build_fee_bump(_signed_call: &Call) -> Option<FeeBump<C, Call>> {
+		None
+	}
+}
+
+/// A call that increases the effective fee of an earlier, still unconfirmed, transaction.
+pub struct FeeBump<C: Chain, Call> {
+	/// The unsigned call to be threshold signed and broadcast.
+	pub call: Call,
+	/// The fee spent on top of what the original transaction paid.
+	pub extra_fee: C::ChainAmount,
 }
 
 pub trait TransactionMetadata<C: Chain> {
@@ -356,13 +389,37 @@ pub enum AllBatchError {
 
 #[allow(clippy::result_unit_err)]
 pub trait AllBatch<C: Chain>: ApiCall<C::ChainCrypto> {
+	/// The maximum number of transfers a single batch can pay out, if limited. Any further
+	/// transfers have to wait for a later batch.
+	fn max_transfers_per_batch() -> Option<usize> {
+		None
+	}
+
 	fn new_unsigned(
 		fetch_params: Vec<FetchAssetParams<C>>,
-		transfer_params: Vec<TransferAssetParams<C>>,
+		transfer_params: Vec<(TransferAssetParams<C>, EgressId)>,
 	) -> Result<Self, AllBatchError>;
 }
 
-#[allow(clippy::result_unit_err)]
+#[derive(Debug)]
+pub enum ConsolidationError {
+	NotRequired,
+	Other,
+}
+
+pub trait ConsolidateCall<C: Chain>: ApiCall<C::ChainCrypto> {
+	fn consolidate_utxos() -> Result<Self, ConsolidationError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecutexSwapAndCallError {
+	/// The call can't be built at the moment, for example because the vault is not available or
+	/// there are not enough funds in it. It can be retried later.
+	Unavailable,
+	/// The call can never be built for this message.
+	Invalid(DispatchError),
+}
+
 pub trait ExecutexSwapAndCall<C: Chain>: ApiCall<C::ChainCrypto> {
 	fn new_unsigned(
 		egress_id: EgressId,
@@ -371,7 +428,34 @@ pub trait ExecutexSwapAndCall<C: Chain>: ApiCall<C::ChainCrypto> {
 		source_address: Option<ForeignChainAddress>,
 		gas_budget: C::ChainAmount,
 		message: Vec<u8>,
-	) -> Result<Self, DispatchError>;
+	) -> Result<Self, ExecutexSwapAndCallError>;
+}
+
+/// The expected cost of fetching and egressing an asset, measured in the chain's unit of
+/// transaction cost: gas on EVM chains, the inclusion fee on Polkadot and virtual bytes on Bitcoin.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	PartialEq,
+	Eq,
+	Encode,
+	Decode,
+	TypeInfo,
+	Serialize,
+	Deserialize,
+	MaxEncodedLen,
+)]
+pub struct TransactionCostEstimate {
+	pub ingress: u64,
+	pub egress: u64,
+}
+
+/// Estimates the cost of transactions on a chain from its tracked data.
+pub trait FeeEstimationApi<C: Chain> {
+	/// The estimated fee, in the chain's gas asset, of a transaction that costs `cost` units.
+	fn estimate_fee(&self, cost: u64) -> C::ChainAmount;
 }
 
 pub trait FeeRefundCalculator<C: Chain> {
@@ -402,6 +486,28 @@ pub const MAX_CCM_CF_PARAM_LENGTH: u32 = 1_000;
 pub type CcmMessage = BoundedVec<u8, ConstU32<MAX_CCM_MSG_LENGTH>>;
 pub type CcmCfParameters = BoundedVec<u8, ConstU32<MAX_CCM_CF_PARAM_LENGTH>>;
 
+/// The maximum length of a CCM message that can be delivered to the given chain by its
+/// [ExecutexSwapAndCall] implementation, or `None` if CCM is not supported for the chain.
+///
+/// On Polkadot the message is recorded as a remark made by the vault, and on Bitcoin it is attached
+/// to the egress as an OP_RETURN output, which limits its size.
+pub fn max_ccm_message_length(chain: ForeignChain) -> Option<u32> {
+	match chain {
+		ForeignChain::Ethereum | ForeignChain::Arbitrum | ForeignChain::Polkadot =>
+			Some(MAX_CCM_MSG_LENGTH),
+		ForeignChain::Bitcoin => Some(btc::MAX_OP_RETURN_DATA_BYTES),
+	}
+}
+
+/// Whether the given chain's [ExecutexSwapAndCall] implementation spends a CCM's gas budget. On
+/// chains without contracts to call, no gas budget is taken from the deposit.
+pub fn ccm_uses_gas_budget(chain: ForeignChain) -> bool {
+	match chain {
+		ForeignChain::Ethereum | ForeignChain::Arbitrum => true,
+		ForeignChain::Polkadot | ForeignChain::Bitcoin => false,
+	}
+}
+
 #[cfg(feature = "std")]
 mod bounded_hex {
 	use super::*;
@@ -455,6 +561,33 @@ pub struct CcmDepositMetadata {
 	pub channel_metadata: CcmChannelMetadata,
 }
 
+/// Protects a swap against adverse price movements between deposit and execution.
+///
+/// If the swap would output less than `min_output`, it is not executed and the deposited funds
+/// are returned to `refund_address` on the source chain instead.
+#[derive(
+	Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, Serialize, Deserialize, MaxEncodedLen,
+)]
+pub struct SwapRefundParameters<Address = ForeignChainAddress> {
+	/// Address on the source chain that the deposit is refunded to.
+	pub refund_address: Address,
+	/// The minimum amount of the destination asset the swap must output.
+	#[cfg_attr(feature = "std", serde(with = "cf_utilities::serde_helpers::number_or_hex"))]
+	pub min_output: AssetAmount,
+}
+
+impl<A> SwapRefundParameters<A> {
+	pub fn try_map_address<B, E>(
+		self,
+		f: impl FnOnce(A) -> Result<B, E>,
+	) -> Result<SwapRefundParameters<B>, E> {
+		Ok(SwapRefundParameters {
+			refund_address: f(self.refund_address)?,
+			min_output: self.min_output,
+		})
+	}
+}
+
 #[derive(
 	PartialEqNoBound,
 	EqNoBound,
@@ -335,6 +335,7 @@ impl<C: ChainCrypto + 'static> ApiCall<C> for MockApiCall<C> {
 
 thread_local! {
 	pub static IS_VALID_BROADCAST: std::cell::RefCell<bool> = RefCell::new(true);
+	pub static FEE_BUMP: std::cell::RefCell<Option<Vec<u8>>> = RefCell::new(None);
 }
 
 pub struct MockTransactionBuilder<C, Call>(PhantomData<(C, Call)>);
@@ -345,6 +346,14 @@ impl<C, Call> MockTransactionBuilder<C, Call> {
 	}
 }
 
+impl<C: Chain, Call: Encode> MockTransactionBuilder<C, Call> {
+	/// Any subsequent fee bump request will return the given call and fee, or `None` if no call
+	/// is given.
+	pub fn set_fee_bump(fee_bump: Option<(Call, C::ChainAmount)>) {
+		FEE_BUMP.with(|cell| *cell.borrow_mut() = fee_bump.map(|fee_bump| fee_bump.encode()))
+	}
+}
+
 impl<C: Chain<Transaction = MockTransaction>, Call: ApiCall<C::ChainCrypto>>
 	TransactionBuilder<C, Call> for MockTransactionBuilder<C, Call>
 {
@@ -364,4 +373,14 @@ impl<C: Chain<Transaction = MockTransaction>, Call: ApiCall<C::ChainCrypto>>
 	) -> bool {
 		IS_VALID_BROADCAST.with(|is_valid| *is_valid.borrow())
 	}
+
+	fn build_fee_bump(_signed_call: &Call) -> Option<FeeBump<C, Call>> {
+		FEE_BUMP.with(|fee_bump| {
+			fee_bump.borrow().as_ref().map(|encoded| {
+				let (call, extra_fee) = Decode::decode(&mut &encoded[..])
+					.expect("Fee bump was encoded from the same types.");
+				FeeBump { call, extra_fee }
+			})
+		})
+	}
 }
@@ -7,7 +7,7 @@ use cf_chains::{
 	eth::Address as EthereumAddress,
 };
 use cf_primitives::{
-	AccountRole, Asset, AssetAmount, ForeignChain, NetworkEnvironment, SemVer, SwapOutput,
+	AccountRole, Asset, AssetAmount, BasisPoints, ForeignChain, NetworkEnvironment, SemVer,
 };
 use cf_utilities::rpc::NumberOrHex;
 use core::ops::Range;
@@ -18,7 +18,9 @@ use jsonrpsee::{
 	SubscriptionSink,
 };
 use pallet_cf_governance::GovCallHash;
-use pallet_cf_pools::{AssetsMap, PoolInfo, PoolLiquidity, PoolOrders, UnidirectionalPoolDepth};
+use pallet_cf_pools::{
+	AssetsMap, PoolInfo, PoolLiquidity, PoolOrders, PoolStats, SwapRoute, UnidirectionalPoolDepth,
+};
 use sc_client_api::{BlockchainEvents, HeaderBackend};
 use serde::{Deserialize, Serialize};
 use sp_api::BlockT;
@@ -26,7 +28,7 @@ use sp_runtime::DispatchError;
 use state_chain_runtime::{
 	chainflip::Offence,
 	constants::common::TX_FEE_MULTIPLIER,
-	runtime_apis::{CustomRuntimeApi, LiquidityProviderInfo, RuntimeApiAccountInfoV2},
+	runtime_apis::{CustomRuntimeApi, LiquidityProviderInfo, RuntimeApiAccountInfoV2, SwapQuote},
 };
 use std::{
 	collections::{BTreeMap, HashMap},
@@ -207,19 +209,92 @@ pub struct RpcAuctionState {
 	min_active_bid: Option<NumberOrHex>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct RpcSwapHop {
+	pub from: RpcAsset,
+	pub to: RpcAsset,
+	pub input_amount: NumberOrHex,
+	pub output_amount: NumberOrHex,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct RpcSwapOutput {
 	// Intermediary amount, if there's any
 	pub intermediary: Option<NumberOrHex>,
 	// Final output of the swap
 	pub output: NumberOrHex,
+	// The assets the swap is routed through, starting with the input asset
+	pub route: Vec<RpcAsset>,
+	// The amounts going in and out of each pool along the route
+	pub hops: Vec<RpcSwapHop>,
 }
 
-impl From<SwapOutput> for RpcSwapOutput {
-	fn from(swap_output: SwapOutput) -> Self {
+impl From<SwapRoute> for RpcSwapOutput {
+	fn from(swap_route: SwapRoute) -> Self {
 		Self {
-			intermediary: swap_output.intermediary.map(Into::into),
-			output: swap_output.output.into(),
+			intermediary: (swap_route.hops.len() > 1)
+				.then(|| swap_route.hops.first().map(|hop| hop.output_amount.into()))
+				.flatten(),
+			output: swap_route.output.into(),
+			route: swap_route
+				.hops
+				.first()
+				.map(|hop| hop.from)
+				.into_iter()
+				.chain(swap_route.hops.iter().map(|hop| hop.to))
+				.map(Into::into)
+				.collect(),
+			hops: swap_route
+				.hops
+				.into_iter()
+				.map(|hop| RpcSwapHop {
+					from: hop.from.into(),
+					to: hop.to.into(),
+					input_amount: hop.input_amount.into(),
+					output_amount: hop.output_amount.into(),
+				})
+				.collect(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RpcSwapQuote {
+	pub intermediary: Option<NumberOrHex>,
+	pub output: NumberOrHex,
+	// Broker commission, in the input asset
+	pub broker_commission: NumberOrHex,
+	// Network fee, in the stable asset
+	pub network_fee: NumberOrHex,
+	// Liquidity provider fees, in the asset swapped into each pool
+	pub pool_fees: Vec<(RpcAsset, NumberOrHex)>,
+	// Estimated ingress fee, in the gas asset of the input chain
+	pub ingress_fee: (RpcAsset, NumberOrHex),
+	// Estimated egress fee, in the gas asset of the output chain
+	pub egress_fee: (RpcAsset, NumberOrHex),
+	// Price impact of the swap, in parts per million
+	pub price_impact: u32,
+	pub minimum_deposit: NumberOrHex,
+	pub minimum_swap: NumberOrHex,
+}
+
+impl From<SwapQuote> for RpcSwapQuote {
+	fn from(quote: SwapQuote) -> Self {
+		Self {
+			intermediary: quote.intermediary.map(Into::into),
+			output: quote.output.into(),
+			broker_commission: quote.broker_commission.into(),
+			network_fee: quote.network_fee.into(),
+			pool_fees: quote
+				.pool_fees
+				.into_iter()
+				.map(|(asset, fee)| (asset.into(), fee.into()))
+				.collect(),
+			ingress_fee: (quote.ingress_fee.0.into(), quote.ingress_fee.1.into()),
+			egress_fee: (quote.egress_fee.0.into(), quote.egress_fee.1.into()),
+			price_impact: quote.price_impact.deconstruct(),
+			minimum_deposit: quote.minimum_deposit.into(),
+			minimum_swap: quote.minimum_swap.into(),
 		}
 	}
 }
@@ -365,6 +440,14 @@ pub trait CustomApi {
 		to_asset: RpcAsset,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Option<Price>>;
+	#[method(name = "pool_twap")]
+	fn cf_pool_twap(
+		&self,
+		base_asset: RpcAsset,
+		pair_asset: RpcAsset,
+		window: u32,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<Price>>;
 	#[method(name = "swap_rate")]
 	fn cf_pool_swap_rate(
 		&self,
@@ -373,6 +456,15 @@ pub trait CustomApi {
 		amount: NumberOrHex,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<RpcSwapOutput>;
+	#[method(name = "swap_quote")]
+	fn cf_swap_quote(
+		&self,
+		from_asset: RpcAsset,
+		to_asset: RpcAsset,
+		amount: NumberOrHex,
+		broker_commission_bps: Option<BasisPoints>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcSwapQuote>;
 	#[method(name = "required_asset_ratio_for_range_order")]
 	fn cf_required_asset_ratio_for_range_order(
 		&self,
@@ -411,6 +503,14 @@ pub trait CustomApi {
 		lp: state_chain_runtime::AccountId,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Option<PoolOrders>>;
+	#[method(name = "pool_stats")]
+	fn cf_pool_stats(
+		&self,
+		base_asset: RpcAsset,
+		pair_asset: RpcAsset,
+		lp: Option<state_chain_runtime::AccountId>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<PoolStats>>;
 	#[method(name = "pool_range_order_liquidity_value")]
 	fn cf_pool_range_order_liquidity_value(
 		&self,
@@ -766,6 +866,26 @@ where
 			.map_err(to_rpc_error)
 	}
 
+	fn cf_pool_twap(
+		&self,
+		base_asset: RpcAsset,
+		pair_asset: RpcAsset,
+		window: u32,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<Price>> {
+		self.client
+			.runtime_api()
+			.cf_pool_twap(
+				self.unwrap_or_best(at),
+				base_asset.try_into()?,
+				pair_asset.try_into()?,
+				window,
+			)
+			.map_err(to_rpc_error)?
+			.transpose()
+			.map_err(map_dispatch_error)
+	}
+
 	fn cf_pool_swap_rate(
 		&self,
 		from_asset: RpcAsset,
@@ -775,7 +895,7 @@ where
 	) -> RpcResult<RpcSwapOutput> {
 		self.client
 			.runtime_api()
-			.cf_pool_simulate_swap(
+			.cf_pool_swap_route(
 				self.unwrap_or_best(at),
 				from_asset.try_into()?,
 				to_asset.try_into()?,
@@ -791,12 +911,41 @@ where
 					.map_err(|str| anyhow::anyhow!(str))?,
 			)
 			.map_err(to_rpc_error)
-			.and_then(|r| {
-				r.ok_or(jsonrpsee::core::Error::from(anyhow::anyhow!("Unable to process swap.")))
-			})
+			.and_then(|result| result.map_err(map_dispatch_error))
 			.map(RpcSwapOutput::from)
 	}
 
+	fn cf_swap_quote(
+		&self,
+		from_asset: RpcAsset,
+		to_asset: RpcAsset,
+		amount: NumberOrHex,
+		broker_commission_bps: Option<BasisPoints>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<RpcSwapQuote> {
+		self.client
+			.runtime_api()
+			.cf_swap_quote(
+				self.unwrap_or_best(at),
+				from_asset.try_into()?,
+				to_asset.try_into()?,
+				amount
+					.try_into()
+					.and_then(|amount| {
+						if amount == 0 {
+							Err("Swap input amount cannot be zero.")
+						} else {
+							Ok(amount)
+						}
+					})
+					.map_err(|str| anyhow::anyhow!(str))?,
+				broker_commission_bps.unwrap_or_default(),
+			)
+			.map_err(to_rpc_error)?
+			.map(Into::into)
+			.map_err(map_dispatch_error)
+	}
+
 	fn cf_pool_info(
 		&self,
 		base_asset: RpcAsset,
@@ -883,6 +1032,24 @@ where
 			.map_err(to_rpc_error)
 	}
 
+	fn cf_pool_stats(
+		&self,
+		base_asset: RpcAsset,
+		pair_asset: RpcAsset,
+		lp: Option<state_chain_runtime::AccountId>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<PoolStats>> {
+		self.client
+			.runtime_api()
+			.cf_pool_stats(
+				self.unwrap_or_best(at),
+				base_asset.try_into()?,
+				pair_asset.try_into()?,
+				lp,
+			)
+			.map_err(to_rpc_error)
+	}
+
 	fn cf_pool_range_order_liquidity_value(
 		&self,
 		base_asset: RpcAsset,
@@ -1290,6 +1457,7 @@ mod test {
 		assert_eq!(try_into_asset(Asset::Usdc, ForeignChain::Ethereum).unwrap(), Asset::Usdc);
 		assert_eq!(try_into_asset(Asset::Dot, ForeignChain::Polkadot).unwrap(), Asset::Dot);
 		assert_eq!(try_into_asset(Asset::Btc, ForeignChain::Bitcoin).unwrap(), Asset::Btc);
+		assert_eq!(try_into_asset(Asset::ArbEth, ForeignChain::Arbitrum).unwrap(), Asset::ArbEth);
 		let implicit_chain_asset: Asset = RpcAsset::ImplicitChain(Asset::Flip).try_into().unwrap();
 		assert_eq!(implicit_chain_asset, Asset::Flip);
 
@@ -1299,6 +1467,8 @@ mod test {
 		assert!(try_into_asset(Asset::Dot, ForeignChain::Ethereum).is_err());
 		assert!(try_into_asset(Asset::Usdc, ForeignChain::Bitcoin).is_err());
 		assert!(try_into_asset(Asset::Btc, ForeignChain::Ethereum).is_err());
+		assert!(try_into_asset(Asset::Eth, ForeignChain::Arbitrum).is_err());
+		assert!(try_into_asset(Asset::ArbUsdc, ForeignChain::Ethereum).is_err());
 	}
 
 	#[test]
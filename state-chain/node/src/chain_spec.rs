@@ -3,15 +3,16 @@ use cf_chains::{
 	ChainState,
 };
 use cf_primitives::{
-	chains::assets, AccountRole, AssetAmount, AuthorityCount, NetworkEnvironment,
+	chains::assets, AccountRole, AssetAmount, AuthorityCount, ForeignChain, NetworkEnvironment,
 	DEFAULT_MAX_AUTHORITY_SET_CONTRACTION,
 };
 
 use cf_chains::{
+	arb::ArbitrumTrackedData,
 	btc::{BitcoinFeeInfo, BitcoinTrackedData},
 	dot::{PolkadotTrackedData, RuntimeVersion},
 	eth::EthereumTrackedData,
-	Bitcoin, Ethereum, Polkadot,
+	Arbitrum, Bitcoin, Ethereum, Polkadot,
 };
 use common::FLIPPERINOS_PER_FLIP;
 use frame_benchmarking::sp_std::collections::btree_set::BTreeSet;
@@ -24,14 +25,16 @@ use sp_core::{
 	Pair, Public,
 };
 use state_chain_runtime::{
-	chainflip::Offence, opaque::SessionKeys, AccountId, AccountRolesConfig, AuraConfig,
-	BitcoinChainTrackingConfig, BitcoinIngressEgressConfig, BitcoinThresholdSignerConfig,
-	BitcoinVaultConfig, BlockNumber, EmissionsConfig, EnvironmentConfig,
-	EthereumChainTrackingConfig, EthereumIngressEgressConfig, EthereumThresholdSignerConfig,
-	EthereumVaultConfig, FlipBalance, FlipConfig, FundingConfig, GovernanceConfig, GrandpaConfig,
-	PolkadotChainTrackingConfig, PolkadotIngressEgressConfig, PolkadotThresholdSignerConfig,
-	PolkadotVaultConfig, ReputationConfig, RuntimeGenesisConfig, SessionConfig, SetSizeParameters,
-	Signature, SwappingConfig, SystemConfig, ValidatorConfig, WASM_BINARY,
+	chainflip::Offence, initial_transaction_cost_estimates, opaque::SessionKeys, AccountId,
+	AccountRolesConfig, ArbitrumChainTrackingConfig, ArbitrumIngressEgressConfig,
+	ArbitrumThresholdSignerConfig, ArbitrumVaultConfig, AuraConfig, BitcoinChainTrackingConfig,
+	BitcoinIngressEgressConfig, BitcoinThresholdSignerConfig, BitcoinVaultConfig, BlockNumber,
+	EmissionsConfig, EnvironmentConfig, EthereumChainTrackingConfig, EthereumIngressEgressConfig,
+	EthereumThresholdSignerConfig, EthereumVaultConfig, FlipBalance, FlipConfig, FundingConfig,
+	GovernanceConfig, GrandpaConfig, PolkadotChainTrackingConfig, PolkadotIngressEgressConfig,
+	PolkadotThresholdSignerConfig, PolkadotVaultConfig, ReputationConfig, RuntimeGenesisConfig,
+	SessionConfig, SetSizeParameters, Signature, SwappingConfig, SystemConfig, ValidatorConfig,
+	WASM_BINARY,
 };
 
 use std::{
@@ -99,6 +102,11 @@ pub struct StateChainEnvironment {
 	dot_genesis_hash: PolkadotHash,
 	dot_vault_account_id: Option<PolkadotAccountId>,
 	dot_runtime_version: RuntimeVersion,
+	arb_usdc_address: [u8; 20],
+	arb_key_manager_address: [u8; 20],
+	arb_vault_address: [u8; 20],
+	arb_address_checker_address: [u8; 20],
+	arbitrum_chain_id: u64,
 }
 
 /// Get the values from the State Chain's environment variables. Else set them via the defaults
@@ -129,6 +137,11 @@ pub fn get_environment_or_defaults(defaults: StateChainEnvironment) -> StateChai
 	from_env_var!(FromStr::from_str, ETH_DEPLOYMENT_BLOCK, ethereum_deployment_block);
 	from_env_var!(FromStr::from_str, GENESIS_FUNDING, genesis_funding_amount);
 	from_env_var!(FromStr::from_str, MIN_FUNDING, min_funding);
+	from_env_var!(clean_hex_address, ARB_USDC_ADDRESS, arb_usdc_address);
+	from_env_var!(clean_hex_address, ARB_KEY_MANAGER_ADDRESS, arb_key_manager_address);
+	from_env_var!(clean_hex_address, ARB_VAULT_ADDRESS, arb_vault_address);
+	from_env_var!(clean_hex_address, ARB_ADDRESS_CHECKER_ADDRESS, arb_address_checker_address);
+	from_env_var!(FromStr::from_str, ARBITRUM_CHAIN_ID, arbitrum_chain_id);
 
 	let dot_genesis_hash = match env::var("DOT_GENESIS_HASH") {
 		Ok(s) => hex_decode::<32>(&s).unwrap().into(),
@@ -166,6 +179,11 @@ pub fn get_environment_or_defaults(defaults: StateChainEnvironment) -> StateChai
 			spec_version: dot_spec_version,
 			transaction_version: dot_transaction_version,
 		},
+		arb_usdc_address,
+		arb_key_manager_address,
+		arb_vault_address,
+		arb_address_checker_address,
+		arbitrum_chain_id,
 	}
 }
 
@@ -220,6 +238,11 @@ pub fn inner_cf_development_config(
 		dot_genesis_hash,
 		dot_vault_account_id,
 		dot_runtime_version,
+		arb_usdc_address,
+		arb_key_manager_address,
+		arb_vault_address,
+		arb_address_checker_address,
+		arbitrum_chain_id,
 	} = get_environment_or_defaults(testnet::ENV);
 	Ok(ChainSpec::from_genesis(
 		"CF Develop",
@@ -245,6 +268,11 @@ pub fn inner_cf_development_config(
 					ethereum_chain_id,
 					polkadot_genesis_hash: dot_genesis_hash,
 					polkadot_vault_account_id: dot_vault_account_id,
+					arb_usdc_address: arb_usdc_address.into(),
+					arb_key_manager_address: arb_key_manager_address.into(),
+					arb_vault_address: arb_vault_address.into(),
+					arb_address_checker_address: arb_address_checker_address.into(),
+					arbitrum_chain_id,
 					network_environment: NetworkEnvironment::Development,
 					_config: PhantomData,
 				},
@@ -272,6 +300,7 @@ pub fn inner_cf_development_config(
 				devnet::BITCOIN_EXPIRY_BLOCKS,
 				devnet::ETHEREUM_EXPIRY_BLOCKS,
 				devnet::POLKADOT_EXPIRY_BLOCKS,
+				devnet::ARBITRUM_EXPIRY_BLOCKS,
 				devnet::AUCTION_BID_CUTOFF_PERCENTAGE,
 			)
 		},
@@ -320,6 +349,11 @@ macro_rules! network_spec {
 					dot_genesis_hash,
 					dot_vault_account_id,
 					dot_runtime_version,
+					arb_usdc_address,
+					arb_key_manager_address,
+					arb_vault_address,
+					arb_address_checker_address,
+					arbitrum_chain_id,
 				} = env_override.unwrap_or(ENV);
 				let protocol_id = format!(
 					"{}-{}",
@@ -374,6 +408,11 @@ macro_rules! network_spec {
 								ethereum_chain_id,
 								polkadot_genesis_hash: dot_genesis_hash,
 								polkadot_vault_account_id: dot_vault_account_id.clone(),
+								arb_usdc_address: arb_usdc_address.into(),
+								arb_key_manager_address: arb_key_manager_address.into(),
+								arb_vault_address: arb_vault_address.into(),
+								arb_address_checker_address: arb_address_checker_address.into(),
+								arbitrum_chain_id,
 								network_environment: NETWORK_ENVIRONMENT,
 								_config: PhantomData,
 							},
@@ -400,6 +439,7 @@ macro_rules! network_spec {
 							BITCOIN_EXPIRY_BLOCKS,
 							ETHEREUM_EXPIRY_BLOCKS,
 							POLKADOT_EXPIRY_BLOCKS,
+							ARBITRUM_EXPIRY_BLOCKS,
 							AUCTION_BID_CUTOFF_PERCENTAGE,
 						)
 					},
@@ -460,6 +500,7 @@ fn testnet_genesis(
 	bitcoin_deposit_channel_lifetime: u32,
 	ethereum_deposit_channel_lifetime: u32,
 	polkadot_deposit_channel_lifetime: u32,
+	arbitrum_deposit_channel_lifetime: u32,
 	auction_bid_cutoff_percentage: Percent,
 ) -> RuntimeGenesisConfig {
 	// Sanity Checks
@@ -620,6 +661,12 @@ fn testnet_genesis(
 			keygen_response_timeout: keygen_ceremony_timeout_blocks,
 			amount_to_slash: FLIPPERINOS_PER_FLIP,
 		},
+		arbitrum_vault: ArbitrumVaultConfig {
+			vault_key: None,
+			deployment_block: 0,
+			keygen_response_timeout: keygen_ceremony_timeout_blocks,
+			amount_to_slash: FLIPPERINOS_PER_FLIP,
+		},
 		ethereum_threshold_signer: EthereumThresholdSignerConfig {
 			threshold_signature_response_timeout: threshold_signature_ceremony_timeout_blocks,
 			_instance: PhantomData,
@@ -633,6 +680,10 @@ fn testnet_genesis(
 			threshold_signature_response_timeout: threshold_signature_ceremony_timeout_blocks,
 			_instance: PhantomData,
 		},
+		arbitrum_threshold_signer: ArbitrumThresholdSignerConfig {
+			threshold_signature_response_timeout: threshold_signature_ceremony_timeout_blocks,
+			_instance: PhantomData,
+		},
 		emissions: EmissionsConfig {
 			current_authority_emission_inflation: current_authority_emission_inflation_perbill,
 			backup_node_emission_inflation: backup_node_emission_inflation_perbill,
@@ -664,18 +715,31 @@ fn testnet_genesis(
 				tracked_data: BitcoinTrackedData { btc_fee_info: BitcoinFeeInfo::new(1000) },
 			},
 		},
+		arbitrum_chain_tracking: ArbitrumChainTrackingConfig {
+			init_chain_state: ChainState::<Arbitrum> {
+				block_height: 0,
+				tracked_data: ArbitrumTrackedData { base_fee: 100000000u32.into() },
+			},
+		},
 		transaction_payment: Default::default(),
 		liquidity_pools: Default::default(),
 		swapping: SwappingConfig { minimum_swap_amounts, _phantom: PhantomData },
 		// These are set to ~2 hours at average block times.
 		bitcoin_ingress_egress: BitcoinIngressEgressConfig {
 			deposit_channel_lifetime: bitcoin_deposit_channel_lifetime.into(),
+			transaction_cost_estimates: initial_transaction_cost_estimates(ForeignChain::Bitcoin),
 		},
 		ethereum_ingress_egress: EthereumIngressEgressConfig {
 			deposit_channel_lifetime: ethereum_deposit_channel_lifetime.into(),
+			transaction_cost_estimates: initial_transaction_cost_estimates(ForeignChain::Ethereum),
 		},
 		polkadot_ingress_egress: PolkadotIngressEgressConfig {
 			deposit_channel_lifetime: polkadot_deposit_channel_lifetime,
+			transaction_cost_estimates: initial_transaction_cost_estimates(ForeignChain::Polkadot),
+		},
+		arbitrum_ingress_egress: ArbitrumIngressEgressConfig {
+			deposit_channel_lifetime: arbitrum_deposit_channel_lifetime.into(),
+			transaction_cost_estimates: initial_transaction_cost_estimates(ForeignChain::Arbitrum),
 		},
 	}
 }
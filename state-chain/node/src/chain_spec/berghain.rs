@@ -1,6 +1,8 @@
 pub use super::common::*;
 use super::StateChainEnvironment;
-use cf_chains::{dot::RuntimeVersion, eth::CHAIN_ID_MAINNET};
+use cf_chains::{
+	arb::CHAIN_ID_MAINNET as ARBITRUM_CHAIN_ID_MAINNET, dot::RuntimeVersion, eth::CHAIN_ID_MAINNET,
+};
 use cf_primitives::{
 	AccountId, AccountRole, Asset, AssetAmount, BlockNumber, FlipBalance, NetworkEnvironment,
 };
@@ -19,6 +21,7 @@ pub const PROTOCOL_ID: &str = "flip-berghain";
 pub const BITCOIN_EXPIRY_BLOCKS: u32 = 24 * 60 / 10;
 pub const ETHEREUM_EXPIRY_BLOCKS: u32 = 24 * 3600 / 14;
 pub const POLKADOT_EXPIRY_BLOCKS: u32 = 24 * 3600 / 6;
+pub const ARBITRUM_EXPIRY_BLOCKS: u32 = 24 * 3600 * 4;
 
 pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	flip_token_address: hex_literal::hex!("826180541412D574cf1336d22c0C0a287822678A"),
@@ -39,6 +42,12 @@ pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	)),
 	dot_vault_account_id: None,
 	dot_runtime_version: RuntimeVersion { spec_version: 9431, transaction_version: 24 },
+	arb_usdc_address: hex_literal::hex!("af88d065e77c8cC2239327C5EDb3A432268e5831"),
+	// The Arbitrum contracts have not been deployed on mainnet yet.
+	arb_key_manager_address: [0u8; 20],
+	arb_vault_address: [0u8; 20],
+	arb_address_checker_address: [0u8; 20],
+	arbitrum_chain_id: ARBITRUM_CHAIN_ID_MAINNET,
 };
 
 pub const EPOCH_DURATION_BLOCKS: BlockNumber = 24 * HOURS;
@@ -1,9 +1,14 @@
 use super::StateChainEnvironment;
 pub use super::{
 	common::*,
-	testnet::{BITCOIN_EXPIRY_BLOCKS, ETHEREUM_EXPIRY_BLOCKS, POLKADOT_EXPIRY_BLOCKS},
+	testnet::{
+		ARBITRUM_EXPIRY_BLOCKS, BITCOIN_EXPIRY_BLOCKS, ETHEREUM_EXPIRY_BLOCKS,
+		POLKADOT_EXPIRY_BLOCKS,
+	},
+};
+use cf_chains::{
+	arb::CHAIN_ID_SEPOLIA as ARBITRUM_CHAIN_ID_SEPOLIA, dot::RuntimeVersion, eth::CHAIN_ID_GOERLI,
 };
-use cf_chains::{dot::RuntimeVersion, eth::CHAIN_ID_GOERLI};
 use cf_primitives::{AccountId, AccountRole, BlockNumber, FlipBalance, NetworkEnvironment};
 use sc_service::ChainType;
 use sp_core::H256;
@@ -34,6 +39,12 @@ pub const ENV: StateChainEnvironment = StateChainEnvironment {
 	)),
 	dot_vault_account_id: None,
 	dot_runtime_version: RuntimeVersion { spec_version: 10000, transaction_version: 25 },
+	arb_usdc_address: hex_literal::hex!("75faf114eafb1BDbe2F0316DF893fd58CE46AA4d"),
+	// The Arbitrum contracts have not been deployed on this network yet.
+	arb_key_manager_address: [0u8; 20],
+	arb_vault_address: [0u8; 20],
+	arb_address_checker_address: [0u8; 20],
+	arbitrum_chain_id: ARBITRUM_CHAIN_ID_SEPOLIA,
 };
 
 pub const BASHFUL_ACCOUNT_ID: &str = "cFLbasoV5juCGacy9LvvwSgkupFiFmwt8RmAuA3xcaY5YmkBe";
@@ -157,4 +157,13 @@ benchmarks_instance_pallet! {
 		// We expect the unwrap to error if the extrinsic didn't fire an event - if an event has been emitted we reached the end of the extrinsic
 		let _ = frame_system::Pallet::<T>::events().pop().expect("No event has been emitted from the transaction_succeeded extrinsic").event;
 	}
+	// Only chains that support fee bumping build a bump transaction, so this should be run
+	// against the Bitcoin instance with enough utxos available to cover the bump.
+	bump_fee_if_unconfirmed {
+		let broadcast_id = 1;
+		ThresholdSignatureData::<T, I>::insert(broadcast_id, (ApiCallFor::<T, I>::benchmark_value(), ThresholdSignatureFor::<T, I>::benchmark_value()));
+		T::KeyProvider::set_key(AggKeyFor::<T, I>::benchmark_value());
+	} : {
+		Pallet::<T, I>::bump_fee_if_unconfirmed(broadcast_id);
+	}
 }
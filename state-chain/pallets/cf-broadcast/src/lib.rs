@@ -8,7 +8,7 @@ mod tests;
 
 pub mod migrations;
 pub mod weights;
-use cf_primitives::{BroadcastId, ThresholdSignatureRequestId};
+use cf_primitives::{BroadcastId, EgressId, ThresholdSignatureRequestId};
 use cf_traits::{GetBlockHeight, SafeMode};
 use frame_support::RuntimeDebug;
 use sp_std::marker;
@@ -29,18 +29,20 @@ impl<I: 'static> SafeMode for PalletSafeMode<I> {
 }
 
 use cf_chains::{
-	ApiCall, Chain, ChainCrypto, FeeRefundCalculator, TransactionBuilder, TransactionMetadata as _,
+	ApiCall, Chain, ChainCrypto, FeeBump, FeeRefundCalculator, TransactionBuilder,
+	TransactionMetadata as _,
 };
 use cf_traits::{
-	offence_reporting::OffenceReporter, BroadcastNomination, Broadcaster, Chainflip, EpochInfo,
-	EpochKey, OnBroadcastReady, ThresholdSigner,
+	offence_reporting::OffenceReporter, BroadcastNomination, Broadcaster, CcmGasRefundHandler,
+	Chainflip, EpochInfo, EpochKey, OnBroadcastReady, ThresholdSigner,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::DispatchResultWithPostInfo,
 	pallet_prelude::DispatchResult,
-	sp_runtime::traits::Saturating,
+	sp_runtime::traits::{Saturating, Zero},
 	traits::{Get, StorageVersion, UnfilteredDispatchable},
+	weights::Weight,
 	Twox64Concat,
 };
 
@@ -99,6 +101,9 @@ pub mod pallet {
 	/// Type alias for the instance's configured SignerId.
 	pub type SignerIdFor<T, I> = <<T as Config<I>>::TargetChain as Chain>::ChainAccount;
 
+	/// Type alias for the Account type of a particular chain.
+	pub type ChainAccountFor<T, I> = <<T as Config<I>>::TargetChain as Chain>::ChainAccount;
+
 	/// Type alias for the payload hash
 	pub type ThresholdSignatureFor<T, I> =
 		<<<T as Config<I>>::TargetChain as Chain>::ChainCrypto as ChainCrypto>::ThresholdSignature;
@@ -218,6 +223,14 @@ pub mod pallet {
 		/// The save mode block margin
 		type SafeModeBlockMargin: Get<BlockNumberFor<Self>>;
 
+		/// The number of blocks a broadcast can remain unconfirmed before we attempt to bump its
+		/// fee. Zero disables fee bumping.
+		#[pallet::constant]
+		type FeeBumpThreshold: Get<BlockNumberFor<Self>>;
+
+		/// Refunds the unused part of the gas budget of cross chain messages.
+		type CcmGasRefundHandler: CcmGasRefundHandler<Self::TargetChain>;
+
 		/// The weights for the pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -285,6 +298,12 @@ pub mod pallet {
 	pub type Timeouts<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<BroadcastAttemptId>, ValueQuery>;
 
+	/// A mapping from block number to a list of broadcasts whose fee should be bumped at that
+	/// block number if they are still unconfirmed.
+	#[pallet::storage]
+	pub type FeeBumpChecks<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<BroadcastId>, ValueQuery>;
+
 	/// Stores all needed information to be able to re-request the signature
 	#[pallet::storage]
 	pub type ThresholdSignatureData<T: Config<I>, I: 'static = ()> = StorageMap<
@@ -305,6 +324,22 @@ pub mod pallet {
 	pub type TransactionFeeDeficit<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, SignerIdFor<T, I>, ChainAmountFor<T, I>, ValueQuery>;
 
+	/// The extra fees paid from the vault to speed up the confirmation of each broadcast.
+	#[pallet::storage]
+	pub type TransactionFeeBumps<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, BroadcastId, ChainAmountFor<T, I>, ValueQuery>;
+
+	/// The gas budget of broadcasts whose unused gas should be refunded once they succeed, along
+	/// with the address the refund is sent to.
+	#[pallet::storage]
+	pub type CcmGasRefunds<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BroadcastId,
+		(ChainAmountFor<T, I>, ChainAccountFor<T, I>),
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -339,6 +374,28 @@ pub mod pallet {
 		},
 		/// The fee paid for broadcasting a transaction has been refused.
 		TransactionFeeDeficitRefused { beneficiary: SignerIdFor<T, I> },
+		/// A broadcast remained unconfirmed for too long and a new transaction has been requested
+		/// to bump its fee.
+		TransactionFeeBumped {
+			broadcast_id: BroadcastId,
+			bump_broadcast_id: BroadcastId,
+			extra_fee: ChainAmountFor<T, I>,
+		},
+		/// The part of a cross chain message's gas budget that was not spent on broadcasting it
+		/// has been scheduled for egress to the refund address, less the egress fee.
+		CcmGasRefunded {
+			broadcast_id: BroadcastId,
+			egress_id: EgressId,
+			refund_address: ChainAccountFor<T, I>,
+			amount: ChainAmountFor<T, I>,
+		},
+		/// The unused gas of a cross chain message was not refunded, either because too little of
+		/// it was left to pay for the refund or because the fee of its broadcast is not known.
+		CcmGasRefundDropped {
+			broadcast_id: BroadcastId,
+			refund_address: ChainAccountFor<T, I>,
+			amount: ChainAmountFor<T, I>,
+		},
 	}
 
 	#[pallet::error]
@@ -357,11 +414,12 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
-		/// The `on_initialize` hook for this pallet handles scheduled expiries.
+		/// The `on_initialize` hook for this pallet handles scheduled expiries and fee bumps.
 		///
 		/// /// ## Events
 		///
 		/// - [BroadcastAttemptTimeout](Event::BroadcastAttemptTimeout)
+		/// - [TransactionFeeBumped](Event::TransactionFeeBumped)
 		fn on_initialize(block_number: BlockNumberFor<T>) -> frame_support::weights::Weight {
 			// NB: We don't want broadcasts that timeout to ever expire. We will keep retrying
 			// forever. It's possible that the reason for timeout could be something like a chain
@@ -369,6 +427,9 @@ pub mod pallet {
 			// eventually. For outlying, unknown unknowns, these can be something governance can
 			// handle if absolutely necessary (though it likely never will be).
 			let expiries = Timeouts::<T, I>::take(block_number);
+			let fee_bump_checks = FeeBumpChecks::<T, I>::take(block_number);
+			let mut weight = T::WeightInfo::on_initialize(expiries.len() as u32)
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1));
 			if T::SafeMode::get().retry_enabled {
 				for attempt_id in expiries.iter() {
 					if let Some(attempt) = Self::take_awaiting_broadcast(*attempt_id) {
@@ -378,13 +439,20 @@ pub mod pallet {
 						Self::start_next_broadcast_attempt(attempt);
 					}
 				}
+				for broadcast_id in fee_bump_checks.iter() {
+					weight.saturating_accrue(Self::bump_fee_if_unconfirmed(*broadcast_id));
+				}
 			} else {
 				Timeouts::<T, I>::insert(
 					block_number.saturating_add(T::SafeModeBlockMargin::get()),
 					expiries.clone(),
 				);
+				FeeBumpChecks::<T, I>::mutate(
+					block_number.saturating_add(T::SafeModeBlockMargin::get()),
+					|checks| checks.extend(fee_bump_checks.iter().copied()),
+				);
 			}
-			T::WeightInfo::on_initialize(expiries.len() as u32)
+			weight
 		}
 
 		// We want to retry broadcasts when we have free block space.
@@ -571,6 +639,17 @@ pub mod pallet {
 						beneficiary: signer_id,
 						amount: to_refund,
 					});
+
+					// The fee is paid out of the gas budget, anything left over is returned.
+					if let Some((gas_budget, refund_address)) =
+						CcmGasRefunds::<T, I>::take(broadcast_id)
+					{
+						Self::refund_unused_gas(
+							broadcast_id,
+							gas_budget.saturating_sub(to_refund),
+							refund_address,
+						);
+					}
 				} else {
 					Self::deposit_event(Event::<T, I>::TransactionFeeDeficitRefused {
 						beneficiary: signer_id,
@@ -657,6 +736,39 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		TransactionMetadata::<T, I>::remove(broadcast_id);
 		RequestCallbacks::<T, I>::remove(broadcast_id);
 		ThresholdSignatureData::<T, I>::remove(broadcast_id);
+		// Any gas refund still pending at this point can't be paid.
+		if let Some((gas_budget, refund_address)) = CcmGasRefunds::<T, I>::take(broadcast_id) {
+			Self::deposit_event(Event::<T, I>::CcmGasRefundDropped {
+				broadcast_id,
+				refund_address,
+				amount: gas_budget,
+			});
+		}
+	}
+
+	/// Schedules the egress of the unused gas of a cross chain message, unless too little of it
+	/// is left to pay for the egress.
+	///
+	/// ## Events
+	///
+	/// - [CcmGasRefunded](Event::CcmGasRefunded)
+	/// - [CcmGasRefundDropped](Event::CcmGasRefundDropped)
+	fn refund_unused_gas(
+		broadcast_id: BroadcastId,
+		amount: ChainAmountFor<T, I>,
+		refund_address: ChainAccountFor<T, I>,
+	) {
+		Self::deposit_event(
+			match T::CcmGasRefundHandler::refund_gas(amount, refund_address.clone()) {
+				Some((egress_id, refunded_amount)) => Event::<T, I>::CcmGasRefunded {
+					broadcast_id,
+					egress_id,
+					refund_address,
+					amount: refunded_amount,
+				},
+				None => Event::<T, I>::CcmGasRefundDropped { broadcast_id, refund_address, amount },
+			},
+		);
 	}
 
 	pub fn take_awaiting_broadcast(
@@ -734,6 +846,14 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		ThresholdSignatureData::<T, I>::insert(broadcast_id, (api_call, signature));
 
+		let fee_bump_threshold = T::FeeBumpThreshold::get();
+		if !fee_bump_threshold.is_zero() {
+			FeeBumpChecks::<T, I>::append(
+				frame_system::Pallet::<T>::block_number().saturating_add(fee_bump_threshold),
+				broadcast_id,
+			);
+		}
+
 		let broadcast_attempt_id = BroadcastAttemptId { broadcast_id, attempt_count: 0 };
 		Self::start_broadcast_attempt(BroadcastAttempt::<T, I> {
 			broadcast_attempt_id,
@@ -744,6 +864,44 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		broadcast_attempt_id
 	}
 
+	/// If the broadcast is still awaiting confirmation, request a transaction that bumps its fee
+	/// and record the extra fee in [TransactionFeeBumps]. The bump transaction is checked in turn
+	/// once it has been signed. If no bump is possible yet, the broadcast is checked again after
+	/// another [Config::FeeBumpThreshold] blocks.
+	///
+	/// Returns the weight used.
+	///
+	/// ## Events
+	///
+	/// - [TransactionFeeBumped](Event::TransactionFeeBumped)
+	fn bump_fee_if_unconfirmed(broadcast_id: BroadcastId) -> Weight {
+		let Some((api_call, _signature)) = ThresholdSignatureData::<T, I>::get(broadcast_id) else {
+			return T::DbWeight::get().reads(1)
+		};
+
+		match T::TransactionBuilder::build_fee_bump(&api_call) {
+			Some(FeeBump { call, extra_fee }) => {
+				let (bump_broadcast_id, _) = Self::threshold_sign_and_broadcast(call, None);
+				TransactionFeeBumps::<T, I>::mutate(broadcast_id, |fee_bumps| {
+					fee_bumps.saturating_accrue(extra_fee);
+				});
+				Self::deposit_event(Event::<T, I>::TransactionFeeBumped {
+					broadcast_id,
+					bump_broadcast_id,
+					extra_fee,
+				});
+			},
+			None => {
+				FeeBumpChecks::<T, I>::append(
+					frame_system::Pallet::<T>::block_number()
+						.saturating_add(T::FeeBumpThreshold::get()),
+					broadcast_id,
+				);
+			},
+		}
+		T::WeightInfo::bump_fee_if_unconfirmed()
+	}
+
 	fn start_next_broadcast_attempt(broadcast_attempt: BroadcastAttempt<T, I>) {
 		let broadcast_id = broadcast_attempt.broadcast_attempt_id.broadcast_id;
 
@@ -779,11 +937,15 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			// If the signature verification fails, we want
 			// to retry from the threshold signing stage.
 			else {
+				let maybe_gas_refund = CcmGasRefunds::<T, I>::take(broadcast_id);
 				Self::clean_up_broadcast_storage(broadcast_id);
 				let (retry_broadcast_id, _) = Self::threshold_sign_and_broadcast(
 					api_call,
 					RequestCallbacks::<T, I>::get(broadcast_id),
 				);
+				if let Some(gas_refund) = maybe_gas_refund {
+					CcmGasRefunds::<T, I>::insert(retry_broadcast_id, gas_refund);
+				}
 				log::info!(
 					"Signature is invalid -> rescheduled threshold signature for broadcast id {}.",
 					broadcast_id
@@ -872,4 +1034,14 @@ impl<T: Config<I>, I: 'static> Broadcaster<T::TargetChain> for Pallet<T, I> {
 	) -> (BroadcastId, ThresholdSignatureRequestId) {
 		Self::threshold_sign_and_broadcast(api_call, Some(callback))
 	}
+
+	fn threshold_sign_and_broadcast_with_gas_refund(
+		api_call: Self::ApiCall,
+		gas_budget: ChainAmountFor<T, I>,
+		refund_address: ChainAccountFor<T, I>,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		let ids @ (broadcast_id, _) = Self::threshold_sign_and_broadcast(api_call, None);
+		CcmGasRefunds::<T, I>::insert(broadcast_id, (gas_budget, refund_address));
+		ids
+	}
 }
@@ -14,8 +14,8 @@ use cf_chains::{
 use cf_traits::{
 	impl_mock_chainflip, impl_mock_runtime_safe_mode,
 	mocks::{
-		block_height_provider::BlockHeightProvider, signer_nomination::MockNominator,
-		threshold_signer::MockThresholdSigner,
+		block_height_provider::BlockHeightProvider, egress_handler::MockEgressHandler,
+		signer_nomination::MockNominator, threshold_signer::MockThresholdSigner,
 	},
 	AccountRoleRegistry, EpochKey, KeyState, OnBroadcastReady,
 };
@@ -71,6 +71,7 @@ impl frame_system::Config for Test {
 }
 
 pub const BROADCAST_EXPIRY_BLOCKS: BlockNumberFor<Test> = 4;
+pub const FEE_BUMP_THRESHOLD: BlockNumberFor<Test> = 20;
 
 parameter_types! {
 	pub const BroadcastTimeout: BlockNumberFor<Test> = BROADCAST_EXPIRY_BLOCKS;
@@ -156,7 +157,9 @@ impl pallet_cf_broadcast::Config<Instance1> for Test {
 	type SafeMode = MockRuntimeSafeMode;
 	type BroadcastReadyProvider = MockBroadcastReadyProvider;
 	type SafeModeBlockMargin = ConstU64<10>;
+	type FeeBumpThreshold = ConstU64<FEE_BUMP_THRESHOLD>;
 	type ChainTracking = BlockHeightProvider<MockEthereum>;
+	type CcmGasRefundHandler = MockEgressHandler<MockEthereum>;
 }
 
 impl_mock_chainflip!(Test);
@@ -2,9 +2,10 @@
 
 use crate::{
 	mock::*, AwaitingBroadcast, BroadcastAttemptCount, BroadcastAttemptId, BroadcastId,
-	BroadcastRetryQueue, Error, Event as BroadcastEvent, FailedBroadcasters, Instance1,
-	PalletOffence, RequestCallbacks, ThresholdSignatureData, Timeouts, TransactionFeeDeficit,
-	TransactionMetadata, TransactionOutIdToBroadcastId, WeightInfo,
+	BroadcastRetryQueue, CcmGasRefunds, Error, Event as BroadcastEvent, FailedBroadcasters,
+	FeeBumpChecks, Instance1, PalletOffence, RequestCallbacks, ThresholdSignatureData, Timeouts,
+	TransactionFeeBumps, TransactionFeeDeficit, TransactionMetadata, TransactionOutIdToBroadcastId,
+	WeightInfo,
 };
 use cf_chains::{
 	evm::SchnorrVerificationComponents,
@@ -16,7 +17,10 @@ use cf_chains::{
 	ChainCrypto, FeeRefundCalculator,
 };
 use cf_traits::{
-	mocks::{signer_nomination::MockNominator, threshold_signer::MockThresholdSigner},
+	mocks::{
+		egress_handler::MockEgressHandler, signer_nomination::MockNominator,
+		threshold_signer::MockThresholdSigner,
+	},
 	AsyncResult, Chainflip, EpochInfo, SetSafeMode, ThresholdSigner,
 };
 use frame_support::{assert_noop, assert_ok, dispatch::Weight, traits::Hooks};
@@ -528,6 +532,91 @@ fn threshold_sign_and_broadcast_with_callback() {
 	});
 }
 
+#[test]
+fn unused_ccm_gas_is_refunded_on_success() {
+	new_test_ext().execute_with(|| {
+		const GAS_BUDGET: u128 = 1_000_000;
+		const REFUND_ADDRESS: u64 = 0xcf;
+
+		let (broadcast_id, _threshold_request_id) = <Broadcaster as cf_traits::Broadcaster<
+			MockEthereum,
+		>>::threshold_sign_and_broadcast_with_gas_refund(
+			MockApiCall {
+				payload: Default::default(),
+				sig: Default::default(),
+				tx_out_id: MOCK_TRANSACTION_OUT_ID,
+			},
+			GAS_BUDGET,
+			REFUND_ADDRESS,
+		);
+		assert_eq!(
+			CcmGasRefunds::<Test, Instance1>::get(broadcast_id),
+			Some((GAS_BUDGET, REFUND_ADDRESS))
+		);
+
+		EthMockThresholdSigner::execute_signature_result_against_last_request(Ok(ETH_DUMMY_SIG));
+		assert_ok!(Broadcaster::transaction_succeeded(
+			RuntimeOrigin::root(),
+			MOCK_TRANSACTION_OUT_ID,
+			Default::default(),
+			ETH_TX_FEE,
+			MOCK_TX_METADATA,
+		));
+
+		let expected_refund = GAS_BUDGET - MockTransaction.return_fee_refund(ETH_TX_FEE);
+		assert_eq!(
+			MockEgressHandler::<MockEthereum>::get_gas_refunds(),
+			vec![(expected_refund, REFUND_ADDRESS)]
+		);
+		System::assert_has_event(RuntimeEvent::Broadcaster(crate::Event::CcmGasRefunded {
+			broadcast_id,
+			egress_id: (cf_primitives::ForeignChain::Ethereum, 1),
+			refund_address: REFUND_ADDRESS,
+			amount: expected_refund,
+		}));
+		assert!(CcmGasRefunds::<Test, Instance1>::get(broadcast_id).is_none());
+	});
+}
+
+#[test]
+fn unused_ccm_gas_is_not_refunded_if_it_does_not_cover_the_egress_fee() {
+	new_test_ext().execute_with(|| {
+		const GAS_BUDGET: u128 = 1_000_000;
+		const REFUND_ADDRESS: u64 = 0xcf;
+
+		let (broadcast_id, _threshold_request_id) = <Broadcaster as cf_traits::Broadcaster<
+			MockEthereum,
+		>>::threshold_sign_and_broadcast_with_gas_refund(
+			MockApiCall {
+				payload: Default::default(),
+				sig: Default::default(),
+				tx_out_id: MOCK_TRANSACTION_OUT_ID,
+			},
+			GAS_BUDGET,
+			REFUND_ADDRESS,
+		);
+		let unused_gas = GAS_BUDGET - MockTransaction.return_fee_refund(ETH_TX_FEE);
+		MockEgressHandler::<MockEthereum>::set_egress_fee(Some(unused_gas));
+
+		EthMockThresholdSigner::execute_signature_result_against_last_request(Ok(ETH_DUMMY_SIG));
+		assert_ok!(Broadcaster::transaction_succeeded(
+			RuntimeOrigin::root(),
+			MOCK_TRANSACTION_OUT_ID,
+			Default::default(),
+			ETH_TX_FEE,
+			MOCK_TX_METADATA,
+		));
+
+		assert!(MockEgressHandler::<MockEthereum>::get_gas_refunds().is_empty());
+		System::assert_has_event(RuntimeEvent::Broadcaster(crate::Event::CcmGasRefundDropped {
+			broadcast_id,
+			refund_address: REFUND_ADDRESS,
+			amount: unused_gas,
+		}));
+		assert!(CcmGasRefunds::<Test, Instance1>::get(broadcast_id).is_none());
+	});
+}
+
 #[test]
 fn ensure_safe_mode_is_moving_timeouts() {
 	new_test_ext().execute_with(|| {
@@ -579,3 +668,92 @@ fn transaction_succeeded_results_in_refund_refuse_for_signer() {
 		);
 	});
 }
+
+#[test]
+fn fee_of_unconfirmed_broadcast_is_bumped_after_threshold() {
+	const STUCK_TRANSACTION_OUT_ID: [u8; 4] = [0xbd; 4];
+	const BUMP_TRANSACTION_OUT_ID: [u8; 4] = [0xbe; 4];
+	const EXTRA_FEE: u128 = 1_000;
+
+	let mock_api_call = |tx_out_id| MockApiCall::<MockEthereumChainCrypto> {
+		payload: Default::default(),
+		sig: Default::default(),
+		tx_out_id,
+	};
+
+	new_test_ext().execute_with(|| {
+		let check_block = System::block_number() + FEE_BUMP_THRESHOLD;
+
+		let (confirmed_broadcast_id, _) =
+			Broadcaster::threshold_sign_and_broadcast(mock_api_call(MOCK_TRANSACTION_OUT_ID), None);
+		EthMockThresholdSigner::execute_signature_result_against_last_request(Ok(ETH_DUMMY_SIG));
+		let (stuck_broadcast_id, _) = Broadcaster::threshold_sign_and_broadcast(
+			mock_api_call(STUCK_TRANSACTION_OUT_ID),
+			None,
+		);
+		EthMockThresholdSigner::execute_signature_result_against_last_request(Ok(ETH_DUMMY_SIG));
+		assert_eq!(
+			FeeBumpChecks::<Test, Instance1>::get(check_block),
+			vec![confirmed_broadcast_id, stuck_broadcast_id]
+		);
+
+		assert_ok!(Broadcaster::transaction_succeeded(
+			RuntimeOrigin::root(),
+			MOCK_TRANSACTION_OUT_ID,
+			Default::default(),
+			ETH_TX_FEE,
+			MOCK_TX_METADATA,
+		));
+
+		// If no bump is possible yet, the unconfirmed broadcast is checked again later.
+		System::reset_events();
+		System::set_block_number(check_block);
+		Broadcaster::on_initialize(check_block);
+		assert!(FeeBumpChecks::<Test, Instance1>::get(check_block).is_empty());
+		assert!(System::events().is_empty());
+		let check_block = check_block + FEE_BUMP_THRESHOLD;
+		assert_eq!(FeeBumpChecks::<Test, Instance1>::get(check_block), vec![stuck_broadcast_id]);
+
+		MockTransactionBuilder::<MockEthereum, MockApiCall<MockEthereumChainCrypto>>::set_fee_bump(
+			Some((mock_api_call(BUMP_TRANSACTION_OUT_ID), EXTRA_FEE)),
+		);
+		System::set_block_number(check_block);
+		Broadcaster::on_initialize(check_block);
+		assert!(FeeBumpChecks::<Test, Instance1>::get(check_block).is_empty());
+
+		let bump_broadcast_id = stuck_broadcast_id + 1;
+		assert_eq!(
+			System::events().into_iter().map(|record| record.event).collect::<Vec<_>>(),
+			vec![RuntimeEvent::Broadcaster(crate::Event::TransactionFeeBumped {
+				broadcast_id: stuck_broadcast_id,
+				bump_broadcast_id,
+				extra_fee: EXTRA_FEE,
+			})]
+		);
+		assert_eq!(TransactionFeeBumps::<Test, Instance1>::get(stuck_broadcast_id), EXTRA_FEE);
+
+		// The bump transaction is threshold signed and broadcast alongside the original.
+		EthMockThresholdSigner::execute_signature_result_against_last_request(Ok(ETH_DUMMY_SIG));
+		assert_eq!(
+			TransactionOutIdToBroadcastId::<Test, Instance1>::get(BUMP_TRANSACTION_OUT_ID)
+				.map(|(broadcast_id, _)| broadcast_id),
+			Some(bump_broadcast_id)
+		);
+		assert!(ThresholdSignatureData::<Test, Instance1>::get(stuck_broadcast_id).is_some());
+	});
+}
+
+#[test]
+fn fee_bump_checks_are_deferred_during_safe_mode() {
+	new_test_ext().execute_with(|| {
+		<MockRuntimeSafeMode as SetSafeMode<MockRuntimeSafeMode>>::set_code_red();
+		let broadcast_attempt_id = start_mock_broadcast();
+		let check_block = System::block_number() + FEE_BUMP_THRESHOLD;
+		Broadcaster::on_initialize(check_block);
+		assert!(FeeBumpChecks::<Test, Instance1>::get(check_block).is_empty());
+		assert_eq!(
+			FeeBumpChecks::<Test, Instance1>::get(check_block + 10),
+			vec![broadcast_attempt_id.broadcast_id]
+		);
+	});
+}
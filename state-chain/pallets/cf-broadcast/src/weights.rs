@@ -37,6 +37,7 @@ pub trait WeightInfo {
 	fn on_signature_ready() -> Weight;
 	fn start_next_broadcast_attempt() -> Weight;
 	fn transaction_succeeded() -> Weight;
+	fn bump_fee_if_unconfirmed() -> Weight;
 }
 
 /// Weights for pallet_cf_broadcast using the Substrate node and recommended hardware.
@@ -168,6 +169,37 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
+	/// Storage: `BitcoinBroadcaster::ThresholdSignatureData` (r:1 w:0)
+	/// Proof: `BitcoinBroadcaster::ThresholdSignatureData` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `BitcoinChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(48), added: 543, mode: `MaxEncodedLen`)
+	/// Storage: `BitcoinVault::CurrentVaultEpochAndState` (r:1 w:0)
+	/// Proof: `BitcoinVault::CurrentVaultEpochAndState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinVault::Vaults` (r:2 w:0)
+	/// Proof: `BitcoinVault::Vaults` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::BitcoinAvailableUtxos` (r:1 w:1)
+	/// Proof: `Environment::BitcoinAvailableUtxos` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::BroadcastIdCounter` (r:1 w:1)
+	/// Proof: `BitcoinBroadcaster::BroadcastIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinThresholdSigner::ThresholdSignatureRequestIdCounter` (r:1 w:1)
+	/// Proof: `BitcoinThresholdSigner::ThresholdSignatureRequestIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
+	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::TransactionFeeBumps` (r:1 w:1)
+	/// Proof: `BitcoinBroadcaster::TransactionFeeBumps` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinThresholdSigner::PendingRequestInstructions` (r:0 w:1)
+	/// Proof: `BitcoinThresholdSigner::PendingRequestInstructions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::RequestCallbacks` (r:0 w:1)
+	/// Proof: `BitcoinBroadcaster::RequestCallbacks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn bump_fee_if_unconfirmed() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2412`
+		//  Estimated: `8352`
+		// Minimum execution time: 118_704_000 picoseconds.
+		Weight::from_parts(121_385_000, 8352)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -298,4 +330,35 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
+	/// Storage: `BitcoinBroadcaster::ThresholdSignatureData` (r:1 w:0)
+	/// Proof: `BitcoinBroadcaster::ThresholdSignatureData` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `BitcoinChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(48), added: 543, mode: `MaxEncodedLen`)
+	/// Storage: `BitcoinVault::CurrentVaultEpochAndState` (r:1 w:0)
+	/// Proof: `BitcoinVault::CurrentVaultEpochAndState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinVault::Vaults` (r:2 w:0)
+	/// Proof: `BitcoinVault::Vaults` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::BitcoinAvailableUtxos` (r:1 w:1)
+	/// Proof: `Environment::BitcoinAvailableUtxos` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::BroadcastIdCounter` (r:1 w:1)
+	/// Proof: `BitcoinBroadcaster::BroadcastIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinThresholdSigner::ThresholdSignatureRequestIdCounter` (r:1 w:1)
+	/// Proof: `BitcoinThresholdSigner::ThresholdSignatureRequestIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
+	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::TransactionFeeBumps` (r:1 w:1)
+	/// Proof: `BitcoinBroadcaster::TransactionFeeBumps` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinThresholdSigner::PendingRequestInstructions` (r:0 w:1)
+	/// Proof: `BitcoinThresholdSigner::PendingRequestInstructions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::RequestCallbacks` (r:0 w:1)
+	/// Proof: `BitcoinBroadcaster::RequestCallbacks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn bump_fee_if_unconfirmed() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2412`
+		//  Estimated: `8352`
+		// Minimum execution time: 118_704_000 picoseconds.
+		Weight::from_parts(121_385_000, 8352)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
 }
@@ -3,7 +3,7 @@
 use crate::{self as pallet_cf_emissions, PalletSafeMode};
 use cf_chains::{
 	mocks::{MockEthereum, MockEthereumChainCrypto},
-	AnyChain, ApiCall, ChainCrypto, UpdateFlipSupply,
+	AnyChain, ApiCall, Chain, ChainCrypto, UpdateFlipSupply,
 };
 use cf_primitives::{BroadcastId, FlipBalance, ThresholdSignatureRequestId};
 use cf_traits::{
@@ -186,6 +186,14 @@ impl Broadcaster<MockEthereum> for MockBroadcast {
 	) -> (BroadcastId, ThresholdSignatureRequestId) {
 		unimplemented!()
 	}
+
+	fn threshold_sign_and_broadcast_with_gas_refund(
+		_api_call: Self::ApiCall,
+		_gas_budget: <MockEthereum as Chain>::ChainAmount,
+		_refund_address: <MockEthereum as Chain>::ChainAccount,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		unimplemented!()
+	}
 }
 
 impl_mock_runtime_safe_mode! { emissions: PalletSafeMode }
@@ -15,5 +15,31 @@ benchmarks! {
 	verify {
 		assert_eq!(RuntimeSafeMode::<T>::get(), SafeMode::CODE_RED);
 	}
+	consolidate_bitcoin_utxos {
+		// The number of available utxos, which are all sorted. Up to the maximum that can be signed
+		// for are swept.
+		let x in 2 .. 1000;
+		BitcoinUtxoConsolidationParameters::<T>::put(UtxoConsolidationParameters {
+			utxo_count_threshold: 0,
+			max_fee_per_input_utxo: BtcAmount::MAX,
+			consolidation_size: MAX_BTC_SIGNING_PAYLOADS as u32,
+			..Default::default()
+		});
+		for i in 0..x {
+			Pallet::<T>::add_bitcoin_utxo_to_list(
+				1_000_000 + i as BtcAmount,
+				UtxoId { tx_id: [0u8; 32], vout: i },
+				DepositAddress::new([1u8; 32], i),
+			);
+		}
+	}: {
+		Pallet::<T>::consolidate_bitcoin_utxos_if_required();
+	}
+	verify {
+		assert_eq!(
+			BitcoinAvailableUtxos::<T>::decode_len().unwrap_or_default(),
+			(x as usize).saturating_sub(MAX_BTC_SIGNING_PAYLOADS)
+		);
+	}
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
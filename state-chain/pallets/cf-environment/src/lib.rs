@@ -3,19 +3,28 @@
 #![doc = include_str!("../../cf-doc-head.md")]
 
 use cf_chains::{
+	arb::Arbitrum,
 	btc::{
 		api::{SelectedUtxosAndChangeAmount, UtxoSelectionType},
 		deposit_address::DepositAddress,
 		utxo_selection::select_utxos_from_pool,
-		Bitcoin, BitcoinFeeInfo, BtcAmount, Utxo, UtxoId, CHANGE_ADDRESS_SALT,
+		Bitcoin, BitcoinFeeInfo, BtcAmount, Utxo, UtxoConsolidationParameters, UtxoId,
+		CHANGE_ADDRESS_SALT, MAX_BTC_SIGNING_PAYLOADS,
 	},
 	dot::{Polkadot, PolkadotAccountId, PolkadotHash, PolkadotIndex},
 	eth::Address as EthereumAddress,
+	Chain, ConsolidateCall, ConsolidationError, Ethereum,
+};
+use cf_primitives::{
+	chains::assets::{arb::Asset as ArbAsset, eth::Asset as EthAsset},
+	BroadcastId, NetworkEnvironment, SemVer,
+};
+use cf_traits::{
+	Broadcaster, CompatibleCfeVersions, GetBitcoinFeeInfo, SafeMode, SetMinimumDeposit,
 };
-use cf_primitives::{chains::assets::eth::Asset as EthAsset, NetworkEnvironment, SemVer};
-use cf_traits::{CompatibleCfeVersions, GetBitcoinFeeInfo, SafeMode};
 use frame_support::{
 	pallet_prelude::*,
+	sp_runtime::traits::Zero,
 	traits::{OnRuntimeUpgrade, StorageVersion},
 };
 use frame_system::pallet_prelude::*;
@@ -31,7 +40,7 @@ pub mod weights;
 pub use weights::WeightInfo;
 pub mod migrations;
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(6);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(9);
 
 type SignatureNonce = u64;
 
@@ -67,6 +76,11 @@ pub mod pallet {
 		type PolkadotVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Polkadot>;
 		/// On new key witnessed handler for Bitcoin
 		type BitcoinVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Bitcoin>;
+		/// On new key witnessed handler for Arbitrum
+		type ArbitrumVaultKeyWitnessedHandler: VaultKeyWitnessedHandler<Arbitrum>;
+
+		/// Sets the minimum deposit amount for newly registered Ethereum tokens.
+		type EthereumMinimumDeposit: SetMinimumDeposit<Ethereum>;
 
 		/// The runtime's safe mode is stored in this pallet.
 		type RuntimeSafeMode: cf_traits::SafeMode + Member + Parameter + Default;
@@ -74,6 +88,16 @@ pub mod pallet {
 		/// Get Bitcoin Fee info from chain tracking
 		type BitcoinFeeInfo: cf_traits::GetBitcoinFeeInfo;
 
+		/// The api call used to consolidate the Bitcoin vault's utxos.
+		type BitcoinConsolidationCall: ConsolidateCall<Bitcoin>;
+
+		/// A broadcaster for Bitcoin utxo consolidation transactions.
+		type BitcoinBroadcaster: Broadcaster<Bitcoin, ApiCall = Self::BitcoinConsolidationCall>;
+
+		/// How often, in blocks, to check whether the Bitcoin vault's utxos need consolidating.
+		#[pallet::constant]
+		type BitcoinUtxoConsolidationInterval: Get<BlockNumberFor<Self>>;
+
 		/// Used to access the current Chainflip runtime's release version (distinct from the
 		/// substrate RuntimeVersion)
 		#[pallet::constant]
@@ -87,6 +111,10 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Eth is not an Erc20 token, so its address can't be updated.
 		EthAddressNotUpdateable,
+		/// The provided utxo consolidation parameters are invalid.
+		InvalidUtxoConsolidationParameters,
+		/// The token address is already registered for a different asset.
+		Erc20TokenAlreadyRegistered,
 	}
 
 	#[pallet::pallet]
@@ -101,6 +129,11 @@ pub mod pallet {
 	pub type EthereumSupportedAssets<T: Config> =
 		StorageMap<_, Blake2_128Concat, EthAsset, EthereumAddress>;
 
+	#[pallet::storage]
+	/// Registry of the ERC-20 tokens supported on Ethereum, keyed by token contract address.
+	pub type EthereumErc20Tokens<T: Config> =
+		StorageMap<_, Blake2_128Concat, EthereumAddress, EthAsset>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn state_chain_gateway_address)]
 	/// The address of the ETH state chain gatweay contract
@@ -149,6 +182,45 @@ pub mod pallet {
 	/// The set of available UTXOs available in our Bitcoin Vault.
 	pub type BitcoinAvailableUtxos<T> = StorageValue<_, Vec<Utxo>, ValueQuery>;
 
+	#[pallet::storage]
+	/// Determines when and how the utxos in our Bitcoin Vault are consolidated.
+	pub type BitcoinUtxoConsolidationParameters<T> =
+		StorageValue<_, UtxoConsolidationParameters, ValueQuery>;
+
+	#[pallet::storage]
+	/// Whether Bitcoin batch transfers are tagged with the ids of the egresses they pay out.
+	pub type BitcoinEgressMemosEnabled<T> = StorageValue<_, bool, ValueQuery>;
+
+	// ARBITRUM CHAIN RELATED ENVIRONMENT ITEMS
+	#[pallet::storage]
+	#[pallet::getter(fn supported_arb_assets)]
+	/// Map of supported assets for Arbitrum
+	pub type ArbitrumSupportedAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, ArbAsset, EthereumAddress>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arb_key_manager_address)]
+	/// The address of the key manager contract on Arbitrum
+	pub type ArbitrumKeyManagerAddress<T> = StorageValue<_, EthereumAddress, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arb_vault_address)]
+	/// The address of the vault contract on Arbitrum
+	pub type ArbitrumVaultAddress<T> = StorageValue<_, EthereumAddress, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arb_address_checker_address)]
+	/// The address of the Address Checker contract on Arbitrum
+	pub type ArbitrumAddressCheckerAddress<T> = StorageValue<_, EthereumAddress, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn arbitrum_chain_id)]
+	/// The Arbitrum chain id
+	pub type ArbitrumChainId<T> = StorageValue<_, cf_chains::evm::api::EvmChainId, ValueQuery>;
+
+	#[pallet::storage]
+	pub type ArbitrumSignatureNonce<T> = StorageValue<_, SignatureNonce, ValueQuery>;
+
 	// OTHER ENVIRONMENT ITEMS
 	#[pallet::storage]
 	#[pallet::getter(fn safe_mode)]
@@ -179,6 +251,30 @@ pub mod pallet {
 		BitcoinBlockNumberSetForVault { block_number: cf_chains::btc::BlockNumber },
 		/// The Safe Mode settings for the chain has been updated
 		RuntimeSafeModeUpdated { safe_mode: SafeModeUpdate<T> },
+		/// The Bitcoin utxo consolidation parameters have been updated
+		UtxoConsolidationParametersUpdated { params: UtxoConsolidationParameters },
+		/// A transaction consolidating the Bitcoin vault's utxos has been requested
+		BitcoinUtxoConsolidationRequested { broadcast_id: BroadcastId },
+		/// Tagging Bitcoin batch transfers with their egress ids was enabled or disabled
+		BitcoinEgressMemosSet { enabled: bool },
+		/// The addresses of the Arbitrum Vault, Key Manager and Address Checker contracts were set
+		ArbitrumVaultContractsSet {
+			key_manager_address: EthereumAddress,
+			vault_address: EthereumAddress,
+			address_checker_address: EthereumAddress,
+		},
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+			let interval = T::BitcoinUtxoConsolidationInterval::get();
+			if !interval.is_zero() && (block_number % interval).is_zero() {
+				Self::consolidate_bitcoin_utxos_if_required()
+			} else {
+				Weight::zero()
+			}
+		}
 	}
 
 	#[pallet::call]
@@ -279,6 +375,145 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Update the parameters that determine when and how the Bitcoin vault's utxos are
+		/// consolidated.
+		///
+		/// ## Events
+		///
+		/// - [UtxoConsolidationParametersUpdated](Event::UtxoConsolidationParametersUpdated)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		/// - [InvalidUtxoConsolidationParameters](Error::InvalidUtxoConsolidationParameters)
+		#[pallet::call_index(4)]
+		// This weight is not strictly correct but since it's a governance call, weight is
+		// irrelevant.
+		#[pallet::weight(Weight::zero())]
+		pub fn update_consolidation_parameters(
+			origin: OriginFor<T>,
+			params: UtxoConsolidationParameters,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			ensure!(params.are_valid(), Error::<T>::InvalidUtxoConsolidationParameters);
+			BitcoinUtxoConsolidationParameters::<T>::put(params);
+
+			Self::deposit_event(Event::<T>::UtxoConsolidationParametersUpdated { params });
+
+			Ok(())
+		}
+
+		/// Manually witnesses the deployment of the Arbitrum contracts to complete the pending
+		/// vault rotation. The contracts are deployed with the newly generated aggregate key, so
+		/// the key is active from the block the deployment was included in.
+		///
+		/// ## Events
+		///
+		/// - [ArbitrumVaultContractsSet](Event::ArbitrumVaultContractsSet)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(5)]
+		// This weight is not strictly correct but since it's a governance call, weight is
+		// irrelevant.
+		#[pallet::weight(Weight::zero())]
+		pub fn witness_arbitrum_vault_deployment(
+			origin: OriginFor<T>,
+			key_manager_address: EthereumAddress,
+			vault_address: EthereumAddress,
+			address_checker_address: EthereumAddress,
+			deployment_block: <Arbitrum as Chain>::ChainBlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			ArbitrumKeyManagerAddress::<T>::put(key_manager_address);
+			ArbitrumVaultAddress::<T>::put(vault_address);
+			ArbitrumAddressCheckerAddress::<T>::put(address_checker_address);
+			Self::deposit_event(Event::<T>::ArbitrumVaultContractsSet {
+				key_manager_address,
+				vault_address,
+				address_checker_address,
+			});
+
+			T::ArbitrumVaultKeyWitnessedHandler::on_new_key_activated(deployment_block)
+		}
+
+		/// Registers the ERC-20 token at `address` on Ethereum as `asset`, replacing any address
+		/// previously registered for that asset. The token can only be swapped once a pool has
+		/// been created for it.
+		///
+		/// Engines load the registry when they start, so they only begin witnessing deposits of a
+		/// newly registered token once they are restarted.
+		///
+		/// ## Events
+		///
+		/// - [AddedNewEthAsset](Event::AddedNewEthAsset)
+		/// - [UpdatedEthAsset](Event::UpdatedEthAsset)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		/// - [EthAddressNotUpdateable](Error::EthAddressNotUpdateable)
+		/// - [Erc20TokenAlreadyRegistered](Error::Erc20TokenAlreadyRegistered)
+		#[pallet::call_index(6)]
+		// This weight is not strictly correct but since it's a governance call, weight is
+		// irrelevant.
+		#[pallet::weight(Weight::zero())]
+		pub fn register_eth_erc20_token(
+			origin: OriginFor<T>,
+			address: EthereumAddress,
+			asset: EthAsset,
+			minimum_deposit: <Ethereum as Chain>::ChainAmount,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			ensure!(asset != EthAsset::Eth, Error::<T>::EthAddressNotUpdateable);
+			ensure!(
+				EthereumErc20Tokens::<T>::get(address)
+					.map_or(true, |registered| registered == asset),
+				Error::<T>::Erc20TokenAlreadyRegistered
+			);
+
+			EthereumErc20Tokens::<T>::insert(address, asset);
+			match EthereumSupportedAssets::<T>::mutate(asset, |entry| entry.replace(address)) {
+				Some(previous_address) => {
+					if previous_address != address {
+						EthereumErc20Tokens::<T>::remove(previous_address);
+					}
+					Self::deposit_event(Event::<T>::UpdatedEthAsset(asset, address));
+				},
+				None => Self::deposit_event(Event::<T>::AddedNewEthAsset(asset, address)),
+			}
+			T::EthereumMinimumDeposit::set_minimum_deposit(asset, minimum_deposit);
+
+			Ok(())
+		}
+
+		/// Enables or disables tagging Bitcoin batch transfers with an OP_RETURN memo holding the
+		/// ids of the egresses they pay out. While enabled, a batch pays out at most as many
+		/// egresses as fit in a single memo.
+		///
+		/// ## Events
+		///
+		/// - [BitcoinEgressMemosSet](Event::BitcoinEgressMemosSet)
+		///
+		/// ## Errors
+		///
+		/// - [BadOrigin](frame_support::error::BadOrigin)
+		#[pallet::call_index(7)]
+		// This weight is not strictly correct but since it's a governance call, weight is
+		// irrelevant.
+		#[pallet::weight(Weight::zero())]
+		pub fn set_bitcoin_egress_memos(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			BitcoinEgressMemosEnabled::<T>::put(enabled);
+			Self::deposit_event(Event::<T>::BitcoinEgressMemosSet { enabled });
+
+			Ok(())
+		}
 	}
 
 	#[pallet::genesis_config]
@@ -293,6 +528,11 @@ pub mod pallet {
 		pub ethereum_chain_id: u64,
 		pub polkadot_genesis_hash: PolkadotHash,
 		pub polkadot_vault_account_id: Option<PolkadotAccountId>,
+		pub arb_usdc_address: EthereumAddress,
+		pub arb_key_manager_address: EthereumAddress,
+		pub arb_vault_address: EthereumAddress,
+		pub arb_address_checker_address: EthereumAddress,
+		pub arbitrum_chain_id: u64,
 		pub network_environment: NetworkEnvironment,
 		pub _config: PhantomData<T>,
 	}
@@ -309,6 +549,8 @@ pub mod pallet {
 			EthereumChainId::<T>::set(self.ethereum_chain_id);
 			EthereumSupportedAssets::<T>::insert(EthAsset::Flip, self.flip_token_address);
 			EthereumSupportedAssets::<T>::insert(EthAsset::Usdc, self.eth_usdc_address);
+			EthereumErc20Tokens::<T>::insert(self.flip_token_address, EthAsset::Flip);
+			EthereumErc20Tokens::<T>::insert(self.eth_usdc_address, EthAsset::Usdc);
 
 			PolkadotGenesisHash::<T>::set(self.polkadot_genesis_hash);
 			PolkadotVaultAccountId::<T>::set(self.polkadot_vault_account_id);
@@ -316,6 +558,12 @@ pub mod pallet {
 
 			BitcoinAvailableUtxos::<T>::set(vec![]);
 
+			ArbitrumKeyManagerAddress::<T>::set(self.arb_key_manager_address);
+			ArbitrumVaultAddress::<T>::set(self.arb_vault_address);
+			ArbitrumAddressCheckerAddress::<T>::set(self.arb_address_checker_address);
+			ArbitrumChainId::<T>::set(self.arbitrum_chain_id);
+			ArbitrumSupportedAssets::<T>::insert(ArbAsset::ArbUsdc, self.arb_usdc_address);
+
 			ChainflipNetworkEnvironment::<T>::set(self.network_environment);
 
 			Pallet::<T>::update_current_release_version();
@@ -335,6 +583,13 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	pub fn next_arbitrum_signature_nonce() -> SignatureNonce {
+		ArbitrumSignatureNonce::<T>::mutate(|nonce| {
+			*nonce += 1;
+			*nonce
+		})
+	}
+
 	pub fn next_polkadot_proxy_account_nonce(reset_nonce: bool) -> PolkadotIndex {
 		PolkadotProxyAccountNonce::<T>::mutate(|nonce| {
 			let current_nonce = *nonce;
@@ -364,6 +619,41 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
+	/// Requests a consolidation of the Bitcoin vault's utxos if fees are low and the utxos are
+	/// numerous or fragmented enough.
+	///
+	/// ## Events
+	///
+	/// - [BitcoinUtxoConsolidationRequested](Event::BitcoinUtxoConsolidationRequested)
+	///
+	/// Returns the weight used.
+	pub fn consolidate_bitcoin_utxos_if_required() -> Weight {
+		let params = BitcoinUtxoConsolidationParameters::<T>::get();
+		let fee_per_input_utxo = T::BitcoinFeeInfo::bitcoin_fee_info().fee_per_input_utxo;
+		if fee_per_input_utxo > params.max_fee_per_input_utxo {
+			return T::DbWeight::get().reads(2)
+		}
+		let available_utxos = BitcoinAvailableUtxos::<T>::get();
+		// Covers sorting all the available utxos and signing for the largest possible selection.
+		let weight = T::WeightInfo::consolidate_bitcoin_utxos(available_utxos.len() as u32);
+		if !params.consolidation_required(&available_utxos, fee_per_input_utxo) {
+			return weight
+		}
+
+		match T::BitcoinConsolidationCall::consolidate_utxos() {
+			Ok(api_call) => {
+				let (broadcast_id, _) =
+					T::BitcoinBroadcaster::threshold_sign_and_broadcast(api_call);
+				Self::deposit_event(Event::<T>::BitcoinUtxoConsolidationRequested { broadcast_id });
+			},
+			Err(ConsolidationError::NotRequired) => {},
+			Err(ConsolidationError::Other) => {
+				log::error!("Failed to construct the Bitcoin utxo consolidation call.");
+			},
+		}
+		weight
+	}
+
 	// Calculate the selection of utxos, return them and remove them from the list. The fee required
 	// to spend the input utxos are accounted for while selection. The fee required to include
 	// outputs and the minimum constant tx fee is incorporated by adding to the output amount. The
@@ -417,6 +707,76 @@ impl<T: Config> Pallet<T> {
 							min_fee_required_per_tx,
 					)
 				}),
+			UtxoSelectionType::ChildPaysForParent { change_utxo, parent_fee_deficit } =>
+				BitcoinAvailableUtxos::<T>::try_mutate(|available_utxos| {
+					let index = available_utxos
+						.iter()
+						.position(|utxo| utxo.id == change_utxo)
+						.ok_or_else(|| {
+							log::warn!("Change utxo to be bumped is no longer available.");
+						})?;
+					let change_utxo = available_utxos.remove(index);
+					let required_amount = fee_per_input_utxo +
+						fee_per_output_utxo + min_fee_required_per_tx +
+						parent_fee_deficit;
+					match change_utxo.amount.checked_sub(required_amount) {
+						Some(change_amount) => Ok((vec![change_utxo], change_amount)),
+						None => {
+							// Make up the difference with other utxos, whose own input fees are
+							// accounted for by the selection.
+							let shortfall = required_amount - change_utxo.amount;
+							let (mut selected_utxos, total_input_spendable_amount) =
+								select_utxos_from_pool(
+									available_utxos,
+									fee_per_input_utxo,
+									shortfall,
+								)
+								.ok_or_else(|| {
+									log::warn!(
+										"Not enough utxos available to pay for the fee bump."
+									);
+								})?;
+							selected_utxos.insert(0, change_utxo);
+							Ok::<_, ()>((selected_utxos, total_input_spendable_amount - shortfall))
+						},
+					}
+				})
+				.ok(),
+			UtxoSelectionType::SelectForConsolidation =>
+				BitcoinAvailableUtxos::<T>::try_mutate(|available_utxos| {
+					let consolidation_size = sp_std::cmp::min(
+						BitcoinUtxoConsolidationParameters::<T>::get().consolidation_size as usize,
+						MAX_BTC_SIGNING_PAYLOADS,
+					);
+					available_utxos.sort_by_key(|utxo| utxo.amount);
+
+					// Sweep the smallest utxos, skipping any that cost more to spend than they
+					// hold.
+					let (mut selected_utxos, mut remaining_utxos): (Vec<_>, Vec<_>) =
+						sp_std::mem::take(available_utxos)
+							.into_iter()
+							.partition(|utxo| utxo.is_worth_spending(fee_per_input_utxo));
+					remaining_utxos.extend(
+						selected_utxos
+							.split_off(sp_std::cmp::min(consolidation_size, selected_utxos.len())),
+					);
+					*available_utxos = remaining_utxos;
+
+					if selected_utxos.len() < 2 {
+						return Err(())
+					}
+					selected_utxos
+						.iter()
+						.map(|utxo| utxo.amount)
+						.sum::<u64>()
+						.checked_sub(
+							selected_utxos.len() as u64 * fee_per_input_utxo +
+								fee_per_output_utxo + min_fee_required_per_tx,
+						)
+						.map(|change_amount| (selected_utxos, change_amount))
+						.ok_or(())
+				})
+				.ok(),
 		}
 	}
 }
@@ -2,6 +2,9 @@ pub mod v3;
 pub mod v4;
 pub mod v5;
 pub mod v6;
+pub mod v7;
+pub mod v8;
+pub mod v9;
 
 use cf_runtime_upgrade_utilities::VersionedMigration;
 
@@ -9,4 +12,7 @@ pub type PalletMigration<T> = (
 	VersionedMigration<crate::Pallet<T>, v4::Migration<T>, 3, 4>,
 	VersionedMigration<crate::Pallet<T>, v5::Migration<T>, 4, 5>,
 	VersionedMigration<crate::Pallet<T>, v6::Migration<T>, 5, 6>,
+	VersionedMigration<crate::Pallet<T>, v7::Migration<T>, 6, 7>,
+	VersionedMigration<crate::Pallet<T>, v8::Migration<T>, 7, 8>,
+	VersionedMigration<crate::Pallet<T>, v9::Migration<T>, 8, 9>,
 );
@@ -0,0 +1,34 @@
+use crate::*;
+
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::marker::PhantomData;
+
+pub struct Migration<T: Config>(PhantomData<T>);
+
+// Registers the supported Ethereum assets in the ERC-20 token registry.
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		for (asset, address) in EthereumSupportedAssets::<T>::iter() {
+			EthereumErc20Tokens::<T>::insert(address, asset);
+		}
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<sp_std::vec::Vec<u8>, DispatchError> {
+		Ok(Default::default())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: sp_std::vec::Vec<u8>) -> Result<(), DispatchError> {
+		for (asset, address) in EthereumSupportedAssets::<T>::iter() {
+			assert_eq!(
+				EthereumErc20Tokens::<T>::get(address),
+				Some(asset),
+				"Supported asset missing from the token registry"
+			);
+		}
+		Ok(())
+	}
+}
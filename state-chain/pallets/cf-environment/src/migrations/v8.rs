@@ -0,0 +1,63 @@
+use crate::*;
+
+use cf_chains::arb;
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::marker::PhantomData;
+
+pub struct Migration<T: Config>(PhantomData<T>);
+
+// Chain ids and addresses mirror the Arbitrum values in the chain specs of each network.
+const CHAIN_ID_ARBITRUM_LOCALNET: u64 = 412346;
+
+const ARB_USDC_MAINNET: [u8; 20] = hex_literal::hex!("af88d065e77c8cC2239327C5EDb3A432268e5831");
+const ARB_USDC_TESTNET: [u8; 20] = hex_literal::hex!("75faf114eafb1BDbe2F0316DF893fd58CE46AA4d");
+const ARB_USDC_LOCALNET: [u8; 20] = hex_literal::hex!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+const ARB_KEY_MANAGER_LOCALNET: [u8; 20] =
+	hex_literal::hex!("5FbDB2315678afecb367f032d93F642f64180aa3");
+const ARB_VAULT_LOCALNET: [u8; 20] = hex_literal::hex!("e7f1725E7734CE288F8367e1Bb143E90bb3F0512");
+const ARB_ADDRESS_CHECKER_LOCALNET: [u8; 20] =
+	hex_literal::hex!("e7f1725E7734CE288F8367e1Bb143E90bb3F0512");
+
+// Initialises the Arbitrum environment on networks that were running before Arbitrum was added.
+// The contract addresses are only known up front on localnets. Elsewhere they are set once the
+// contracts are deployed, via `witness_arbitrum_vault_deployment`.
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		let (chain_id, usdc_address) = match ChainflipNetworkEnvironment::<T>::get() {
+			NetworkEnvironment::Mainnet => (arb::CHAIN_ID_MAINNET, ARB_USDC_MAINNET),
+			NetworkEnvironment::Testnet => (arb::CHAIN_ID_SEPOLIA, ARB_USDC_TESTNET),
+			NetworkEnvironment::Development => {
+				ArbitrumKeyManagerAddress::<T>::put(EthereumAddress::from(
+					ARB_KEY_MANAGER_LOCALNET,
+				));
+				ArbitrumVaultAddress::<T>::put(EthereumAddress::from(ARB_VAULT_LOCALNET));
+				ArbitrumAddressCheckerAddress::<T>::put(EthereumAddress::from(
+					ARB_ADDRESS_CHECKER_LOCALNET,
+				));
+				(CHAIN_ID_ARBITRUM_LOCALNET, ARB_USDC_LOCALNET)
+			},
+		};
+		ArbitrumChainId::<T>::put(chain_id);
+		ArbitrumSupportedAssets::<T>::insert(
+			ArbAsset::ArbUsdc,
+			EthereumAddress::from(usdc_address),
+		);
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<sp_std::vec::Vec<u8>, DispatchError> {
+		Ok(Default::default())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: sp_std::vec::Vec<u8>) -> Result<(), DispatchError> {
+		assert_ne!(ArbitrumChainId::<T>::get(), 0, "Arbitrum chain id was not set");
+		assert!(
+			ArbitrumSupportedAssets::<T>::contains_key(ArbAsset::ArbUsdc),
+			"Arbitrum USDC address was not set"
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,17 @@
+use crate::*;
+
+use cf_traits::SafeMode;
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::marker::PhantomData;
+
+pub struct Migration<T: Config>(PhantomData<T>);
+
+// The pools safe mode gained a flag for cancelling all orders, so the stored safe mode no longer
+// decodes and is reset.
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		RuntimeSafeMode::<T>::set(SafeMode::CODE_GREEN);
+
+		Weight::zero()
+	}
+}
@@ -2,21 +2,27 @@
 
 use crate::{self as pallet_cf_environment, Decode, Encode, TypeInfo};
 use cf_chains::{
-	btc::BitcoinFeeInfo,
+	arb::Arbitrum,
+	btc::{api::UtxoSelectionType, BitcoinCrypto, BitcoinFeeInfo, BtcAmount, Utxo},
 	dot::{api::CreatePolkadotVault, PolkadotCrypto},
-	eth, ApiCall, Bitcoin, Chain, ChainCrypto, Polkadot,
+	eth, ApiCall, Bitcoin, Chain, ChainCrypto, ConsolidateCall, ConsolidationError, Ethereum,
+	Polkadot,
 };
 use cf_primitives::{
-	BroadcastId, SemVer, ThresholdSignatureRequestId, INPUT_UTXO_SIZE_IN_BYTES,
-	MINIMUM_BTC_TX_SIZE_IN_BYTES, OUTPUT_UTXO_SIZE_IN_BYTES,
+	chains::assets::eth::Asset as EthAsset, BroadcastId, SemVer, ThresholdSignatureRequestId,
+	INPUT_UTXO_SIZE_IN_BYTES, MINIMUM_BTC_TX_SIZE_IN_BYTES, OUTPUT_UTXO_SIZE_IN_BYTES,
 };
 use cf_traits::{
 	impl_mock_callback, impl_mock_chainflip, impl_mock_runtime_safe_mode, impl_pallet_safe_mode,
-	Broadcaster, GetBitcoinFeeInfo, VaultKeyWitnessedHandler,
+	Broadcaster, GetBitcoinFeeInfo, SetMinimumDeposit, VaultKeyWitnessedHandler,
+};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU64, UnfilteredDispatchable},
 };
-use frame_support::{parameter_types, traits::UnfilteredDispatchable};
 use sp_core::{H160, H256};
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use std::cell::RefCell;
 
 type AccountId = u64;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -115,7 +121,92 @@ impl Broadcaster<Polkadot> for MockPolkadotBroadcaster {
 	) -> (BroadcastId, ThresholdSignatureRequestId) {
 		unimplemented!()
 	}
+
+	fn threshold_sign_and_broadcast_with_gas_refund(
+		_api_call: Self::ApiCall,
+		_gas_budget: <Polkadot as Chain>::ChainAmount,
+		_refund_address: <Polkadot as Chain>::ChainAccount,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		unimplemented!()
+	}
+}
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct MockBitcoinConsolidationCall {
+	pub utxos: Vec<Utxo>,
+	pub change_amount: BtcAmount,
+}
+
+impl ConsolidateCall<Bitcoin> for MockBitcoinConsolidationCall {
+	fn consolidate_utxos() -> Result<Self, ConsolidationError> {
+		Environment::select_and_take_bitcoin_utxos(UtxoSelectionType::SelectForConsolidation)
+			.map(|(utxos, change_amount)| Self { utxos, change_amount })
+			.ok_or(ConsolidationError::NotRequired)
+	}
 }
+
+impl ApiCall<BitcoinCrypto> for MockBitcoinConsolidationCall {
+	fn threshold_signature_payload(&self) -> <BitcoinCrypto as ChainCrypto>::Payload {
+		unimplemented!()
+	}
+	fn chain_encoded(&self) -> Vec<u8> {
+		unimplemented!()
+	}
+	fn signed(
+		self,
+		_threshold_signature: &<BitcoinCrypto as ChainCrypto>::ThresholdSignature,
+	) -> Self {
+		unimplemented!()
+	}
+	fn is_signed(&self) -> bool {
+		unimplemented!()
+	}
+	fn transaction_out_id(&self) -> <BitcoinCrypto as ChainCrypto>::TransactionOutId {
+		unimplemented!()
+	}
+}
+
+thread_local! {
+	pub static BITCOIN_BROADCASTS: RefCell<Vec<MockBitcoinConsolidationCall>> = RefCell::new(vec![]);
+	pub static ARBITRUM_KEY_ACTIVATED_AT: RefCell<Option<u64>> = RefCell::new(None);
+	pub static ETHEREUM_MINIMUM_DEPOSITS: RefCell<Vec<(EthAsset, u128)>> = RefCell::new(vec![]);
+}
+
+pub struct MockBitcoinBroadcaster;
+impl MockBitcoinBroadcaster {
+	pub fn broadcasts() -> Vec<MockBitcoinConsolidationCall> {
+		BITCOIN_BROADCASTS.with(|broadcasts| broadcasts.borrow().clone())
+	}
+}
+impl Broadcaster<Bitcoin> for MockBitcoinBroadcaster {
+	type ApiCall = MockBitcoinConsolidationCall;
+	type Callback = MockCallback;
+
+	fn threshold_sign_and_broadcast(
+		api_call: Self::ApiCall,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		BITCOIN_BROADCASTS.with(|broadcasts| {
+			let mut broadcasts = broadcasts.borrow_mut();
+			broadcasts.push(api_call);
+			(broadcasts.len() as BroadcastId, broadcasts.len() as ThresholdSignatureRequestId)
+		})
+	}
+
+	fn threshold_sign_and_broadcast_with_callback(
+		_api_call: Self::ApiCall,
+		_callback: Self::Callback,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		unimplemented!()
+	}
+
+	fn threshold_sign_and_broadcast_with_gas_refund(
+		_api_call: Self::ApiCall,
+		_gas_budget: <Bitcoin as Chain>::ChainAmount,
+		_refund_address: <Bitcoin as Chain>::ChainAccount,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		unimplemented!()
+	}
+}
+
 pub struct MockPolkadotVaultKeyWitnessedHandler;
 impl VaultKeyWitnessedHandler<Polkadot> for MockPolkadotVaultKeyWitnessedHandler {
 	fn on_new_key_activated(
@@ -133,6 +224,32 @@ impl VaultKeyWitnessedHandler<Bitcoin> for MockBitcoinVaultKeyWitnessedHandler {
 	}
 }
 
+pub struct MockArbitrumVaultKeyWitnessedHandler;
+impl VaultKeyWitnessedHandler<Arbitrum> for MockArbitrumVaultKeyWitnessedHandler {
+	fn on_new_key_activated(
+		block_number: <Arbitrum as Chain>::ChainBlockNumber,
+	) -> frame_support::pallet_prelude::DispatchResultWithPostInfo {
+		ARBITRUM_KEY_ACTIVATED_AT.with(|cell| *cell.borrow_mut() = Some(block_number));
+		Ok(().into())
+	}
+}
+
+pub struct MockEthereumMinimumDeposit;
+impl MockEthereumMinimumDeposit {
+	pub fn minimum_deposits() -> Vec<(EthAsset, u128)> {
+		ETHEREUM_MINIMUM_DEPOSITS.with(|deposits| deposits.borrow().clone())
+	}
+}
+impl SetMinimumDeposit<Ethereum> for MockEthereumMinimumDeposit {
+	fn set_minimum_deposit(
+		asset: <Ethereum as Chain>::ChainAsset,
+		minimum_deposit: <Ethereum as Chain>::ChainAmount,
+	) {
+		ETHEREUM_MINIMUM_DEPOSITS
+			.with(|deposits| deposits.borrow_mut().push((asset, minimum_deposit)));
+	}
+}
+
 parameter_types! {
 	pub CurrentReleaseVersion: SemVer = SemVer {
 		major: env!("CARGO_PKG_VERSION_MAJOR").parse::<u8>().unwrap(),
@@ -159,7 +276,12 @@ impl pallet_cf_environment::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type PolkadotVaultKeyWitnessedHandler = MockPolkadotVaultKeyWitnessedHandler;
 	type BitcoinVaultKeyWitnessedHandler = MockBitcoinVaultKeyWitnessedHandler;
+	type ArbitrumVaultKeyWitnessedHandler = MockArbitrumVaultKeyWitnessedHandler;
+	type EthereumMinimumDeposit = MockEthereumMinimumDeposit;
 	type BitcoinFeeInfo = MockBitcoinFeeInfo;
+	type BitcoinConsolidationCall = MockBitcoinConsolidationCall;
+	type BitcoinBroadcaster = MockBitcoinBroadcaster;
+	type BitcoinUtxoConsolidationInterval = ConstU64<CONSOLIDATION_INTERVAL>;
 	type RuntimeSafeMode = MockRuntimeSafeMode;
 	type CurrentReleaseVersion = CurrentReleaseVersion;
 	type WeightInfo = ();
@@ -170,6 +292,7 @@ pub const KEY_MANAGER_ADDRESS: eth::Address = H160([1u8; 20]);
 pub const VAULT_ADDRESS: eth::Address = H160([2u8; 20]);
 pub const ADDRESS_CHECKER: eth::Address = H160([3u8; 20]);
 pub const ETH_CHAIN_ID: u64 = 1;
+pub const CONSOLIDATION_INTERVAL: u64 = 10;
 
 cf_test_utilities::impl_test_helpers! {
 	Test,
@@ -1,9 +1,23 @@
 #![cfg(test)]
-use cf_chains::btc::{api::UtxoSelectionType, deposit_address::DepositAddress, Utxo};
+use cf_chains::{
+	btc::{
+		api::UtxoSelectionType, deposit_address::DepositAddress, Utxo, UtxoConsolidationParameters,
+		UtxoId,
+	},
+	eth::Address as EthereumAddress,
+};
+use cf_primitives::chains::assets::eth::Asset as EthAsset;
 use cf_traits::SafeMode;
-use frame_support::{assert_ok, traits::OriginTrait};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Hooks, OriginTrait},
+};
 
-use crate::{RuntimeSafeMode, SafeModeUpdate};
+use crate::{
+	ArbitrumAddressCheckerAddress, ArbitrumKeyManagerAddress, ArbitrumVaultAddress,
+	BitcoinAvailableUtxos, BitcoinEgressMemosEnabled, BitcoinUtxoConsolidationParameters, Error,
+	EthereumErc20Tokens, EthereumSupportedAssets, RuntimeSafeMode, SafeModeUpdate,
+};
 
 use crate::mock::*;
 
@@ -92,6 +106,78 @@ fn test_btc_utxo_selection() {
 	});
 }
 
+#[test]
+fn test_btc_child_pays_for_parent_utxo_selection() {
+	const CHANGE_UTXO: UtxoId = UtxoId { tx_id: [1u8; 32], vout: 2 };
+
+	let change_utxo = Utxo {
+		amount: 10000,
+		id: CHANGE_UTXO,
+		deposit_address: DepositAddress::new(Default::default(), Default::default()),
+	};
+
+	new_test_ext().execute_with(|| {
+		// returns none if the change utxo is not available
+		assert_eq!(
+			Environment::select_and_take_bitcoin_utxos(UtxoSelectionType::ChildPaysForParent {
+				change_utxo: CHANGE_UTXO,
+				parent_fee_deficit: 1000,
+			}),
+			None
+		);
+
+		let other_utxo = Utxo {
+			amount: 1000000,
+			id: Default::default(),
+			deposit_address: DepositAddress::new(Default::default(), Default::default()),
+		};
+		let add_utxo = |utxo: &Utxo| {
+			Environment::add_bitcoin_utxo_to_list(
+				utxo.amount,
+				utxo.id.clone(),
+				utxo.deposit_address.clone(),
+			)
+		};
+		add_utxo(&other_utxo);
+		add_utxo(&change_utxo);
+
+		// Only the change utxo is selected, paying for one input, one output and the deficit.
+		assert_eq!(
+			Environment::select_and_take_bitcoin_utxos(UtxoSelectionType::ChildPaysForParent {
+				change_utxo: CHANGE_UTXO,
+				parent_fee_deficit: 1000,
+			})
+			.unwrap(),
+			(vec![change_utxo.clone()], 6760)
+		);
+		assert_eq!(BitcoinAvailableUtxos::<Test>::decode_len(), Some(1));
+
+		// If the change utxo can't cover the fees, other utxos are spent alongside it, paying for
+		// their own inputs too.
+		add_utxo(&change_utxo);
+		assert_eq!(
+			Environment::select_and_take_bitcoin_utxos(UtxoSelectionType::ChildPaysForParent {
+				change_utxo: CHANGE_UTXO,
+				parent_fee_deficit: 9000,
+			})
+			.unwrap(),
+			(vec![change_utxo.clone(), other_utxo], 996980)
+		);
+		assert_eq!(BitcoinAvailableUtxos::<Test>::decode_len(), Some(0));
+
+		// Returns none if the available utxos can't cover the fees, leaving them available.
+		add_utxo(&change_utxo);
+		assert_eq!(
+			Environment::select_and_take_bitcoin_utxos(UtxoSelectionType::ChildPaysForParent {
+				change_utxo: CHANGE_UTXO,
+				parent_fee_deficit: 9000,
+			}),
+			None
+		);
+		assert_eq!(BitcoinAvailableUtxos::<Test>::get(), vec![change_utxo]);
+	});
+}
+
 #[test]
 fn update_safe_mode() {
 	new_test_ext().execute_with(|| {
@@ -122,3 +208,249 @@ fn update_safe_mode() {
 		));
 	});
 }
+
+fn add_utxo_amounts(amounts: &[crate::BtcAmount]) {
+	for amount in amounts {
+		Environment::add_bitcoin_utxo_to_list(
+			*amount,
+			Default::default(),
+			DepositAddress::new(Default::default(), Default::default()),
+		);
+	}
+}
+
+fn utxo_amounts(utxos: &[Utxo]) -> Vec<crate::BtcAmount> {
+	utxos.iter().map(|utxo| utxo.amount).collect()
+}
+
+#[test]
+fn test_btc_utxo_consolidation_selection() {
+	new_test_ext().execute_with(|| {
+		BitcoinUtxoConsolidationParameters::<Test>::mutate(|params| params.consolidation_size = 3);
+
+		// A single spendable utxo is not worth consolidating.
+		add_utxo_amounts(&[1000, 8000]);
+		assert_eq!(
+			Environment::select_and_take_bitcoin_utxos(UtxoSelectionType::SelectForConsolidation),
+			None
+		);
+
+		// The smallest spendable utxos are swept, skipping those too small to cover their fee.
+		add_utxo_amounts(&[7000, 5000, 6000]);
+		let (selected_utxos, change_amount) =
+			Environment::select_and_take_bitcoin_utxos(UtxoSelectionType::SelectForConsolidation)
+				.unwrap();
+		assert_eq!(utxo_amounts(&selected_utxos), vec![5000, 6000, 7000]);
+		assert_eq!(change_amount, 12200);
+		assert_eq!(utxo_amounts(&BitcoinAvailableUtxos::<Test>::get()), vec![1000, 8000]);
+	});
+}
+
+#[test]
+fn btc_utxos_are_consolidated_periodically() {
+	new_test_ext().execute_with(|| {
+		BitcoinUtxoConsolidationParameters::<Test>::put(UtxoConsolidationParameters {
+			utxo_count_threshold: 5,
+			dust_amount: 5000,
+			dust_fraction_threshold: sp_runtime::Percent::from_percent(50),
+			..Default::default()
+		});
+
+		// Not enough utxos and not enough dust.
+		add_utxo_amounts(&[4000, 10000, 20000]);
+		Environment::on_initialize(CONSOLIDATION_INTERVAL);
+		assert!(MockBitcoinBroadcaster::broadcasts().is_empty());
+
+		// Enough dust, but consolidations are only checked periodically.
+		add_utxo_amounts(&[3000]);
+		Environment::on_initialize(CONSOLIDATION_INTERVAL + 1);
+		assert!(MockBitcoinBroadcaster::broadcasts().is_empty());
+
+		Environment::on_initialize(2 * CONSOLIDATION_INTERVAL);
+		assert_eq!(
+			MockBitcoinBroadcaster::broadcasts()
+				.iter()
+				.map(|call| utxo_amounts(&call.utxos))
+				.collect::<Vec<_>>(),
+			vec![vec![3000, 4000, 10000, 20000]]
+		);
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::BitcoinUtxoConsolidationRequested { broadcast_id: 1 },
+		));
+		assert!(BitcoinAvailableUtxos::<Test>::get().is_empty());
+
+		// No consolidation while fees are too high.
+		BitcoinUtxoConsolidationParameters::<Test>::mutate(|params| {
+			params.max_fee_per_input_utxo = 0;
+		});
+		add_utxo_amounts(&[3000, 4000, 10000, 20000, 30000]);
+		Environment::on_initialize(3 * CONSOLIDATION_INTERVAL);
+		assert_eq!(MockBitcoinBroadcaster::broadcasts().len(), 1);
+	});
+}
+
+#[test]
+fn utxos_not_worth_spending_do_not_trigger_consolidation() {
+	new_test_ext().execute_with(|| {
+		BitcoinUtxoConsolidationParameters::<Test>::put(UtxoConsolidationParameters {
+			utxo_count_threshold: 3,
+			dust_amount: 5000,
+			dust_fraction_threshold: sp_runtime::Percent::from_percent(50),
+			..Default::default()
+		});
+
+		// Only one of these utxos covers the fee of spending it, so there is nothing to sweep.
+		add_utxo_amounts(&[1000, 1000, 1000, 10000]);
+		assert!(!BitcoinUtxoConsolidationParameters::<Test>::get().consolidation_required(
+			&BitcoinAvailableUtxos::<Test>::get(),
+			<MockBitcoinFeeInfo as cf_traits::GetBitcoinFeeInfo>::bitcoin_fee_info()
+				.fee_per_input_utxo,
+		));
+		Environment::on_initialize(CONSOLIDATION_INTERVAL);
+		assert!(MockBitcoinBroadcaster::broadcasts().is_empty());
+
+		// Dust is only counted if it's worth spending.
+		add_utxo_amounts(&[4000, 4000]);
+		Environment::on_initialize(2 * CONSOLIDATION_INTERVAL);
+		assert_eq!(
+			MockBitcoinBroadcaster::broadcasts()
+				.iter()
+				.map(|call| utxo_amounts(&call.utxos))
+				.collect::<Vec<_>>(),
+			vec![vec![4000, 4000, 10000]]
+		);
+	});
+}
+
+#[test]
+fn update_consolidation_parameters() {
+	new_test_ext().execute_with(|| {
+		let params = UtxoConsolidationParameters { consolidation_size: 50, ..Default::default() };
+		assert_ok!(Environment::update_consolidation_parameters(OriginTrait::root(), params));
+		assert_eq!(BitcoinUtxoConsolidationParameters::<Test>::get(), params);
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::UtxoConsolidationParametersUpdated { params },
+		));
+
+		for consolidation_size in [1, cf_chains::btc::MAX_BTC_SIGNING_PAYLOADS as u32 + 1] {
+			assert_noop!(
+				Environment::update_consolidation_parameters(
+					OriginTrait::root(),
+					UtxoConsolidationParameters { consolidation_size, ..Default::default() },
+				),
+				Error::<Test>::InvalidUtxoConsolidationParameters
+			);
+		}
+	});
+}
+
+#[test]
+fn bitcoin_egress_memos_are_opt_in() {
+	new_test_ext().execute_with(|| {
+		assert!(!BitcoinEgressMemosEnabled::<Test>::get());
+
+		assert_ok!(Environment::set_bitcoin_egress_memos(OriginTrait::root(), true));
+		assert!(BitcoinEgressMemosEnabled::<Test>::get());
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::BitcoinEgressMemosSet { enabled: true },
+		));
+
+		assert_ok!(Environment::set_bitcoin_egress_memos(OriginTrait::root(), false));
+		assert!(!BitcoinEgressMemosEnabled::<Test>::get());
+	});
+}
+
+#[test]
+fn witness_arbitrum_vault_deployment() {
+	new_test_ext().execute_with(|| {
+		let key_manager_address = [0xaa; 20].into();
+		let vault_address = [0xbb; 20].into();
+		let address_checker_address = [0xcc; 20].into();
+		assert_ok!(Environment::witness_arbitrum_vault_deployment(
+			OriginTrait::root(),
+			key_manager_address,
+			vault_address,
+			address_checker_address,
+			1_000,
+		));
+		assert_eq!(ArbitrumKeyManagerAddress::<Test>::get(), key_manager_address);
+		assert_eq!(ArbitrumVaultAddress::<Test>::get(), vault_address);
+		assert_eq!(ArbitrumAddressCheckerAddress::<Test>::get(), address_checker_address);
+		assert_eq!(ARBITRUM_KEY_ACTIVATED_AT.with(|cell| *cell.borrow()), Some(1_000));
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::ArbitrumVaultContractsSet {
+				key_manager_address,
+				vault_address,
+				address_checker_address,
+			},
+		));
+	});
+}
+
+#[test]
+fn register_eth_erc20_token() {
+	new_test_ext().execute_with(|| {
+		let genesis_address = EthereumSupportedAssets::<Test>::get(EthAsset::Flip).unwrap();
+		let new_address: EthereumAddress = [0xaa; 20].into();
+
+		// Registering a new address for an asset replaces the old one.
+		assert_ok!(Environment::register_eth_erc20_token(
+			OriginTrait::root(),
+			new_address,
+			EthAsset::Flip,
+			1_000,
+		));
+		assert_eq!(EthereumSupportedAssets::<Test>::get(EthAsset::Flip), Some(new_address));
+		assert_eq!(EthereumErc20Tokens::<Test>::get(new_address), Some(EthAsset::Flip));
+		assert_eq!(EthereumErc20Tokens::<Test>::get(genesis_address), None);
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::UpdatedEthAsset(EthAsset::Flip, new_address),
+		));
+
+		// Assets that aren't registered yet are added.
+		EthereumSupportedAssets::<Test>::remove(EthAsset::Usdc);
+		let usdc_address: EthereumAddress = [0xbb; 20].into();
+		assert_ok!(Environment::register_eth_erc20_token(
+			OriginTrait::root(),
+			usdc_address,
+			EthAsset::Usdc,
+			2_000,
+		));
+		assert_eq!(EthereumSupportedAssets::<Test>::get(EthAsset::Usdc), Some(usdc_address));
+		assert_eq!(EthereumErc20Tokens::<Test>::get(usdc_address), Some(EthAsset::Usdc));
+		System::assert_last_event(RuntimeEvent::Environment(
+			crate::Event::<Test>::AddedNewEthAsset(EthAsset::Usdc, usdc_address),
+		));
+
+		// A token can't be registered as more than one asset.
+		assert_noop!(
+			Environment::register_eth_erc20_token(
+				OriginTrait::root(),
+				new_address,
+				EthAsset::Usdc,
+				0,
+			),
+			Error::<Test>::Erc20TokenAlreadyRegistered
+		);
+
+		assert_eq!(
+			MockEthereumMinimumDeposit::minimum_deposits(),
+			vec![(EthAsset::Flip, 1_000), (EthAsset::Usdc, 2_000)]
+		);
+	});
+}
+
+#[test]
+fn eth_can_not_be_registered_as_an_erc20_token() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Environment::register_eth_erc20_token(
+				OriginTrait::root(),
+				[0xaa; 20].into(),
+				EthAsset::Eth,
+				0,
+			),
+			Error::<Test>::EthAddressNotUpdateable
+		);
+	});
+}
@@ -33,6 +33,7 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_cf_environment.
 pub trait WeightInfo {
 	fn update_safe_mode() -> Weight;
+	fn consolidate_bitcoin_utxos(x: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_environment using the Substrate node and recommended hardware.
@@ -48,6 +49,39 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 		Weight::from_parts(10_113_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Environment::BitcoinUtxoConsolidationParameters` (r:1 w:0)
+	/// Proof: `Environment::BitcoinUtxoConsolidationParameters` (`max_values`: Some(1), `max_size`: Some(37), added: 532, mode: `MaxEncodedLen`)
+	/// Storage: `BitcoinChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `BitcoinChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(48), added: 543, mode: `MaxEncodedLen`)
+	/// Storage: `Environment::BitcoinAvailableUtxos` (r:1 w:1)
+	/// Proof: `Environment::BitcoinAvailableUtxos` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinVault::CurrentVaultEpochAndState` (r:1 w:0)
+	/// Proof: `BitcoinVault::CurrentVaultEpochAndState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinVault::Vaults` (r:2 w:0)
+	/// Proof: `BitcoinVault::Vaults` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::BroadcastIdCounter` (r:1 w:1)
+	/// Proof: `BitcoinBroadcaster::BroadcastIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinThresholdSigner::ThresholdSignatureRequestIdCounter` (r:1 w:1)
+	/// Proof: `BitcoinThresholdSigner::ThresholdSignatureRequestIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
+	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinThresholdSigner::PendingRequestInstructions` (r:0 w:1)
+	/// Proof: `BitcoinThresholdSigner::PendingRequestInstructions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::RequestCallbacks` (r:0 w:1)
+	/// Proof: `BitcoinBroadcaster::RequestCallbacks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `x` is `[2, 1000]`.
+	fn consolidate_bitcoin_utxos(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1306 + x * (81 ±0)`
+		//  Estimated: `4771 + x * (81 ±0)`
+		// Minimum execution time: 96_411_000 picoseconds.
+		Weight::from_parts(98_036_512, 4771)
+			// Standard Error: 4_127
+			.saturating_add(Weight::from_parts(3_482_715, 0).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 81).saturating_mul(x.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -62,4 +96,37 @@ impl WeightInfo for () {
 		Weight::from_parts(10_113_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Environment::BitcoinUtxoConsolidationParameters` (r:1 w:0)
+	/// Proof: `Environment::BitcoinUtxoConsolidationParameters` (`max_values`: Some(1), `max_size`: Some(37), added: 532, mode: `MaxEncodedLen`)
+	/// Storage: `BitcoinChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `BitcoinChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(48), added: 543, mode: `MaxEncodedLen`)
+	/// Storage: `Environment::BitcoinAvailableUtxos` (r:1 w:1)
+	/// Proof: `Environment::BitcoinAvailableUtxos` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinVault::CurrentVaultEpochAndState` (r:1 w:0)
+	/// Proof: `BitcoinVault::CurrentVaultEpochAndState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinVault::Vaults` (r:2 w:0)
+	/// Proof: `BitcoinVault::Vaults` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::BroadcastIdCounter` (r:1 w:1)
+	/// Proof: `BitcoinBroadcaster::BroadcastIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinThresholdSigner::ThresholdSignatureRequestIdCounter` (r:1 w:1)
+	/// Proof: `BitcoinThresholdSigner::ThresholdSignatureRequestIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
+	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinThresholdSigner::PendingRequestInstructions` (r:0 w:1)
+	/// Proof: `BitcoinThresholdSigner::PendingRequestInstructions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BitcoinBroadcaster::RequestCallbacks` (r:0 w:1)
+	/// Proof: `BitcoinBroadcaster::RequestCallbacks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `x` is `[2, 1000]`.
+	fn consolidate_bitcoin_utxos(x: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1306 + x * (81 ±0)`
+		//  Estimated: `4771 + x * (81 ±0)`
+		// Minimum execution time: 96_411_000 picoseconds.
+		Weight::from_parts(98_036_512, 4771)
+			// Standard Error: 4_127
+			.saturating_add(Weight::from_parts(3_482_715, 0).saturating_mul(x.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(Weight::from_parts(0, 81).saturating_mul(x.into()))
+	}
 }
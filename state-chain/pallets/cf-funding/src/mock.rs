@@ -169,6 +169,14 @@ impl Broadcaster<Ethereum> for MockBroadcaster {
 	) -> (BroadcastId, ThresholdSignatureRequestId) {
 		unimplemented!()
 	}
+
+	fn threshold_sign_and_broadcast_with_gas_refund(
+		_api_call: Self::ApiCall,
+		_gas_budget: <Ethereum as Chain>::ChainAmount,
+		_refund_address: <Ethereum as Chain>::ChainAccount,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		unimplemented!()
+	}
 }
 
 impl_mock_runtime_safe_mode! { funding: PalletSafeMode }
@@ -53,6 +53,15 @@ benchmarks_instance_pallet! {
 		), amount);
 	}
 
+	set_transaction_cost_estimate {
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+		let asset: <<T as Config<I>>::TargetChain as Chain>::ChainAsset = BenchmarkValue::benchmark_value();
+		let estimate = TransactionCostEstimate { ingress: 50_000, egress: 50_000 };
+	} : { let _ = Pallet::<T, I>::set_transaction_cost_estimate(origin, asset, estimate); }
+	verify {
+		assert_eq!(TransactionCostEstimates::<T, I>::get(asset), Some(estimate));
+	}
+
 	finalise_ingress {
 		let a in 1 .. 100;
 		let mut addresses = vec![];
@@ -17,20 +17,23 @@ pub use weights::WeightInfo;
 
 use cf_chains::{
 	address::{AddressConverter, AddressDerivationApi, AddressDerivationError},
-	AllBatch, AllBatchError, CcmCfParameters, CcmChannelMetadata, CcmDepositMetadata, CcmMessage,
-	Chain, ChannelLifecycleHooks, DepositChannel, ExecutexSwapAndCall, FetchAssetParams,
-	ForeignChainAddress, SwapOrigin, TransferAssetParams,
+	AllBatch, AllBatchError, AnyChain, CcmCfParameters, CcmChannelMetadata, CcmDepositMetadata,
+	CcmMessage, Chain, ChannelLifecycleHooks, DepositChannel, ExecutexSwapAndCall,
+	ExecutexSwapAndCallError, FetchAssetParams, ForeignChainAddress, SwapOrigin,
+	SwapRefundParameters, TransactionCostEstimate, TransferAssetParams,
 };
 use cf_primitives::{
-	Asset, AssetAmount, BasisPoints, ChannelId, EgressCounter, EgressId, ForeignChain,
+	chains::assets::AssetError, Asset, AssetAmount, BasisPoints, ChannelId, EgressCounter,
+	EgressId, ForeignChain,
 };
 use cf_traits::{
-	liquidity::LpBalanceApi, Broadcaster, CcmHandler, Chainflip, DepositApi, DepositHandler,
-	EgressApi, GetBlockHeight, SwapDepositHandler,
+	liquidity::LpBalanceApi, Broadcaster, CcmGasRefundHandler, CcmHandler, Chainflip, DepositApi,
+	DepositHandler, EgressApi, EgressFeeEstimator, GetBlockHeight, SetMinimumDeposit,
+	SwapDepositHandler,
 };
 use frame_support::{
 	pallet_prelude::*,
-	sp_runtime::{DispatchError, Saturating, TransactionOutcome},
+	sp_runtime::{traits::Zero, DispatchError, Saturating, TransactionOutcome},
 };
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
@@ -76,6 +79,8 @@ pub(crate) struct CrossChainMessage<C: Chain> {
 	// Where funds might be returned to if the message fails.
 	pub cf_parameters: CcmCfParameters,
 	pub gas_budget: C::ChainAmount,
+	// Where unused gas is refunded to, in preference to the source address.
+	pub gas_refund_address: Option<ForeignChainAddress>,
 }
 
 impl<C: Chain> CrossChainMessage<C> {
@@ -91,7 +96,7 @@ pub struct VaultTransfer<C: Chain> {
 	destination_address: C::ChainAccount,
 }
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(1);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(4);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -149,6 +154,7 @@ pub mod pallet {
 			destination_address: ForeignChainAddress,
 			broker_id: AccountId,
 			broker_commission_bps: BasisPoints,
+			refund_parameters: Option<SwapRefundParameters>,
 		},
 		LiquidityProvision {
 			lp_account: AccountId,
@@ -157,6 +163,7 @@ pub mod pallet {
 			destination_asset: Asset,
 			destination_address: ForeignChainAddress,
 			channel_metadata: CcmChannelMetadata,
+			refund_address: Option<ForeignChainAddress>,
 		},
 	}
 
@@ -209,11 +216,15 @@ pub mod pallet {
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		pub deposit_channel_lifetime: TargetChainBlockNumber<T, I>,
+		pub transaction_cost_estimates: Vec<(TargetChainAsset<T, I>, TransactionCostEstimate)>,
 	}
 
 	impl<T: Config<I>, I: 'static> Default for GenesisConfig<T, I> {
 		fn default() -> Self {
-			Self { deposit_channel_lifetime: Default::default() }
+			Self {
+				deposit_channel_lifetime: Default::default(),
+				transaction_cost_estimates: Default::default(),
+			}
 		}
 	}
 
@@ -221,6 +232,9 @@ pub mod pallet {
 	impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
 		fn build(&self) {
 			DepositChannelLifetime::<T, I>::put(self.deposit_channel_lifetime);
+			for (asset, estimate) in &self.transaction_cost_estimates {
+				TransactionCostEstimates::<T, I>::insert(asset, estimate);
+			}
 		}
 	}
 
@@ -264,6 +278,9 @@ pub mod pallet {
 		/// Get the latest block height of the target chain via Chain Tracking.
 		type ChainTracking: GetBlockHeight<Self::TargetChain>;
 
+		/// Estimates the egress fee that is deducted from gas refunds.
+		type EgressFeeEstimator: EgressFeeEstimator<AnyChain>;
+
 		/// A broadcaster instance.
 		type Broadcaster: Broadcaster<
 			Self::TargetChain,
@@ -323,6 +340,12 @@ pub mod pallet {
 	pub type MinimumDeposit<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, TargetChainAsset<T, I>, TargetChainAmount<T, I>, ValueQuery>;
 
+	/// The expected cost of fetching and egressing each asset, used to estimate ingress and egress
+	/// fees from the target chain's tracked fee data.
+	#[pallet::storage]
+	pub type TransactionCostEstimates<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TargetChainAsset<T, I>, TransactionCostEstimate, OptionQuery>;
+
 	#[pallet::storage]
 	pub type DepositChannelLifetime<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, TargetChainBlockNumber<T, I>, ValueQuery>;
@@ -399,6 +422,10 @@ pub mod pallet {
 			reason: DispatchError,
 			deposit_witness: DepositWitness<T::TargetChain>,
 		},
+		TransactionCostEstimateSet {
+			asset: TargetChainAsset<T, I>,
+			estimate: TransactionCostEstimate,
+		},
 	}
 
 	#[pallet::error]
@@ -572,9 +599,11 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::EnsureGovernance::ensure_origin(origin)?;
 
-			MinimumDeposit::<T, I>::insert(asset, minimum_deposit);
+			<Self as SetMinimumDeposit<T::TargetChain>>::set_minimum_deposit(
+				asset,
+				minimum_deposit,
+			);
 
-			Self::deposit_event(Event::<T, I>::MinimumDepositSet { asset, minimum_deposit });
 			Ok(())
 		}
 
@@ -607,6 +636,28 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Sets the expected cost of fetching and egressing an asset, from which ingress and
+		/// egress fees are estimated.
+		/// Requires governance
+		///
+		/// ## Events
+		///
+		/// - [on_success](Event::TransactionCostEstimateSet)
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_transaction_cost_estimate())]
+		pub fn set_transaction_cost_estimate(
+			origin: OriginFor<T>,
+			asset: TargetChainAsset<T, I>,
+			estimate: TransactionCostEstimate,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			TransactionCostEstimates::<T, I>::insert(asset, estimate);
+
+			Self::deposit_event(Event::<T, I>::TransactionCostEstimateSet { asset, estimate });
+			Ok(())
+		}
 	}
 }
 
@@ -629,7 +680,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Take all scheduled egress requests and send them out in an `AllBatch` call.
 	///
 	/// Note: Egress transactions with Blacklisted assets are not sent, and kept in storage.
+	/// Transfers beyond the batch's limit, if any, are kept in storage for the next batch.
 	fn do_egress_scheduled_fetch_transfer() -> TransactionOutcome<DispatchResult> {
+		let mut remaining_transfers =
+			<T::ChainApiCall as AllBatch<T::TargetChain>>::max_transfers_per_batch()
+				.unwrap_or(usize::MAX);
 		let batch_to_send: Vec<_> =
 			ScheduledEgressFetchOrTransfer::<T, I>::mutate(|requests: &mut Vec<_>| {
 				// Filter out disabled assets and requests that are not ready to be egressed.
@@ -664,7 +719,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 											.unwrap_or(false)
 									},
 								),
-								FetchOrTransfer::Transfer { .. } => true,
+								FetchOrTransfer::Transfer { .. } =>
+									remaining_transfers.checked_sub(1).map_or(false, |remaining| {
+										remaining_transfers = remaining;
+										true
+									}),
 							}
 					})
 					.collect()
@@ -703,11 +762,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					egress_id,
 				} => {
 					egress_ids.push(egress_id);
-					transfer_params.push(TransferAssetParams {
-						asset,
-						amount,
-						to: destination_address,
-					});
+					transfer_params.push((
+						TransferAssetParams { asset, amount, to: destination_address },
+						egress_id,
+					));
 					DepositBalances::<T, I>::mutate(asset, |tracker| {
 						tracker.register_transfer(amount);
 					});
@@ -740,7 +798,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 	/// Send all scheduled Cross Chain Messages out to the target chain.
 	///
-	/// Blacklisted assets are not sent and will remain in storage.
+	/// Blacklisted assets are not sent and will remain in storage, as are messages that can't be
+	/// sent yet, for example because the vault is unavailable.
 	fn do_egress_scheduled_ccm() {
 		let ccms_to_send: Vec<CrossChainMessage<T::TargetChain>> =
 			ScheduledEgressCcm::<T, I>::mutate(|ccms: &mut Vec<_>| {
@@ -748,32 +807,60 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				ccms.extract_if(|ccm| !DisabledEgressAssets::<T, I>::contains_key(ccm.asset()))
 					.collect()
 			});
+		let mut ccms_to_retry = Vec::new();
 		for ccm in ccms_to_send {
+			// Unused gas is refunded to the designated refund address, or else to the source
+			// address, whichever is on the target chain. There is nothing to refund without a
+			// gas budget.
+			let maybe_gas_refund_address = [&ccm.gas_refund_address, &ccm.source_address]
+				.into_iter()
+				.flatten()
+				.filter(|_| !ccm.gas_budget.is_zero())
+				.find(|address| address.chain() == <T as Config<I>>::TargetChain::get())
+				.and_then(|address| {
+					T::TargetChain::try_from_foreign_chain_address(address.clone()).ok()
+				});
 			match <T::ChainApiCall as ExecutexSwapAndCall<T::TargetChain>>::new_unsigned(
 				ccm.egress_id,
 				TransferAssetParams {
 					asset: ccm.asset,
 					amount: ccm.amount,
-					to: ccm.destination_address,
+					to: ccm.destination_address.clone(),
 				},
 				ccm.source_chain,
-				ccm.source_address,
+				ccm.source_address.clone(),
 				ccm.gas_budget,
 				ccm.message.to_vec(),
 			) {
 				Ok(api_call) => {
-					let (broadcast_id, _) = T::Broadcaster::threshold_sign_and_broadcast(api_call);
+					let (broadcast_id, _) = match maybe_gas_refund_address {
+						Some(refund_address) =>
+							T::Broadcaster::threshold_sign_and_broadcast_with_gas_refund(
+								api_call,
+								ccm.gas_budget,
+								refund_address,
+							),
+						None => T::Broadcaster::threshold_sign_and_broadcast(api_call),
+					};
 					Self::deposit_event(Event::<T, I>::CcmBroadcastRequested {
 						broadcast_id,
 						egress_id: ccm.egress_id,
 					});
 				},
-				Err(error) => Self::deposit_event(Event::<T, I>::CcmEgressInvalid {
-					egress_id: ccm.egress_id,
-					error,
-				}),
+				Err(ExecutexSwapAndCallError::Unavailable) => ccms_to_retry.push(ccm),
+				Err(ExecutexSwapAndCallError::Invalid(error)) =>
+					Self::deposit_event(Event::<T, I>::CcmEgressInvalid {
+						egress_id: ccm.egress_id,
+						error,
+					}),
 			};
 		}
+		if !ccms_to_retry.is_empty() {
+			// Keep the retried messages ahead of any that were scheduled in the meantime.
+			ScheduledEgressCcm::<T, I>::mutate(|ccms| {
+				ccms.splice(0..0, ccms_to_retry);
+			});
+		}
 	}
 
 	/// Completes a single deposit request.
@@ -833,9 +920,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				destination_asset,
 				broker_id,
 				broker_commission_bps,
-				..
+				refund_parameters,
 			} => T::SwapDepositHandler::schedule_swap_from_channel(
-				deposit_address.clone().into(),
+				T::TargetChain::into_foreign_chain_address(deposit_address.clone()),
 				block_height.into(),
 				asset.into(),
 				destination_asset,
@@ -844,12 +931,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				broker_id,
 				broker_commission_bps,
 				channel_id,
+				refund_parameters,
 			),
 			ChannelAction::CcmTransfer {
 				destination_asset,
 				destination_address,
 				channel_metadata,
-				..
+				refund_address,
 			} => T::CcmHandler::on_ccm_deposit(
 				asset.into(),
 				amount.into(),
@@ -862,11 +950,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				},
 				SwapOrigin::DepositChannel {
 					deposit_address: T::AddressConverter::to_encoded_address(
-						deposit_address.clone().into(),
+						T::TargetChain::into_foreign_chain_address(deposit_address.clone()),
 					),
 					channel_id,
 					deposit_block_height: block_height.into(),
 				},
+				refund_address,
 			),
 		};
 
@@ -969,6 +1058,7 @@ impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 			Some((
 				CcmDepositMetadata { source_chain, source_address, channel_metadata },
 				gas_budget,
+				gas_refund_address,
 			)) => ScheduledEgressCcm::<T, I>::append(CrossChainMessage {
 				egress_id,
 				asset,
@@ -979,6 +1069,7 @@ impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 				source_chain,
 				source_address,
 				gas_budget,
+				gas_refund_address,
 			}),
 			None => ScheduledEgressFetchOrTransfer::<T, I>::append(FetchOrTransfer::<
 				T::TargetChain,
@@ -1001,6 +1092,49 @@ impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 	}
 }
 
+impl<T: Config<I>, I: 'static> CcmGasRefundHandler<T::TargetChain> for Pallet<T, I>
+where
+	TargetChainAsset<T, I>: TryFrom<Asset, Error = AssetError>,
+{
+	/// The refund pays for its own egress, and is not made unless what is left is at least the
+	/// minimum deposit of the gas asset.
+	fn refund_gas(
+		amount: TargetChainAmount<T, I>,
+		refund_address: TargetChainAccount<T, I>,
+	) -> Option<(EgressId, TargetChainAmount<T, I>)> {
+		let gas_asset: TargetChainAsset<T, I> = <T as Config<I>>::TargetChain::get()
+			.gas_asset()
+			.try_into()
+			.expect("The gas asset of a chain is always one of its assets.");
+		let egress_fee: TargetChainAmount<T, I> =
+			T::EgressFeeEstimator::estimate_egress_fee(gas_asset.into())?.try_into().ok()?;
+		let refund_amount = amount.saturating_sub(egress_fee);
+		if refund_amount.is_zero() || refund_amount < MinimumDeposit::<T, I>::get(gas_asset) {
+			return None
+		}
+		Some((
+			<Self as EgressApi<T::TargetChain>>::schedule_egress(
+				gas_asset,
+				refund_amount,
+				refund_address,
+				None,
+			),
+			refund_amount,
+		))
+	}
+}
+
+impl<T: Config<I>, I: 'static> SetMinimumDeposit<T::TargetChain> for Pallet<T, I> {
+	fn set_minimum_deposit(
+		asset: TargetChainAsset<T, I>,
+		minimum_deposit: TargetChainAmount<T, I>,
+	) {
+		MinimumDeposit::<T, I>::insert(asset, minimum_deposit);
+
+		Self::deposit_event(Event::<T, I>::MinimumDepositSet { asset, minimum_deposit });
+	}
+}
+
 impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 	type AccountId = T::AccountId;
 	// This should be callable by the LP pallet.
@@ -1014,7 +1148,7 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 		let (channel_id, deposit_address, expiry_block) =
 			Self::open_channel(source_asset, ChannelAction::LiquidityProvision { lp_account })?;
 
-		Ok((channel_id, deposit_address.into(), expiry_block))
+		Ok((channel_id, T::TargetChain::into_foreign_chain_address(deposit_address), expiry_block))
 	}
 
 	// This should only be callable by the broker.
@@ -1025,6 +1159,7 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 		broker_commission_bps: BasisPoints,
 		broker_id: T::AccountId,
 		channel_metadata: Option<CcmChannelMetadata>,
+		refund_parameters: Option<SwapRefundParameters>,
 	) -> Result<
 		(ChannelId, ForeignChainAddress, <T::TargetChain as Chain>::ChainBlockNumber),
 		DispatchError,
@@ -1036,16 +1171,18 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 					destination_asset,
 					destination_address,
 					channel_metadata: msg,
+					refund_address: refund_parameters.map(|params| params.refund_address),
 				},
 				None => ChannelAction::Swap {
 					destination_asset,
 					destination_address,
 					broker_commission_bps,
 					broker_id,
+					refund_parameters,
 				},
 			},
 		)?;
 
-		Ok((channel_id, deposit_address.into(), expiry_height))
+		Ok((channel_id, T::TargetChain::into_foreign_chain_address(deposit_address), expiry_height))
 	}
 }
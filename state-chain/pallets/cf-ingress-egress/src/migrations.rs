@@ -1,6 +1,13 @@
+pub mod add_gas_refund_address;
+pub mod add_refund_parameters;
 pub mod ingress_expiry;
+pub mod transaction_cost_estimates;
 
 use cf_runtime_upgrade_utilities::VersionedMigration;
 
-pub type PalletMigration<T, I> =
-	(VersionedMigration<crate::Pallet<T, I>, ingress_expiry::Migration<T, I>, 0, 1>,);
+pub type PalletMigration<T, I> = (
+	VersionedMigration<crate::Pallet<T, I>, ingress_expiry::Migration<T, I>, 0, 1>,
+	VersionedMigration<crate::Pallet<T, I>, add_refund_parameters::Migration<T, I>, 1, 2>,
+	VersionedMigration<crate::Pallet<T, I>, transaction_cost_estimates::Migration<T, I>, 2, 3>,
+	VersionedMigration<crate::Pallet<T, I>, add_gas_refund_address::Migration<T, I>, 3, 4>,
+);
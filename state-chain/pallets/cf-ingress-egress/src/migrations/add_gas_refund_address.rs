@@ -0,0 +1,65 @@
+use crate::*;
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::marker::PhantomData;
+
+pub struct Migration<T: Config<I>, I: 'static>(PhantomData<(T, I)>);
+
+mod old {
+	use super::*;
+
+	#[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo)]
+	pub struct CrossChainMessage<C: Chain> {
+		pub egress_id: EgressId,
+		pub asset: C::ChainAsset,
+		pub amount: C::ChainAmount,
+		pub destination_address: C::ChainAccount,
+		pub message: CcmMessage,
+		pub source_chain: ForeignChain,
+		pub source_address: Option<ForeignChainAddress>,
+		pub cf_parameters: CcmCfParameters,
+		pub gas_budget: C::ChainAmount,
+	}
+}
+
+impl<C: Chain> From<old::CrossChainMessage<C>> for CrossChainMessage<C> {
+	fn from(old: old::CrossChainMessage<C>) -> Self {
+		CrossChainMessage {
+			egress_id: old.egress_id,
+			asset: old.asset,
+			amount: old.amount,
+			destination_address: old.destination_address,
+			message: old.message,
+			source_chain: old.source_chain,
+			source_address: old.source_address,
+			cf_parameters: old.cf_parameters,
+			gas_budget: old.gas_budget,
+			gas_refund_address: None,
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		let _ = ScheduledEgressCcm::<T, I>::translate::<
+			Vec<old::CrossChainMessage<T::TargetChain>>,
+			_,
+		>(|maybe_old| maybe_old.map(|old| old.into_iter().map(Into::into).collect()));
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((ScheduledEgressCcm::<T, I>::decode_len().unwrap_or_default() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let number_of_ccms_pre_migration = <u32>::decode(&mut &state[..]).unwrap();
+		ensure!(
+			ScheduledEgressCcm::<T, I>::get().len() as u32 == number_of_ccms_pre_migration,
+			"ScheduledEgressCcm migration failed."
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,101 @@
+use crate::*;
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::marker::PhantomData;
+
+pub struct Migration<T: Config<I>, I: 'static>(PhantomData<(T, I)>);
+
+mod old {
+
+	use super::*;
+
+	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub enum ChannelAction<AccountId> {
+		Swap {
+			destination_asset: Asset,
+			destination_address: ForeignChainAddress,
+			broker_id: AccountId,
+			broker_commission_bps: BasisPoints,
+		},
+		LiquidityProvision {
+			lp_account: AccountId,
+		},
+		CcmTransfer {
+			destination_asset: Asset,
+			destination_address: ForeignChainAddress,
+			channel_metadata: CcmChannelMetadata,
+		},
+	}
+
+	#[derive(CloneNoBound, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct DepositChannelDetails<T: Config<I>, I: 'static> {
+		pub deposit_channel: DepositChannel<T::TargetChain>,
+		pub opened_at: TargetChainBlockNumber<T, I>,
+		pub expires_at: TargetChainBlockNumber<T, I>,
+		pub action: ChannelAction<T::AccountId>,
+	}
+}
+
+impl<AccountId> From<old::ChannelAction<AccountId>> for ChannelAction<AccountId> {
+	fn from(old: old::ChannelAction<AccountId>) -> Self {
+		match old {
+			old::ChannelAction::Swap {
+				destination_asset,
+				destination_address,
+				broker_id,
+				broker_commission_bps,
+			} => ChannelAction::Swap {
+				destination_asset,
+				destination_address,
+				broker_id,
+				broker_commission_bps,
+				refund_parameters: None,
+			},
+			old::ChannelAction::LiquidityProvision { lp_account } =>
+				ChannelAction::LiquidityProvision { lp_account },
+			old::ChannelAction::CcmTransfer {
+				destination_asset,
+				destination_address,
+				channel_metadata,
+			} => ChannelAction::CcmTransfer {
+				destination_asset,
+				destination_address,
+				channel_metadata,
+				refund_address: None,
+			},
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		DepositChannelLookup::<T, I>::translate_values::<old::DepositChannelDetails<T, I>, _>(
+			|old| {
+				Some(DepositChannelDetails {
+					deposit_channel: old.deposit_channel,
+					opened_at: old.opened_at,
+					expires_at: old.expires_at,
+					action: old.action.into(),
+				})
+			},
+		);
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((DepositChannelLookup::<T, I>::iter_keys().count() as u32).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let number_of_channels_pre_migration = <u32>::decode(&mut &state[..]).unwrap();
+		ensure!(
+			DepositChannelLookup::<T, I>::iter_values().count() as u32 ==
+				number_of_channels_pre_migration,
+			"DepositChannelLookup migration failed."
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,76 @@
+use crate::*;
+use cf_chains::dot::xcm::ASSET_HUB_EXECUTION_FEE;
+use cf_primitives::{INPUT_UTXO_SIZE_IN_BYTES, OUTPUT_UTXO_SIZE_IN_BYTES};
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::marker::PhantomData;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::dispatch::DispatchError;
+
+/// Polkadot's inclusion fee for a balance transfer, excluding the tip.
+const POLKADOT_TRANSFER_FEE: u64 = 200_000_000;
+
+/// The initial cost estimates of each asset. These can be adjusted by governance.
+pub fn initial_transaction_cost_estimate(asset: Asset) -> TransactionCostEstimate {
+	let (ingress, egress) = match asset {
+		Asset::Eth => (40_000, 21_000),
+		Asset::Flip | Asset::Usdc => (60_000, 65_000),
+		// Arbitrum gas includes the cost of posting the transaction data to L1.
+		Asset::ArbEth => (120_000, 100_000),
+		Asset::ArbUsdc => (170_000, 150_000),
+		Asset::Dot => (POLKADOT_TRANSFER_FEE, POLKADOT_TRANSFER_FEE),
+		Asset::DotUsdt => (
+			POLKADOT_TRANSFER_FEE + ASSET_HUB_EXECUTION_FEE as u64,
+			POLKADOT_TRANSFER_FEE + ASSET_HUB_EXECUTION_FEE as u64,
+		),
+		Asset::Btc => (INPUT_UTXO_SIZE_IN_BYTES, OUTPUT_UTXO_SIZE_IN_BYTES),
+	};
+	TransactionCostEstimate { ingress, egress }
+}
+
+/// The initial cost estimates of all assets of the given chain.
+pub fn initial_transaction_cost_estimates<A: TryFrom<Asset>>(
+	chain: ForeignChain,
+) -> Vec<(A, TransactionCostEstimate)> {
+	Asset::all()
+		.into_iter()
+		.filter(|asset| ForeignChain::from(*asset) == chain)
+		.filter_map(|asset| {
+			Some((asset.try_into().ok()?, initial_transaction_cost_estimate(asset)))
+		})
+		.collect()
+}
+
+pub struct Migration<T: Config<I>, I: 'static>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> OnRuntimeUpgrade for Migration<T, I>
+where
+	TargetChainAsset<T, I>: TryFrom<Asset, Error = AssetError>,
+{
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		let estimates =
+			initial_transaction_cost_estimates::<TargetChainAsset<T, I>>(T::TargetChain::get());
+		let writes = estimates.len() as u64;
+		for (asset, estimate) in estimates {
+			TransactionCostEstimates::<T, I>::insert(asset, estimate);
+		}
+
+		T::DbWeight::get().writes(writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok(Default::default())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		ensure!(
+			initial_transaction_cost_estimates::<TargetChainAsset<T, I>>(T::TargetChain::get())
+				.into_iter()
+				.all(|(asset, _)| TransactionCostEstimates::<T, I>::contains_key(asset)),
+			"TransactionCostEstimates migration failed."
+		);
+		Ok(())
+	}
+}
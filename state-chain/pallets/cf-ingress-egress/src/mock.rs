@@ -8,7 +8,7 @@ pub use cf_chains::{
 };
 use cf_primitives::ChannelId;
 pub use cf_primitives::{
-	chains::{assets, Ethereum},
+	chains::{assets, AnyChain, Ethereum},
 	Asset, AssetAmount,
 };
 use cf_test_utilities::{impl_test_helpers, TestExternalities};
@@ -20,6 +20,7 @@ use cf_traits::{
 		block_height_provider::BlockHeightProvider,
 		broadcaster::MockBroadcaster,
 		ccm_handler::MockCcmHandler,
+		egress_handler::MockEgressHandler,
 		lp_balance::MockBalance,
 		swap_deposit_handler::MockSwapDepositHandler,
 	},
@@ -110,6 +111,7 @@ impl crate::Config for Test {
 	type DepositHandler = MockDepositHandler;
 	type CcmHandler = MockCcmHandler;
 	type ChainTracking = BlockHeightProvider<Ethereum>;
+	type EgressFeeEstimator = MockEgressHandler<AnyChain>;
 	type WeightInfo = ();
 }
 
@@ -120,7 +122,10 @@ impl_test_helpers! {
 	Test,
 	RuntimeGenesisConfig {
 		system: Default::default(),
-		ingress_egress: IngressEgressConfig { deposit_channel_lifetime: 100 },
+		ingress_egress: IngressEgressConfig {
+			deposit_channel_lifetime: 100,
+			transaction_cost_estimates: Default::default(),
+		},
 	}
 }
 
@@ -227,6 +232,7 @@ impl<Ctx: Clone> RequestAddress for TestExternalities<Test, Ctx> {
 						Default::default(),
 						BROKER,
 						None,
+						None,
 					)
 					.map(|(channel_id, deposit_address, ..)| {
 						(request, channel_id, TestChainAccount::try_from(deposit_address).unwrap())
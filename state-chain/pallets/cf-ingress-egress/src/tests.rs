@@ -2,18 +2,21 @@ use crate::{
 	mock::*, Call as PalletCall, ChannelAction, ChannelIdCounter, CrossChainMessage,
 	DepositChannelLookup, DepositChannelPool, DepositWitness, DisabledEgressAssets,
 	Event as PalletEvent, FailedVaultTransfers, FetchOrTransfer, MinimumDeposit, Pallet,
-	ScheduledEgressCcm, ScheduledEgressFetchOrTransfer, TargetChainAccount, VaultTransfer,
+	ScheduledEgressCcm, ScheduledEgressFetchOrTransfer, TargetChainAccount,
+	TransactionCostEstimates, VaultTransfer,
 };
 use cf_chains::{
 	address::AddressConverter, evm::EvmFetchId, mocks::MockEthereum, CcmChannelMetadata,
-	DepositChannel, ExecutexSwapAndCall, SwapOrigin, TransferAssetParams,
+	DepositChannel, ExecutexSwapAndCall, SwapOrigin, TransactionCostEstimate, TransferAssetParams,
 };
 use cf_primitives::{chains::assets::eth, ChannelId, ForeignChain};
 use cf_test_utilities::assert_has_event;
 use cf_traits::{
 	mocks::{
 		address_converter::MockAddressConverter,
-		api_call::{MockAllBatch, MockEthEnvironment, MockEthereumApiCall},
+		api_call::{
+			MockAllBatch, MockEthEnvironment, MockEthereumApiCall, MockExecutexSwapAndCall,
+		},
 		block_height_provider::BlockHeightProvider,
 		ccm_handler::{CcmRequest, MockCcmHandler},
 	},
@@ -107,13 +110,13 @@ fn blacklisted_asset_will_not_egress_via_ccm() {
 			asset,
 			1_000,
 			ALICE_ETH_ADDRESS,
-			Some((ccm.clone(), gas_budget)),
+			Some((ccm.clone(), gas_budget, None)),
 		);
 		IngressEgress::schedule_egress(
 			ETH_FLIP,
 			1_000,
 			ALICE_ETH_ADDRESS,
-			Some((ccm.clone(), gas_budget)),
+			Some((ccm.clone(), gas_budget, None)),
 		);
 
 		IngressEgress::on_finalize(1);
@@ -131,6 +134,7 @@ fn blacklisted_asset_will_not_egress_via_ccm() {
 				source_address: ccm.source_address.clone(),
 				cf_parameters: ccm.channel_metadata.cf_parameters,
 				gas_budget,
+				gas_refund_address: None,
 			}]
 		);
 
@@ -290,6 +294,36 @@ fn on_finalize_can_send_batch_all() {
 	});
 }
 
+#[test]
+fn transfers_beyond_the_batch_limit_are_sent_in_the_next_batch() {
+	new_test_ext().execute_with(|| {
+		MockAllBatch::<MockEthEnvironment>::set_max_transfers_per_batch(Some(2));
+		IngressEgress::schedule_egress(ETH_ETH, 1_000, ALICE_ETH_ADDRESS, None);
+		IngressEgress::schedule_egress(ETH_ETH, 2_000, ALICE_ETH_ADDRESS, None);
+		IngressEgress::schedule_egress(ETH_ETH, 3_000, BOB_ETH_ADDRESS, None);
+
+		IngressEgress::on_finalize(1);
+
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			crate::Event::BatchBroadcastRequested {
+				broadcast_id: 1,
+				egress_ids: vec![(ForeignChain::Ethereum, 1), (ForeignChain::Ethereum, 2)],
+			},
+		));
+		assert_eq!(ScheduledEgressFetchOrTransfer::<Test>::decode_len(), Some(1));
+
+		IngressEgress::on_finalize(2);
+
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			crate::Event::BatchBroadcastRequested {
+				broadcast_id: 2,
+				egress_ids: vec![(ForeignChain::Ethereum, 3)],
+			},
+		));
+		assert!(ScheduledEgressFetchOrTransfer::<Test>::get().is_empty());
+	});
+}
+
 #[test]
 fn all_batch_apicall_creation_failure_should_rollback_storage() {
 	new_test_ext().execute_with(|| {
@@ -468,6 +502,7 @@ fn can_process_ccm_deposit() {
 			0,
 			1,
 			Some(channel_metadata),
+			None,
 		)
 		.unwrap();
 
@@ -500,7 +535,8 @@ fn can_process_ccm_deposit() {
 					),
 					channel_id: 1,
 					deposit_block_height: Default::default()
-				}
+				},
+				refund_address: None,
 			}]
 		);
 	});
@@ -526,7 +562,7 @@ fn can_egress_ccm() {
 			destination_asset,
 			amount,
 			destination_address,
-			Some((ccm.clone(), gas_budget))
+			Some((ccm.clone(), gas_budget, None))
 		);
 
 		assert!(ScheduledEgressFetchOrTransfer::<Test>::get().is_empty());
@@ -541,6 +577,7 @@ fn can_egress_ccm() {
 				source_chain: ForeignChain::Ethereum,
 				source_address: Some(ForeignChainAddress::Eth([0xcf; 20].into())),
 				gas_budget,
+				gas_refund_address: None,
 			}
 		]);
 		System::assert_last_event(RuntimeEvent::IngressEgress(
@@ -569,11 +606,84 @@ fn can_egress_ccm() {
 			ccm.channel_metadata.message.to_vec(),
 		).unwrap()]);
 
+		// Unused gas is refunded to the source address, since it's on the target chain.
+		assert_eq!(
+			MockEgressBroadcaster::get_gas_refund::<Ethereum>(1),
+			Some((gas_budget, [0xcf; 20].into()))
+		);
+
 		// Storage should be cleared
 		assert_eq!(ScheduledEgressCcm::<Test>::decode_len(), Some(0));
 	});
 }
 
+#[test]
+fn unused_gas_is_refunded_to_the_designated_refund_address_on_the_target_chain() {
+	new_test_ext().execute_with(|| {
+		let gas_budget = 1_000u128;
+		let ccm = CcmDepositMetadata {
+			source_chain: ForeignChain::Ethereum,
+			source_address: Some(ForeignChainAddress::Eth([0xcf; 20].into())),
+			channel_metadata: CcmChannelMetadata {
+				message: vec![0x00, 0x01, 0x02].try_into().unwrap(),
+				gas_budget,
+				cf_parameters: vec![].try_into().unwrap(),
+			},
+		};
+
+		// A refund address on another chain can't receive the gas, so the source address does.
+		IngressEgress::schedule_egress(
+			ETH_ETH,
+			5_000,
+			ALICE_ETH_ADDRESS,
+			Some((ccm.clone(), gas_budget, Some(ForeignChainAddress::Dot(Default::default())))),
+		);
+		IngressEgress::schedule_egress(
+			ETH_ETH,
+			5_000,
+			ALICE_ETH_ADDRESS,
+			Some((ccm, gas_budget, Some(ForeignChainAddress::Eth(BOB_ETH_ADDRESS)))),
+		);
+		IngressEgress::on_finalize(1);
+
+		assert_eq!(
+			MockEgressBroadcaster::get_gas_refund::<Ethereum>(1),
+			Some((gas_budget, [0xcf; 20].into()))
+		);
+		assert_eq!(
+			MockEgressBroadcaster::get_gas_refund::<Ethereum>(2),
+			Some((gas_budget, BOB_ETH_ADDRESS))
+		);
+	});
+}
+
+#[test]
+fn ccm_is_retried_while_egress_is_unavailable() {
+	new_test_ext().execute_with(|| {
+		let ccm = CcmDepositMetadata {
+			source_chain: ForeignChain::Ethereum,
+			source_address: None,
+			channel_metadata: CcmChannelMetadata {
+				message: vec![0x00, 0x01, 0x02].try_into().unwrap(),
+				gas_budget: 0,
+				cf_parameters: vec![].try_into().unwrap(),
+			},
+		};
+		IngressEgress::schedule_egress(ETH_ETH, 5_000, ALICE_ETH_ADDRESS, Some((ccm, 0, None)));
+
+		// The message stays scheduled while the call can't be built.
+		MockExecutexSwapAndCall::<MockEthEnvironment>::set_available(false);
+		IngressEgress::on_finalize(1);
+		assert!(MockEgressBroadcaster::get_pending_api_calls().is_empty());
+		assert_eq!(ScheduledEgressCcm::<Test>::decode_len(), Some(1));
+
+		MockExecutexSwapAndCall::<MockEthEnvironment>::set_available(true);
+		IngressEgress::on_finalize(2);
+		assert_eq!(MockEgressBroadcaster::get_pending_api_calls().len(), 1);
+		assert_eq!(ScheduledEgressCcm::<Test>::decode_len(), Some(0));
+	});
+}
+
 #[test]
 fn multi_deposit_includes_deposit_beyond_recycle_height() {
 	const ETH: eth::Asset = eth::Asset::Eth;
@@ -852,6 +962,32 @@ fn can_set_minimum_deposit() {
 	});
 }
 
+#[test]
+fn can_set_transaction_cost_estimate() {
+	new_test_ext().execute_with(|| {
+		let asset = eth::Asset::Flip;
+		let estimate = TransactionCostEstimate { ingress: 60_000, egress: 65_000 };
+		assert_eq!(TransactionCostEstimates::<Test>::get(asset), None);
+
+		assert!(IngressEgress::set_transaction_cost_estimate(
+			RuntimeOrigin::signed(ALICE),
+			asset,
+			estimate
+		)
+		.is_err());
+		assert_ok!(IngressEgress::set_transaction_cost_estimate(
+			RuntimeOrigin::root(),
+			asset,
+			estimate
+		));
+
+		assert_eq!(TransactionCostEstimates::<Test>::get(asset), Some(estimate));
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			crate::Event::<Test>::TransactionCostEstimateSet { asset, estimate },
+		));
+	});
+}
+
 #[test]
 fn deposits_below_minimum_are_rejected() {
 	new_test_ext().execute_with(|| {
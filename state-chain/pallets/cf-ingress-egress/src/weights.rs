@@ -37,6 +37,7 @@ pub trait WeightInfo {
 	fn set_minimum_deposit() -> Weight;
 	fn finalise_ingress(a: u32, ) -> Weight;
 	fn vault_transfer_failed() -> Weight;
+	fn set_transaction_cost_estimate() -> Weight;
 }
 
 /// Weights for pallet_cf_ingress_egress using the Substrate node and recommended hardware.
@@ -109,6 +110,16 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `EthereumIngressEgress::TransactionCostEstimates` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::TransactionCostEstimates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_transaction_cost_estimate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 10_211_000 picoseconds.
+		Weight::from_parts(10_634_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -180,4 +191,14 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `EthereumIngressEgress::TransactionCostEstimates` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::TransactionCostEstimates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_transaction_cost_estimate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 10_211_000 picoseconds.
+		Weight::from_parts(10_634_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
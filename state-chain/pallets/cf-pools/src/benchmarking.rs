@@ -28,6 +28,55 @@ fn new_lp_account<T: Chainflip + Config>() -> T::AccountId {
 	caller
 }
 
+/// Schedules the expiry of the given order in a full block, and fills the block of its new expiry
+/// up to the limit, which is the worst case for replacing the order's expiry.
+fn setup_limit_order_expiries<T: Config>(
+	key: LimitOrderKey<T::AccountId>,
+	expiry: BlockNumberFor<T>,
+	new_expiry: BlockNumberFor<T>,
+) {
+	let other_keys = || {
+		(1..T::MaxExpiredLimitOrdersPerBlock::get() as u64)
+			.map(|id| LimitOrderKey { id, ..key.clone() })
+			.collect::<Vec<_>>()
+	};
+	let mut keys = other_keys();
+	keys.push(key.clone());
+	LimitOrderExpiries::<T>::insert(expiry, BoundedVec::truncate_from(keys));
+	LimitOrderExpiryBlocks::<T>::insert(&key, expiry);
+	LimitOrderExpiries::<T>::insert(new_expiry, BoundedVec::truncate_from(other_keys()));
+}
+
+/// Gives the Eth pool a price and records an observation of it, then moves to the next block, so
+/// that the next change to the pool records a new observation in the worst case.
+fn setup_pool_with_observation<T: Chainflip + Config>(lp: &T::AccountId) {
+	assert_ok!(Pallet::<T>::new_pool(
+		T::EnsureGovernance::try_successful_origin().unwrap(),
+		Asset::Eth,
+		Asset::Usdc,
+		0,
+		price_at_tick(0).unwrap()
+	));
+	for asset in [Asset::Eth, Asset::Usdc] {
+		assert_ok!(T::LpBalance::try_credit_account(lp, asset, 1_000_000_000));
+	}
+	assert_ok!(Pallet::<T>::set_range_order(
+		RawOrigin::Signed(lp.clone()).into(),
+		Asset::Eth,
+		Asset::Usdc,
+		u64::MAX,
+		Some(-1_000..1_000),
+		RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+	));
+	let canonical_asset_pair = CanonicalAssetPair::<T>::new(Asset::Eth, Asset::Usdc).unwrap();
+	Pools::<T>::mutate(canonical_asset_pair, |pool| {
+		Pallet::<T>::record_observation(canonical_asset_pair, pool.as_mut().unwrap())
+	});
+	frame_system::Pallet::<T>::set_block_number(
+		frame_system::Pallet::<T>::block_number() + One::one(),
+	);
+}
+
 benchmarks! {
 	update_buy_interval {
 		let call = Call::<T>::update_buy_interval{
@@ -68,7 +117,7 @@ benchmarks! {
 
 	update_range_order {
 		let caller = new_lp_account::<T>();
-		assert_ok!(Pallet::<T>::new_pool(T::EnsureGovernance::try_successful_origin().unwrap(), Asset::Eth, Asset::Usdc, 0, price_at_tick(0).unwrap()));
+		setup_pool_with_observation::<T>(&caller);
 		assert_ok!(T::LpBalance::try_credit_account(
 			&caller,
 			Asset::Eth,
@@ -102,7 +151,7 @@ benchmarks! {
 
 	set_range_order {
 		let caller = new_lp_account::<T>();
-		assert_ok!(Pallet::<T>::new_pool(T::EnsureGovernance::try_successful_origin().unwrap(), Asset::Eth, Asset::Usdc, 0, price_at_tick(0).unwrap()));
+		setup_pool_with_observation::<T>(&caller);
 		assert_ok!(T::LpBalance::try_credit_account(
 			&caller,
 			Asset::Eth,
@@ -134,7 +183,7 @@ benchmarks! {
 
 	update_limit_order {
 		let caller = new_lp_account::<T>();
-		assert_ok!(Pallet::<T>::new_pool(T::EnsureGovernance::try_successful_origin().unwrap(), Asset::Eth, Asset::Usdc, 0, price_at_tick(0).unwrap()));
+		setup_pool_with_observation::<T>(&caller);
 		assert_ok!(T::LpBalance::try_credit_account(
 			&caller,
 			Asset::Eth,
@@ -145,19 +194,25 @@ benchmarks! {
 			Asset::Usdc,
 			1_000_000,
 		));
+		setup_limit_order_expiries::<T>(
+			LimitOrderKey { lp: caller.clone(), sell_asset: Asset::Eth, buy_asset: Asset::Usdc, id: 0 },
+			BlockNumberFor::<T>::from(500u32),
+			BlockNumberFor::<T>::from(1_000u32),
+		);
 	}: _(
 		RawOrigin::Signed(caller.clone()),
 		Asset::Eth,
 		Asset::Usdc,
 		0,
 		Some(100),
-		IncreaseOrDecrease::Increase(1_000_000)
+		IncreaseOrDecrease::Increase(1_000_000),
+		Some(BlockNumberFor::<T>::from(1_000u32))
 	)
 	verify {}
 
 	set_limit_order {
 		let caller = new_lp_account::<T>();
-		assert_ok!(Pallet::<T>::new_pool(T::EnsureGovernance::try_successful_origin().unwrap(), Asset::Eth, Asset::Usdc, 0, price_at_tick(0).unwrap()));
+		setup_pool_with_observation::<T>(&caller);
 		assert_ok!(T::LpBalance::try_credit_account(
 			&caller,
 			Asset::Eth,
@@ -168,13 +223,19 @@ benchmarks! {
 			Asset::Usdc,
 			1_000_000,
 		));
+		setup_limit_order_expiries::<T>(
+			LimitOrderKey { lp: caller.clone(), sell_asset: Asset::Eth, buy_asset: Asset::Usdc, id: 0 },
+			BlockNumberFor::<T>::from(500u32),
+			BlockNumberFor::<T>::from(1_000u32),
+		);
 	}: _(
 		RawOrigin::Signed(caller.clone()),
 		Asset::Eth,
 		Asset::Usdc,
 		0,
 		Some(100),
-		1_000
+		1_000,
+		Some(BlockNumberFor::<T>::from(1_000u32))
 	)
 	verify {}
 
@@ -198,6 +259,7 @@ benchmarks! {
 			0,
 			Some(0),
 			10_000,
+			None,
 		));
 		assert_ok!(Pallet::<T>::set_limit_order(
 			RawOrigin::Signed(caller.clone()).into(),
@@ -206,6 +268,7 @@ benchmarks! {
 			1,
 			Some(0),
 			10_000,
+			None,
 		));
 		assert_ok!(Pallet::<T>::swap_with_network_fee(STABLE_ASSET, Asset::Eth, 1_000));
 		let fee = 1_000;
@@ -225,6 +288,46 @@ benchmarks! {
 		);
 	}
 
+	swap_single_leg {
+		setup_pool_with_observation::<T>(&new_lp_account::<T>());
+	}: {
+		assert_ok!(Pallet::<T>::swap_single_leg(Asset::Usdc, Asset::Eth, 1_000));
+	}
+	verify {
+		let canonical_asset_pair = CanonicalAssetPair::<T>::new(Asset::Eth, Asset::Usdc).unwrap();
+		assert_eq!(PoolObservationCursors::<T>::get(canonical_asset_pair).unwrap().len, 2);
+	}
+
+	update_orders {
+		let r in 0 .. T::MaxOrdersPerBatch::get() / 2;
+		let l in 0 .. T::MaxOrdersPerBatch::get() / 2;
+		let caller = new_lp_account::<T>();
+		setup_pool_with_observation::<T>(&caller);
+		let orders = (0..r)
+			.map(|id| OrderUpdate::SetRangeOrder {
+				base_asset: Asset::Eth,
+				pair_asset: Asset::Usdc,
+				id: id.into(),
+				option_tick_range: Some(-100..100),
+				size: RangeOrderSize::Liquidity { liquidity: 1_000 },
+			})
+			.chain((0..l).map(|id| OrderUpdate::SetLimitOrder {
+				sell_asset: Asset::Eth,
+				buy_asset: Asset::Usdc,
+				id: id.into(),
+				option_tick: Some(100),
+				sell_amount: 1_000,
+				expiry: Some(BlockNumberFor::<T>::from(1_000u32 + id)),
+			}))
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller.clone()), BoundedVec::try_from(orders).unwrap())
+	verify {
+		let orders = Pallet::<T>::pool_orders(Asset::Eth, Asset::Usdc, &caller).unwrap();
+		// The setup adds a range order of its own.
+		assert_eq!(orders.range_orders.len() as u32, r + 1);
+		assert_eq!(orders.limit_orders.base.len() as u32, l);
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext(),
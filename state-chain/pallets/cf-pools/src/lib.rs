@@ -2,7 +2,10 @@
 use core::ops::Range;
 
 use cf_amm::{
-	common::{Amount, Order, Price, Side, SideMap, Tick},
+	common::{
+		mul_div_floor, price_at_tick, tick_at_price, Amount, Order, Price, Side, SideMap, Tick,
+		PRICE_FRACTIONAL_BITS,
+	},
 	limit_orders,
 	limit_orders::{Collected, PositionInfo},
 	range_orders,
@@ -14,12 +17,13 @@ use cf_traits::{impl_pallet_safe_mode, Chainflip, LpBalanceApi, PoolApi, Swappin
 use frame_support::{
 	pallet_prelude::*,
 	sp_runtime::{Permill, Saturating},
+	storage::{with_transaction, TransactionOutcome},
 	transactional,
 };
 use frame_system::pallet_prelude::OriginFor;
 use serde::{Deserialize, Serialize};
-use sp_arithmetic::traits::Zero;
-use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+use sp_arithmetic::traits::{One, UniqueSaturatedInto, Zero};
+use sp_std::{collections::btree_set::BTreeSet, vec, vec::Vec};
 
 pub use pallet::*;
 
@@ -33,7 +37,15 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-impl_pallet_safe_mode!(PalletSafeMode; range_order_update_enabled, limit_order_update_enabled);
+impl_pallet_safe_mode!(
+	PalletSafeMode;
+	range_order_update_enabled,
+	limit_order_update_enabled,
+	cancel_all_orders_enabled,
+);
+
+/// The number of blocks searched for room when rescheduling the expiry of a limit order.
+pub const MAX_LIMIT_ORDER_EXPIRY_RESCHEDULE_BLOCKS: u32 = 600;
 
 /// For referring to either the stable or unstable asset of a particular pool
 enum Stability {
@@ -237,6 +249,15 @@ pub mod pallet {
 
 	pub type OrderId = u64;
 
+	/// Identifies a limit order, for scheduling its expiry.
+	#[derive(Clone, Debug, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq)]
+	pub struct LimitOrderKey<AccountId> {
+		pub lp: AccountId,
+		pub sell_asset: Asset,
+		pub buy_asset: Asset,
+		pub id: OrderId,
+	}
+
 	#[derive(
 		Copy,
 		Clone,
@@ -319,6 +340,54 @@ pub mod pallet {
 		Liquidity { liquidity: Liquidity },
 	}
 
+	/// An update to a single order, as applied by the corresponding order call.
+	#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Deserialize, Serialize)]
+	#[serde(rename_all = "snake_case")]
+	pub enum OrderUpdate<BlockNumber> {
+		UpdateRangeOrder {
+			base_asset: Asset,
+			pair_asset: Asset,
+			id: OrderId,
+			option_tick_range: Option<core::ops::Range<Tick>>,
+			size_change: IncreaseOrDecrease<RangeOrderSize>,
+		},
+		SetRangeOrder {
+			base_asset: Asset,
+			pair_asset: Asset,
+			id: OrderId,
+			option_tick_range: Option<core::ops::Range<Tick>>,
+			size: RangeOrderSize,
+		},
+		UpdateLimitOrder {
+			sell_asset: Asset,
+			buy_asset: Asset,
+			id: OrderId,
+			option_tick: Option<Tick>,
+			amount_change: IncreaseOrDecrease<AssetAmount>,
+			expiry: Option<BlockNumber>,
+		},
+		SetLimitOrder {
+			sell_asset: Asset,
+			buy_asset: Asset,
+			id: OrderId,
+			option_tick: Option<Tick>,
+			sell_amount: AssetAmount,
+			expiry: Option<BlockNumber>,
+		},
+	}
+
+	impl<BlockNumber> OrderUpdate<BlockNumber> {
+		fn is_range_order(&self) -> bool {
+			matches!(self, OrderUpdate::UpdateRangeOrder { .. } | OrderUpdate::SetRangeOrder { .. })
+		}
+
+		/// The weight of applying the given updates in a single call.
+		pub fn batch_weight<W: WeightInfo>(orders: &[Self]) -> Weight {
+			let range_orders = orders.iter().filter(|order| order.is_range_order()).count();
+			W::update_orders(range_orders as u32, (orders.len() - range_orders) as u32)
+		}
+	}
+
 	/// Indicates the change caused by an operation in the positions size, both in terms of
 	/// liquidity and equivalently in asset amounts
 	#[derive(
@@ -400,6 +469,20 @@ pub mod pallet {
 		/// Safe Mode access.
 		type SafeMode: Get<PalletSafeMode>;
 
+		/// The number of price observations kept for each pool. This bounds the longest window
+		/// over which a time-weighted average price can be calculated.
+		#[pallet::constant]
+		type ObservationCapacity: Get<u32>;
+
+		/// The maximum number of limit orders that can expire in a single block. This also bounds
+		/// the number of expired orders burned, and of expiry blocks processed, per block.
+		#[pallet::constant]
+		type MaxExpiredLimitOrdersPerBlock: Get<u32>;
+
+		/// The maximum number of order updates in a single `update_orders` call.
+		#[pallet::constant]
+		type MaxOrdersPerBatch: Get<u32>;
+
 		/// Benchmark weights
 		type WeightInfo: WeightInfo;
 	}
@@ -425,6 +508,67 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type CollectedNetworkFee<T: Config> = StorageValue<_, AssetAmount, ValueQuery>;
 
+	/// Ring buffer of price observations for each pool, used to calculate time-weighted average
+	/// prices.
+	#[pallet::storage]
+	pub type PoolObservations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CanonicalAssetPair<T>,
+		Twox64Concat,
+		u32,
+		Observation<BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	/// The position of the latest observation in each pool's ring buffer.
+	#[pallet::storage]
+	pub type PoolObservationCursors<T: Config> =
+		StorageMap<_, Twox64Concat, CanonicalAssetPair<T>, ObservationCursor, OptionQuery>;
+
+	/// Limit orders that will be burned at the start of the given block.
+	#[pallet::storage]
+	pub type LimitOrderExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<LimitOrderKey<T::AccountId>, T::MaxExpiredLimitOrdersPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The earliest block whose limit order expiries have not been processed yet.
+	#[pallet::storage]
+	pub type LimitOrderExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	/// The block at which each limit order with an expiry will be burned.
+	#[pallet::storage]
+	pub type LimitOrderExpiryBlocks<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LimitOrderKey<T::AccountId>,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Cumulative statistics of the swaps through each pool, grouped by the side sold into the
+	/// pool.
+	#[pallet::storage]
+	pub type PoolSwapStats<T: Config> =
+		StorageMap<_, Twox64Concat, CanonicalAssetPair<T>, SideMap<SwapStats>, ValueQuery>;
+
+	/// Cumulative fees collected by each LP from its orders in each pool, grouped by the side the
+	/// fees were paid in.
+	#[pallet::storage]
+	pub type LpCollectedFees<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		CanonicalAssetPair<T>,
+		SideMap<Amount>,
+		ValueQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub flip_buy_interval: BlockNumberFor<T>,
@@ -472,6 +616,7 @@ pub mod pallet {
 					}
 				}
 			}
+			weight_used.saturating_accrue(Self::expire_limit_orders(current_block));
 			weight_used
 		}
 	}
@@ -515,6 +660,14 @@ pub mod pallet {
 		UpdatingLimitOrdersDisabled,
 		/// Updating Range Orders is disabled
 		UpdatingRangeOrdersDisabled,
+		/// Cancelling all orders is disabled
+		CancellingAllOrdersDisabled,
+		/// Not enough price observations have been recorded to cover the requested window.
+		InsufficientObservations,
+		/// The expiry block of a limit order must be in the future.
+		InvalidExpiryBlock,
+		/// The maximum number of limit orders already expire in the given block.
+		LimitOrderExpiryBlockFull,
 	}
 
 	#[pallet::event]
@@ -561,6 +714,14 @@ pub mod pallet {
 			collected_fees: AssetAmount,
 			bought_amount: AssetAmount,
 		},
+		/// A limit order reached its expiry block and was burned. The details of the burn are
+		/// given by the accompanying [LimitOrderUpdated](Event::LimitOrderUpdated) event.
+		LimitOrderExpired {
+			lp: T::AccountId,
+			sell_asset: Asset,
+			buy_asset: Asset,
+			id: OrderId,
+		},
 		NetworkFeeTaken {
 			fee_amount: AssetAmount,
 		},
@@ -713,72 +874,16 @@ pub mod pallet {
 			option_tick_range: Option<core::ops::Range<Tick>>,
 			size_change: IncreaseOrDecrease<RangeOrderSize>,
 		) -> DispatchResult {
-			ensure!(
-				T::SafeMode::get().range_order_update_enabled,
-				Error::<T>::UpdatingRangeOrdersDisabled
-			);
 			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
-			Self::try_mutate_order(&lp, base_asset, pair_asset, |asset_pair, pool| {
-				let tick_range = match (
-					pool.range_orders_cache
-						.get(&lp)
-						.and_then(|range_orders| range_orders.get(&id))
-						.cloned(),
-					option_tick_range,
-				) {
-					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
-					(None, Some(tick_range)) | (Some(tick_range), None) => Ok(tick_range),
-					(Some(previous_tick_range), Some(new_tick_range)) => {
-						if previous_tick_range != new_tick_range {
-							let withdrawn_asset_amounts = Self::inner_update_range_order(
-								pool,
-								&lp,
-								asset_pair,
-								id,
-								previous_tick_range,
-								IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
-									liquidity: Liquidity::MAX,
-								}),
-								/* allow_noop */ false,
-							)?;
-							Self::inner_update_range_order(
-								pool,
-								&lp,
-								asset_pair,
-								id,
-								new_tick_range.clone(),
-								IncreaseOrDecrease::Increase(range_orders::Size::Amount {
-									minimum: Default::default(),
-									maximum: asset_pair
-										.asset_amounts_to_side_map(withdrawn_asset_amounts),
-								}),
-								/* allow_noop */ true,
-							)?;
-						}
-
-						Ok(new_tick_range)
-					},
-				}?;
-				Self::inner_update_range_order(
-					pool,
-					&lp,
-					asset_pair,
-					id,
-					tick_range,
-					size_change.map(|size| match size {
-						RangeOrderSize::Liquidity { liquidity } =>
-							range_orders::Size::Liquidity { liquidity },
-						RangeOrderSize::AssetAmounts { maximum, minimum } =>
-							range_orders::Size::Amount {
-								maximum: asset_pair.asset_amounts_to_side_map(maximum),
-								minimum: asset_pair.asset_amounts_to_side_map(minimum),
-							},
-					}),
-					/* allow_noop */ false,
-				)?;
-
-				Ok(())
-			})
+			Self::ensure_range_order_updates_enabled()?;
+			Self::do_update_range_order(
+				&lp,
+				base_asset,
+				pair_asset,
+				id,
+				option_tick_range,
+				size_change,
+			)
 		}
 
 		/// Optionally move the order to a different range and then set its amount of liquidity. The
@@ -795,57 +900,9 @@ pub mod pallet {
 			option_tick_range: Option<core::ops::Range<Tick>>,
 			size: RangeOrderSize,
 		) -> DispatchResult {
-			ensure!(
-				T::SafeMode::get().range_order_update_enabled,
-				Error::<T>::UpdatingRangeOrdersDisabled
-			);
 			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
-			Self::try_mutate_order(&lp, base_asset, pair_asset, |asset_pair, pool| {
-				let tick_range = match (
-					pool.range_orders_cache
-						.get(&lp)
-						.and_then(|range_orders| range_orders.get(&id))
-						.cloned(),
-					option_tick_range,
-				) {
-					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
-					(None, Some(tick_range)) => Ok(tick_range),
-					(Some(previous_tick_range), option_new_tick_range) => {
-						Self::inner_update_range_order(
-							pool,
-							&lp,
-							asset_pair,
-							id,
-							previous_tick_range.clone(),
-							IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
-								liquidity: Liquidity::MAX,
-							}),
-							/* allow noop */ false,
-						)?;
-
-						Ok(option_new_tick_range.unwrap_or(previous_tick_range))
-					},
-				}?;
-				Self::inner_update_range_order(
-					pool,
-					&lp,
-					asset_pair,
-					id,
-					tick_range,
-					IncreaseOrDecrease::Increase(match size {
-						RangeOrderSize::Liquidity { liquidity } =>
-							range_orders::Size::Liquidity { liquidity },
-						RangeOrderSize::AssetAmounts { maximum, minimum } =>
-							range_orders::Size::Amount {
-								maximum: asset_pair.asset_amounts_to_side_map(maximum),
-								minimum: asset_pair.asset_amounts_to_side_map(minimum),
-							},
-					}),
-					/* allow noop */ true,
-				)?;
-
-				Ok(())
-			})
+			Self::ensure_range_order_updates_enabled()?;
+			Self::do_set_range_order(&lp, base_asset, pair_asset, id, option_tick_range, size)
 		}
 
 		/// Optionally move the order to a different tick and then increase or decrease its amount
@@ -855,7 +912,9 @@ pub mod pallet {
 		/// order_id's are independent of range order order_id's. In addition to that, order_id's
 		/// for buy and sell limit orders i.e. those in different directions are independent.
 		/// Therefore you may have two limit orders with the same order_id in the same pool, one to
-		/// buy Eth and one to sell Eth for example.
+		/// buy Eth and one to sell Eth for example. If an expiry block is given, the order will be
+		/// burned at the start of that block and its assets credited to your balance. The expiry
+		/// replaces any expiry previously set for the order, which is otherwise kept.
 		#[pallet::call_index(5)]
 		#[pallet::weight(T::WeightInfo::update_limit_order())]
 		pub fn update_limit_order(
@@ -865,59 +924,19 @@ pub mod pallet {
 			id: OrderId,
 			option_tick: Option<Tick>,
 			amount_change: IncreaseOrDecrease<AssetAmount>,
+			expiry: Option<BlockNumberFor<T>>,
 		) -> DispatchResult {
-			ensure!(
-				T::SafeMode::get().limit_order_update_enabled,
-				Error::<T>::UpdatingLimitOrdersDisabled
-			);
 			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
-			Self::try_mutate_order(&lp, sell_asset, buy_asset, |asset_pair, pool| {
-				let tick = match (
-					pool.limit_orders_cache[asset_pair.base_side]
-						.get(&lp)
-						.and_then(|limit_orders| limit_orders.get(&id))
-						.cloned(),
-					option_tick,
-				) {
-					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
-					(None, Some(tick)) | (Some(tick), None) => Ok(tick),
-					(Some(previous_tick), Some(new_tick)) => {
-						if previous_tick != new_tick {
-							let withdrawn_asset_amount = Self::inner_update_limit_order(
-								pool,
-								&lp,
-								asset_pair,
-								id,
-								previous_tick,
-								IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
-								/* allow_noop */ false,
-							)?;
-							Self::inner_update_limit_order(
-								pool,
-								&lp,
-								asset_pair,
-								id,
-								new_tick,
-								IncreaseOrDecrease::Increase(withdrawn_asset_amount.into()),
-								/* allow_noop */ true,
-							)?;
-						}
-
-						Ok(new_tick)
-					},
-				}?;
-				Self::inner_update_limit_order(
-					pool,
-					&lp,
-					asset_pair,
-					id,
-					tick,
-					amount_change.map(|amount| amount.into()),
-					/* allow_noop */ false,
-				)?;
-
-				Ok(())
-			})
+			Self::ensure_limit_order_updates_enabled()?;
+			Self::do_update_limit_order(
+				&lp,
+				sell_asset,
+				buy_asset,
+				id,
+				option_tick,
+				amount_change,
+				expiry,
+			)
 		}
 
 		/// Optionally move the order to a different tick and then set its amount of liquidity. The
@@ -927,7 +946,9 @@ pub mod pallet {
 		/// independent of range order order_id's. In addition to that, order_id's for buy and sell
 		/// limit orders i.e. those in different directions are independent. Therefore you may have
 		/// two limit orders with the same order_id in the same pool, one to buy Eth and one to sell
-		/// Eth for example.
+		/// Eth for example. If an expiry block is given, the order will be burned at the start of
+		/// that block and its assets credited to your balance. The expiry replaces any expiry
+		/// previously set for the order, which is otherwise kept.
 		#[pallet::call_index(6)]
 		#[pallet::weight(T::WeightInfo::set_limit_order())]
 		pub fn set_limit_order(
@@ -937,48 +958,19 @@ pub mod pallet {
 			id: OrderId,
 			option_tick: Option<Tick>,
 			sell_amount: AssetAmount,
+			expiry: Option<BlockNumberFor<T>>,
 		) -> DispatchResult {
-			ensure!(
-				T::SafeMode::get().limit_order_update_enabled,
-				Error::<T>::UpdatingLimitOrdersDisabled
-			);
 			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
-			Self::try_mutate_order(&lp, sell_asset, buy_asset, |asset_pair, pool| {
-				let tick = match (
-					pool.limit_orders_cache[asset_pair.base_side]
-						.get(&lp)
-						.and_then(|limit_orders| limit_orders.get(&id))
-						.cloned(),
-					option_tick,
-				) {
-					(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
-					(None, Some(tick)) => Ok(tick),
-					(Some(previous_tick), option_new_tick) => {
-						Self::inner_update_limit_order(
-							pool,
-							&lp,
-							asset_pair,
-							id,
-							previous_tick,
-							IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
-							/* allow noop */ false,
-						)?;
-
-						Ok(option_new_tick.unwrap_or(previous_tick))
-					},
-				}?;
-				Self::inner_update_limit_order(
-					pool,
-					&lp,
-					asset_pair,
-					id,
-					tick,
-					IncreaseOrDecrease::Increase(sell_amount.into()),
-					/* allow noop */ true,
-				)?;
-
-				Ok(())
-			})
+			Self::ensure_limit_order_updates_enabled()?;
+			Self::do_set_limit_order(
+				&lp,
+				sell_asset,
+				buy_asset,
+				id,
+				option_tick,
+				sell_amount,
+				expiry,
+			)
 		}
 
 		/// Sets the Liquidity Pool fees. Also collect earned fees and bought amount for
@@ -1043,11 +1035,62 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Applies a list of order updates in a single call. Each update behaves like the
+		/// corresponding order call, and if any of them fails none of the updates are applied.
+		#[pallet::call_index(8)]
+		#[pallet::weight(OrderUpdate::batch_weight::<T::WeightInfo>(orders))]
+		#[transactional]
+		pub fn update_orders(
+			origin: OriginFor<T>,
+			orders: BoundedVec<OrderUpdate<BlockNumberFor<T>>, T::MaxOrdersPerBatch>,
+		) -> DispatchResult {
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			for order in orders {
+				if order.is_range_order() {
+					Self::ensure_range_order_updates_enabled()?;
+				} else {
+					Self::ensure_limit_order_updates_enabled()?;
+				}
+				Self::apply_order_update(&lp, order)?;
+			}
+			Ok(())
+		}
+
+		/// Burns all of your range and limit orders, crediting the assets to your balance. If a
+		/// pool is given, only the orders in that pool are burned, otherwise the orders in every
+		/// enabled pool are burned. At most `MaxOrdersPerBatch` orders are burned in a single call,
+		/// so it may need to be called again if you have more orders than that.
+		///
+		/// Burning orders is allowed even while order updates are disabled by safe mode, as long as
+		/// cancelling all orders is itself enabled.
+		#[pallet::call_index(9)]
+		#[pallet::weight(
+			T::WeightInfo::update_orders(T::MaxOrdersPerBatch::get(), 0)
+				.max(T::WeightInfo::update_orders(0, T::MaxOrdersPerBatch::get()))
+		)]
+		pub fn cancel_all_orders(
+			origin: OriginFor<T>,
+			asset_pair: Option<(Asset, Asset)>,
+		) -> DispatchResultWithPostInfo {
+			let lp = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+			ensure!(
+				T::SafeMode::get().cancel_all_orders_enabled,
+				Error::<T>::CancellingAllOrdersDisabled
+			);
+			let mut orders = Self::cancel_order_updates(&lp, asset_pair)?;
+			orders.truncate(T::MaxOrdersPerBatch::get() as usize);
+			let weight_used = OrderUpdate::batch_weight::<T::WeightInfo>(&orders);
+			for order in orders {
+				Self::apply_order_update(&lp, order)?;
+			}
+			Ok(Some(weight_used).into())
+		}
 	}
 }
 
 impl<T: Config> SwappingApi for Pallet<T> {
-	fn take_network_fee(input: AssetAmount) -> AssetAmount {
+	fn take_network_fee(input: AssetAmount, from: any::Asset, to: any::Asset) -> AssetAmount {
 		if input.is_zero() {
 			return input
 		}
@@ -1055,38 +1098,18 @@ impl<T: Config> SwappingApi for Pallet<T> {
 		CollectedNetworkFee::<T>::mutate(|total| {
 			total.saturating_accrue(fee);
 		});
+		Self::record_network_fee(from, to, fee);
 		Self::deposit_event(Event::<T>::NetworkFeeTaken { fee_amount: fee });
 		remaining
 	}
 
-	#[transactional]
 	fn swap_single_leg(
 		from: any::Asset,
 		to: any::Asset,
 		input_amount: AssetAmount,
 	) -> Result<AssetAmount, DispatchError> {
-		Self::try_mutate_enabled_pool(
-			from,
-			to,
-			|_| Ok(()),
-			|asset_pair, pool| {
-				let (output_amount, remaining_amount) =
-					pool.pool_state.swap(asset_pair.base_side, Order::Sell, input_amount.into());
-				remaining_amount
-					.is_zero()
-					.then_some(())
-					.ok_or(Error::<T>::InsufficientLiquidity)?;
-				let output_amount =
-					output_amount.try_into().map_err(|_| Error::<T>::OutputOverflow)?;
-				Self::deposit_event(Event::<T>::AssetSwapped {
-					from,
-					to,
-					input_amount,
-					output_amount,
-				});
-				Ok(output_amount)
-			},
-		)
+		Self::swap_single_leg_with_lp_fee(from, to, input_amount)
+			.map(|(output_amount, _lp_fee)| output_amount)
 	}
 }
 
@@ -1126,6 +1149,39 @@ pub struct PoolInfo {
 	pub range_order_fee_hundredth_pips: u32,
 }
 
+/// Cumulative statistics of the swaps selling one of a pool's assets.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	Deserialize,
+	Serialize,
+)]
+pub struct SwapStats {
+	/// The total amount sold into the pool.
+	pub volume: Amount,
+	/// The total fees paid to liquidity providers, in the asset sold.
+	pub lp_fees: Amount,
+	/// The total network fees taken from these swaps, in the stable asset. A swap between two
+	/// other assets only counts its fee on its leg into the stable asset.
+	pub network_fees: Amount,
+}
+
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PoolStats {
+	/// Swap statistics grouped by the asset sold into the pool.
+	pub swaps: AssetsMap<SwapStats>,
+	/// The fees collected by the requested LP from its orders in the pool, in each asset.
+	pub lp_collected_fees: Option<AssetsMap<Amount>>,
+}
+
 #[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Deserialize, Serialize)]
 pub struct PoolOrders {
 	/// Limit orders are groups by which asset they are selling.
@@ -1164,57 +1220,197 @@ pub struct UnidirectionalPoolDepth {
 	pub range_orders: UnidirectionalSubPoolDepth,
 }
 
-impl<T: Config> Pallet<T> {
-	fn inner_sweep(lp: &T::AccountId, range_order_base_side: Side) -> DispatchResult {
-		// Collect to avoid undefined behaviour (See StorsgeMap::iter_keys documentation)
-		for canonical_asset_pair in Pools::<T>::iter_keys().collect::<Vec<_>>() {
-			let mut pool = Pools::<T>::get(canonical_asset_pair).unwrap();
+/// The amounts and fees of a simulated swap.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SwapBreakdown {
+	/// The amount of the stable asset before the network fee, if the swap has two legs.
+	pub intermediary: Option<AssetAmount>,
+	pub output: AssetAmount,
+	/// The network fee, in the stable asset.
+	pub network_fee: AssetAmount,
+	/// The liquidity provider fee of each leg, in the leg's input asset.
+	pub pool_fees: Vec<(Asset, AssetAmount)>,
+	/// The output, after fees, if the whole swap were executed at the current pool prices.
+	pub spot_output: AssetAmount,
+}
 
-			if let Some(range_orders_cache) = pool.range_orders_cache.get(lp).cloned() {
-				let asset_pair =
-					AssetPair { canonical_asset_pair, base_side: range_order_base_side };
+/// A swap through a single pool, as part of a swap route.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SwapHop {
+	pub from: Asset,
+	pub to: Asset,
+	pub input_amount: AssetAmount,
+	pub output_amount: AssetAmount,
+}
 
-				for (id, range) in range_orders_cache.iter() {
-					Self::inner_update_range_order(
-						&mut pool,
-						lp,
-						&asset_pair,
-						*id,
-						range.clone(),
-						IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
-							liquidity: 0,
-						}),
-						false,
-					)?;
-				}
-			}
+/// The route of a simulated swap through the pools.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SwapRoute {
+	pub hops: Vec<SwapHop>,
+	/// The network fee, in the stable asset, taken where the route passes through it.
+	pub network_fee: AssetAmount,
+	pub output: AssetAmount,
+}
 
-			for (side, limit_orders_cache) in pool
-				.limit_orders_cache
-				.as_ref()
-				.into_iter()
-				.filter_map(|(side, limit_orders_cache)| {
-					limit_orders_cache
-						.get(lp)
-						.cloned()
-						.map(|limit_orders_cache| (side, limit_orders_cache))
-				})
-				.collect::<Vec<_>>()
-			{
-				let asset_pair = AssetPair { canonical_asset_pair, base_side: side };
+/// A snapshot of a pool's accumulated price, taken before the first swap or order update in a
+/// block.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	Deserialize,
+	Serialize,
+)]
+pub struct Observation<BlockNumber> {
+	pub block_number: BlockNumber,
+	/// The sum of the pool's tick over every block since the first observation. The difference
+	/// between two observations divided by the number of blocks between them is the mean tick.
+	pub tick_cumulative: i64,
+}
 
-				for (id, tick) in limit_orders_cache {
-					Self::inner_update_limit_order(
-						&mut pool,
-						lp,
-						&asset_pair,
-						id,
-						tick,
-						IncreaseOrDecrease::Decrease(Default::default()),
-						false,
-					)?;
-				}
-			}
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+	PartialEq,
+	Eq,
+	Deserialize,
+	Serialize,
+)]
+pub struct ObservationCursor {
+	/// The index of the latest observation.
+	pub latest: u32,
+	/// The number of observations in the ring buffer.
+	pub len: u32,
+}
+
+impl ObservationCursor {
+	/// The index of the `n`th oldest observation.
+	fn index_of(&self, n: u32) -> u32 {
+		self.latest.saturating_add(1).saturating_add(n) % self.len
+	}
+
+	/// Advances the cursor to the slot for the next observation. The buffer grows until it
+	/// reaches `capacity`, after which the oldest observation is overwritten. Note that reducing
+	/// the capacity does not shrink an existing buffer.
+	fn advance(&mut self, capacity: u32) {
+		if self.latest.saturating_add(1) == self.len && self.len < capacity {
+			self.len.saturating_accrue(1);
+		}
+		self.latest = self.latest.saturating_add(1) % self.len;
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Swaps through a single pool, and also returns the fee paid to the limit and range orders the
+	/// swap was executed against, in the `from` asset.
+	#[transactional]
+	fn swap_single_leg_with_lp_fee(
+		from: any::Asset,
+		to: any::Asset,
+		input_amount: AssetAmount,
+	) -> Result<(AssetAmount, AssetAmount), DispatchError> {
+		// Swaps are executed outside of extrinsics, so their weight, including the price
+		// observation, is registered here.
+		frame_system::Pallet::<T>::register_extra_weight_unchecked(
+			T::WeightInfo::swap_single_leg(),
+			DispatchClass::Mandatory,
+		);
+		Self::try_mutate_enabled_pool(
+			from,
+			to,
+			|_| Ok(()),
+			|asset_pair, pool| {
+				let (output_amount, remaining_amount, lp_fee) = pool.pool_state.swap_with_fees(
+					asset_pair.base_side,
+					Order::Sell,
+					input_amount.into(),
+				);
+				remaining_amount
+					.is_zero()
+					.then_some(())
+					.ok_or(Error::<T>::InsufficientLiquidity)?;
+				let output_amount: AssetAmount =
+					output_amount.try_into().map_err(|_| Error::<T>::OutputOverflow)?;
+				PoolSwapStats::<T>::mutate(&asset_pair.canonical_asset_pair, |stats| {
+					let stats = &mut stats[asset_pair.base_side];
+					stats.volume = stats.volume.saturating_add(input_amount.into());
+					stats.lp_fees = stats.lp_fees.saturating_add(lp_fee);
+				});
+				Self::deposit_event(Event::<T>::AssetSwapped {
+					from,
+					to,
+					input_amount,
+					output_amount,
+				});
+				// The fee is a part of the input amount, so it always fits.
+				Ok((output_amount, lp_fee.low_u128()))
+			},
+		)
+	}
+
+	fn inner_sweep(lp: &T::AccountId, range_order_base_side: Side) -> DispatchResult {
+		// Collect to avoid undefined behaviour (See StorsgeMap::iter_keys documentation)
+		for canonical_asset_pair in Pools::<T>::iter_keys().collect::<Vec<_>>() {
+			let mut pool = Pools::<T>::get(canonical_asset_pair).unwrap();
+
+			if let Some(range_orders_cache) = pool.range_orders_cache.get(lp).cloned() {
+				let asset_pair =
+					AssetPair { canonical_asset_pair, base_side: range_order_base_side };
+
+				for (id, range) in range_orders_cache.iter() {
+					Self::inner_update_range_order(
+						&mut pool,
+						lp,
+						&asset_pair,
+						*id,
+						range.clone(),
+						IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
+							liquidity: 0,
+						}),
+						false,
+					)?;
+				}
+			}
+
+			for (side, limit_orders_cache) in pool
+				.limit_orders_cache
+				.as_ref()
+				.into_iter()
+				.filter_map(|(side, limit_orders_cache)| {
+					limit_orders_cache
+						.get(lp)
+						.cloned()
+						.map(|limit_orders_cache| (side, limit_orders_cache))
+				})
+				.collect::<Vec<_>>()
+			{
+				let asset_pair = AssetPair { canonical_asset_pair, base_side: side };
+
+				for (id, tick) in limit_orders_cache {
+					Self::inner_update_limit_order(
+						&mut pool,
+						lp,
+						&asset_pair,
+						id,
+						tick,
+						IncreaseOrDecrease::Decrease(Default::default()),
+						false,
+					)?;
+				}
+			}
 
 			Pools::<T>::insert(canonical_asset_pair, pool);
 		}
@@ -1391,6 +1587,7 @@ impl<T: Config> Pallet<T> {
 		};
 
 		let collected_fees = asset_pair.try_credit_assets(lp, collected.fees)?;
+		Self::record_lp_collected_fees(lp, asset_pair, collected.fees);
 
 		if position_info.liquidity == 0 {
 			if let Some(range_orders) = pool.range_orders_cache.get_mut(lp) {
@@ -1439,18 +1636,47 @@ impl<T: Config> Pallet<T> {
 	) -> Result<SwapOutput, DispatchError> {
 		Ok(match (from, to) {
 			(_, STABLE_ASSET) | (STABLE_ASSET, _) => {
-				let output = Self::take_network_fee(Self::swap_single_leg(from, to, input_amount)?);
-				SwapOutput { intermediary: None, output }
+				let output = Self::swap_single_leg(from, to, input_amount)?;
+				let output_after_fee = Self::take_network_fee(output, from, to);
+				SwapOutput { intermediary: None, output: output_after_fee }
 			},
 			_ => {
 				let intermediary = Self::swap_single_leg(from, STABLE_ASSET, input_amount)?;
-				let output =
-					Self::swap_single_leg(STABLE_ASSET, to, Self::take_network_fee(intermediary))?;
+				let intermediary_after_fee = Self::take_network_fee(intermediary, from, to);
+				let output = Self::swap_single_leg(STABLE_ASSET, to, intermediary_after_fee)?;
 				SwapOutput { intermediary: Some(intermediary), output }
 			},
 		})
 	}
 
+	/// Attributes a network fee taken from a swap to the leg of the swap next to the stable asset
+	/// amount it was taken from: the leg into the stable asset if there is one, otherwise the leg
+	/// out of it.
+	fn record_network_fee(from: any::Asset, to: any::Asset, fee: AssetAmount) {
+		let (leg_from, leg_to) =
+			if from == STABLE_ASSET { (STABLE_ASSET, to) } else { (from, STABLE_ASSET) };
+		if let Ok(asset_pair) = AssetPair::<T>::new(leg_from, leg_to) {
+			PoolSwapStats::<T>::mutate(&asset_pair.canonical_asset_pair, |stats| {
+				let stats = &mut stats[asset_pair.base_side];
+				stats.network_fees = stats.network_fees.saturating_add(fee.into());
+			});
+		}
+	}
+
+	fn record_lp_collected_fees(
+		lp: &T::AccountId,
+		asset_pair: &AssetPair<T>,
+		fees: SideMap<Amount>,
+	) {
+		if fees != Default::default() {
+			LpCollectedFees::<T>::mutate(lp, &asset_pair.canonical_asset_pair, |total| {
+				for (side, fee) in fees {
+					total[side] = total[side].saturating_add(fee);
+				}
+			});
+		}
+	}
+
 	fn try_mutate_pool<
 		R,
 		E: From<pallet::Error<T>>,
@@ -1483,10 +1709,369 @@ impl<T: Config> Pallet<T> {
 	) -> Result<R, E> {
 		Self::try_mutate_pool(base_asset, pair_asset, f, |asset_pair, pool| {
 			ensure!(pool.enabled, Error::<T>::PoolDisabled);
+			// Swaps and order updates can both move the price, so the price needs to be observed
+			// before either.
+			Self::record_observation(asset_pair.canonical_asset_pair, pool);
 			g(asset_pair, pool)
 		})
 	}
 
+	fn do_update_range_order(
+		lp: &T::AccountId,
+		base_asset: Asset,
+		pair_asset: Asset,
+		id: OrderId,
+		option_tick_range: Option<core::ops::Range<Tick>>,
+		size_change: IncreaseOrDecrease<RangeOrderSize>,
+	) -> DispatchResult {
+		Self::try_mutate_order(lp, base_asset, pair_asset, |asset_pair, pool| {
+			let tick_range = match (
+				pool.range_orders_cache
+					.get(lp)
+					.and_then(|range_orders| range_orders.get(&id))
+					.cloned(),
+				option_tick_range,
+			) {
+				(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
+				(None, Some(tick_range)) | (Some(tick_range), None) => Ok(tick_range),
+				(Some(previous_tick_range), Some(new_tick_range)) => {
+					if previous_tick_range != new_tick_range {
+						let withdrawn_asset_amounts = Self::inner_update_range_order(
+							pool,
+							lp,
+							asset_pair,
+							id,
+							previous_tick_range,
+							IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
+								liquidity: Liquidity::MAX,
+							}),
+							/* allow_noop */ false,
+						)?;
+						Self::inner_update_range_order(
+							pool,
+							lp,
+							asset_pair,
+							id,
+							new_tick_range.clone(),
+							IncreaseOrDecrease::Increase(range_orders::Size::Amount {
+								minimum: Default::default(),
+								maximum: asset_pair
+									.asset_amounts_to_side_map(withdrawn_asset_amounts),
+							}),
+							/* allow_noop */ true,
+						)?;
+					}
+
+					Ok(new_tick_range)
+				},
+			}?;
+			Self::inner_update_range_order(
+				pool,
+				lp,
+				asset_pair,
+				id,
+				tick_range,
+				size_change.map(|size| match size {
+					RangeOrderSize::Liquidity { liquidity } =>
+						range_orders::Size::Liquidity { liquidity },
+					RangeOrderSize::AssetAmounts { maximum, minimum } =>
+						range_orders::Size::Amount {
+							maximum: asset_pair.asset_amounts_to_side_map(maximum),
+							minimum: asset_pair.asset_amounts_to_side_map(minimum),
+						},
+				}),
+				/* allow_noop */ false,
+			)?;
+
+			Ok(())
+		})
+	}
+
+	fn do_set_range_order(
+		lp: &T::AccountId,
+		base_asset: Asset,
+		pair_asset: Asset,
+		id: OrderId,
+		option_tick_range: Option<core::ops::Range<Tick>>,
+		size: RangeOrderSize,
+	) -> DispatchResult {
+		Self::try_mutate_order(lp, base_asset, pair_asset, |asset_pair, pool| {
+			let tick_range = match (
+				pool.range_orders_cache
+					.get(lp)
+					.and_then(|range_orders| range_orders.get(&id))
+					.cloned(),
+				option_tick_range,
+			) {
+				(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
+				(None, Some(tick_range)) => Ok(tick_range),
+				(Some(previous_tick_range), option_new_tick_range) => {
+					Self::inner_update_range_order(
+						pool,
+						lp,
+						asset_pair,
+						id,
+						previous_tick_range.clone(),
+						IncreaseOrDecrease::Decrease(range_orders::Size::Liquidity {
+							liquidity: Liquidity::MAX,
+						}),
+						/* allow noop */ false,
+					)?;
+
+					Ok(option_new_tick_range.unwrap_or(previous_tick_range))
+				},
+			}?;
+			Self::inner_update_range_order(
+				pool,
+				lp,
+				asset_pair,
+				id,
+				tick_range,
+				IncreaseOrDecrease::Increase(match size {
+					RangeOrderSize::Liquidity { liquidity } =>
+						range_orders::Size::Liquidity { liquidity },
+					RangeOrderSize::AssetAmounts { maximum, minimum } =>
+						range_orders::Size::Amount {
+							maximum: asset_pair.asset_amounts_to_side_map(maximum),
+							minimum: asset_pair.asset_amounts_to_side_map(minimum),
+						},
+				}),
+				/* allow noop */ true,
+			)?;
+
+			Ok(())
+		})
+	}
+
+	fn do_update_limit_order(
+		lp: &T::AccountId,
+		sell_asset: any::Asset,
+		buy_asset: any::Asset,
+		id: OrderId,
+		option_tick: Option<Tick>,
+		amount_change: IncreaseOrDecrease<AssetAmount>,
+		expiry: Option<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		let key = LimitOrderKey { lp: lp.clone(), sell_asset, buy_asset, id };
+		Self::ensure_valid_expiry(&key, expiry)?;
+		Self::try_mutate_order(lp, sell_asset, buy_asset, |asset_pair, pool| {
+			let tick = match (
+				pool.limit_orders_cache[asset_pair.base_side]
+					.get(lp)
+					.and_then(|limit_orders| limit_orders.get(&id))
+					.cloned(),
+				option_tick,
+			) {
+				(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
+				(None, Some(tick)) | (Some(tick), None) => Ok(tick),
+				(Some(previous_tick), Some(new_tick)) => {
+					if previous_tick != new_tick {
+						let withdrawn_asset_amount = Self::inner_update_limit_order(
+							pool,
+							lp,
+							asset_pair,
+							id,
+							previous_tick,
+							IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
+							/* allow_noop */ false,
+						)?;
+						Self::inner_update_limit_order(
+							pool,
+							lp,
+							asset_pair,
+							id,
+							new_tick,
+							IncreaseOrDecrease::Increase(withdrawn_asset_amount.into()),
+							/* allow_noop */ true,
+						)?;
+					}
+
+					Ok(new_tick)
+				},
+			}?;
+			Self::inner_update_limit_order(
+				pool,
+				lp,
+				asset_pair,
+				id,
+				tick,
+				amount_change.map(|amount| amount.into()),
+				/* allow_noop */ false,
+			)?;
+
+			Ok(Self::limit_order_exists(pool, lp, asset_pair, id))
+		})
+		.and_then(|order_exists| Self::schedule_limit_order_expiry(key, expiry, order_exists))
+	}
+
+	fn do_set_limit_order(
+		lp: &T::AccountId,
+		sell_asset: any::Asset,
+		buy_asset: any::Asset,
+		id: OrderId,
+		option_tick: Option<Tick>,
+		sell_amount: AssetAmount,
+		expiry: Option<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		let key = LimitOrderKey { lp: lp.clone(), sell_asset, buy_asset, id };
+		Self::ensure_valid_expiry(&key, expiry)?;
+		Self::try_mutate_order(lp, sell_asset, buy_asset, |asset_pair, pool| {
+			let tick = match (
+				pool.limit_orders_cache[asset_pair.base_side]
+					.get(lp)
+					.and_then(|limit_orders| limit_orders.get(&id))
+					.cloned(),
+				option_tick,
+			) {
+				(None, None) => Err(Error::<T>::UnspecifiedOrderPrice),
+				(None, Some(tick)) => Ok(tick),
+				(Some(previous_tick), option_new_tick) => {
+					Self::inner_update_limit_order(
+						pool,
+						lp,
+						asset_pair,
+						id,
+						previous_tick,
+						IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
+						/* allow noop */ false,
+					)?;
+
+					Ok(option_new_tick.unwrap_or(previous_tick))
+				},
+			}?;
+			Self::inner_update_limit_order(
+				pool,
+				lp,
+				asset_pair,
+				id,
+				tick,
+				IncreaseOrDecrease::Increase(sell_amount.into()),
+				/* allow noop */ true,
+			)?;
+
+			Ok(Self::limit_order_exists(pool, lp, asset_pair, id))
+		})
+		.and_then(|order_exists| Self::schedule_limit_order_expiry(key, expiry, order_exists))
+	}
+
+	fn ensure_range_order_updates_enabled() -> DispatchResult {
+		ensure!(
+			T::SafeMode::get().range_order_update_enabled,
+			Error::<T>::UpdatingRangeOrdersDisabled
+		);
+		Ok(())
+	}
+
+	fn ensure_limit_order_updates_enabled() -> DispatchResult {
+		ensure!(
+			T::SafeMode::get().limit_order_update_enabled,
+			Error::<T>::UpdatingLimitOrdersDisabled
+		);
+		Ok(())
+	}
+
+	/// Applies the update without checking safe mode, callers must check it where required.
+	fn apply_order_update(
+		lp: &T::AccountId,
+		order: OrderUpdate<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		match order {
+			OrderUpdate::UpdateRangeOrder {
+				base_asset,
+				pair_asset,
+				id,
+				option_tick_range,
+				size_change,
+			} => Self::do_update_range_order(
+				lp,
+				base_asset,
+				pair_asset,
+				id,
+				option_tick_range,
+				size_change,
+			),
+			OrderUpdate::SetRangeOrder { base_asset, pair_asset, id, option_tick_range, size } =>
+				Self::do_set_range_order(lp, base_asset, pair_asset, id, option_tick_range, size),
+			OrderUpdate::UpdateLimitOrder {
+				sell_asset,
+				buy_asset,
+				id,
+				option_tick,
+				amount_change,
+				expiry,
+			} => Self::do_update_limit_order(
+				lp,
+				sell_asset,
+				buy_asset,
+				id,
+				option_tick,
+				amount_change,
+				expiry,
+			),
+			OrderUpdate::SetLimitOrder {
+				sell_asset,
+				buy_asset,
+				id,
+				option_tick,
+				sell_amount,
+				expiry,
+			} => Self::do_set_limit_order(
+				lp,
+				sell_asset,
+				buy_asset,
+				id,
+				option_tick,
+				sell_amount,
+				expiry,
+			),
+		}
+	}
+
+	/// Returns updates that burn each of the LP's orders, either in the given pool or in every
+	/// enabled pool.
+	fn cancel_order_updates(
+		lp: &T::AccountId,
+		asset_pair: Option<(Asset, Asset)>,
+	) -> Result<Vec<OrderUpdate<BlockNumberFor<T>>>, DispatchError> {
+		let pools = match asset_pair {
+			Some((base_asset, pair_asset)) => {
+				let canonical_asset_pair =
+					AssetPair::<T>::new(base_asset, pair_asset)?.canonical_asset_pair;
+				vec![(
+					canonical_asset_pair,
+					Pools::<T>::get(canonical_asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?,
+				)]
+			},
+			None => Pools::<T>::iter().filter(|(_, pool)| pool.enabled).collect(),
+		};
+
+		let mut updates = Vec::new();
+		for (canonical_asset_pair, pool) in pools {
+			if let Some(range_orders) = pool.range_orders_cache.get(lp) {
+				updates.extend(range_orders.keys().map(|id| OrderUpdate::SetRangeOrder {
+					base_asset: canonical_asset_pair.side_to_asset(Side::Zero),
+					pair_asset: canonical_asset_pair.side_to_asset(Side::One),
+					id: *id,
+					option_tick_range: None,
+					size: RangeOrderSize::Liquidity { liquidity: 0 },
+				}));
+			}
+			for side in [Side::Zero, Side::One] {
+				if let Some(limit_orders) = pool.limit_orders_cache[side].get(lp) {
+					updates.extend(limit_orders.keys().map(|id| OrderUpdate::SetLimitOrder {
+						sell_asset: canonical_asset_pair.side_to_asset(side),
+						buy_asset: canonical_asset_pair.side_to_asset(!side),
+						id: *id,
+						option_tick: None,
+						sell_amount: 0,
+						expiry: None,
+					}));
+				}
+			}
+		}
+		Ok(updates)
+	}
+
 	fn try_mutate_order<R, F: FnOnce(&AssetPair<T>, &mut Pool<T>) -> Result<R, DispatchError>>(
 		lp: &T::AccountId,
 		base_asset: any::Asset,
@@ -1502,6 +2087,460 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	fn ensure_valid_expiry(
+		key: &LimitOrderKey<T::AccountId>,
+		expiry: Option<BlockNumberFor<T>>,
+	) -> DispatchResult {
+		if let Some(expiry) = expiry {
+			ensure!(
+				expiry > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiryBlock
+			);
+			ensure!(
+				LimitOrderExpiryBlocks::<T>::get(key) == Some(expiry) ||
+					LimitOrderExpiries::<T>::decode_len(expiry).unwrap_or_default() <
+						T::MaxExpiredLimitOrdersPerBlock::get() as usize,
+				Error::<T>::LimitOrderExpiryBlockFull
+			);
+		}
+		Ok(())
+	}
+
+	fn limit_order_exists(
+		pool: &Pool<T>,
+		lp: &T::AccountId,
+		asset_pair: &AssetPair<T>,
+		id: OrderId,
+	) -> bool {
+		pool.limit_orders_cache[asset_pair.base_side]
+			.get(lp)
+			.is_some_and(|limit_orders| limit_orders.contains_key(&id))
+	}
+
+	/// Schedules the limit order to be burned at the start of the expiry block. A new expiry
+	/// replaces any previously scheduled expiry, and the expiry of an order that no longer exists
+	/// is removed.
+	fn schedule_limit_order_expiry(
+		key: LimitOrderKey<T::AccountId>,
+		expiry: Option<BlockNumberFor<T>>,
+		order_exists: bool,
+	) -> DispatchResult {
+		if !order_exists || expiry.is_some() {
+			if let Some(previous_expiry) = LimitOrderExpiryBlocks::<T>::take(&key) {
+				LimitOrderExpiries::<T>::mutate_exists(previous_expiry, |maybe_keys| {
+					if let Some(keys) = maybe_keys {
+						keys.retain(|scheduled_key| scheduled_key != &key);
+						if keys.is_empty() {
+							*maybe_keys = None;
+						}
+					}
+				});
+			}
+		}
+		if let (true, Some(expiry)) = (order_exists, expiry) {
+			LimitOrderExpiries::<T>::try_mutate(expiry, |keys| keys.try_push(key.clone()))
+				.map_err(|_| Error::<T>::LimitOrderExpiryBlockFull)?;
+			LimitOrderExpiryBlocks::<T>::insert(&key, expiry);
+		}
+		Ok(())
+	}
+
+	/// Schedules the expiry of a limit order in the first block from `earliest_block` that isn't
+	/// full. At most [MAX_LIMIT_ORDER_EXPIRY_RESCHEDULE_BLOCKS] blocks are searched, after which
+	/// the order is left without an expiry.
+	fn reschedule_limit_order_expiry(
+		key: &LimitOrderKey<T::AccountId>,
+		earliest_block: BlockNumberFor<T>,
+		weight_used: &mut Weight,
+	) -> DispatchResult {
+		let mut block = earliest_block;
+		for _ in 0..MAX_LIMIT_ORDER_EXPIRY_RESCHEDULE_BLOCKS {
+			weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			if LimitOrderExpiries::<T>::try_mutate(block, |keys| keys.try_push(key.clone())).is_ok()
+			{
+				weight_used.saturating_accrue(T::DbWeight::get().writes(1));
+				LimitOrderExpiryBlocks::<T>::insert(key, block);
+				return Ok(())
+			}
+			block.saturating_inc();
+		}
+		Err(Error::<T>::LimitOrderExpiryBlockFull.into())
+	}
+
+	/// Burns the limit orders whose expiry block has been reached, starting from the earliest block
+	/// that hasn't been processed yet. Expiries aren't processed while limit order updates are
+	/// disabled, and at most `MaxExpiredLimitOrdersPerBlock` orders and expiry blocks are processed
+	/// per block, so any backlog is worked through over the following blocks. Orders in disabled
+	/// pools are rescheduled for a later block.
+	fn expire_limit_orders(current_block: BlockNumberFor<T>) -> Weight {
+		let mut weight_used = T::DbWeight::get().reads_writes(2, 1);
+		let mut block = LimitOrderExpiryCursor::<T>::get().unwrap_or(current_block);
+		if T::SafeMode::get().limit_order_update_enabled {
+			let max_processed = T::MaxExpiredLimitOrdersPerBlock::get() as usize;
+			let mut remaining_orders = max_processed;
+			let mut processed_blocks = 0;
+			while block <= current_block && processed_blocks < max_processed {
+				weight_used.saturating_accrue(T::DbWeight::get().reads(1));
+				// A block can't hold more expiries than the limit, so at least one block is always
+				// processed.
+				let scheduled_orders =
+					LimitOrderExpiries::<T>::decode_len(block).unwrap_or_default();
+				if scheduled_orders > remaining_orders {
+					break
+				}
+				remaining_orders -= scheduled_orders;
+				processed_blocks += 1;
+
+				if scheduled_orders > 0 {
+					weight_used.saturating_accrue(T::DbWeight::get().writes(1));
+					for key in LimitOrderExpiries::<T>::take(block) {
+						weight_used.saturating_accrue(
+							T::WeightInfo::set_limit_order()
+								.saturating_add(T::DbWeight::get().writes(1)),
+						);
+						LimitOrderExpiryBlocks::<T>::remove(&key);
+						match Self::burn_expired_limit_order(&key) {
+							Ok(()) => {},
+							Err(e) if e == Error::<T>::PoolDisabled.into() =>
+								if let Err(e) = Self::reschedule_limit_order_expiry(
+									&key,
+									current_block.saturating_add(One::one()),
+									&mut weight_used,
+								) {
+									log::warn!(
+										"Unable to reschedule the expiry of limit order {key:?}: {e:?}"
+									);
+								},
+							Err(e) =>
+								log::warn!("Unable to burn expired limit order {key:?}: {e:?}"),
+						}
+					}
+				}
+				block.saturating_inc();
+			}
+		}
+		LimitOrderExpiryCursor::<T>::put(block);
+
+		weight_used
+	}
+
+	fn burn_expired_limit_order(key: &LimitOrderKey<T::AccountId>) -> DispatchResult {
+		Self::try_mutate_order(&key.lp, key.sell_asset, key.buy_asset, |asset_pair, pool| {
+			if let Some(tick) = pool.limit_orders_cache[asset_pair.base_side]
+				.get(&key.lp)
+				.and_then(|limit_orders| limit_orders.get(&key.id))
+				.cloned()
+			{
+				Self::inner_update_limit_order(
+					pool,
+					&key.lp,
+					asset_pair,
+					key.id,
+					tick,
+					IncreaseOrDecrease::Decrease(cf_amm::common::Amount::MAX),
+					/* allow_noop */ false,
+				)?;
+				Self::deposit_event(Event::<T>::LimitOrderExpired {
+					lp: key.lp.clone(),
+					sell_asset: key.sell_asset,
+					buy_asset: key.buy_asset,
+					id: key.id,
+				});
+			}
+			Ok(())
+		})
+	}
+
+	/// The mid point of the ticks at which each asset can currently be sold. If only one of the
+	/// assets can be sold, its tick is used.
+	fn current_tick(pool: &mut Pool<T>) -> Option<Tick> {
+		match SideMap::<()>::default().map(|side, ()| {
+			pool.pool_state.current_price(side, Order::Sell).and_then(tick_at_price)
+		}) {
+			SideMap { zero: Some(zero), one: Some(one) } => Some(zero + (one - zero) / 2),
+			SideMap { zero, one } => zero.or(one),
+		}
+	}
+
+	fn blocks_between(from: BlockNumberFor<T>, to: BlockNumberFor<T>) -> i64 {
+		UniqueSaturatedInto::<u32>::unique_saturated_into(to.saturating_sub(from)).into()
+	}
+
+	/// Records the pool's accumulated tick, if no observation has been recorded yet in this block.
+	/// This must be called before any change to the pool's price, so that the tick that has applied
+	/// since the previous observation is accumulated.
+	fn record_observation(canonical_asset_pair: CanonicalAssetPair<T>, pool: &mut Pool<T>) {
+		let current_block = frame_system::Pallet::<T>::block_number();
+		if let Some(tick) = Self::current_tick(pool) {
+			PoolObservationCursors::<T>::mutate(canonical_asset_pair, |maybe_cursor| {
+				let (cursor, tick_cumulative) = match maybe_cursor.and_then(|cursor| {
+					PoolObservations::<T>::get(canonical_asset_pair, cursor.latest)
+						.map(|latest| (cursor, latest))
+				}) {
+					Some((_, latest)) if latest.block_number == current_block => return,
+					Some((mut cursor, latest)) => {
+						cursor.advance(T::ObservationCapacity::get());
+						(
+							cursor,
+							latest.tick_cumulative.saturating_add(i64::from(tick).saturating_mul(
+								Self::blocks_between(latest.block_number, current_block),
+							)),
+						)
+					},
+					None => (ObservationCursor { latest: 0, len: 1 }, 0),
+				};
+				PoolObservations::<T>::insert(
+					canonical_asset_pair,
+					cursor.latest,
+					Observation { block_number: current_block, tick_cumulative },
+				);
+				*maybe_cursor = Some(cursor);
+			});
+		}
+	}
+
+	/// The accumulated tick of the pool at the given block. Between observations the tick is
+	/// constant, and after the latest observation it is assumed to be `current_tick`.
+	fn tick_cumulative_at(
+		canonical_asset_pair: CanonicalAssetPair<T>,
+		cursor: ObservationCursor,
+		block_number: BlockNumberFor<T>,
+		current_tick: Tick,
+	) -> Option<i64> {
+		let observation_at =
+			|n| PoolObservations::<T>::get(canonical_asset_pair, cursor.index_of(n));
+
+		let latest = observation_at(cursor.len - 1)?;
+		if block_number >= latest.block_number {
+			return Some(
+				latest.tick_cumulative.saturating_add(
+					i64::from(current_tick)
+						.saturating_mul(Self::blocks_between(latest.block_number, block_number)),
+				),
+			)
+		}
+
+		// Binary search for the latest observation at or before `block_number`.
+		let (mut low, mut high) = (0, cursor.len - 1);
+		if observation_at(low)?.block_number > block_number {
+			return None
+		}
+		while high - low > 1 {
+			let mid = low + (high - low) / 2;
+			if observation_at(mid)?.block_number <= block_number {
+				low = mid;
+			} else {
+				high = mid;
+			}
+		}
+		let (before, after) = (observation_at(low)?, observation_at(high)?);
+
+		Some(
+			before.tick_cumulative.saturating_add(
+				after
+					.tick_cumulative
+					.saturating_sub(before.tick_cumulative)
+					.saturating_mul(Self::blocks_between(before.block_number, block_number)) /
+					Self::blocks_between(before.block_number, after.block_number),
+			),
+		)
+	}
+
+	/// Returns the geometric mean price of the pool over the last `window` blocks. Like
+	/// [Self::current_price], the price is measured in the pool's stable asset, regardless of the
+	/// order of the given assets. A window of zero returns the current mid price.
+	pub fn pool_twap(
+		base_asset: any::Asset,
+		pair_asset: any::Asset,
+		window: BlockNumberFor<T>,
+	) -> Option<Result<Price, DispatchError>> {
+		let canonical_asset_pair = CanonicalAssetPair::<T>::new(base_asset, pair_asset).ok()?;
+		let mut pool = Pools::<T>::get(canonical_asset_pair)?;
+		Some(Self::inner_pool_twap(canonical_asset_pair, &mut pool, window))
+	}
+
+	fn inner_pool_twap(
+		canonical_asset_pair: CanonicalAssetPair<T>,
+		pool: &mut Pool<T>,
+		window: BlockNumberFor<T>,
+	) -> Result<Price, DispatchError> {
+		let current_tick = Self::current_tick(pool).ok_or(Error::<T>::InsufficientLiquidity)?;
+		let mean_tick = if window.is_zero() {
+			current_tick
+		} else {
+			let current_block = frame_system::Pallet::<T>::block_number();
+			ensure!(window <= current_block, Error::<T>::InsufficientObservations);
+			let start_block = current_block - window;
+			let cursor = PoolObservationCursors::<T>::get(canonical_asset_pair)
+				.ok_or(Error::<T>::InsufficientObservations)?;
+			let tick_cumulative_at = |block_number| {
+				Self::tick_cumulative_at(canonical_asset_pair, cursor, block_number, current_tick)
+					.ok_or(Error::<T>::InsufficientObservations)
+			};
+			let tick_delta =
+				tick_cumulative_at(current_block)?.saturating_sub(tick_cumulative_at(start_block)?);
+
+			// Round towards negative infinity, as in Uniswap v3.
+			Tick::try_from(tick_delta.div_euclid(Self::blocks_between(start_block, current_block)))
+				.map_err(|_| Error::<T>::InvalidTick)?
+		};
+
+		price_at_tick(mean_tick).ok_or(Error::<T>::InvalidTick.into())
+	}
+
+	/// Swaps along the given route, taking the network fee where the route passes through the
+	/// stable asset.
+	fn swap_along_route(
+		route: &[Asset],
+		input_amount: AssetAmount,
+	) -> Result<SwapRoute, DispatchError> {
+		let take_network_fee =
+			|amount| utilities::calculate_network_fee(T::NetworkFee::get(), amount);
+
+		let (mut amount, mut network_fee) = if route.first() == Some(&STABLE_ASSET) {
+			take_network_fee(input_amount)
+		} else {
+			(input_amount, Zero::zero())
+		};
+		let mut hops = Vec::with_capacity(route.len().saturating_sub(1));
+		for hop in route.windows(2) {
+			let (from, to) = (hop[0], hop[1]);
+			let output_amount = Self::swap_single_leg(from, to, amount)?;
+			hops.push(SwapHop { from, to, input_amount: amount, output_amount });
+			amount = output_amount;
+			if to == STABLE_ASSET {
+				(amount, network_fee) = take_network_fee(amount);
+			}
+		}
+
+		Ok(SwapRoute { hops, network_fee, output: amount })
+	}
+
+	/// Simulates a swap along the route it would be executed along: through the stable asset,
+	/// unless either asset is the stable asset. The pools are left unchanged.
+	pub fn swap_route(
+		from: Asset,
+		to: Asset,
+		input_amount: AssetAmount,
+	) -> Result<SwapRoute, DispatchError> {
+		let route = match (from, to) {
+			(_, STABLE_ASSET) | (STABLE_ASSET, _) => vec![from, to],
+			_ => vec![from, STABLE_ASSET, to],
+		};
+		with_transaction(|| {
+			TransactionOutcome::Rollback(Self::swap_along_route(&route, input_amount))
+		})
+	}
+
+	/// Swaps an asset, like `swap_with_network_fee`, and also returns the fees paid and the output
+	/// the swap would have at the current pool prices.
+	///
+	/// Note: this changes the pools, so it must only be used for simulating swaps.
+	pub fn swap_with_breakdown(
+		from: any::Asset,
+		to: any::Asset,
+		input_amount: AssetAmount,
+	) -> Result<SwapBreakdown, DispatchError> {
+		let mut pool_fees = Vec::new();
+		let (stable_amount, spot_stable_amount) = if from == STABLE_ASSET {
+			(input_amount, input_amount)
+		} else {
+			Self::swap_leg_with_breakdown(
+				from,
+				STABLE_ASSET,
+				input_amount,
+				input_amount,
+				&mut pool_fees,
+			)?
+		};
+
+		let stable_amount_after_fee = Self::take_network_fee(stable_amount, from, to);
+		let (spot_stable_amount_after_fee, _) =
+			utilities::calculate_network_fee(T::NetworkFee::get(), spot_stable_amount);
+
+		let (output, spot_output) = if to == STABLE_ASSET {
+			(stable_amount_after_fee, spot_stable_amount_after_fee)
+		} else {
+			Self::swap_leg_with_breakdown(
+				STABLE_ASSET,
+				to,
+				stable_amount_after_fee,
+				spot_stable_amount_after_fee,
+				&mut pool_fees,
+			)?
+		};
+
+		Ok(SwapBreakdown {
+			intermediary: (from != STABLE_ASSET && to != STABLE_ASSET).then_some(stable_amount),
+			output,
+			network_fee: stable_amount - stable_amount_after_fee,
+			pool_fees,
+			spot_output,
+		})
+	}
+
+	/// Swaps `input_amount`, and converts `spot_input_amount` at the pool's price before the swap.
+	/// Both amounts are after the pool's fee. The fee the swap paid is recorded in `pool_fees`.
+	fn swap_leg_with_breakdown(
+		from: any::Asset,
+		to: any::Asset,
+		input_amount: AssetAmount,
+		spot_input_amount: AssetAmount,
+		pool_fees: &mut Vec<(Asset, AssetAmount)>,
+	) -> Result<(AssetAmount, AssetAmount), DispatchError> {
+		let asset_pair = AssetPair::<T>::new(from, to)?;
+		let mut pool =
+			Pools::<T>::get(asset_pair.canonical_asset_pair).ok_or(Error::<T>::PoolDoesNotExist)?;
+		let pool_fee = Permill::from_parts(pool.pool_state.range_order_fee());
+		let spot_price = pool
+			.pool_state
+			.current_price(asset_pair.base_side, Order::Sell)
+			.ok_or(Error::<T>::InsufficientLiquidity)?;
+
+		let spot_input_amount = spot_input_amount.saturating_sub(pool_fee * spot_input_amount);
+		let spot_output_amount =
+			Self::convert_at_price(spot_input_amount, spot_price, asset_pair.base_side);
+
+		let (output_amount, lp_fee) = Self::swap_single_leg_with_lp_fee(from, to, input_amount)?;
+		pool_fees.push((from, lp_fee));
+
+		Ok((output_amount, spot_output_amount))
+	}
+
+	/// Converts an amount at the given price, where the price is that of selling the `base_side`.
+	fn convert_at_price(amount: AssetAmount, price: Price, base_side: Side) -> AssetAmount {
+		match base_side {
+			Side::Zero =>
+				mul_div_floor(amount.into(), price, Price::one() << PRICE_FRACTIONAL_BITS),
+			Side::One => mul_div_floor(amount.into(), Price::one() << PRICE_FRACTIONAL_BITS, price),
+		}
+		.try_into()
+		.unwrap_or(AssetAmount::MAX)
+	}
+
+	/// Converts `amount` of `from` into `to` at the current pool prices, routing via the stable
+	/// asset, without swapping and without fees. Returns `None` if a pool has no price.
+	pub fn convert_at_current_price(
+		from: Asset,
+		to: Asset,
+		amount: AssetAmount,
+	) -> Option<AssetAmount> {
+		let convert_single_leg = |from: Asset, to: Asset, amount: AssetAmount| {
+			let asset_pair = AssetPair::<T>::new(from, to).ok()?;
+			let mut pool = Pools::<T>::get(asset_pair.canonical_asset_pair)?;
+			let price = pool.pool_state.current_price(asset_pair.base_side, Order::Sell)?;
+			Some(Self::convert_at_price(amount, price, asset_pair.base_side))
+		};
+
+		if from == to {
+			Some(amount)
+		} else if from == STABLE_ASSET || to == STABLE_ASSET {
+			convert_single_leg(from, to, amount)
+		} else {
+			convert_single_leg(STABLE_ASSET, to, convert_single_leg(from, STABLE_ASSET, amount)?)
+		}
+	}
+
 	pub fn current_price(from: Asset, to: Asset) -> Option<Price> {
 		let asset_pair = AssetPair::new(from, to).ok()?;
 		Pools::<T>::get(asset_pair.canonical_asset_pair)
@@ -1615,6 +2654,27 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	pub fn pool_stats(
+		base_asset: any::Asset,
+		pair_asset: any::Asset,
+		lp: Option<&T::AccountId>,
+	) -> Option<PoolStats> {
+		let asset_pair = AssetPair::new(base_asset, pair_asset).ok()?;
+		if !Pools::<T>::contains_key(&asset_pair.canonical_asset_pair) {
+			return None
+		}
+		Some(PoolStats {
+			swaps: asset_pair
+				.side_map_to_assets_map(PoolSwapStats::<T>::get(&asset_pair.canonical_asset_pair)),
+			lp_collected_fees: lp.map(|lp| {
+				asset_pair.side_map_to_assets_map(LpCollectedFees::<T>::get(
+					lp,
+					&asset_pair.canonical_asset_pair,
+				))
+			}),
+		})
+	}
+
 	pub fn pool_liquidity(base_asset: any::Asset, pair_asset: any::Asset) -> Option<PoolLiquidity> {
 		let asset_pair = AssetPair::new(base_asset, pair_asset).ok()?;
 		let pool = Pools::<T>::get(asset_pair.canonical_asset_pair)?;
@@ -1707,6 +2767,17 @@ impl<T: Config> Pallet<T> {
 		amount_change: IncreaseOrDecrease<AssetAmount>,
 	) -> DispatchResult {
 		let collected_fees = asset_pair.try_credit_asset(lp, !side, collected.fees)?;
+		Self::record_lp_collected_fees(
+			lp,
+			asset_pair,
+			SideMap::<()>::default().map(|fee_side, ()| {
+				if fee_side == !side {
+					collected.fees
+				} else {
+					Amount::zero()
+				}
+			}),
+		);
 		let bought_amount = asset_pair.try_credit_asset(lp, !side, collected.bought_amount)?;
 		let limit_orders = &mut pool.limit_orders_cache[side];
 		if position_info.amount.is_zero() {
@@ -3,7 +3,7 @@ use cf_primitives::{Asset, AssetAmount};
 use cf_traits::{
 	impl_mock_chainflip, impl_mock_runtime_safe_mode, AccountRoleRegistry, LpBalanceApi,
 };
-use frame_support::parameter_types;
+use frame_support::{parameter_types, traits::ConstU32};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
@@ -127,6 +127,9 @@ impl pallet_cf_pools::Config for Test {
 	type LpBalance = MockBalance;
 	type NetworkFee = NetworkFee;
 	type SafeMode = MockRuntimeSafeMode;
+	type ObservationCapacity = ConstU32<3>;
+	type MaxExpiredLimitOrdersPerBlock = ConstU32<2>;
+	type MaxOrdersPerBatch = ConstU32<10>;
 	type WeightInfo = ();
 }
 
@@ -1,12 +1,14 @@
 use crate::{
 	mock::*, utilities, AssetAmounts, AssetPair, AssetsMap, CanonicalAssetPair,
-	CollectedNetworkFee, Error, Event, FlipBuyInterval, FlipToBurn, PoolInfo, PoolOrders, Pools,
-	RangeOrderSize, STABLE_ASSET,
+	CollectedNetworkFee, Error, Event, FlipBuyInterval, FlipToBurn, IncreaseOrDecrease,
+	LimitOrderExpiries, LimitOrderExpiryBlocks, LimitOrderKey, OrderUpdate, PoolInfo,
+	PoolObservationCursors, PoolOrders, PoolStats, Pools, RangeOrderSize, SwapStats,
+	MAX_LIMIT_ORDER_EXPIRY_RESCHEDULE_BLOCKS, STABLE_ASSET,
 };
-use cf_amm::common::{price_at_tick, Tick};
+use cf_amm::common::{price_at_tick, tick_at_price, Tick};
 use cf_primitives::{chains::assets::any::Asset, AssetAmount, SwapOutput};
 use cf_test_utilities::{assert_events_match, assert_has_event};
-use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, BoundedVec};
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_runtime::Permill;
 
@@ -216,6 +218,7 @@ fn test_sweeping() {
 			0,
 			Some(TICK),
 			POSITION_0_SIZE,
+			None,
 		));
 
 		assert_eq!(AliceCollectedEth::get(), 0);
@@ -237,6 +240,7 @@ fn test_sweeping() {
 			1,
 			Some(TICK),
 			POSITION_1_SIZE,
+			None,
 		));
 
 		assert_eq!(AliceCollectedEth::get(), SWAP_AMOUNT);
@@ -351,6 +355,7 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 			0,
 			Some(0),
 			5_000,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(ALICE),
@@ -359,6 +364,7 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 			1,
 			Some(0),
 			1_000,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(BOB),
@@ -367,6 +373,7 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 			0,
 			Some(0),
 			10_000,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(BOB),
@@ -375,6 +382,7 @@ fn can_update_pool_liquidity_fee_and_collect_for_limit_order() {
 			1,
 			Some(0),
 			10_000,
+			None,
 		));
 		assert_eq!(
 			LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, &ALICE,),
@@ -504,6 +512,7 @@ fn pallet_limit_order_is_in_sync_with_pool() {
 			0,
 			Some(0),
 			100,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(BOB),
@@ -512,6 +521,7 @@ fn pallet_limit_order_is_in_sync_with_pool() {
 			0,
 			Some(tick),
 			100_000,
+			None,
 		));
 		assert_ok!(LiquidityPools::set_limit_order(
 			RuntimeOrigin::signed(BOB),
@@ -520,6 +530,7 @@ fn pallet_limit_order_is_in_sync_with_pool() {
 			1,
 			Some(tick),
 			10_000,
+			None,
 		));
 		assert_eq!(
 			LiquidityPools::pool_orders(Asset::Eth, STABLE_ASSET, &ALICE,),
@@ -705,3 +716,787 @@ fn update_pool_liquidity_fee_collects_fees_for_range_order() {
 		assert_eq!(BobDebitedUsdc::get(), 4_988u128);
 	});
 }
+
+#[test]
+fn twap_is_calculated_from_observations() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		let current_tick =
+			|| tick_at_price(LiquidityPools::current_price(ETH, STABLE_ASSET).unwrap()).unwrap();
+		let twap = |window| LiquidityPools::pool_twap(ETH, STABLE_ASSET, window).unwrap();
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+
+		// No observations have been recorded before the pool is first used.
+		assert_eq!(twap(1), Err(Error::<Test>::InsufficientObservations.into()));
+		assert_eq!(twap(0), Ok(price_at_tick(current_tick()).unwrap()));
+
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			STABLE_ASSET,
+			ETH,
+			0,
+			Some(-100_000..100_000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		LiquidityPools::swap_with_network_fee(ETH, STABLE_ASSET, 1_000).unwrap();
+		let first_tick = current_tick();
+
+		System::set_block_number(11);
+		LiquidityPools::swap_with_network_fee(STABLE_ASSET, ETH, 3_000).unwrap();
+		let second_tick = current_tick();
+		assert!(first_tick < 0 && second_tick > 0);
+
+		System::set_block_number(21);
+		// Blocks 1 to 11 at the first tick and blocks 11 to 21 at the second tick.
+		assert_eq!(twap(20), Ok(price_at_tick((first_tick + second_tick).div_euclid(2)).unwrap()));
+		assert_eq!(twap(10), Ok(price_at_tick(second_tick).unwrap()));
+		assert_eq!(twap(0), Ok(price_at_tick(second_tick).unwrap()));
+		// Observations are interpolated.
+		assert_eq!(
+			twap(15),
+			Ok(price_at_tick((5 * first_tick + 10 * second_tick).div_euclid(15)).unwrap())
+		);
+		// The order of the assets doesn't matter.
+		assert_eq!(LiquidityPools::pool_twap(STABLE_ASSET, ETH, 20).unwrap(), twap(20));
+		// The window can't go back further than the oldest observation.
+		assert_eq!(twap(21), Err(Error::<Test>::InsufficientObservations.into()));
+
+		// The mock only keeps 3 observations, so the oldest one is overwritten.
+		LiquidityPools::swap_with_network_fee(ETH, STABLE_ASSET, 1_000).unwrap();
+		let third_tick = current_tick();
+		System::set_block_number(31);
+		LiquidityPools::swap_with_network_fee(ETH, STABLE_ASSET, 1_000).unwrap();
+		assert_eq!(
+			PoolObservationCursors::<Test>::get(
+				CanonicalAssetPair::new(ETH, STABLE_ASSET).unwrap()
+			)
+			.unwrap()
+			.len,
+			3
+		);
+		assert_eq!(twap(20), Ok(price_at_tick((second_tick + third_tick).div_euclid(2)).unwrap()));
+		assert_eq!(twap(21), Err(Error::<Test>::InsufficientObservations.into()));
+	});
+}
+
+#[test]
+fn swap_breakdown_includes_fees_and_spot_output() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		const FLIP: Asset = Asset::Flip;
+		const POOL_FEE: u32 = 10_000;
+		let pool_fee = Permill::from_parts(POOL_FEE);
+
+		for asset in [ETH, FLIP] {
+			assert_ok!(LiquidityPools::new_pool(
+				RuntimeOrigin::root(),
+				asset,
+				STABLE_ASSET,
+				POOL_FEE,
+				price_at_tick(0).unwrap(),
+			));
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				asset,
+				0,
+				Some(-100_000..100_000),
+				RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+			));
+		}
+
+		let breakdown = LiquidityPools::swap_with_breakdown(ETH, FLIP, 1_000).unwrap();
+		let intermediary = breakdown.intermediary.unwrap();
+		let (intermediary_after_fee, network_fee) =
+			utilities::calculate_network_fee(NetworkFee::get(), intermediary);
+		assert_eq!(breakdown.network_fee, network_fee);
+		// The fees paid are rounded down.
+		assert_eq!(
+			breakdown.pool_fees,
+			vec![
+				(ETH, pool_fee * 1_000),
+				(STABLE_ASSET, intermediary_after_fee * POOL_FEE as u128 / 1_000_000)
+			]
+		);
+
+		// At tick 0 both pools have a price of 1, so only fees are taken from the spot output.
+		let (spot_intermediary, _) =
+			utilities::calculate_network_fee(NetworkFee::get(), 1_000 - pool_fee * 1_000);
+		assert_eq!(breakdown.spot_output, spot_intermediary - pool_fee * spot_intermediary);
+		assert!(breakdown.output < breakdown.spot_output);
+
+		// Swaps to or from the stable asset only use one pool.
+		let breakdown = LiquidityPools::swap_with_breakdown(STABLE_ASSET, ETH, 1_000).unwrap();
+		assert_eq!(breakdown.intermediary, None);
+		assert_eq!(breakdown.pool_fees.len(), 1);
+	});
+}
+
+#[test]
+fn swap_route_is_simulated_against_pool_state() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		const FLIP: Asset = Asset::Flip;
+		const AMOUNT: AssetAmount = 1_000;
+
+		assert_noop!(
+			LiquidityPools::swap_route(ETH, FLIP, AMOUNT),
+			Error::<Test>::PoolDoesNotExist
+		);
+
+		for (asset, tick) in [(ETH, 100), (FLIP, -200)] {
+			assert_ok!(LiquidityPools::new_pool(
+				RuntimeOrigin::root(),
+				asset,
+				STABLE_ASSET,
+				Default::default(),
+				price_at_tick(tick).unwrap(),
+			));
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				asset,
+				0,
+				Some(-100_000..100_000),
+				RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+			));
+		}
+		let prices =
+			|| [ETH, FLIP].map(|asset| LiquidityPools::current_price(asset, STABLE_ASSET).unwrap());
+		let prices_before = prices();
+
+		// Eth -> Flip is routed through the stable asset.
+		let route = LiquidityPools::swap_route(ETH, FLIP, AMOUNT).unwrap();
+		assert_eq!(
+			route.hops.iter().map(|hop| (hop.from, hop.to)).collect::<Vec<_>>(),
+			vec![(ETH, STABLE_ASSET), (STABLE_ASSET, FLIP)]
+		);
+		assert_eq!(route.hops[0].input_amount, AMOUNT);
+		let (intermediary_after_fee, network_fee) =
+			utilities::calculate_network_fee(NetworkFee::get(), route.hops[0].output_amount);
+		assert_eq!(route.network_fee, network_fee);
+		assert_eq!(route.hops[1].input_amount, intermediary_after_fee);
+		assert_eq!(route.hops[1].output_amount, route.output);
+
+		// The simulation doesn't change the pools.
+		assert_eq!(prices(), prices_before);
+
+		// Executing the swap gives the quoted amounts.
+		assert_eq!(
+			LiquidityPools::swap_with_network_fee(ETH, FLIP, AMOUNT).unwrap(),
+			SwapOutput { intermediary: Some(route.hops[0].output_amount), output: route.output }
+		);
+
+		// Swaps from the stable asset use a single pool, after the network fee.
+		let route = LiquidityPools::swap_route(STABLE_ASSET, ETH, AMOUNT).unwrap();
+		assert_eq!(route.hops.len(), 1);
+		assert_eq!(
+			(route.hops[0].input_amount, route.network_fee),
+			utilities::calculate_network_fee(NetworkFee::get(), AMOUNT)
+		);
+
+		// Swaps can't be routed through disabled pools.
+		assert_ok!(LiquidityPools::update_pool_enabled(
+			RuntimeOrigin::root(),
+			FLIP,
+			STABLE_ASSET,
+			false
+		));
+		assert_noop!(LiquidityPools::swap_route(ETH, FLIP, AMOUNT), Error::<Test>::PoolDisabled);
+	});
+}
+
+#[test]
+fn limit_orders_are_burned_at_expiry() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		let key = |id| LimitOrderKey { lp: ALICE, sell_asset: STABLE_ASSET, buy_asset: ETH, id };
+		let alice_orders = || {
+			LiquidityPools::pool_orders(ETH, STABLE_ASSET, &ALICE)
+				.unwrap()
+				.limit_orders
+				.pair
+		};
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+
+		// The expiry must be in the future.
+		assert_noop!(
+			LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				ETH,
+				0,
+				Some(0),
+				1_000,
+				Some(1),
+			),
+			Error::<Test>::InvalidExpiryBlock,
+		);
+
+		for id in 0..2 {
+			assert_ok!(LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				ETH,
+				id,
+				Some(0),
+				1_000,
+				Some(5),
+			));
+		}
+		// Only two orders can expire per block in the mock.
+		assert_noop!(
+			LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				ETH,
+				2,
+				Some(0),
+				1_000,
+				Some(5),
+			),
+			Error::<Test>::LimitOrderExpiryBlockFull,
+		);
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			STABLE_ASSET,
+			ETH,
+			2,
+			Some(0),
+			1_000,
+			Some(6),
+		));
+		// Setting a new expiry replaces the previous one.
+		assert_ok!(LiquidityPools::update_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			STABLE_ASSET,
+			ETH,
+			3,
+			Some(0),
+			IncreaseOrDecrease::Increase(1_000),
+			Some(10),
+		));
+		assert_ok!(LiquidityPools::update_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			STABLE_ASSET,
+			ETH,
+			3,
+			None,
+			IncreaseOrDecrease::Increase(1_000),
+			Some(20),
+		));
+		assert!(!LimitOrderExpiries::<Test>::contains_key(10));
+		assert_eq!(LimitOrderExpiries::<Test>::get(20), vec![key(3)]);
+		// Updating an order without an expiry keeps its expiry.
+		assert_ok!(LiquidityPools::update_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			STABLE_ASSET,
+			ETH,
+			3,
+			None,
+			IncreaseOrDecrease::Increase(1_000),
+			None,
+		));
+		assert_eq!(LimitOrderExpiries::<Test>::get(20), vec![key(3)]);
+		assert_eq!(LimitOrderExpiryBlocks::<Test>::get(key(3)), Some(20));
+		assert_eq!(alice_orders().len(), 4);
+
+		System::reset_events();
+		LiquidityPools::on_initialize(5);
+		assert_eq!(alice_orders().len(), 2);
+		assert!(!LimitOrderExpiries::<Test>::contains_key(5));
+		assert_eq!(LimitOrderExpiryBlocks::<Test>::get(key(0)), None);
+		for id in 0..2 {
+			assert_has_event::<Test>(RuntimeEvent::LiquidityPools(
+				Event::<Test>::LimitOrderExpired {
+					lp: ALICE,
+					sell_asset: STABLE_ASSET,
+					buy_asset: ETH,
+					id,
+				},
+			));
+		}
+
+		LiquidityPools::on_initialize(6);
+		assert_eq!(alice_orders().len(), 1);
+		assert!(!LimitOrderExpiries::<Test>::contains_key(6));
+
+		// The expiry of an order that no longer exists is removed.
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			STABLE_ASSET,
+			ETH,
+			3,
+			None,
+			0,
+			None,
+		));
+		assert!(alice_orders().is_empty());
+		assert!(!LimitOrderExpiries::<Test>::contains_key(20));
+		assert!(!LimitOrderExpiryBlocks::<Test>::contains_key(key(3)));
+	});
+}
+
+#[test]
+fn limit_order_expiries_are_deferred_while_they_cannot_be_burned() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		let key = |id| LimitOrderKey { lp: ALICE, sell_asset: STABLE_ASSET, buy_asset: ETH, id };
+		let alice_orders = || {
+			LiquidityPools::pool_orders(ETH, STABLE_ASSET, &ALICE)
+				.unwrap()
+				.limit_orders
+				.pair
+		};
+		let set_limit_orders_enabled = |limit_order_update_enabled| {
+			MockRuntimeSafeMode::set_safe_mode(MockRuntimeSafeMode {
+				pools: crate::PalletSafeMode {
+					range_order_update_enabled: true,
+					limit_order_update_enabled,
+					cancel_all_orders_enabled: true,
+				},
+			});
+		};
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+		for (id, expiry) in [(0, 2), (1, 2), (2, 3)] {
+			assert_ok!(LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				ETH,
+				id,
+				Some(0),
+				1_000,
+				Some(expiry),
+			));
+		}
+
+		// Expiries are not processed while limit order updates are disabled.
+		set_limit_orders_enabled(false);
+		LiquidityPools::on_initialize(2);
+		LiquidityPools::on_initialize(3);
+		assert_eq!(alice_orders().len(), 3);
+
+		// Once re-enabled, the backlog is burned, up to the per-block limit.
+		set_limit_orders_enabled(true);
+		LiquidityPools::on_initialize(4);
+		assert_eq!(alice_orders().len(), 1);
+		assert!(!LimitOrderExpiries::<Test>::contains_key(2));
+		assert_eq!(LimitOrderExpiries::<Test>::get(3), vec![key(2)]);
+		LiquidityPools::on_initialize(5);
+		assert!(alice_orders().is_empty());
+		assert!(!LimitOrderExpiries::<Test>::contains_key(3));
+
+		// Orders in a disabled pool are rescheduled, into the next block that isn't full.
+		for id in 0..2 {
+			assert_ok!(LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				ETH,
+				id,
+				Some(0),
+				1_000,
+				Some(if id == 0 { 6 } else { 7 }),
+			));
+		}
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(BOB),
+			STABLE_ASSET,
+			ETH,
+			0,
+			Some(0),
+			1_000,
+			Some(7),
+		));
+		assert_ok!(LiquidityPools::update_pool_enabled(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			false
+		));
+		LiquidityPools::on_initialize(6);
+		assert_eq!(LimitOrderExpiries::<Test>::get(8), vec![key(0)]);
+		assert_eq!(LimitOrderExpiryBlocks::<Test>::get(key(0)), Some(8));
+
+		assert_ok!(LiquidityPools::update_pool_enabled(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			true
+		));
+		LiquidityPools::on_initialize(7);
+		LiquidityPools::on_initialize(8);
+		assert!(alice_orders().is_empty());
+		assert!(LimitOrderExpiryBlocks::<Test>::iter().next().is_none());
+	});
+}
+
+#[test]
+fn limit_order_expiry_is_not_rescheduled_beyond_the_search_limit() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		let key = |id| LimitOrderKey { lp: ALICE, sell_asset: STABLE_ASSET, buy_asset: ETH, id };
+
+		System::set_block_number(1);
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(ALICE),
+			STABLE_ASSET,
+			ETH,
+			0,
+			Some(0),
+			1_000,
+			Some(2),
+		));
+		assert_ok!(LiquidityPools::update_pool_enabled(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			false
+		));
+
+		// Every block that is searched is full.
+		for block in 3..3 + MAX_LIMIT_ORDER_EXPIRY_RESCHEDULE_BLOCKS as u64 {
+			LimitOrderExpiries::<Test>::insert(
+				block,
+				BoundedVec::truncate_from(vec![key(1), key(2)]),
+			);
+		}
+
+		// The order can't be burned in the disabled pool, and is left without an expiry.
+		LiquidityPools::on_initialize(2);
+		assert_eq!(LimitOrderExpiryBlocks::<Test>::get(key(0)), None);
+		assert!(!LimitOrderExpiries::<Test>::iter_values().any(|keys| keys.contains(&key(0))));
+		assert_eq!(
+			LiquidityPools::pool_orders(ETH, STABLE_ASSET, &ALICE)
+				.unwrap()
+				.limit_orders
+				.pair
+				.len(),
+			1
+		);
+	});
+}
+
+#[test]
+fn order_updates_are_applied_atomically() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			Default::default(),
+			price_at_tick(0).unwrap(),
+		));
+
+		assert_ok!(LiquidityPools::update_orders(
+			RuntimeOrigin::signed(ALICE),
+			vec![
+				OrderUpdate::SetLimitOrder {
+					sell_asset: ETH,
+					buy_asset: STABLE_ASSET,
+					id: 0,
+					option_tick: Some(100),
+					sell_amount: 1_000,
+					expiry: None,
+				},
+				OrderUpdate::UpdateLimitOrder {
+					sell_asset: ETH,
+					buy_asset: STABLE_ASSET,
+					id: 0,
+					option_tick: None,
+					amount_change: IncreaseOrDecrease::Increase(500),
+					expiry: None,
+				},
+				OrderUpdate::SetRangeOrder {
+					base_asset: STABLE_ASSET,
+					pair_asset: ETH,
+					id: 0,
+					option_tick_range: Some(-100..100),
+					size: RangeOrderSize::Liquidity { liquidity: 1_000 },
+				},
+			]
+			.try_into()
+			.unwrap(),
+		));
+		let orders = LiquidityPools::pool_orders(ETH, STABLE_ASSET, &ALICE).unwrap();
+		assert_eq!(orders.limit_orders.base, vec![(0, 100, 1_500u128.into())]);
+		assert_eq!(orders.range_orders.len(), 1);
+
+		// If any update fails, none of them are applied.
+		assert_noop!(
+			LiquidityPools::update_orders(
+				RuntimeOrigin::signed(ALICE),
+				vec![
+					OrderUpdate::SetLimitOrder {
+						sell_asset: ETH,
+						buy_asset: STABLE_ASSET,
+						id: 0,
+						option_tick: None,
+						sell_amount: 0,
+						expiry: None,
+					},
+					OrderUpdate::SetLimitOrder {
+						sell_asset: ETH,
+						buy_asset: STABLE_ASSET,
+						id: 1,
+						option_tick: None,
+						sell_amount: 1_000,
+						expiry: None,
+					},
+				]
+				.try_into()
+				.unwrap(),
+			),
+			Error::<Test>::UnspecifiedOrderPrice,
+		);
+	});
+}
+
+#[test]
+fn can_cancel_all_orders() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		const FLIP: Asset = Asset::Flip;
+		let has_orders = |asset| {
+			let orders = LiquidityPools::pool_orders(asset, STABLE_ASSET, &ALICE).unwrap();
+			!orders.range_orders.is_empty() ||
+				!orders.limit_orders.base.is_empty() ||
+				!orders.limit_orders.pair.is_empty()
+		};
+
+		for asset in [ETH, FLIP] {
+			assert_ok!(LiquidityPools::new_pool(
+				RuntimeOrigin::root(),
+				asset,
+				STABLE_ASSET,
+				Default::default(),
+				price_at_tick(0).unwrap(),
+			));
+			assert_ok!(LiquidityPools::set_range_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				asset,
+				0,
+				Some(-100..100),
+				RangeOrderSize::Liquidity { liquidity: 1_000 },
+			));
+			assert_ok!(LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				asset,
+				STABLE_ASSET,
+				0,
+				Some(100),
+				1_000,
+				Some(10),
+			));
+			assert_ok!(LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				STABLE_ASSET,
+				asset,
+				0,
+				Some(-100),
+				1_000,
+				None,
+			));
+			assert!(has_orders(asset));
+		}
+
+		// Only the orders in the given pool are cancelled.
+		assert_ok!(LiquidityPools::cancel_all_orders(
+			RuntimeOrigin::signed(ALICE),
+			Some((ETH, STABLE_ASSET))
+		));
+		assert!(!has_orders(ETH));
+		assert!(has_orders(FLIP));
+		assert!(!LimitOrderExpiryBlocks::<Test>::contains_key(LimitOrderKey {
+			lp: ALICE,
+			sell_asset: ETH,
+			buy_asset: STABLE_ASSET,
+			id: 0
+		}));
+
+		// Cancelling all orders can be disabled on its own.
+		MockRuntimeSafeMode::set_safe_mode(MockRuntimeSafeMode {
+			pools: crate::PalletSafeMode {
+				range_order_update_enabled: true,
+				limit_order_update_enabled: true,
+				cancel_all_orders_enabled: false,
+			},
+		});
+		assert_noop!(
+			LiquidityPools::cancel_all_orders(RuntimeOrigin::signed(ALICE), None),
+			Error::<Test>::CancellingAllOrdersDisabled,
+		);
+		assert!(has_orders(FLIP));
+
+		// Orders can still be cancelled while order updates are disabled.
+		MockRuntimeSafeMode::set_safe_mode(MockRuntimeSafeMode {
+			pools: crate::PalletSafeMode {
+				range_order_update_enabled: false,
+				limit_order_update_enabled: false,
+				cancel_all_orders_enabled: true,
+			},
+		});
+		assert_noop!(
+			LiquidityPools::set_limit_order(
+				RuntimeOrigin::signed(ALICE),
+				FLIP,
+				STABLE_ASSET,
+				0,
+				Some(100),
+				0,
+				None,
+			),
+			Error::<Test>::UpdatingLimitOrdersDisabled,
+		);
+		assert_ok!(LiquidityPools::cancel_all_orders(RuntimeOrigin::signed(ALICE), None));
+		assert!(!has_orders(FLIP));
+		assert!(!LimitOrderExpiries::<Test>::contains_key(10));
+	});
+}
+
+#[test]
+fn pool_stats_record_swap_volume_and_fees() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		const POOL_FEE: u32 = 10_000;
+		let pool_fee = Permill::from_parts(POOL_FEE);
+
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			POOL_FEE,
+			price_at_tick(0).unwrap(),
+		));
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			ETH,
+			STABLE_ASSET,
+			0,
+			Some(-100_000..100_000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		assert_eq!(
+			LiquidityPools::pool_stats(ETH, STABLE_ASSET, Some(&ALICE)),
+			Some(PoolStats {
+				swaps: Default::default(),
+				lp_collected_fees: Some(Default::default()),
+			})
+		);
+
+		assert_ok!(LiquidityPools::swap_with_network_fee(ETH, STABLE_ASSET, 1_000));
+		let stats = LiquidityPools::pool_stats(ETH, STABLE_ASSET, None).unwrap();
+		assert_eq!(stats.lp_collected_fees, None);
+		assert_eq!(stats.swaps.pair, SwapStats::default());
+		assert_eq!(stats.swaps.base.volume, 1_000.into());
+		assert_eq!(stats.swaps.base.lp_fees, (pool_fee * 1_000u128).into());
+		assert_eq!(stats.swaps.base.network_fees, CollectedNetworkFee::<Test>::get().into());
+		assert!(!stats.swaps.base.network_fees.is_zero());
+
+		// The stats are the same whichever way around the pool is requested.
+		assert_eq!(
+			LiquidityPools::pool_stats(STABLE_ASSET, ETH, None).unwrap().swaps.pair,
+			stats.swaps.base
+		);
+
+		// Fees are recorded against the LP once they are collected.
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			ETH,
+			STABLE_ASSET,
+			0,
+			None,
+			RangeOrderSize::Liquidity { liquidity: 0 },
+		));
+		let lp_collected_fees = LiquidityPools::pool_stats(ETH, STABLE_ASSET, Some(&ALICE))
+			.unwrap()
+			.lp_collected_fees
+			.unwrap();
+		assert!(!lp_collected_fees.base.is_zero());
+		assert!(lp_collected_fees.base <= stats.swaps.base.lp_fees);
+		assert!(lp_collected_fees.pair.is_zero());
+	});
+}
+
+#[test]
+fn lp_fees_are_paid_by_limit_and_range_orders() {
+	new_test_ext().execute_with(|| {
+		const ETH: Asset = Asset::Eth;
+		const POOL_FEE: u32 = 10_000;
+		const AMOUNT: AssetAmount = 2_000;
+
+		assert_ok!(LiquidityPools::new_pool(
+			RuntimeOrigin::root(),
+			ETH,
+			STABLE_ASSET,
+			POOL_FEE,
+			price_at_tick(0).unwrap(),
+		));
+		assert_ok!(LiquidityPools::set_range_order(
+			RuntimeOrigin::signed(ALICE),
+			ETH,
+			STABLE_ASSET,
+			0,
+			Some(-100_000..100_000),
+			RangeOrderSize::Liquidity { liquidity: 1_000_000 },
+		));
+		// The swap is executed against the limit order first, then the range order.
+		assert_ok!(LiquidityPools::set_limit_order(
+			RuntimeOrigin::signed(BOB),
+			STABLE_ASSET,
+			ETH,
+			0,
+			Some(0),
+			1_000,
+			None,
+		));
+
+		let breakdown = LiquidityPools::swap_with_breakdown(ETH, STABLE_ASSET, AMOUNT).unwrap();
+		let [(ETH, lp_fee)] = breakdown.pool_fees[..] else { panic!("Expected a single pool fee") };
+		assert!(lp_fee.abs_diff(Permill::from_parts(POOL_FEE) * AMOUNT) <= 1);
+		assert!(LiquidityPools::pool_orders(ETH, STABLE_ASSET, &BOB)
+			.unwrap()
+			.limit_orders
+			.pair
+			.iter()
+			.all(|(_, _, amount)| amount.is_zero()));
+
+		// The stats record the fees the swap paid.
+		assert_eq!(
+			LiquidityPools::pool_stats(ETH, STABLE_ASSET, None).unwrap().swaps.base.lp_fees,
+			lp_fee.into()
+		);
+	});
+}
@@ -22,6 +22,10 @@
 // --repeat=10
 // --template=state-chain/chainflip-weight-template.hbs
 
+// NOTE: The storage entries of the order calls and the `swap_single_leg` and `update_orders` weights
+// were updated by hand after the benchmarks changed, and are estimates until this file is
+// regenerated with `state-chain/scripts/benchmark.sh pools`.
+
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -40,6 +44,8 @@ pub trait WeightInfo {
 	fn update_limit_order() -> Weight;
 	fn set_limit_order() -> Weight;
 	fn set_pool_fees() -> Weight;
+	fn swap_single_leg() -> Weight;
+	fn update_orders(r: u32, l: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_pools using the Substrate node and recommended hardware.
@@ -87,14 +93,18 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `LiquidityProvider::FreeBalances` (r:2 w:2)
 	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn update_range_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `7391`
 		// Minimum execution time: 93_773_000 picoseconds.
 		Weight::from_parts(94_559_000, 7391)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
@@ -106,14 +116,18 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `LiquidityProvider::FreeBalances` (r:2 w:2)
 	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn set_range_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `7391`
 		// Minimum execution time: 94_229_000 picoseconds.
 		Weight::from_parts(95_328_000, 7391)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
@@ -125,14 +139,22 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `LiquidityProvider::FreeBalances` (r:1 w:1)
 	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiryBlocks` (r:1 w:1)
+	/// Proof: `LiquidityPools::LimitOrderExpiryBlocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiries` (r:2 w:2)
+	/// Proof: `LiquidityPools::LimitOrderExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn update_limit_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `7391`
 		// Minimum execution time: 74_830_000 picoseconds.
 		Weight::from_parts(75_962_000, 7391)
-			.saturating_add(T::DbWeight::get().reads(6_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
@@ -144,14 +166,22 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `LiquidityProvider::FreeBalances` (r:1 w:1)
 	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiryBlocks` (r:1 w:1)
+	/// Proof: `LiquidityPools::LimitOrderExpiryBlocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiries` (r:2 w:2)
+	/// Proof: `LiquidityPools::LimitOrderExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn set_limit_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `7391`
 		// Minimum execution time: 74_496_000 picoseconds.
 		Weight::from_parts(75_207_000, 7391)
-			.saturating_add(T::DbWeight::get().reads(6_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
 	/// Storage: `LiquidityPools::Pools` (r:1 w:1)
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -166,6 +196,62 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: `LiquidityPools::Pools` (r:1 w:1)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolSwapStats` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolSwapStats` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn swap_single_leg() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1642`
+		//  Estimated: `5107`
+		// Minimum execution time: 58_922_000 picoseconds.
+		Weight::from_parts(59_873_000, 5107)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::LiquidityRefundAddress` (r:1 w:0)
+	/// Proof: `LiquidityProvider::LiquidityRefundAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:1)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::FreeBalances` (r:1 w:1)
+	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiryBlocks` (r:50 w:50)
+	/// Proof: `LiquidityPools::LimitOrderExpiryBlocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiries` (r:50 w:50)
+	/// Proof: `LiquidityPools::LimitOrderExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `r` is `[0, 50]`.
+	/// The range of component `l` is `[0, 50]`.
+	fn update_orders(r: u32, l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1451 + l * (72 ±0) + r * (64 ±0)`
+		//  Estimated: `7391 + l * (2547 ±0) + r * (64 ±0)`
+		// Minimum execution time: 57_431_000 picoseconds.
+		Weight::from_parts(31_806_000, 7391)
+			// Standard Error: 41_372
+			.saturating_add(Weight::from_parts(71_394_000, 0).saturating_mul(r.into()))
+			// Standard Error: 41_372
+			.saturating_add(Weight::from_parts(58_217_000, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().reads((7_u64).saturating_mul(l.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2547).saturating_mul(l.into()))
+			.saturating_add(Weight::from_parts(0, 64).saturating_mul(r.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -212,14 +298,18 @@ impl WeightInfo for () {
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `LiquidityProvider::FreeBalances` (r:2 w:2)
 	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn update_range_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `7391`
 		// Minimum execution time: 93_773_000 picoseconds.
 		Weight::from_parts(94_559_000, 7391)
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
@@ -231,14 +321,18 @@ impl WeightInfo for () {
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `LiquidityProvider::FreeBalances` (r:2 w:2)
 	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn set_range_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `7391`
 		// Minimum execution time: 94_229_000 picoseconds.
 		Weight::from_parts(95_328_000, 7391)
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
@@ -250,14 +344,22 @@ impl WeightInfo for () {
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `LiquidityProvider::FreeBalances` (r:1 w:1)
 	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiryBlocks` (r:1 w:1)
+	/// Proof: `LiquidityPools::LimitOrderExpiryBlocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiries` (r:2 w:2)
+	/// Proof: `LiquidityPools::LimitOrderExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn update_limit_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `7391`
 		// Minimum execution time: 74_830_000 picoseconds.
 		Weight::from_parts(75_962_000, 7391)
-			.saturating_add(RocksDbWeight::get().reads(6_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
@@ -269,14 +371,22 @@ impl WeightInfo for () {
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `LiquidityProvider::FreeBalances` (r:1 w:1)
 	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiryBlocks` (r:1 w:1)
+	/// Proof: `LiquidityPools::LimitOrderExpiryBlocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiries` (r:2 w:2)
+	/// Proof: `LiquidityPools::LimitOrderExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn set_limit_order() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1451`
 		//  Estimated: `7391`
 		// Minimum execution time: 74_496_000 picoseconds.
 		Weight::from_parts(75_207_000, 7391)
-			.saturating_add(RocksDbWeight::get().reads(6_u64))
-			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `LiquidityPools::Pools` (r:1 w:1)
 	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -291,4 +401,60 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: `LiquidityPools::Pools` (r:1 w:1)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolSwapStats` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolSwapStats` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn swap_single_leg() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1642`
+		//  Estimated: `5107`
+		// Minimum execution time: 58_922_000 picoseconds.
+		Weight::from_parts(59_873_000, 5107)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::LiquidityRefundAddress` (r:1 w:0)
+	/// Proof: `LiquidityProvider::LiquidityRefundAddress` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:1)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::FreeBalances` (r:1 w:1)
+	/// Proof: `LiquidityProvider::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservationCursors` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservationCursors` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::PoolObservations` (r:1 w:1)
+	/// Proof: `LiquidityPools::PoolObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiryBlocks` (r:50 w:50)
+	/// Proof: `LiquidityPools::LimitOrderExpiryBlocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::LimitOrderExpiries` (r:50 w:50)
+	/// Proof: `LiquidityPools::LimitOrderExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `r` is `[0, 50]`.
+	/// The range of component `l` is `[0, 50]`.
+	fn update_orders(r: u32, l: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1451 + l * (72 ±0) + r * (64 ±0)`
+		//  Estimated: `7391 + l * (2547 ±0) + r * (64 ±0)`
+		// Minimum execution time: 57_431_000 picoseconds.
+		Weight::from_parts(31_806_000, 7391)
+			// Standard Error: 41_372
+			.saturating_add(Weight::from_parts(71_394_000, 0).saturating_mul(r.into()))
+			// Standard Error: 41_372
+			.saturating_add(Weight::from_parts(58_217_000, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().reads((7_u64).saturating_mul(l.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(l.into())))
+			.saturating_add(Weight::from_parts(0, 2547).saturating_mul(l.into()))
+			.saturating_add(Weight::from_parts(0, 64).saturating_mul(r.into()))
+	}
 }
@@ -21,6 +21,7 @@ benchmarks! {
 			destination_address: EncodedAddress::benchmark_value(),
 			broker_commission_bps: 0,
 			channel_metadata: None,
+			refund_parameters: None,
 		};
 	} : { call.dispatch_bypass_filter(origin.into())?; }
 
@@ -62,6 +63,7 @@ benchmarks! {
 			deposit_amount,
 			destination_address: EncodedAddress::benchmark_value(),
 			tx_hash: [0; 32],
+			refund_parameters: None,
 		};
 	}: {
 		call.dispatch_bypass_filter(witness_origin)?;
@@ -149,6 +151,26 @@ benchmarks! {
 		assert_eq!(crate::MaximumSwapAmount::<T>::get(asset), Some(amount));
 	}
 
+	refund_swaps {
+		let n in 1..100;
+
+		let swaps = (0..n as u64).map(|swap_id| Swap {
+			refund_parameters: Some(SwapRefundParameters {
+				refund_address: ForeignChainAddress::benchmark_value(),
+				min_output: AssetAmount::MAX,
+			}),
+			..Swap::new(
+				swap_id,
+				Asset::Eth,
+				Asset::Usdc,
+				1_000,
+				SwapType::Swap(ForeignChainAddress::benchmark_value()),
+			)
+		}).collect::<Vec<_>>();
+	}: {
+		swaps.into_iter().for_each(Pallet::<T>::refund_swap);
+	}
+
 	impl_benchmark_test_suite!(
 		Pallet,
 		crate::mock::new_test_ext(),
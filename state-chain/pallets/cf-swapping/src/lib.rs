@@ -1,7 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use cf_chains::{
 	address::{AddressConverter, ForeignChainAddress},
-	CcmChannelMetadata, CcmDepositMetadata, SwapOrigin,
+	CcmChannelMetadata, CcmDepositMetadata, SwapOrigin, SwapRefundParameters,
 };
 use cf_primitives::{
 	Asset, AssetAmount, ChannelId, ForeignChain, SwapLeg, TransactionHash, STABLE_ASSET,
@@ -18,7 +18,11 @@ use frame_support::{
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use sp_arithmetic::{helpers_128bit::multiply_by_rational_with_rounding, traits::Zero, Rounding};
-use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec,
+	vec::Vec,
+};
 #[cfg(test)]
 mod mock;
 
@@ -31,10 +35,14 @@ pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(1);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(2);
 
 const BASIS_POINTS_PER_MILLION: u32 = 100;
 
+/// How often a batch is re-executed after removing the swaps that did not meet their minimum
+/// output, before all swaps with a minimum output are refunded.
+const MAX_SWAP_RETRIES: u32 = 2;
+
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum SwapType {
 	Swap(ForeignChainAddress),
@@ -51,6 +59,7 @@ pub struct Swap {
 	pub stable_amount: Option<AssetAmount>,
 	pub final_output: Option<AssetAmount>,
 	pub fee_taken: bool,
+	pub refund_parameters: Option<SwapRefundParameters>,
 }
 
 impl Swap {
@@ -64,6 +73,7 @@ impl Swap {
 			stable_amount: if from == STABLE_ASSET { Some(amount) } else { None },
 			final_output: if from == to { Some(amount) } else { None },
 			fee_taken: false,
+			refund_parameters: None,
 		}
 	}
 
@@ -95,6 +105,15 @@ impl Swap {
 		}
 	}
 
+	/// Returns true if the swap has been executed, but its output is below the minimum the user
+	/// asked for.
+	fn is_below_min_output(&self) -> bool {
+		match (&self.refund_parameters, self.final_output) {
+			(Some(SwapRefundParameters { min_output, .. }), Some(output)) => output < *min_output,
+			_ => false,
+		}
+	}
+
 	fn intermediate_amount(&self) -> Option<AssetAmount> {
 		if self.from == STABLE_ASSET || self.to == STABLE_ASSET {
 			None
@@ -119,6 +138,7 @@ pub(crate) struct CcmSwapOutput {
 
 enum BatchExecutionError {
 	SwapLegFailed { asset: Asset, direction: SwapLeg, amount: AssetAmount },
+	MinOutputNotMet { swap_ids: BTreeSet<u64> },
 	DispatchError { error: DispatchError },
 }
 
@@ -165,6 +185,7 @@ pub enum CcmFailReason {
 	UnsupportedForTargetChain,
 	InsufficientDepositAmount,
 	PrincipalSwapAmountTooLow,
+	MessageTooLongForTargetChain,
 }
 
 impl_pallet_safe_mode! {
@@ -176,7 +197,9 @@ pub mod pallet {
 
 	use cf_chains::{address::EncodedAddress, AnyChain, Chain};
 	use cf_primitives::{Asset, AssetAmount, BasisPoints, EgressId};
-	use cf_traits::{AccountRoleRegistry, Chainflip, EgressApi, SwapDepositHandler};
+	use cf_traits::{
+		AccountRoleRegistry, Chainflip, EgressApi, EgressFeeEstimator, SwapDepositHandler,
+	};
 
 	use super::*;
 
@@ -195,6 +218,9 @@ pub mod pallet {
 		/// API for handling asset egress.
 		type EgressHandler: EgressApi<AnyChain>;
 
+		/// Estimates the egress fee that is deducted from refunds.
+		type EgressFeeEstimator: EgressFeeEstimator<AnyChain>;
+
 		/// An interface to the AMM api implementation.
 		type SwappingApi: SwappingApi;
 
@@ -235,6 +261,11 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type CcmGasBudget<T: Config> = StorageMap<_, Twox64Concat, u64, (Asset, AssetAmount)>;
 
+	/// The address designated to receive the unused gas of each pending CCM, if any.
+	#[pallet::storage]
+	pub(crate) type CcmGasRefundAddress<T: Config> =
+		StorageMap<_, Twox64Concat, u64, ForeignChainAddress>;
+
 	/// Storage for storing CCMs pending assets to be swapped.
 	#[pallet::storage]
 	pub(crate) type PendingCcms<T: Config> = StorageMap<_, Twox64Concat, u64, CcmSwap>;
@@ -272,6 +303,7 @@ pub mod pallet {
 			broker_commission_rate: BasisPoints,
 			channel_metadata: Option<CcmChannelMetadata>,
 			source_chain_expiry_block: <AnyChain as Chain>::ChainBlockNumber,
+			refund_parameters: Option<SwapRefundParameters<EncodedAddress>>,
 		},
 		/// A swap deposit has been received.
 		SwapScheduled {
@@ -351,6 +383,34 @@ pub mod pallet {
 			total_amount: AssetAmount,
 			confiscated_amount: AssetAmount,
 		},
+		/// Funds that could not be swapped have been returned to the user's refund address.
+		RejectedFundsRefunded {
+			asset: Asset,
+			amount: AssetAmount,
+			egress_fee: AssetAmount,
+			refund_address: EncodedAddress,
+			egress_id: EgressId,
+			origin: SwapOrigin,
+		},
+		/// A swap would have output less than its minimum, so the deposit has been refunded, less
+		/// the egress fee.
+		SwapRefunded {
+			swap_id: u64,
+			asset: Asset,
+			amount: AssetAmount,
+			egress_fee: AssetAmount,
+			min_output: AssetAmount,
+			refund_address: EncodedAddress,
+			egress_id: EgressId,
+		},
+		/// A swap would have output less than its minimum, but the deposit does not cover the
+		/// egress fee of a refund, so it has been kept.
+		SwapRefundCollected {
+			swap_id: u64,
+			asset: Asset,
+			amount: AssetAmount,
+			min_output: AssetAmount,
+		},
 	}
 	#[pallet::error]
 	pub enum Error<T> {
@@ -374,6 +434,10 @@ pub mod pallet {
 		DepositsDisabled,
 		/// Broker registration is disabled due to Safe Mode.
 		BrokerRegistrationDisabled,
+		/// A minimum output can not be set for cross chain messages.
+		MinOutputUnsupportedForCcm,
+		/// The CCM message is too long to be delivered to the target chain.
+		CcmMessageTooLongForTargetChain,
 	}
 
 	#[pallet::genesis_config]
@@ -409,21 +473,40 @@ pub mod pallet {
 			if let Err(failed_swap) = with_storage_layer(|| -> Result<(), BatchExecutionError> {
 				let mut swaps = SwapQueue::<T>::take();
 
-				// Swap into Stable asset first.
-				Self::do_group_and_swap(&mut swaps, SwapLeg::ToStable)?;
-
-				// Take NetworkFee for all swaps
-				for swap in swaps.iter_mut() {
-					debug_assert!(
-						swap.stable_amount.is_some(),
-						"All swaps should have Stable amount set here"
-					);
-					let stable_amount = swap.stable_amount.get_or_insert_with(Default::default);
-					*stable_amount = T::SwappingApi::take_network_fee(*stable_amount);
-				}
-
-				// Swap from Stable asset, and complete the swap logic.
-				Self::do_group_and_swap(&mut swaps, SwapLeg::FromStable)?;
+				// Swaps that would output less than their minimum are removed and refunded, and the
+				// remaining swaps are re-executed as a batch. Removing swaps changes the prices for
+				// the rest of the batch, so once the retries are used up, all remaining swaps with
+				// a minimum output are refunded. The last attempt can then no longer fail. Swaps of
+				// a CCM have no minimum output, so both legs of a CCM are always executed.
+				let mut retries = 0;
+				let mut refunded_swaps = 0u32;
+				let swaps = loop {
+					match Self::try_execute_batch(swaps.clone()) {
+						Ok(executed_swaps) => break executed_swaps,
+						Err(BatchExecutionError::MinOutputNotMet { swap_ids }) => {
+							let (refunded, remaining) = if retries < MAX_SWAP_RETRIES {
+								swaps
+									.into_iter()
+									.partition::<Vec<_>, _>(|swap| swap_ids.contains(&swap.swap_id))
+							} else {
+								swaps.into_iter().partition::<Vec<_>, _>(|swap| {
+									swap.refund_parameters
+										.as_ref()
+										.map_or(false, |params| params.min_output > 0)
+								})
+							};
+							retries += 1;
+							refunded_swaps.saturating_accrue(refunded.len() as u32);
+							refunded.into_iter().for_each(Self::refund_swap);
+							swaps = remaining;
+						},
+						Err(other) => return Err(other),
+					}
+				};
+				frame_system::Pallet::<T>::register_extra_weight_unchecked(
+					T::WeightInfo::refund_swaps(refunded_swaps),
+					DispatchClass::Mandatory,
+				);
 
 				for swap in swaps {
 					if let Some(egress_amount) = swap.final_output {
@@ -493,6 +576,11 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Request a swap deposit address.
 		///
+		/// If `refund_parameters` are provided, the swap is only executed if it outputs at least
+		/// `min_output`, otherwise the deposit is refunded to the given address on the source
+		/// chain. Deposits that are rejected, for example because they are below the minimum swap
+		/// amount, are also refunded to this address.
+		///
 		/// ## Events
 		///
 		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
@@ -505,6 +593,7 @@ pub mod pallet {
 			destination_address: EncodedAddress,
 			broker_commission_bps: BasisPoints,
 			channel_metadata: Option<CcmChannelMetadata>,
+			refund_parameters: Option<SwapRefundParameters<EncodedAddress>>,
 		) -> DispatchResult {
 			ensure!(T::SafeMode::get().deposits_enabled, Error::<T>::DepositsDisabled);
 			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
@@ -512,14 +601,28 @@ pub mod pallet {
 			let destination_address_internal =
 				Self::validate_destination_address(&destination_address, destination_asset)?;
 
-			if channel_metadata.is_some() {
-				// Currently only Ethereum supports CCM.
+			if let Some(channel_metadata) = &channel_metadata {
+				let max_message_length =
+					cf_chains::max_ccm_message_length(destination_asset.into())
+						.ok_or(Error::<T>::CcmUnsupportedForTargetChain)?;
+				ensure!(
+					channel_metadata.message.len() <= max_message_length as usize,
+					Error::<T>::CcmMessageTooLongForTargetChain
+				);
 				ensure!(
-					ForeignChain::Ethereum == destination_asset.into(),
-					Error::<T>::CcmUnsupportedForTargetChain
+					refund_parameters.as_ref().map_or(true, |params| params.min_output.is_zero()),
+					Error::<T>::MinOutputUnsupportedForCcm
 				);
 			}
 
+			let refund_parameters_internal = refund_parameters
+				.map(|params| {
+					params.try_map_address(|refund_address| {
+						Self::validate_destination_address(&refund_address, source_asset)
+					})
+				})
+				.transpose()?;
+
 			let (channel_id, deposit_address, expiry_height) =
 				T::DepositHandler::request_swap_deposit_address(
 					source_asset,
@@ -528,6 +631,7 @@ pub mod pallet {
 					broker_commission_bps,
 					broker,
 					channel_metadata.clone(),
+					refund_parameters_internal,
 				)?;
 
 			Self::deposit_event(Event::<T>::SwapDepositAddressReady {
@@ -539,6 +643,7 @@ pub mod pallet {
 				broker_commission_rate: broker_commission_bps,
 				channel_metadata,
 				source_chain_expiry_block: expiry_height,
+				refund_parameters,
 			});
 
 			Ok(())
@@ -596,11 +701,19 @@ pub mod pallet {
 			deposit_amount: AssetAmount,
 			destination_address: EncodedAddress,
 			tx_hash: TransactionHash,
+			refund_parameters: Option<SwapRefundParameters<EncodedAddress>>,
 		) -> DispatchResult {
 			T::EnsureWitnessed::ensure_origin(origin)?;
 
 			let destination_address_internal =
 				Self::validate_destination_address(&destination_address, to)?;
+			let refund_parameters = refund_parameters
+				.map(|params| {
+					params.try_map_address(|refund_address| {
+						Self::validate_destination_address(&refund_address, from)
+					})
+				})
+				.transpose()?;
 			let swap_origin = SwapOrigin::Vault { tx_hash };
 
 			if let Some(swap_id) = Self::schedule_swap_with_check(
@@ -609,6 +722,7 @@ pub mod pallet {
 				deposit_amount,
 				destination_address_internal.clone(),
 				&swap_origin,
+				refund_parameters,
 			) {
 				Self::deposit_event(Event::<T>::SwapScheduled {
 					swap_id,
@@ -648,6 +762,7 @@ pub mod pallet {
 				destination_address_internal,
 				deposit_metadata,
 				SwapOrigin::Vault { tx_hash },
+				None,
 			);
 
 			Ok(())
@@ -725,11 +840,18 @@ pub mod pallet {
 			source_asset: Asset,
 			destination_asset: Asset,
 		) -> Result<CcmSwapAmounts, CcmFailReason> {
-			let gas_budget = channel_metadata.gas_budget;
+			let gas_budget = if cf_chains::ccm_uses_gas_budget(destination_asset.into()) {
+				channel_metadata.gas_budget
+			} else {
+				0
+			};
 			let principal_swap_amount = deposit_amount.saturating_sub(gas_budget);
 
-			if ForeignChain::Ethereum != destination_asset.into() {
-				return Err(CcmFailReason::UnsupportedForTargetChain)
+			let max_message_length = cf_chains::max_ccm_message_length(destination_asset.into())
+				.ok_or(CcmFailReason::UnsupportedForTargetChain)?;
+
+			if channel_metadata.message.len() > max_message_length as usize {
+				return Err(CcmFailReason::MessageTooLongForTargetChain)
 			} else if deposit_amount < gas_budget {
 				return Err(CcmFailReason::InsufficientDepositAmount)
 			} else if source_asset != destination_asset &&
@@ -767,6 +889,161 @@ pub mod pallet {
 			Ok(destination_address_internal)
 		}
 
+		/// Executes the batch in its own storage layer. The weight of each swap leg is registered
+		/// as it is executed, and rolled back with the rest of the storage if the batch fails, so
+		/// the weight of a failed attempt is registered again.
+		fn try_execute_batch(swaps: Vec<Swap>) -> Result<Vec<Swap>, BatchExecutionError> {
+			let mut failed_attempt_weight = Weight::zero();
+			let result = with_storage_layer(|| {
+				let weight_before = frame_system::Pallet::<T>::block_weight().total();
+				Self::execute_batch(swaps).map_err(|error| {
+					failed_attempt_weight = frame_system::Pallet::<T>::block_weight()
+						.total()
+						.saturating_sub(weight_before);
+					error
+				})
+			});
+			if result.is_err() {
+				frame_system::Pallet::<T>::register_extra_weight_unchecked(
+					failed_attempt_weight,
+					DispatchClass::Mandatory,
+				);
+			}
+			result
+		}
+
+		/// Executes both legs of the batch and takes the network fee. Fails with
+		/// [BatchExecutionError::MinOutputNotMet] if any swap's output is below its minimum.
+		fn execute_batch(mut swaps: Vec<Swap>) -> Result<Vec<Swap>, BatchExecutionError> {
+			// Swap into Stable asset first.
+			Self::do_group_and_swap(&mut swaps, SwapLeg::ToStable)?;
+
+			// Take NetworkFee for all swaps
+			for swap in swaps.iter_mut() {
+				debug_assert!(
+					swap.stable_amount.is_some(),
+					"All swaps should have Stable amount set here"
+				);
+				let stable_amount = swap.stable_amount.get_or_insert_with(Default::default);
+				*stable_amount =
+					T::SwappingApi::take_network_fee(*stable_amount, swap.from, swap.to);
+			}
+
+			// Swap from Stable asset, and complete the swap logic.
+			Self::do_group_and_swap(&mut swaps, SwapLeg::FromStable)?;
+
+			let swap_ids = swaps
+				.iter()
+				.filter(|swap| swap.is_below_min_output())
+				.map(|swap| swap.swap_id)
+				.collect::<BTreeSet<_>>();
+			if swap_ids.is_empty() {
+				Ok(swaps)
+			} else {
+				Err(BatchExecutionError::MinOutputNotMet { swap_ids })
+			}
+		}
+
+		/// Deducts the estimated egress fee from an amount that is to be refunded. Returns the
+		/// refund and the fee, or `None` if the amount does not cover the fee or the fee cannot be
+		/// estimated.
+		fn deduct_refund_egress_fee(
+			asset: Asset,
+			amount: AssetAmount,
+		) -> Option<(AssetAmount, AssetAmount)> {
+			let egress_fee = T::EgressFeeEstimator::estimate_egress_fee(asset)?;
+			amount
+				.checked_sub(egress_fee)
+				.filter(|refund| !refund.is_zero())
+				.map(|refund| (refund, egress_fee))
+		}
+
+		fn collect_rejected_funds(asset: Asset, amount: AssetAmount) {
+			if !amount.is_zero() {
+				CollectedRejectedFunds::<T>::mutate(asset, |fund| {
+					*fund = fund.saturating_add(amount)
+				});
+			}
+		}
+
+		/// Returns the deposited funds of a swap that did not meet its minimum output, less the
+		/// egress fee. The fee is kept in [CollectedRejectedFunds], as is the whole deposit if it
+		/// does not cover the fee.
+		fn refund_swap(swap: Swap) {
+			if let Some(SwapRefundParameters { refund_address, min_output }) =
+				swap.refund_parameters
+			{
+				match Self::deduct_refund_egress_fee(swap.from, swap.amount) {
+					Some((refund_amount, egress_fee)) => {
+						Self::collect_rejected_funds(swap.from, egress_fee);
+						let egress_id = T::EgressHandler::schedule_egress(
+							swap.from,
+							refund_amount,
+							refund_address.clone(),
+							None,
+						);
+						Self::deposit_event(Event::<T>::SwapRefunded {
+							swap_id: swap.swap_id,
+							asset: swap.from,
+							amount: refund_amount,
+							egress_fee,
+							min_output,
+							refund_address: T::AddressConverter::to_encoded_address(refund_address),
+							egress_id,
+						});
+					},
+					None => {
+						Self::collect_rejected_funds(swap.from, swap.amount);
+						Self::deposit_event(Event::<T>::SwapRefundCollected {
+							swap_id: swap.swap_id,
+							asset: swap.from,
+							amount: swap.amount,
+							min_output,
+						});
+					},
+				}
+			} else {
+				debug_assert!(false, "Only swaps with refund parameters can be refunded.");
+			}
+		}
+
+		/// Returns funds that were rejected by the protocol to the user's refund address, less the
+		/// egress fee. The fee is kept in [CollectedRejectedFunds], as are the whole funds if no
+		/// refund address is known or they do not cover the fee.
+		fn refund_or_collect_rejected_funds(
+			asset: Asset,
+			amount: AssetAmount,
+			refund_address: Option<ForeignChainAddress>,
+			origin: &SwapOrigin,
+		) {
+			if amount.is_zero() {
+				return
+			}
+			match refund_address.and_then(|refund_address| {
+				Self::deduct_refund_egress_fee(asset, amount)
+					.map(|(refund_amount, egress_fee)| (refund_address, refund_amount, egress_fee))
+			}) {
+				Some((refund_address, refund_amount, egress_fee)) => {
+					Self::collect_rejected_funds(asset, egress_fee);
+					let egress_id = T::EgressHandler::schedule_egress(
+						asset,
+						refund_amount,
+						refund_address.clone(),
+						None,
+					);
+					Self::deposit_event(Event::<T>::RejectedFundsRefunded {
+						asset,
+						amount: refund_amount,
+						egress_fee,
+						refund_address: T::AddressConverter::to_encoded_address(refund_address),
+						egress_id,
+						origin: origin.clone(),
+					});
+				},
+				None => Self::collect_rejected_funds(asset, amount),
+			}
+		}
+
 		// Helper function that splits swaps of a given direction, group them by asset
 		// and do the swaps of a given direction. Processed and unprocessed swaps are
 		// returned.
@@ -902,7 +1179,11 @@ pub mod pallet {
 				ccm_swap.destination_asset,
 				ccm_output_principal,
 				ccm_swap.destination_address.clone(),
-				Some((ccm_swap.deposit_metadata, ccm_output_gas)),
+				Some((
+					ccm_swap.deposit_metadata,
+					ccm_output_gas,
+					CcmGasRefundAddress::<T>::take(ccm_id),
+				)),
 			);
 			if let Some(swap_id) = ccm_swap.principal_swap_id {
 				Self::deposit_event(Event::<T>::SwapEgressScheduled {
@@ -929,6 +1210,8 @@ pub mod pallet {
 			to: Asset,
 			amount: AssetAmount,
 			swap_type: SwapType,
+			refund_parameters: Option<SwapRefundParameters>,
+			origin: &SwapOrigin,
 		) -> u64 {
 			let swap_id = SwapIdCounter::<T>::mutate(|id| {
 				id.saturating_accrue(1);
@@ -939,9 +1222,6 @@ pub mod pallet {
 				None => (amount, Zero::zero()),
 			};
 			if !confiscated_amount.is_zero() {
-				CollectedRejectedFunds::<T>::mutate(from, |fund| {
-					*fund = fund.saturating_add(confiscated_amount)
-				});
 				Self::deposit_event(Event::<T>::SwapAmountConfiscated {
 					swap_id,
 					source_asset: from,
@@ -949,9 +1229,18 @@ pub mod pallet {
 					total_amount: amount,
 					confiscated_amount,
 				});
+				Self::refund_or_collect_rejected_funds(
+					from,
+					confiscated_amount,
+					refund_parameters.as_ref().map(|params| params.refund_address.clone()),
+					origin,
+				);
 			}
 
-			SwapQueue::<T>::append(Swap::new(swap_id, from, to, swap_amount, swap_type));
+			SwapQueue::<T>::append(Swap {
+				refund_parameters,
+				..Swap::new(swap_id, from, to, swap_amount, swap_type)
+			});
 
 			swap_id
 		}
@@ -963,13 +1252,11 @@ pub mod pallet {
 			amount: AssetAmount,
 			destination_address: ForeignChainAddress,
 			swap_origin: &SwapOrigin,
+			refund_parameters: Option<SwapRefundParameters>,
 		) -> Option<u64> {
 			if amount < MinimumSwapAmount::<T>::get(from) {
 				// If the swap amount is less than the minimum required,
-				// confiscate the fund and emit an event
-				CollectedRejectedFunds::<T>::mutate(from, |fund| {
-					*fund = fund.saturating_add(amount)
-				});
+				// refund or confiscate the fund and emit an event
 				Self::deposit_event(Event::<T>::SwapAmountTooLow {
 					asset: from,
 					amount,
@@ -978,6 +1265,12 @@ pub mod pallet {
 					),
 					origin: swap_origin.clone(),
 				});
+				Self::refund_or_collect_rejected_funds(
+					from,
+					amount,
+					refund_parameters.map(|params| params.refund_address),
+					swap_origin,
+				);
 				None
 			} else {
 				// Otherwise schedule the swap.
@@ -986,6 +1279,8 @@ pub mod pallet {
 					to,
 					amount,
 					SwapType::Swap(destination_address),
+					refund_parameters,
+					swap_origin,
 				))
 			}
 		}
@@ -1005,6 +1300,7 @@ pub mod pallet {
 			broker_id: Self::AccountId,
 			broker_commission_bps: BasisPoints,
 			channel_id: ChannelId,
+			refund_parameters: Option<SwapRefundParameters>,
 		) {
 			// Permill maxes out at 100% so this is safe.
 			let fee = Permill::from_parts(broker_commission_bps as u32 * BASIS_POINTS_PER_MILLION) *
@@ -1031,6 +1327,7 @@ pub mod pallet {
 				amount,
 				destination_address.clone(),
 				&swap_origin,
+				refund_parameters,
 			) {
 				Self::deposit_event(Event::<T>::SwapScheduled {
 					swap_id,
@@ -1054,6 +1351,7 @@ pub mod pallet {
 			destination_address: ForeignChainAddress,
 			deposit_metadata: CcmDepositMetadata,
 			origin: SwapOrigin,
+			refund_address: Option<ForeignChainAddress>,
 		) {
 			let encoded_destination_address =
 				T::AddressConverter::to_encoded_address(destination_address.clone());
@@ -1069,25 +1367,37 @@ pub mod pallet {
 				) {
 					Ok(amounts) => amounts,
 					Err(reason) => {
-						// Confiscate the deposit and emit an event.
-						CollectedRejectedFunds::<T>::mutate(source_asset, |fund| {
-							*fund = fund.saturating_add(deposit_amount)
-						});
-
+						// Refund or confiscate the deposit and emit an event.
 						Self::deposit_event(Event::<T>::CcmFailed {
 							reason,
 							destination_address: encoded_destination_address,
 							deposit_metadata,
 						});
+						Self::refund_or_collect_rejected_funds(
+							source_asset,
+							deposit_amount,
+							refund_address,
+							&origin,
+						);
 						return
 					},
 				};
 
+			// Swaps of a CCM have no minimum output, the refund address is only used for
+			// rejected funds.
+			let refund_parameters = refund_address
+				.map(|refund_address| SwapRefundParameters { refund_address, min_output: 0 });
+
 			let ccm_id = CcmIdCounter::<T>::mutate(|id| {
 				id.saturating_accrue(1);
 				*id
 			});
 
+			// The refund address also receives any gas the message leaves unused.
+			if let Some(SwapRefundParameters { refund_address, .. }) = &refund_parameters {
+				CcmGasRefundAddress::<T>::insert(ccm_id, refund_address.clone());
+			}
+
 			let mut swap_output = CcmSwapOutput::default();
 
 			let principal_swap_id =
@@ -1100,6 +1410,8 @@ pub mod pallet {
 						destination_asset,
 						principal_swap_amount,
 						SwapType::CcmPrincipal(ccm_id),
+						refund_parameters.clone(),
+						&origin,
 					);
 					Self::deposit_event(Event::<T>::SwapScheduled {
 						swap_id,
@@ -1120,6 +1432,8 @@ pub mod pallet {
 					other_gas_asset,
 					gas_budget,
 					SwapType::CcmGas(ccm_id),
+					refund_parameters,
+					&origin,
 				);
 				Self::deposit_event(Event::<T>::SwapScheduled {
 					swap_id,
@@ -1,6 +1,9 @@
+pub mod add_refund_parameters;
 pub mod remove_expiries;
 
 use cf_runtime_upgrade_utilities::VersionedMigration;
 
-pub type PalletMigration<T> =
-	(VersionedMigration<crate::Pallet<T>, remove_expiries::Migration<T>, 0, 1>,);
+pub type PalletMigration<T> = (
+	VersionedMigration<crate::Pallet<T>, remove_expiries::Migration<T>, 0, 1>,
+	VersionedMigration<crate::Pallet<T>, add_refund_parameters::Migration<T>, 1, 2>,
+);
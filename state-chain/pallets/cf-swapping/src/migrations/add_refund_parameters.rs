@@ -0,0 +1,47 @@
+use crate::*;
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_std::marker::PhantomData;
+
+pub struct Migration<T: Config>(PhantomData<T>);
+
+mod old {
+
+	use super::*;
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub struct Swap {
+		pub swap_id: u64,
+		pub from: Asset,
+		pub to: Asset,
+		pub amount: AssetAmount,
+		pub swap_type: SwapType,
+		pub stable_amount: Option<AssetAmount>,
+		pub final_output: Option<AssetAmount>,
+		pub fee_taken: bool,
+	}
+}
+
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		let _ = SwapQueue::<T>::translate::<Vec<old::Swap>, _>(|maybe_old_swaps| {
+			maybe_old_swaps.map(|old_swaps| {
+				old_swaps
+					.into_iter()
+					.map(|old| Swap {
+						swap_id: old.swap_id,
+						from: old.from,
+						to: old.to,
+						amount: old.amount,
+						swap_type: old.swap_type,
+						stable_amount: old.stable_amount,
+						final_output: old.final_output,
+						fee_taken: old.fee_taken,
+						refund_parameters: None,
+					})
+					.collect()
+			})
+		});
+
+		Weight::zero()
+	}
+}
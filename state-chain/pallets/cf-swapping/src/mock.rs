@@ -68,10 +68,12 @@ parameter_types! {
 	pub static NetworkFee: Percent = Percent::from_percent(0);
 	pub static Swaps: Vec<(Asset, Asset, AssetAmount)> = vec![];
 	pub static SwapRate: f64 = 1f64;
+	/// Factor applied to the swap rate after every swap leg, to move prices between attempts.
+	pub static SwapRateChangePerLeg: f64 = 1f64;
 }
 pub struct MockSwappingApi;
 impl SwappingApi for MockSwappingApi {
-	fn take_network_fee(input_amount: AssetAmount) -> AssetAmount {
+	fn take_network_fee(input_amount: AssetAmount, _from: Asset, _to: Asset) -> AssetAmount {
 		input_amount - NetworkFee::get() * input_amount
 	}
 
@@ -83,7 +85,9 @@ impl SwappingApi for MockSwappingApi {
 		let mut swaps = Swaps::get();
 		swaps.push((from, to, input_amount));
 		Swaps::set(swaps);
-		Ok((input_amount as f64 * SwapRate::get()) as AssetAmount)
+		let output = (input_amount as f64 * SwapRate::get()) as AssetAmount;
+		SwapRate::set(SwapRate::get() * SwapRateChangePerLeg::get());
+		Ok(output)
 	}
 }
 
@@ -117,12 +121,17 @@ impl WeightInfo for MockWeightInfo {
 	fn set_maximum_swap_amount() -> Weight {
 		Weight::from_parts(100, 0)
 	}
+
+	fn refund_swaps(n: u32) -> Weight {
+		Weight::from_parts(100, 0).saturating_mul(n.into())
+	}
 }
 
 impl pallet_cf_swapping::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type DepositHandler = MockDepositHandler<AnyChain, Self>;
 	type EgressHandler = MockEgressHandler<AnyChain>;
+	type EgressFeeEstimator = MockEgressHandler<AnyChain>;
 	type AddressConverter = MockAddressConverter;
 	type SwappingApi = MockSwappingApi;
 	type SafeMode = MockRuntimeSafeMode;
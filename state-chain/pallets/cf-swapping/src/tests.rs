@@ -8,7 +8,7 @@ use cf_chains::{
 	address::{to_encoded_address, AddressConverter, EncodedAddress, ForeignChainAddress},
 	btc::{BitcoinNetwork, ScriptPubkey},
 	dot::PolkadotAccountId,
-	AnyChain, CcmChannelMetadata, CcmDepositMetadata,
+	AnyChain, CcmChannelMetadata, CcmDepositMetadata, SwapRefundParameters,
 };
 use cf_primitives::{Asset, AssetAmount, ForeignChain, NetworkEnvironment};
 use cf_test_utilities::assert_event_sequence;
@@ -76,6 +76,7 @@ fn assert_failed_ccm(
 		destination_address.clone(),
 		ccm.clone(),
 		SwapOrigin::Vault { tx_hash: Default::default() },
+		None,
 	);
 	System::assert_last_event(RuntimeEvent::Swapping(Event::CcmFailed {
 		reason,
@@ -97,6 +98,7 @@ fn insert_swaps(swaps: &[Swap]) {
 				broker_id as u64,
 				2,
 				1,
+				None,
 			);
 		}
 	}
@@ -126,6 +128,7 @@ fn request_swap_success_with_valid_parameters() {
 			Asset::Usdc,
 			EncodedAddress::Eth(Default::default()),
 			0,
+			None,
 			None
 		));
 	});
@@ -174,6 +177,7 @@ fn expect_earned_fees_to_be_recorded() {
 			ALICE,
 			200,
 			1,
+			None,
 		);
 		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, cf_primitives::Asset::Flip), 2);
 		<Pallet<Test> as SwapDepositHandler>::schedule_swap_from_channel(
@@ -186,6 +190,7 @@ fn expect_earned_fees_to_be_recorded() {
 			ALICE,
 			200,
 			1,
+			None,
 		);
 		assert_eq!(EarnedBrokerFees::<Test>::get(ALICE, cf_primitives::Asset::Flip), 4);
 	});
@@ -206,6 +211,7 @@ fn cannot_swap_with_incorrect_destination_address_type() {
 			ALICE,
 			2,
 			1,
+			None,
 		);
 		assert_eq!(SwapQueue::<Test>::get(), vec![]);
 	});
@@ -221,6 +227,7 @@ fn expect_swap_id_to_be_emitted() {
 			Asset::Usdc,
 			EncodedAddress::Eth(Default::default()),
 			0,
+			None,
 			None
 		));
 		// 2. Schedule the swap -> SwapScheduled
@@ -234,6 +241,7 @@ fn expect_swap_id_to_be_emitted() {
 			ALICE,
 			0,
 			1,
+			None,
 		);
 		// 3. Process swaps -> SwapExecuted, SwapEgressScheduled
 		Swapping::on_finalize(1);
@@ -314,6 +322,7 @@ fn can_swap_using_witness_origin() {
 			amount,
 			EncodedAddress::Eth(Default::default()),
 			Default::default(),
+			None,
 		));
 
 		System::assert_last_event(RuntimeEvent::Swapping(Event::<Test>::SwapScheduled {
@@ -361,22 +370,18 @@ fn reject_invalid_ccm_deposit() {
 			Error::<Test>::IncompatibleAssetAndAddress
 		);
 
-		assert_failed_ccm(
-			Asset::Eth,
-			1_000_000,
-			Asset::Dot,
-			ForeignChainAddress::Dot(Default::default()),
-			ccm.clone(),
-			CcmFailReason::UnsupportedForTargetChain,
-		);
-
+		let mut long_message_ccm = ccm.clone();
+		long_message_ccm.channel_metadata.message =
+			vec![0x01; cf_chains::btc::MAX_OP_RETURN_DATA_BYTES as usize + 1]
+				.try_into()
+				.unwrap();
 		assert_failed_ccm(
 			Asset::Eth,
 			1_000_000,
 			Asset::Btc,
 			ForeignChainAddress::Btc(cf_chains::btc::ScriptPubkey::P2PKH(Default::default())),
-			ccm.clone(),
-			CcmFailReason::UnsupportedForTargetChain,
+			long_message_ccm,
+			CcmFailReason::MessageTooLongForTargetChain,
 		);
 		assert_failed_ccm(
 			Asset::Eth,
@@ -401,7 +406,8 @@ fn rejects_invalid_swap_deposit() {
 				Asset::Eth,
 				EncodedAddress::Dot(Default::default()),
 				0,
-				Some(ccm.clone())
+				Some(ccm.clone()),
+				None
 			),
 			Error::<Test>::IncompatibleAssetAndAddress
 		);
@@ -410,12 +416,18 @@ fn rejects_invalid_swap_deposit() {
 			Swapping::request_swap_deposit_address(
 				RuntimeOrigin::signed(ALICE),
 				Asset::Eth,
-				Asset::Dot,
-				EncodedAddress::Dot(Default::default()),
+				Asset::Btc,
+				EncodedAddress::Btc(b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_vec()),
 				0,
-				Some(ccm)
+				Some(CcmChannelMetadata {
+					message: vec![0x01; cf_chains::btc::MAX_OP_RETURN_DATA_BYTES as usize + 1]
+						.try_into()
+						.unwrap(),
+					..ccm
+				}),
+				None
 			),
-			Error::<Test>::CcmUnsupportedForTargetChain
+			Error::<Test>::CcmMessageTooLongForTargetChain
 		);
 	});
 }
@@ -442,7 +454,8 @@ fn rejects_invalid_swap_by_witnesser() {
 				Asset::Dot,
 				10000,
 				btc_encoded_address,
-				Default::default()
+				Default::default(),
+				None
 			),
 			Error::<Test>::IncompatibleAssetAndAddress
 		);
@@ -454,7 +467,8 @@ fn rejects_invalid_swap_by_witnesser() {
 				Asset::Btc,
 				10000,
 				EncodedAddress::Btc(vec![0x41, 0x80, 0x41]),
-				Default::default()
+				Default::default(),
+				None
 			),
 			Error::<Test>::InvalidDestinationAddress
 		);
@@ -476,7 +490,8 @@ fn can_process_ccms_via_swap_deposit_address() {
 			Asset::Eth,
 			EncodedAddress::Eth(Default::default()),
 			0,
-			Some(request_ccm)
+			Some(request_ccm),
+			None
 		));
 		Swapping::on_ccm_deposit(
 			Asset::Dot,
@@ -485,6 +500,7 @@ fn can_process_ccms_via_swap_deposit_address() {
 			ForeignChainAddress::Eth(Default::default()),
 			ccm.clone(),
 			SwapOrigin::Vault { tx_hash: Default::default() },
+			None,
 		);
 
 		assert_eq!(
@@ -630,6 +646,46 @@ fn can_process_ccms_via_extrinsic() {
 	});
 }
 
+#[test]
+fn ccms_to_chains_without_contracts_take_no_gas_budget() {
+	new_test_ext().execute_with(|| {
+		let deposit_amount = 10_000;
+		let ccm = generate_ccm_deposit();
+		assert!(ccm.channel_metadata.gas_budget > 0);
+		assert_ok!(Swapping::ccm_deposit(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			deposit_amount,
+			Asset::Dot,
+			EncodedAddress::Dot(Default::default()),
+			ccm.clone(),
+			Default::default(),
+		));
+
+		// The whole deposit is swapped and no gas is swapped for.
+		assert_eq!(
+			PendingCcms::<Test>::get(1),
+			Some(CcmSwap {
+				source_asset: Asset::Eth,
+				deposit_amount,
+				destination_asset: Asset::Dot,
+				destination_address: ForeignChainAddress::Dot(Default::default()),
+				deposit_metadata: ccm,
+				principal_swap_id: Some(1),
+				gas_swap_id: None,
+			})
+		);
+		assert_eq!(
+			SwapQueue::<Test>::get(),
+			vec![Swap::new(1, Asset::Eth, Asset::Dot, deposit_amount, SwapType::CcmPrincipal(1))]
+		);
+		assert_eq!(
+			CcmOutputs::<Test>::get(1),
+			Some(CcmSwapOutput { principal: None, gas: Some(0) })
+		);
+	});
+}
+
 #[test]
 fn can_handle_ccms_with_non_native_gas_asset() {
 	new_test_ext().execute_with(|| {
@@ -881,6 +937,7 @@ fn swap_by_witnesser_happy_path() {
 			amount,
 			EncodedAddress::Eth(Default::default()),
 			Default::default(),
+			None,
 		));
 
 		// Verify this swap is rejected
@@ -905,6 +962,7 @@ fn swap_by_witnesser_happy_path() {
 			amount,
 			EncodedAddress::Eth(Default::default()),
 			Default::default(),
+			None,
 		));
 
 		// Verify this swap is accepted and scheduled
@@ -954,6 +1012,7 @@ fn swap_by_deposit_happy_path() {
 			Default::default(),
 			Default::default(),
 			1,
+			None,
 		);
 
 		// Verify this swap is rejected
@@ -985,6 +1044,7 @@ fn swap_by_deposit_happy_path() {
 			Default::default(),
 			Default::default(),
 			1,
+			None,
 		);
 
 		// Verify this swap is accepted and scheduled
@@ -1042,7 +1102,8 @@ fn ccm_via_deposit_with_principal_below_minimum_are_rejected() {
 			to,
 			EncodedAddress::Eth(Default::default()),
 			0,
-			Some(request_ccm)
+			Some(request_ccm),
+			None
 		));
 
 		assert_failed_ccm(
@@ -1073,6 +1134,7 @@ fn ccm_via_deposit_with_principal_below_minimum_are_rejected() {
 			ForeignChainAddress::Eth(Default::default()),
 			ccm.clone(),
 			SwapOrigin::Vault { tx_hash: Default::default() },
+			None,
 		);
 
 		// Verify the CCM is processed successfully
@@ -1187,6 +1249,7 @@ fn ccm_without_principal_swaps_are_accepted() {
 			ForeignChainAddress::Eth(Default::default()),
 			ccm.clone(),
 			SwapOrigin::Vault { tx_hash: Default::default() },
+			None,
 		);
 
 		// Verify the CCM is processed successfully
@@ -1218,6 +1281,7 @@ fn ccm_without_principal_swaps_are_accepted() {
 			ForeignChainAddress::Eth(Default::default()),
 			ccm,
 			SwapOrigin::Vault { tx_hash: Default::default() },
+			None,
 		);
 
 		// Verify the CCM is processed successfully
@@ -1261,6 +1325,7 @@ fn ccm_with_gas_below_minimum_swap_amount_allowed() {
 			ForeignChainAddress::Eth(Default::default()),
 			ccm.clone(),
 			SwapOrigin::Vault { tx_hash: Default::default() },
+			None,
 		);
 
 		// Verify the CCM is processed successfully
@@ -1290,6 +1355,7 @@ fn process_all_into_stable_swaps_first() {
 			amount,
 			encoded_address.clone(),
 			Default::default(),
+			None,
 		));
 		assert_ok!(Swapping::schedule_swap_from_contract(
 			RuntimeOrigin::root(),
@@ -1298,6 +1364,7 @@ fn process_all_into_stable_swaps_first() {
 			amount,
 			encoded_address.clone(),
 			Default::default(),
+			None,
 		));
 		assert_ok!(Swapping::schedule_swap_from_contract(
 			RuntimeOrigin::root(),
@@ -1306,6 +1373,7 @@ fn process_all_into_stable_swaps_first() {
 			amount,
 			encoded_address.clone(),
 			Default::default(),
+			None,
 		));
 		assert_ok!(Swapping::schedule_swap_from_contract(
 			RuntimeOrigin::root(),
@@ -1314,6 +1382,7 @@ fn process_all_into_stable_swaps_first() {
 			amount,
 			encoded_address,
 			Default::default(),
+			None,
 		));
 		assert_eq!(
 			SwapQueue::<Test>::get(),
@@ -1331,7 +1400,8 @@ fn process_all_into_stable_swaps_first() {
 		assert!(SwapQueue::<Test>::get().is_empty());
 
 		// Network fee should only be taken once.
-		let total_amount_after_network_fee = MockSwappingApi::take_network_fee(amount * 4);
+		let total_amount_after_network_fee =
+			MockSwappingApi::take_network_fee(amount * 4, Asset::Flip, Asset::Eth);
 		let output_amount = total_amount_after_network_fee / 4;
 		// Verify swap "from" -> STABLE_ASSET, then "to" -> Output Asset
 		assert_eq!(
@@ -1449,6 +1519,7 @@ fn ccm_swaps_emits_events() {
 			destination_address.clone(),
 			ccm.clone(),
 			ORIGIN,
+			None,
 		);
 		assert_event_sequence!(
 			Test,
@@ -1490,6 +1561,7 @@ fn ccm_swaps_emits_events() {
 			destination_address.clone(),
 			ccm.clone(),
 			ORIGIN,
+			None,
 		);
 		assert_event_sequence!(
 			Test,
@@ -1521,6 +1593,7 @@ fn ccm_swaps_emits_events() {
 			destination_address,
 			ccm,
 			ORIGIN,
+			None,
 		);
 		assert_event_sequence!(
 			Test,
@@ -1559,6 +1632,7 @@ fn can_handle_ccm_with_zero_swap_outputs() {
 				eth_address,
 				ccm,
 				SwapOrigin::Vault { tx_hash: Default::default() },
+				None,
 			);
 
 			// Change the swap rate so swap output will be 0
@@ -1612,6 +1686,7 @@ fn can_handle_swaps_with_zero_outputs() {
 				Default::default(),
 				0,
 				0,
+				None,
 			);
 			Swapping::schedule_swap_from_channel(
 				eth_address.clone(),
@@ -1623,6 +1698,7 @@ fn can_handle_swaps_with_zero_outputs() {
 				Default::default(),
 				0,
 				0,
+				None,
 			);
 
 			// Change the swap rate so swap output will be 0
@@ -1701,7 +1777,8 @@ fn swap_excess_are_confiscated_ccm_via_deposit() {
 			to,
 			EncodedAddress::Eth(Default::default()),
 			0,
-			Some(request_ccm)
+			Some(request_ccm),
+			None
 		));
 
 		Swapping::on_ccm_deposit(
@@ -1711,6 +1788,7 @@ fn swap_excess_are_confiscated_ccm_via_deposit() {
 			ForeignChainAddress::Eth(Default::default()),
 			ccm.clone(),
 			SwapOrigin::Vault { tx_hash: Default::default() },
+			None,
 		);
 
 		// Excess fee is confiscated
@@ -1740,7 +1818,8 @@ fn swap_excess_are_confiscated_ccm_via_deposit() {
 					swap_type: SwapType::CcmPrincipal(1),
 					stable_amount: Some(max_swap),
 					final_output: None,
-					fee_taken: false
+					fee_taken: false,
+					refund_parameters: None,
 				},
 				Swap {
 					swap_id: 2u64,
@@ -1750,7 +1829,8 @@ fn swap_excess_are_confiscated_ccm_via_deposit() {
 					swap_type: SwapType::CcmGas(1),
 					stable_amount: Some(max_swap),
 					final_output: None,
-					fee_taken: false
+					fee_taken: false,
+					refund_parameters: None,
 				}
 			]
 		);
@@ -1808,7 +1888,8 @@ fn swap_excess_are_confiscated_ccm_via_extrinsic() {
 					swap_type: SwapType::CcmPrincipal(1),
 					stable_amount: Some(max_swap),
 					final_output: None,
-					fee_taken: false
+					fee_taken: false,
+					refund_parameters: None,
 				},
 				Swap {
 					swap_id: 2u64,
@@ -1818,7 +1899,8 @@ fn swap_excess_are_confiscated_ccm_via_extrinsic() {
 					swap_type: SwapType::CcmGas(1),
 					stable_amount: Some(max_swap),
 					final_output: None,
-					fee_taken: false
+					fee_taken: false,
+					refund_parameters: None,
 				}
 			]
 		);
@@ -1843,6 +1925,7 @@ fn swap_excess_are_confiscated_for_swap_via_extrinsic() {
 			amount,
 			EncodedAddress::Eth(Default::default()),
 			Default::default(),
+			None,
 		));
 
 		// Excess fee is confiscated
@@ -1864,7 +1947,8 @@ fn swap_excess_are_confiscated_for_swap_via_extrinsic() {
 				swap_type: SwapType::Swap(ForeignChainAddress::Eth(Default::default())),
 				stable_amount: Some(max_swap),
 				final_output: None,
-				fee_taken: false
+				fee_taken: false,
+				refund_parameters: None,
 			}]
 		);
 		assert_eq!(CollectedRejectedFunds::<Test>::get(from), 900);
@@ -1891,6 +1975,7 @@ fn swap_excess_are_confiscated_for_swap_via_deposit() {
 			ALICE,
 			0,
 			0,
+			None,
 		);
 
 		// Excess fee is confiscated
@@ -1912,7 +1997,8 @@ fn swap_excess_are_confiscated_for_swap_via_deposit() {
 				swap_type: SwapType::Swap(ForeignChainAddress::Eth(Default::default())),
 				stable_amount: Some(max_swap),
 				final_output: None,
-				fee_taken: false
+				fee_taken: false,
+				refund_parameters: None,
 			}]
 		);
 		assert_eq!(CollectedRejectedFunds::<Test>::get(from), 900);
@@ -1936,6 +2022,7 @@ fn max_swap_amount_can_be_removed() {
 			amount,
 			EncodedAddress::Eth(Default::default()),
 			Default::default(),
+			None,
 		));
 
 		assert_eq!(CollectedRejectedFunds::<Test>::get(from), 900u128);
@@ -1954,6 +2041,7 @@ fn max_swap_amount_can_be_removed() {
 			amount,
 			EncodedAddress::Eth(Default::default()),
 			Default::default(),
+			None,
 		));
 
 		assert_eq!(
@@ -1967,7 +2055,8 @@ fn max_swap_amount_can_be_removed() {
 					swap_type: SwapType::Swap(ForeignChainAddress::Eth(Default::default())),
 					stable_amount: Some(max_swap),
 					final_output: None,
-					fee_taken: false
+					fee_taken: false,
+					refund_parameters: None,
 				},
 				// New swap takes the full amount.
 				Swap {
@@ -1978,7 +2067,8 @@ fn max_swap_amount_can_be_removed() {
 					swap_type: SwapType::Swap(ForeignChainAddress::Eth(Default::default())),
 					stable_amount: Some(amount),
 					final_output: None,
-					fee_taken: false
+					fee_taken: false,
+					refund_parameters: None,
 				}
 			]
 		);
@@ -2004,6 +2094,7 @@ fn can_swap_below_max_amount() {
 			amount,
 			EncodedAddress::Eth(Default::default()),
 			Default::default(),
+			None,
 		));
 
 		assert_eq!(CollectedRejectedFunds::<Test>::get(from), 0u128);
@@ -2018,7 +2109,8 @@ fn can_swap_below_max_amount() {
 				swap_type: SwapType::Swap(ForeignChainAddress::Eth(Default::default())),
 				stable_amount: Some(amount),
 				final_output: None,
-				fee_taken: false
+				fee_taken: false,
+				refund_parameters: None,
 			},]
 		);
 	});
@@ -2058,7 +2150,8 @@ fn can_swap_ccm_below_max_amount() {
 					swap_type: SwapType::CcmPrincipal(1),
 					stable_amount: Some(principal_amount),
 					final_output: None,
-					fee_taken: false
+					fee_taken: false,
+					refund_parameters: None,
 				},
 				Swap {
 					swap_id: 2u64,
@@ -2068,10 +2161,464 @@ fn can_swap_ccm_below_max_amount() {
 					swap_type: SwapType::CcmGas(1),
 					stable_amount: Some(gas_budget),
 					final_output: None,
-					fee_taken: false
+					fee_taken: false,
+					refund_parameters: None,
 				}
 			]
 		);
 		assert_eq!(CollectedRejectedFunds::<Test>::get(from), 0);
 	});
 }
+
+#[test]
+fn swaps_below_min_output_are_refunded() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1_000;
+		let refund_address = ForeignChainAddress::Eth([0xaa; 20].into());
+		let destination_address = ForeignChainAddress::Eth([0xbb; 20].into());
+
+		SwapRate::set(2f64);
+
+		// Flip -> Usdc -> Eth outputs 4x the input amount.
+		for (channel_id, min_output) in [(1, AMOUNT * 4), (2, AMOUNT * 4 + 1)] {
+			Swapping::schedule_swap_from_channel(
+				ForeignChainAddress::Eth(Default::default()),
+				Default::default(),
+				Asset::Flip,
+				Asset::Eth,
+				AMOUNT,
+				destination_address.clone(),
+				ALICE,
+				0,
+				channel_id,
+				Some(SwapRefundParameters { refund_address: refund_address.clone(), min_output }),
+			);
+		}
+
+		Swapping::on_finalize(1);
+
+		assert!(SwapQueue::<Test>::get().is_empty());
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapScheduled { swap_id: 1, .. }),
+			RuntimeEvent::Swapping(Event::SwapScheduled { swap_id: 2, .. }),
+			RuntimeEvent::Swapping(Event::SwapRefunded {
+				swap_id: 2,
+				asset: Asset::Flip,
+				amount: AMOUNT,
+				min_output,
+				..
+			}) if min_output == AMOUNT * 4 + 1,
+			RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: 1, egress_amount, .. })
+				if egress_amount == AMOUNT * 4,
+			RuntimeEvent::Swapping(Event::SwapEgressScheduled { swap_id: 1, .. }),
+		);
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![
+				MockEgressParameter::Swap {
+					asset: Asset::Flip,
+					amount: AMOUNT,
+					destination_address: refund_address,
+				},
+				MockEgressParameter::Swap {
+					asset: Asset::Eth,
+					amount: AMOUNT * 4,
+					destination_address,
+				},
+			]
+		);
+	});
+}
+
+#[test]
+fn all_swaps_below_min_output_are_removed_in_one_pass() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1_000;
+		let refund_address = ForeignChainAddress::Eth([0xaa; 20].into());
+
+		SwapRate::set(2f64);
+
+		// Flip -> Usdc -> Eth outputs 4x the input amount, so only the first swap succeeds.
+		for (channel_id, min_output) in
+			[(1, AMOUNT * 4), (2, AMOUNT * 4 + 1), (3, AMOUNT * 4 + 1), (4, AMOUNT * 5)]
+		{
+			Swapping::schedule_swap_from_channel(
+				ForeignChainAddress::Eth(Default::default()),
+				Default::default(),
+				Asset::Flip,
+				Asset::Eth,
+				AMOUNT,
+				ForeignChainAddress::Eth([0xbb; 20].into()),
+				ALICE,
+				0,
+				channel_id,
+				Some(SwapRefundParameters { refund_address: refund_address.clone(), min_output }),
+			);
+		}
+
+		Swapping::on_finalize(1);
+
+		// Both legs are executed once for the full batch and once without the refunded swaps.
+		assert_eq!(Swaps::get().len(), 4);
+		let egresses = MockEgressHandler::<AnyChain>::get_scheduled_egresses();
+		assert_eq!(
+			egresses
+				.iter()
+				.filter(|egress| matches!(
+					egress,
+					MockEgressParameter::Swap { asset: Asset::Flip, amount: AMOUNT, .. }
+				))
+				.count(),
+			3
+		);
+		assert!(matches!(
+			egresses.last(),
+			Some(MockEgressParameter::Swap { asset: Asset::Eth, amount, .. }) if *amount == AMOUNT * 4
+		));
+	});
+}
+
+#[test]
+fn ccm_swaps_are_executed_once_swap_retries_are_used_up() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1_000;
+		let refund_address = ForeignChainAddress::Eth([0xaa; 20].into());
+		let destination_address = ForeignChainAddress::Eth([0xbb; 20].into());
+
+		// Prices get worse with every leg, so every attempt fails for one more swap: Flip -> Eth
+		// outputs 3.6x, 2.36x and 1.55x the input amount in the first three attempts.
+		SwapRate::set(2f64);
+		SwapRateChangePerLeg::set(0.9f64);
+
+		for (channel_id, min_output) in [(1, AMOUNT * 4), (2, AMOUNT * 3), (3, AMOUNT * 2)] {
+			Swapping::schedule_swap_from_channel(
+				ForeignChainAddress::Eth(Default::default()),
+				Default::default(),
+				Asset::Flip,
+				Asset::Eth,
+				AMOUNT,
+				destination_address.clone(),
+				ALICE,
+				0,
+				channel_id,
+				Some(SwapRefundParameters { refund_address: refund_address.clone(), min_output }),
+			);
+		}
+		Swapping::on_ccm_deposit(
+			Asset::Flip,
+			AMOUNT + GAS_BUDGET,
+			Asset::Usdc,
+			destination_address.clone(),
+			generate_ccm_deposit(),
+			SwapOrigin::Vault { tx_hash: Default::default() },
+			Some(refund_address.clone()),
+		);
+		assert_eq!(SwapQueue::<Test>::get().len(), 5);
+
+		Swapping::on_finalize(1);
+
+		assert!(SwapQueue::<Test>::get().is_empty());
+		let refunded_swaps = System::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				RuntimeEvent::Swapping(Event::SwapRefunded { swap_id, .. }) => Some(swap_id),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(refunded_swaps, vec![1, 2, 3]);
+
+		// Both legs of the CCM are executed and its egress is scheduled.
+		assert_eq!(PendingCcms::<Test>::get(1), None);
+		assert_eq!(CcmGasBudget::<Test>::get(1).map(|(asset, _)| asset), Some(Asset::Eth));
+		assert!(System::events().into_iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Swapping(Event::CcmEgressScheduled { ccm_id: 1, .. })
+		)));
+	});
+}
+
+#[test]
+fn egress_fee_is_deducted_from_swap_refunds() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1_000;
+		const EGRESS_FEE: AssetAmount = 100;
+		let refund_address = ForeignChainAddress::Eth([0xaa; 20].into());
+
+		MockEgressHandler::<AnyChain>::set_egress_fee(Some(EGRESS_FEE));
+		for (channel_id, amount) in [(1, AMOUNT), (2, EGRESS_FEE)] {
+			Swapping::schedule_swap_from_channel(
+				ForeignChainAddress::Eth(Default::default()),
+				Default::default(),
+				Asset::Flip,
+				Asset::Eth,
+				amount,
+				ForeignChainAddress::Eth([0xbb; 20].into()),
+				ALICE,
+				0,
+				channel_id,
+				Some(SwapRefundParameters {
+					refund_address: refund_address.clone(),
+					min_output: AssetAmount::MAX,
+				}),
+			);
+		}
+
+		Swapping::on_finalize(1);
+
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapScheduled { swap_id: 1, .. }),
+			RuntimeEvent::Swapping(Event::SwapScheduled { swap_id: 2, .. }),
+			RuntimeEvent::Swapping(Event::SwapRefunded {
+				swap_id: 1,
+				asset: Asset::Flip,
+				amount,
+				egress_fee: EGRESS_FEE,
+				..
+			}) if amount == AMOUNT - EGRESS_FEE,
+			RuntimeEvent::Swapping(Event::SwapRefundCollected {
+				swap_id: 2,
+				asset: Asset::Flip,
+				amount: EGRESS_FEE,
+				..
+			}),
+		);
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: Asset::Flip,
+				amount: AMOUNT - EGRESS_FEE,
+				destination_address: refund_address,
+			}]
+		);
+		assert_eq!(CollectedRejectedFunds::<Test>::get(Asset::Flip), EGRESS_FEE * 2);
+	});
+}
+
+#[test]
+fn refund_address_must_match_source_chain() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Swapping::request_swap_deposit_address(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				Asset::Dot,
+				EncodedAddress::Dot(Default::default()),
+				0,
+				None,
+				Some(SwapRefundParameters {
+					refund_address: EncodedAddress::Dot(Default::default()),
+					min_output: 1,
+				}),
+			),
+			Error::<Test>::IncompatibleAssetAndAddress
+		);
+		assert_noop!(
+			Swapping::schedule_swap_from_contract(
+				RuntimeOrigin::root(),
+				Asset::Eth,
+				Asset::Dot,
+				10_000,
+				EncodedAddress::Dot(Default::default()),
+				Default::default(),
+				Some(SwapRefundParameters {
+					refund_address: EncodedAddress::Dot(Default::default()),
+					min_output: 1,
+				}),
+			),
+			Error::<Test>::IncompatibleAssetAndAddress
+		);
+		assert_ok!(Swapping::request_swap_deposit_address(
+			RuntimeOrigin::signed(ALICE),
+			Asset::Eth,
+			Asset::Dot,
+			EncodedAddress::Dot(Default::default()),
+			0,
+			None,
+			Some(SwapRefundParameters {
+				refund_address: EncodedAddress::Eth(Default::default()),
+				min_output: 1,
+			}),
+		));
+	});
+}
+
+#[test]
+fn swaps_below_minimum_are_refunded_if_refund_address_is_set() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1_000;
+		let refund_address = ForeignChainAddress::Eth([0xaa; 20].into());
+
+		assert_ok!(Swapping::set_minimum_swap_amount(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			AMOUNT + 1
+		));
+		assert_ok!(Swapping::schedule_swap_from_contract(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			Asset::Flip,
+			AMOUNT,
+			EncodedAddress::Eth(Default::default()),
+			Default::default(),
+			Some(SwapRefundParameters {
+				refund_address: EncodedAddress::Eth([0xaa; 20]),
+				min_output: 0,
+			}),
+		));
+
+		assert_eq!(SwapQueue::<Test>::decode_len(), None);
+		assert_eq!(CollectedRejectedFunds::<Test>::get(Asset::Eth), 0);
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::MinimumSwapAmountSet { .. }),
+			RuntimeEvent::Swapping(Event::SwapAmountTooLow { amount: AMOUNT, .. }),
+			RuntimeEvent::Swapping(Event::RejectedFundsRefunded {
+				asset: Asset::Eth,
+				amount: AMOUNT,
+				..
+			}),
+		);
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: Asset::Eth,
+				amount: AMOUNT,
+				destination_address: refund_address,
+			}]
+		);
+	});
+}
+
+#[test]
+fn egress_fee_is_deducted_from_rejected_funds_refunds() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1_000;
+		const EGRESS_FEE: AssetAmount = 100;
+		let refund_address = ForeignChainAddress::Eth([0xaa; 20].into());
+
+		MockEgressHandler::<AnyChain>::set_egress_fee(Some(EGRESS_FEE));
+		assert_ok!(Swapping::set_minimum_swap_amount(
+			RuntimeOrigin::root(),
+			Asset::Eth,
+			AMOUNT + 1
+		));
+		for amount in [AMOUNT, EGRESS_FEE] {
+			assert_ok!(Swapping::schedule_swap_from_contract(
+				RuntimeOrigin::root(),
+				Asset::Eth,
+				Asset::Flip,
+				amount,
+				EncodedAddress::Eth(Default::default()),
+				Default::default(),
+				Some(SwapRefundParameters {
+					refund_address: EncodedAddress::Eth([0xaa; 20]),
+					min_output: 0,
+				}),
+			));
+		}
+
+		// The second deposit does not cover the egress fee, so it is kept.
+		assert_eq!(CollectedRejectedFunds::<Test>::get(Asset::Eth), EGRESS_FEE * 2);
+		System::assert_has_event(RuntimeEvent::Swapping(Event::RejectedFundsRefunded {
+			asset: Asset::Eth,
+			amount: AMOUNT - EGRESS_FEE,
+			egress_fee: EGRESS_FEE,
+			refund_address: EncodedAddress::Eth([0xaa; 20]),
+			egress_id: (ForeignChain::Ethereum, 1),
+			origin: SwapOrigin::Vault { tx_hash: Default::default() },
+		}));
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: Asset::Eth,
+				amount: AMOUNT - EGRESS_FEE,
+				destination_address: refund_address,
+			}]
+		);
+	});
+}
+
+#[test]
+fn swap_excess_is_refunded_if_refund_address_is_set() {
+	new_test_ext().execute_with(|| {
+		let refund_address = ForeignChainAddress::Eth([0xaa; 20].into());
+
+		assert_ok!(Swapping::set_maximum_swap_amount(RuntimeOrigin::root(), Asset::Eth, Some(100)));
+		Swapping::schedule_swap_from_channel(
+			ForeignChainAddress::Eth(Default::default()),
+			Default::default(),
+			Asset::Eth,
+			Asset::Flip,
+			1_000,
+			ForeignChainAddress::Eth(Default::default()),
+			ALICE,
+			0,
+			1,
+			Some(SwapRefundParameters { refund_address: refund_address.clone(), min_output: 0 }),
+		);
+
+		assert_eq!(SwapQueue::<Test>::get()[0].amount, 100);
+		assert_eq!(CollectedRejectedFunds::<Test>::get(Asset::Eth), 0);
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::SwapAmountConfiscated {
+			swap_id: 1,
+			source_asset: Asset::Eth,
+			destination_asset: Asset::Flip,
+			total_amount: 1_000,
+			confiscated_amount: 900,
+		}));
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: Asset::Eth,
+				amount: 900,
+				destination_address: refund_address,
+			}]
+		);
+	});
+}
+
+#[test]
+fn failed_ccms_are_refunded_if_refund_address_is_set() {
+	new_test_ext().execute_with(|| {
+		let gas_budget = 1_000;
+		let refund_address = ForeignChainAddress::Eth([0xaa; 20].into());
+		let destination_address = ForeignChainAddress::Eth(Default::default());
+		let mut ccm = generate_ccm_deposit();
+		ccm.channel_metadata.gas_budget = gas_budget;
+
+		// Deposit amount is not enough to cover the gas budget.
+		Swapping::on_ccm_deposit(
+			Asset::Eth,
+			gas_budget - 1,
+			Asset::Usdc,
+			destination_address.clone(),
+			ccm.clone(),
+			SwapOrigin::Vault { tx_hash: Default::default() },
+			Some(refund_address.clone()),
+		);
+
+		assert_eq!(CollectedRejectedFunds::<Test>::get(Asset::Eth), 0);
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::CcmFailed {
+				reason: CcmFailReason::InsufficientDepositAmount,
+				..
+			}),
+			RuntimeEvent::Swapping(Event::RejectedFundsRefunded {
+				asset: Asset::Eth,
+				amount,
+				..
+			}) if amount == gas_budget - 1,
+		);
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: Asset::Eth,
+				amount: gas_budget - 1,
+				destination_address: refund_address,
+			}]
+		);
+	});
+}
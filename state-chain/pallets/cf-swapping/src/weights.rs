@@ -39,6 +39,7 @@ pub trait WeightInfo {
 	fn ccm_deposit() -> Weight;
 	fn set_minimum_swap_amount() -> Weight;
 	fn set_maximum_swap_amount() -> Weight;
+	fn refund_swaps(n: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_swapping using the Substrate node and recommended hardware.
@@ -161,6 +162,30 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 		Weight::from_parts(10_718_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::CollectedRejectedFunds` (r:1 w:1)
+	/// Proof: `Swapping::CollectedRejectedFunds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::EgressIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 100]`.
+	fn refund_swaps(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1_583 + n * (66 ±0)`
+		//  Estimated: `7_523 + n * (66 ±0)`
+		// Minimum execution time: 52_304_000 picoseconds.
+		Weight::from_parts(36_116_392, 7523)
+			// Standard Error: 14_887
+			.saturating_add(Weight::from_parts(27_963_502, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 66).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -282,4 +307,28 @@ impl WeightInfo for () {
 		Weight::from_parts(10_718_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::CollectedRejectedFunds` (r:1 w:1)
+	/// Proof: `Swapping::CollectedRejectedFunds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::EgressIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 100]`.
+	fn refund_swaps(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1_583 + n * (66 ±0)`
+		//  Estimated: `7_523 + n * (66 ±0)`
+		// Minimum execution time: 52_304_000 picoseconds.
+		Weight::from_parts(36_116_392, 7523)
+			// Standard Error: 14_887
+			.saturating_add(Weight::from_parts(27_963_502, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 66).saturating_mul(n.into()))
+	}
 }
@@ -8,9 +8,10 @@ use cf_primitives::{
 };
 use cf_runtime_utilities::{EnumVariant, StorageDecodeVariant};
 use cf_traits::{
-	offence_reporting::OffenceReporter, AccountRoleRegistry, AsyncResult, Broadcaster, Chainflip,
-	CurrentEpochIndex, EpochKey, GetBlockHeight, KeyProvider, KeyState, SafeMode, SetSafeMode,
-	Slashing, ThresholdSigner, VaultKeyWitnessedHandler, VaultRotator, VaultStatus,
+	offence_reporting::OffenceReporter, AccountRoleRegistry, AsyncResult, Broadcaster,
+	CeremonyIdProvider, Chainflip, CurrentEpochIndex, EpochKey, GetBlockHeight, KeyProvider,
+	KeyState, SafeMode, SetSafeMode, Slashing, ThresholdSigner, VaultKeyWitnessedHandler,
+	VaultRotator, VaultStatus,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -215,6 +216,10 @@ pub mod pallet {
 
 		type ChainTracking: GetBlockHeight<Self::Chain>;
 
+		/// Provides the ids of keygen and key handover ceremonies. Vaults whose ceremonies are
+		/// run by the same multisig client must share a ceremony id sequence.
+		type CeremonyIdProvider: CeremonyIdProvider;
+
 		/// Benchmark stuff
 		type WeightInfo: WeightInfo;
 	}
@@ -162,6 +162,14 @@ impl Broadcaster<MockEthereum> for MockBroadcaster {
 	) -> (BroadcastId, ThresholdSignatureRequestId) {
 		unimplemented!()
 	}
+
+	fn threshold_sign_and_broadcast_with_gas_refund(
+		_api_call: Self::ApiCall,
+		_gas_budget: <MockEthereum as Chain>::ChainAmount,
+		_refund_address: <MockEthereum as Chain>::ChainAccount,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		unimplemented!()
+	}
 }
 
 parameter_types! {
@@ -222,6 +230,7 @@ impl pallet_cf_vaults::Config for Test {
 	type SafeMode = MockRuntimeSafeMode;
 	type Slasher = MockSlasher;
 	type ChainTracking = BlockHeightProvider<MockEthereum>;
+	type CeremonyIdProvider = VaultsPallet;
 }
 
 pub const ALICE: <Test as frame_system::Config>::AccountId = 123u64;
@@ -15,7 +15,7 @@ impl<T: Config<I>, I: 'static> VaultRotator for Pallet<T, I> {
 
 		assert_ne!(Self::status(), AsyncResult::Pending);
 
-		let ceremony_id = Self::increment_ceremony_id();
+		let ceremony_id = T::CeremonyIdProvider::increment_ceremony_id();
 
 		PendingVaultRotation::<T, I>::put(VaultRotationStatus::AwaitingKeygen {
 			ceremony_id,
@@ -53,7 +53,7 @@ impl<T: Config<I>, I: 'static> VaultRotator for Pallet<T, I> {
 							!sharing_participants.is_empty() && !receiving_participants.is_empty()
 						);
 
-						let ceremony_id = Self::increment_ceremony_id();
+						let ceremony_id = T::CeremonyIdProvider::increment_ceremony_id();
 
 						// from the SC's perspective, we don't care what set they're in, they get
 						// reported the same and each participant only gets one vote, like keygen.
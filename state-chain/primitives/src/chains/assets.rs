@@ -57,6 +57,9 @@ pub mod any {
 		Usdc = 3u32,
 		Dot = 4u32,
 		Btc = 5u32,
+		ArbEth = 6u32,
+		ArbUsdc = 7u32,
+		DotUsdt = 8u32,
 	}
 
 	impl TryFrom<u32> for Asset {
@@ -69,6 +72,9 @@ pub mod any {
 				x if x == Self::Usdc as u32 => Ok(Self::Usdc),
 				x if x == Self::Dot as u32 => Ok(Self::Dot),
 				x if x == Self::Btc as u32 => Ok(Self::Btc),
+				x if x == Self::ArbEth as u32 => Ok(Self::ArbEth),
+				x if x == Self::ArbUsdc as u32 => Ok(Self::ArbUsdc),
+				x if x == Self::DotUsdt as u32 => Ok(Self::DotUsdt),
 				_ => Err("Invalid asset id"),
 			}
 		}
@@ -89,6 +95,9 @@ pub mod any {
 				Asset::Usdc => Self::Ethereum,
 				Asset::Dot => Self::Polkadot,
 				Asset::Btc => Self::Bitcoin,
+				Asset::ArbEth => Self::Arbitrum,
+				Asset::ArbUsdc => Self::Arbitrum,
+				Asset::DotUsdt => Self::Polkadot,
 			}
 		}
 	}
@@ -103,6 +112,9 @@ pub mod any {
 				"usdc" => Ok(Asset::Usdc),
 				"dot" => Ok(Asset::Dot),
 				"btc" => Ok(Asset::Btc),
+				"arbeth" => Ok(Asset::ArbEth),
+				"arbusdc" => Ok(Asset::ArbUsdc),
+				"dotusdt" => Ok(Asset::DotUsdt),
 				_ => Err("Unrecognized asset"),
 			}
 		}
@@ -191,8 +203,9 @@ macro_rules! chain_assets {
 // Defines each chain's Asset enum.
 // Must be consistent with the mapping defined in any::Asset
 chain_assets!(eth, Ethereum, Eth, Flip, Usdc);
-chain_assets!(dot, Polkadot, Dot);
+chain_assets!(dot, Polkadot, Dot, DotUsdt);
 chain_assets!(btc, Bitcoin, Btc);
+chain_assets!(arb, Arbitrum, ArbEth, ArbUsdc);
 
 #[cfg(test)]
 mod test_assets {
@@ -219,6 +232,10 @@ mod test_assets {
 		assert_eq!(Asset::try_from(3).unwrap(), Asset::Usdc);
 		assert_eq!(Asset::try_from(4).unwrap(), Asset::Dot);
 		assert_eq!(Asset::try_from(5).unwrap(), Asset::Btc);
+		assert_eq!(Asset::try_from(6).unwrap(), Asset::ArbEth);
+		assert_eq!(Asset::try_from(7).unwrap(), Asset::ArbUsdc);
+		assert_eq!(Asset::try_from(8).unwrap(), Asset::DotUsdt);
+		assert!(Asset::try_from(9).is_err());
 	}
 
 	#[test]
@@ -227,12 +244,19 @@ mod test_assets {
 		assert_conversion!(eth, Flip);
 		assert_conversion!(eth, Usdc);
 		assert_conversion!(dot, Dot);
+		assert_conversion!(dot, DotUsdt);
 		assert_conversion!(btc, Btc);
+		assert_conversion!(arb, ArbEth);
+		assert_conversion!(arb, ArbUsdc);
 
 		assert_incompatible!(eth, Dot);
 		assert_incompatible!(dot, Eth);
 		assert_incompatible!(dot, Flip);
 		assert_incompatible!(dot, Usdc);
 		assert_incompatible!(btc, Usdc);
+		assert_incompatible!(arb, Eth);
+		assert_incompatible!(arb, Usdc);
+		assert_incompatible!(eth, ArbUsdc);
+		assert_incompatible!(eth, DotUsdt);
 	}
 }
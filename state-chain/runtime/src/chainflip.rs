@@ -9,10 +9,11 @@ mod missed_authorship_slots;
 mod offences;
 mod signer_nomination;
 use crate::{
-	AccountId, AccountRoles, Authorship, BitcoinChainTracking, BitcoinIngressEgress, BitcoinVault,
-	BlockNumber, Emissions, Environment, EthereumBroadcaster, EthereumChainTracking,
-	EthereumIngressEgress, Flip, FlipBalance, PolkadotBroadcaster, PolkadotChainTracking,
-	PolkadotIngressEgress, PolkadotVault, Runtime, RuntimeCall, System, Validator, YEAR,
+	AccountId, AccountRoles, ArbitrumChainTracking, ArbitrumIngressEgress, Authorship,
+	BitcoinChainTracking, BitcoinIngressEgress, BitcoinVault, BlockNumber, Emissions, Environment,
+	EthereumBroadcaster, EthereumChainTracking, EthereumIngressEgress, Flip, FlipBalance,
+	LiquidityPools, PolkadotBroadcaster, PolkadotChainTracking, PolkadotIngressEgress,
+	PolkadotVault, Runtime, RuntimeCall, System, Validator, YEAR,
 };
 use backup_node_rewards::calculate_backup_rewards;
 use cf_chains::{
@@ -20,6 +21,7 @@ use cf_chains::{
 		to_encoded_address, try_from_encoded_address, AddressConverter, EncodedAddress,
 		ForeignChainAddress,
 	},
+	arb::{api::ArbitrumApi, Arbitrum},
 	btc::{
 		api::{BitcoinApi, SelectedUtxosAndChangeAmount, UtxoSelectionType},
 		Bitcoin, BitcoinCrypto, BitcoinFeeInfo, BitcoinTransactionData, UtxoId,
@@ -35,19 +37,24 @@ use cf_chains::{
 		Ethereum,
 	},
 	evm::{
-		api::{EthEnvironmentProvider, EvmReplayProtection},
+		api::{EthEnvironmentProvider, EvmEnvironmentProvider, EvmReplayProtection},
 		EvmCrypto, Transaction,
 	},
 	AnyChain, ApiCall, CcmChannelMetadata, CcmDepositMetadata, Chain, ChainCrypto,
-	ChainEnvironment, ChainState, DepositChannel, ForeignChain, ReplayProtectionProvider,
-	SetCommKeyWithAggKey, SetGovKeyWithAggKey, TransactionBuilder,
+	ChainEnvironment, ChainState, DepositChannel, FeeBump, FeeEstimationApi, ForeignChain,
+	ReplayProtectionProvider, SetCommKeyWithAggKey, SetGovKeyWithAggKey, SwapRefundParameters,
+	TransactionBuilder, TransactionCostEstimate,
+};
+use cf_primitives::{
+	chains::assets, AccountRole, Asset, AssetAmount, BasisPoints, ChannelId, EgressId,
 };
-use cf_primitives::{chains::assets, AccountRole, Asset, BasisPoints, ChannelId, EgressId};
 use cf_traits::{
 	AccountInfo, AccountRoleRegistry, BlockEmissions, BroadcastAnyChainGovKey, Broadcaster,
-	Chainflip, CommKeyBroadcaster, DepositApi, DepositHandler, EgressApi, EpochInfo, Heartbeat,
-	Issuance, KeyProvider, OnBroadcastReady, QualifyNode, RewardsDistribution, RuntimeUpgrade,
+	Chainflip, CommKeyBroadcaster, DepositApi, DepositHandler, EgressApi, EgressFeeEstimator,
+	EpochInfo, GetBitcoinFeeInfo, Heartbeat, Issuance, KeyProvider, OnBroadcastReady, QualifyNode,
+	RewardsDistribution, RuntimeUpgrade,
 };
+use chain_instances::PalletInstanceAlias;
 use codec::{Decode, Encode};
 use frame_support::{
 	dispatch::{DispatchError, DispatchErrorWithPostInfo, PostDispatchInfo},
@@ -205,6 +212,76 @@ impl TransactionBuilder<Ethereum, EthereumApi<EthEnvironment>> for EthTransactio
 	}
 }
 
+/// Arbitrum's base fee is adjusted far less aggressively than Ethereum's, so a smaller margin
+/// is sufficient for the transaction to remain valid until it's included.
+const ARBITRUM_BASE_FEE_MULTIPLIER: FixedU64 = FixedU64::from_rational(3, 2);
+// Gas on Arbitrum includes the L1 data costs, so the limit is set higher than on Ethereum.
+const ARBITRUM_MAX_GAS_LIMIT: u128 = 25_000_000;
+
+pub struct ArbTransactionBuilder;
+
+impl TransactionBuilder<Arbitrum, ArbitrumApi<ArbEnvironment>> for ArbTransactionBuilder {
+	fn build_transaction(
+		signed_call: &ArbitrumApi<ArbEnvironment>,
+	) -> <Arbitrum as Chain>::Transaction {
+		Transaction {
+			chain_id: signed_call.replay_protection().chain_id,
+			contract: signed_call.replay_protection().contract_address,
+			data: signed_call.chain_encoded(),
+			gas_limit: Self::calculate_gas_limit(signed_call),
+			..Default::default()
+		}
+	}
+
+	fn refresh_unsigned_data(unsigned_tx: &mut <Arbitrum as Chain>::Transaction) {
+		if let Some(ChainState { tracked_data, .. }) = ArbitrumChainTracking::chain_state() {
+			let max_fee_per_gas =
+				ARBITRUM_BASE_FEE_MULTIPLIER.saturating_mul_int(tracked_data.base_fee);
+			unsigned_tx.max_fee_per_gas = Some(U256::from(max_fee_per_gas));
+			// Priority fees are ignored by the Arbitrum sequencer.
+			unsigned_tx.max_priority_fee_per_gas = Some(U256::zero());
+		} else {
+			log::warn!("No chain data for Arbitrum. This should never happen. Please check Chain Tracking data.");
+		}
+	}
+
+	fn is_valid_for_rebroadcast(
+		call: &ArbitrumApi<ArbEnvironment>,
+		_payload: &<<Arbitrum as Chain>::ChainCrypto as ChainCrypto>::Payload,
+		current_key: &<<Arbitrum as Chain>::ChainCrypto as ChainCrypto>::AggKey,
+		signature: &<<Arbitrum as Chain>::ChainCrypto as ChainCrypto>::ThresholdSignature,
+	) -> bool {
+		<<Arbitrum as Chain>::ChainCrypto as ChainCrypto>::verify_threshold_signature(
+			current_key,
+			&call.threshold_signature_payload(),
+			signature,
+		)
+	}
+
+	/// Calculate the gas limit for an Arbitrum CCM call as `gas_budget / base_fee`. All other calls
+	/// use the default gas limit.
+	fn calculate_gas_limit(call: &ArbitrumApi<ArbEnvironment>) -> Option<U256> {
+		if let Some(gas_budget) = call.gas_budget() {
+			let current_fee_per_gas = ArbitrumChainTracking::chain_state()
+				.or_else(||{
+					log::warn!("No chain data for Arbitrum. This should never happen. Please check Chain Tracking data.");
+					None
+				})?
+				.tracked_data
+				.base_fee;
+			Some(gas_budget
+				.checked_div(current_fee_per_gas)
+				.unwrap_or_else(||{
+					log::warn!("Current gas price for Arbitrum is 0. This should never happen. Please check Chain Tracking data.");
+					Default::default()
+				}).min(ARBITRUM_MAX_GAS_LIMIT)
+				.into())
+		} else {
+			None
+		}
+	}
+}
+
 pub struct DotTransactionBuilder;
 impl TransactionBuilder<Polkadot, PolkadotApi<DotEnvironment>> for DotTransactionBuilder {
 	fn build_transaction(
@@ -257,6 +334,15 @@ impl TransactionBuilder<Bitcoin, BitcoinApi<BtcEnvironment>> for BtcTransactionB
 		// to check anything here and just rebroadcast.
 		true
 	}
+
+	fn build_fee_bump(
+		signed_call: &BitcoinApi<BtcEnvironment>,
+	) -> Option<FeeBump<Bitcoin, BitcoinApi<BtcEnvironment>>> {
+		// Replacing the transaction would change its txid, invalidating the change utxo that
+		// subsequent transactions may already depend on, so we bump the fee by spending the change
+		// output instead.
+		BitcoinApi::new_child_pays_for_parent(signed_call, &BitcoinFeeGetter::bitcoin_fee_info())
+	}
 }
 
 pub struct BlockAuthorRewardDistribution;
@@ -321,6 +407,44 @@ impl EthEnvironmentProvider for EthEnvironment {
 	}
 }
 
+pub struct ArbEnvironment;
+
+impl ReplayProtectionProvider<Arbitrum> for ArbEnvironment {
+	fn replay_protection(contract_address: eth::Address) -> EvmReplayProtection {
+		EvmReplayProtection {
+			nonce: Self::next_nonce(),
+			chain_id: Self::chain_id(),
+			key_manager_address: Self::key_manager_address(),
+			contract_address,
+		}
+	}
+}
+
+impl EvmEnvironmentProvider<Arbitrum> for ArbEnvironment {
+	fn token_address(asset: assets::arb::Asset) -> Option<eth::Address> {
+		match asset {
+			assets::arb::Asset::ArbEth => Some(ETHEREUM_ETH_ADDRESS),
+			erc20 => Environment::supported_arb_assets(erc20),
+		}
+	}
+
+	fn key_manager_address() -> eth::Address {
+		Environment::arb_key_manager_address()
+	}
+
+	fn vault_address() -> eth::Address {
+		Environment::arb_vault_address()
+	}
+
+	fn chain_id() -> cf_chains::evm::api::EvmChainId {
+		Environment::arbitrum_chain_id()
+	}
+
+	fn next_nonce() -> u64 {
+		Environment::next_arbitrum_signature_nonce()
+	}
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
 pub struct DotEnvironment;
 
@@ -366,6 +490,12 @@ impl ChainEnvironment<UtxoSelectionType, SelectedUtxosAndChangeAmount> for BtcEn
 	}
 }
 
+impl ChainEnvironment<cf_chains::btc::api::EgressMemos, bool> for BtcEnvironment {
+	fn lookup(_: cf_chains::btc::api::EgressMemos) -> Option<bool> {
+		Some(pallet_cf_environment::BitcoinEgressMemosEnabled::<Runtime>::get())
+	}
+}
+
 impl ChainEnvironment<(), cf_chains::btc::AggKey> for BtcEnvironment {
 	fn lookup(_: ()) -> Option<cf_chains::btc::AggKey> {
 		<BitcoinVault as KeyProvider<BitcoinCrypto>>::active_epoch_key()
@@ -411,7 +541,7 @@ impl BroadcastAnyChainGovKey for TokenholderGovernanceBroadcaster {
 				Self::broadcast_gov_key::<Ethereum, EthereumBroadcaster>(maybe_old_key, new_key),
 			ForeignChain::Polkadot =>
 				Self::broadcast_gov_key::<Polkadot, PolkadotBroadcaster>(maybe_old_key, new_key),
-			ForeignChain::Bitcoin => Err(()),
+			ForeignChain::Bitcoin | ForeignChain::Arbitrum => Err(()),
 		}
 	}
 
@@ -421,7 +551,7 @@ impl BroadcastAnyChainGovKey for TokenholderGovernanceBroadcaster {
 				Self::is_govkey_compatible::<<Ethereum as Chain>::ChainCrypto>(key),
 			ForeignChain::Polkadot =>
 				Self::is_govkey_compatible::<<Polkadot as Chain>::ChainCrypto>(key),
-			ForeignChain::Bitcoin => false,
+			ForeignChain::Bitcoin | ForeignChain::Arbitrum => false,
 		}
 	}
 }
@@ -463,6 +593,7 @@ macro_rules! impl_deposit_api_for_anychain {
 				broker_commission_bps: BasisPoints,
 				broker_id: Self::AccountId,
 				channel_metadata: Option<CcmChannelMetadata>,
+				refund_parameters: Option<SwapRefundParameters>,
 			) -> Result<(ChannelId, ForeignChainAddress, <AnyChain as cf_chains::Chain>::ChainBlockNumber), DispatchError> {
 				match source_asset.into() {
 					$(
@@ -473,6 +604,7 @@ macro_rules! impl_deposit_api_for_anychain {
 							broker_commission_bps,
 							broker_id,
 							channel_metadata,
+							refund_parameters,
 						).map(|(channel, address, block_number)| (channel, address, block_number.into())),
 					)+
 				}
@@ -489,17 +621,16 @@ macro_rules! impl_egress_api_for_anychain {
 				asset: Asset,
 				amount: <AnyChain as Chain>::ChainAmount,
 				destination_address: <AnyChain as Chain>::ChainAccount,
-				maybe_ccm_with_gas_budget: Option<(CcmDepositMetadata, <AnyChain as Chain>::ChainAmount)>,
+				maybe_ccm_with_gas_budget: Option<(CcmDepositMetadata, <AnyChain as Chain>::ChainAmount, Option<ForeignChainAddress>)>,
 			) -> EgressId {
 				match asset.into() {
 					$(
 						ForeignChain::$chain => $pallet::schedule_egress(
 							asset.try_into().expect("Checked for asset compatibility"),
 							amount.try_into().expect("Checked for amount compatibility"),
-							destination_address
-								.try_into()
+							<$chain as Chain>::try_from_foreign_chain_address(destination_address)
 								.expect("This address cast is ensured to succeed."),
-								maybe_ccm_with_gas_budget.map(|(metadata, gas_budget)| (metadata, gas_budget.try_into().expect("Chain's Amount must be compatible with u128."))),
+								maybe_ccm_with_gas_budget.map(|(metadata, gas_budget, gas_refund_address)| (metadata, gas_budget.try_into().expect("Chain's Amount must be compatible with u128."), gas_refund_address)),
 						),
 
 					)+
@@ -514,22 +645,91 @@ impl_deposit_api_for_anychain!(
 	AnyChainIngressEgressHandler,
 	(Ethereum, EthereumIngressEgress),
 	(Polkadot, PolkadotIngressEgress),
-	(Bitcoin, BitcoinIngressEgress)
+	(Bitcoin, BitcoinIngressEgress),
+	(Arbitrum, ArbitrumIngressEgress)
 );
 
 impl_egress_api_for_anychain!(
 	AnyChainIngressEgressHandler,
 	(Ethereum, EthereumIngressEgress),
 	(Polkadot, PolkadotIngressEgress),
-	(Bitcoin, BitcoinIngressEgress)
+	(Bitcoin, BitcoinIngressEgress),
+	(Arbitrum, ArbitrumIngressEgress)
 );
 
+/// Estimates ingress and egress fees from the governance-set transaction cost estimates of the
+/// ingress-egress pallets, priced using the tracked chain state.
+pub struct TransactionFeeEstimation;
+
+impl TransactionFeeEstimation {
+	fn estimate_fee_for_chain<C>(
+		asset: Asset,
+		cost: impl FnOnce(TransactionCostEstimate) -> u64,
+	) -> Option<AssetAmount>
+	where
+		C: Chain + PalletInstanceAlias,
+		C::TrackedData: FeeEstimationApi<C>,
+		C::ChainAsset: TryFrom<Asset>,
+		Runtime: pallet_cf_chain_tracking::Config<C::Instance, TargetChain = C>
+			+ pallet_cf_ingress_egress::Config<C::Instance, TargetChain = C>,
+	{
+		let estimate =
+			pallet_cf_ingress_egress::TransactionCostEstimates::<Runtime, C::Instance>::get(
+				C::ChainAsset::try_from(asset).ok()?,
+			)?;
+		Some(
+			pallet_cf_chain_tracking::CurrentChainState::<Runtime, C::Instance>::get()?
+				.tracked_data
+				.estimate_fee(cost(estimate))
+				.into(),
+		)
+	}
+
+	/// The estimated fee, in the chain's gas asset, of a transaction that costs the amount
+	/// selected from the asset's [TransactionCostEstimate].
+	fn estimate_fee_in_gas_asset(
+		asset: Asset,
+		cost: impl FnOnce(TransactionCostEstimate) -> u64,
+	) -> Option<AssetAmount> {
+		match ForeignChain::from(asset) {
+			ForeignChain::Ethereum => Self::estimate_fee_for_chain::<Ethereum>(asset, cost),
+			ForeignChain::Polkadot => Self::estimate_fee_for_chain::<Polkadot>(asset, cost),
+			ForeignChain::Bitcoin => Self::estimate_fee_for_chain::<Bitcoin>(asset, cost),
+			ForeignChain::Arbitrum => Self::estimate_fee_for_chain::<Arbitrum>(asset, cost),
+		}
+	}
+
+	/// The estimated fee for fetching a deposit of the given asset, in the chain's gas asset.
+	pub fn estimate_ingress_fee_in_gas_asset(asset: Asset) -> Option<AssetAmount> {
+		Self::estimate_fee_in_gas_asset(asset, |estimate| estimate.ingress)
+	}
+
+	/// The estimated fee for egressing the given asset, in the chain's gas asset.
+	pub fn estimate_egress_fee_in_gas_asset(asset: Asset) -> Option<AssetAmount> {
+		Self::estimate_fee_in_gas_asset(asset, |estimate| estimate.egress)
+	}
+}
+
+/// Egress fees are converted into the egressed asset at the current pool prices.
+impl EgressFeeEstimator<AnyChain> for TransactionFeeEstimation {
+	fn estimate_egress_fee(asset: Asset) -> Option<AssetAmount> {
+		LiquidityPools::convert_at_current_price(
+			ForeignChain::from(asset).gas_asset(),
+			asset,
+			Self::estimate_egress_fee_in_gas_asset(asset)?,
+		)
+	}
+}
+
 pub struct EthDepositHandler;
 impl DepositHandler<Ethereum> for EthDepositHandler {}
 
 pub struct DotDepositHandler;
 impl DepositHandler<Polkadot> for DotDepositHandler {}
 
+pub struct ArbDepositHandler;
+impl DepositHandler<Arbitrum> for ArbDepositHandler {}
+
 pub struct BtcDepositHandler;
 impl DepositHandler<Bitcoin> for BtcDepositHandler {
 	fn on_deposit_made(
@@ -562,6 +762,9 @@ impl OnBroadcastReady<Ethereum> for BroadcastReadyProvider {
 impl OnBroadcastReady<Polkadot> for BroadcastReadyProvider {
 	type ApiCall = PolkadotApi<DotEnvironment>;
 }
+impl OnBroadcastReady<Arbitrum> for BroadcastReadyProvider {
+	type ApiCall = ArbitrumApi<ArbEnvironment>;
+}
 impl OnBroadcastReady<Bitcoin> for BroadcastReadyProvider {
 	type ApiCall = BitcoinApi<BtcEnvironment>;
 
@@ -1,3 +1,4 @@
+pub mod arb;
 pub mod btc;
 pub mod dot;
 pub mod eth;
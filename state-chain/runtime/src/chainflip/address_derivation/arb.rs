@@ -0,0 +1,36 @@
+use super::AddressDerivation;
+use crate::{ArbEnvironment, Environment};
+use cf_chains::{
+	address::{AddressDerivationApi, AddressDerivationError},
+	arb::Arbitrum,
+	eth::deposit_address::get_create_2_address,
+	evm::api::EvmEnvironmentProvider,
+	Chain,
+};
+use cf_primitives::{chains::assets::arb, ChannelId};
+
+impl AddressDerivationApi<Arbitrum> for AddressDerivation {
+	fn generate_address(
+		source_asset: arb::Asset,
+		channel_id: ChannelId,
+	) -> Result<<Arbitrum as Chain>::ChainAccount, AddressDerivationError> {
+		Ok(get_create_2_address(
+			Environment::arb_vault_address(),
+			ArbEnvironment::token_address(source_asset),
+			channel_id,
+		))
+	}
+
+	fn generate_address_and_state(
+		source_asset: <Arbitrum as Chain>::ChainAsset,
+		channel_id: ChannelId,
+	) -> Result<
+		(<Arbitrum as Chain>::ChainAccount, <Arbitrum as Chain>::DepositChannelState),
+		AddressDerivationError,
+	> {
+		Ok((
+			<Self as AddressDerivationApi<Arbitrum>>::generate_address(source_asset, channel_id)?,
+			Default::default(),
+		))
+	}
+}
@@ -1,10 +1,10 @@
 use crate::Vec;
 use cf_chains::{
 	address::{AddressDerivationApi, AddressDerivationError},
-	dot::PolkadotAccountId,
+	dot::{xcm::asset_hub_account_of, PolkadotAccountId},
 	Chain, Polkadot,
 };
-use cf_primitives::ChannelId;
+use cf_primitives::{chains::assets::dot, ChannelId};
 use cf_utilities::SliceToArray;
 use frame_support::sp_runtime::traits::{BlakeTwo256, Hash};
 use sp_std::mem::size_of;
@@ -15,16 +15,23 @@ use super::AddressDerivation;
 
 impl AddressDerivationApi<Polkadot> for AddressDerivation {
 	fn generate_address(
-		_source_asset: <Polkadot as Chain>::ChainAsset,
+		source_asset: <Polkadot as Chain>::ChainAsset,
 		channel_id: ChannelId,
 	) -> Result<<Polkadot as Chain>::ChainAccount, AddressDerivationError> {
 		const PREFIX: &[u8; 16] = b"modlpy/utilisuba";
 		const RAW_PUBLIC_KEY_SIZE: usize = 32;
 		const PAYLOAD_LENGTH: usize = PREFIX.len() + RAW_PUBLIC_KEY_SIZE + size_of::<u16>();
 
-		let master_account = Environment::polkadot_vault_account()
+		let vault_account = Environment::polkadot_vault_account()
 			.ok_or(AddressDerivationError::MissingPolkadotVault)?;
 
+		// Assets on Asset Hub are fetched by the vault's Asset Hub account, so channels for these
+		// assets must be derived from that account instead.
+		let master_account = match source_asset {
+			dot::Asset::Dot => vault_account,
+			dot::Asset::DotUsdt => asset_hub_account_of(vault_account),
+		};
+
 		let mut layers = channel_id
 			.to_be_bytes()
 			.chunks(2)
@@ -69,7 +76,6 @@ impl AddressDerivationApi<Polkadot> for AddressDerivation {
 mod test {
 	use super::*;
 	use crate::Runtime;
-	use cf_primitives::chains::assets::dot;
 	use frame_support::sp_runtime::app_crypto::Ss58Codec;
 	use pallet_cf_environment::PolkadotVaultAccountId;
 
@@ -140,4 +146,38 @@ mod test {
 			);
 		});
 	}
+
+	#[test]
+	fn usdt_channels_are_derived_from_the_asset_hub_vault_account() {
+		frame_support::sp_io::TestExternalities::new_empty().execute_with(|| {
+			let vault_account = PolkadotAccountId::from_aliased([1u8; 32]);
+			PolkadotVaultAccountId::<Runtime>::put(vault_account);
+
+			let dot_address =
+				<AddressDerivation as AddressDerivationApi<Polkadot>>::generate_address(
+					dot::Asset::Dot,
+					1,
+				)
+				.unwrap();
+			let usdt_address =
+				<AddressDerivation as AddressDerivationApi<Polkadot>>::generate_address(
+					dot::Asset::DotUsdt,
+					1,
+				)
+				.unwrap();
+
+			assert_ne!(dot_address, usdt_address);
+
+			// Deriving from the Asset Hub account directly gives the same result.
+			PolkadotVaultAccountId::<Runtime>::put(asset_hub_account_of(vault_account));
+			assert_eq!(
+				usdt_address,
+				<AddressDerivation as AddressDerivationApi<Polkadot>>::generate_address(
+					dot::Asset::Dot,
+					1,
+				)
+				.unwrap()
+			);
+		});
+	}
 }
@@ -10,20 +10,21 @@ pub mod test_runner;
 mod weights;
 use crate::{
 	chainflip::{calculate_account_apy, Offence},
-	runtime_apis::{AuctionState, LiquidityProviderInfo, RuntimeApiPenalty},
+	runtime_apis::{AuctionState, LiquidityProviderInfo, RuntimeApiPenalty, SwapQuote},
 };
 use cf_amm::{
 	common::{Amount, Price, Tick},
 	range_orders::Liquidity,
 };
 use cf_chains::{
+	arb::{api::ArbitrumApi, Arbitrum},
 	btc::BitcoinCrypto,
 	dot::{self, PolkadotCrypto},
 	eth::{self, api::EthereumApi, Address as EthereumAddress, Ethereum},
 	evm::EvmCrypto,
 	Bitcoin, CcmChannelMetadata, ForeignChain, Polkadot,
 };
-use cf_primitives::NetworkEnvironment;
+use cf_primitives::{BasisPoints, NetworkEnvironment};
 use core::ops::Range;
 pub use frame_system::Call as SystemCall;
 use pallet_cf_governance::GovCallHash;
@@ -39,7 +40,7 @@ use crate::runtime_apis::RuntimeApiAccountInfoV2;
 
 pub use frame_support::{
 	construct_runtime, debug,
-	instances::{Instance1, Instance2, Instance3},
+	instances::{Instance1, Instance2, Instance3, Instance4},
 	parameter_types,
 	traits::{
 		ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, ConstU8, Get, KeyOwnerProofSystem,
@@ -56,7 +57,7 @@ pub use frame_support::{
 };
 use frame_system::offchain::SendTransactionTypes;
 use pallet_cf_funding::MinimumFunding;
-use pallet_cf_pools::{PoolInfo, PoolOrders};
+use pallet_cf_pools::{PoolInfo, PoolOrders, PoolStats, SwapRoute};
 use pallet_grandpa::AuthorityId as GrandpaId;
 use pallet_session::historical as session_historical;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -89,13 +90,14 @@ pub use cf_traits::{
 	SwappingApi,
 };
 // Required for genesis config.
+pub use pallet_cf_ingress_egress::migrations::transaction_cost_estimates::initial_transaction_cost_estimates;
 pub use pallet_cf_validator::SetSizeParameters;
 
 pub use chainflip::chain_instances::*;
 use chainflip::{
 	all_vaults_rotator::AllVaultRotator, epoch_transition::ChainflipEpochTransitions,
-	BroadcastReadyProvider, BtcEnvironment, ChainAddressConverter, ChainflipHeartbeat,
-	DotEnvironment, EthEnvironment, TokenholderGovernanceBroadcaster,
+	ArbEnvironment, BroadcastReadyProvider, BtcEnvironment, ChainAddressConverter,
+	ChainflipHeartbeat, DotEnvironment, EthEnvironment, TokenholderGovernanceBroadcaster,
 };
 use safe_mode::{RuntimeSafeMode, WitnesserCallPermission};
 
@@ -172,7 +174,7 @@ impl pallet_cf_validator::Config for Runtime {
 	type Offence = chainflip::Offence;
 	type EpochTransitionHandler = ChainflipEpochTransitions;
 	type ValidatorWeightInfo = pallet_cf_validator::weights::PalletWeight<Runtime>;
-	type VaultRotator = AllVaultRotator<EthereumVault, PolkadotVault, BitcoinVault>;
+	type VaultRotator = AllVaultRotator<EthereumVault, PolkadotVault, BitcoinVault, ArbitrumVault>;
 	type MissedAuthorshipSlots = chainflip::MissedAuraSlots;
 	type BidderProvider = pallet_cf_funding::Pallet<Self>;
 	type KeygenQualification = (
@@ -209,7 +211,12 @@ impl pallet_cf_environment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PolkadotVaultKeyWitnessedHandler = PolkadotVault;
 	type BitcoinVaultKeyWitnessedHandler = BitcoinVault;
+	type ArbitrumVaultKeyWitnessedHandler = ArbitrumVault;
+	type EthereumMinimumDeposit = EthereumIngressEgress;
 	type BitcoinFeeInfo = chainflip::BitcoinFeeGetter;
+	type BitcoinConsolidationCall = cf_chains::btc::api::BitcoinApi<BtcEnvironment>;
+	type BitcoinBroadcaster = BitcoinBroadcaster;
+	type BitcoinUtxoConsolidationInterval = ConstU32<HOURS>;
 	type RuntimeSafeMode = RuntimeSafeMode;
 	type CurrentReleaseVersion = CurrentReleaseVersion;
 	type WeightInfo = pallet_cf_environment::weights::PalletWeight<Runtime>;
@@ -220,6 +227,7 @@ impl pallet_cf_swapping::Config for Runtime {
 	type DepositHandler = chainflip::AnyChainIngressEgressHandler;
 	type EgressHandler = chainflip::AnyChainIngressEgressHandler;
 	type SwappingApi = LiquidityPools;
+	type EgressFeeEstimator = chainflip::TransactionFeeEstimation;
 	type AddressConverter = ChainAddressConverter;
 	type SafeMode = RuntimeSafeMode;
 	type WeightInfo = pallet_cf_swapping::weights::PalletWeight<Runtime>;
@@ -240,6 +248,7 @@ impl pallet_cf_vaults::Config<EthereumInstance> for Runtime {
 	type ChainTracking = EthereumChainTracking;
 	type SafeMode = RuntimeSafeMode;
 	type Slasher = FlipSlasher<Self>;
+	type CeremonyIdProvider = EthereumVault;
 }
 
 impl pallet_cf_vaults::Config<PolkadotInstance> for Runtime {
@@ -257,6 +266,7 @@ impl pallet_cf_vaults::Config<PolkadotInstance> for Runtime {
 	type ChainTracking = PolkadotChainTracking;
 	type SafeMode = RuntimeSafeMode;
 	type Slasher = FlipSlasher<Self>;
+	type CeremonyIdProvider = PolkadotVault;
 }
 
 impl pallet_cf_vaults::Config<BitcoinInstance> for Runtime {
@@ -274,6 +284,27 @@ impl pallet_cf_vaults::Config<BitcoinInstance> for Runtime {
 	type ChainTracking = BitcoinChainTracking;
 	type SafeMode = RuntimeSafeMode;
 	type Slasher = FlipSlasher<Self>;
+	type CeremonyIdProvider = BitcoinVault;
+}
+
+impl pallet_cf_vaults::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type EnsureThresholdSigned =
+		pallet_cf_threshold_signature::EnsureThresholdSigned<Self, ArbitrumInstance>;
+	type ThresholdSigner = ArbitrumThresholdSigner;
+	type Offence = chainflip::Offence;
+	type Chain = Arbitrum;
+	type SetAggKeyWithAggKey = ArbitrumApi<ArbEnvironment>;
+	type Broadcaster = ArbitrumBroadcaster;
+	type OffenceReporter = Reputation;
+	type WeightInfo = pallet_cf_vaults::weights::PalletWeight<Runtime>;
+	type ChainTracking = ArbitrumChainTracking;
+	type SafeMode = RuntimeSafeMode;
+	type Slasher = FlipSlasher<Self>;
+	// Arbitrum ceremonies are run by the same EVM multisig client as Ethereum's, so they share
+	// Ethereum's ceremony id sequence.
+	type CeremonyIdProvider = EthereumVault;
 }
 
 use chainflip::address_derivation::AddressDerivation;
@@ -291,6 +322,7 @@ impl pallet_cf_ingress_egress::Config<EthereumInstance> for Runtime {
 	type DepositHandler = chainflip::EthDepositHandler;
 	type CcmHandler = Swapping;
 	type ChainTracking = EthereumChainTracking;
+	type EgressFeeEstimator = chainflip::TransactionFeeEstimation;
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 }
 
@@ -307,6 +339,7 @@ impl pallet_cf_ingress_egress::Config<PolkadotInstance> for Runtime {
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 	type DepositHandler = chainflip::DotDepositHandler;
 	type ChainTracking = PolkadotChainTracking;
+	type EgressFeeEstimator = chainflip::TransactionFeeEstimation;
 	type CcmHandler = Swapping;
 }
 
@@ -323,6 +356,24 @@ impl pallet_cf_ingress_egress::Config<BitcoinInstance> for Runtime {
 	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
 	type DepositHandler = chainflip::BtcDepositHandler;
 	type ChainTracking = BitcoinChainTracking;
+	type EgressFeeEstimator = chainflip::TransactionFeeEstimation;
+	type CcmHandler = Swapping;
+}
+
+impl pallet_cf_ingress_egress::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type TargetChain = Arbitrum;
+	type AddressDerivation = AddressDerivation;
+	type AddressConverter = ChainAddressConverter;
+	type LpBalance = LiquidityProvider;
+	type SwapDepositHandler = Swapping;
+	type ChainApiCall = ArbitrumApi<ArbEnvironment>;
+	type Broadcaster = ArbitrumBroadcaster;
+	type WeightInfo = pallet_cf_ingress_egress::weights::PalletWeight<Runtime>;
+	type DepositHandler = chainflip::ArbDepositHandler;
+	type ChainTracking = ArbitrumChainTracking;
+	type EgressFeeEstimator = chainflip::TransactionFeeEstimation;
 	type CcmHandler = Swapping;
 }
 
@@ -335,6 +386,9 @@ impl pallet_cf_pools::Config for Runtime {
 	type LpBalance = LiquidityProvider;
 	type NetworkFee = NetworkFee;
 	type SafeMode = RuntimeSafeMode;
+	type ObservationCapacity = ConstU32<1200>;
+	type MaxExpiredLimitOrdersPerBlock = ConstU32<100>;
+	type MaxOrdersPerBatch = ConstU32<100>;
 	type WeightInfo = ();
 }
 
@@ -652,6 +706,21 @@ impl pallet_cf_threshold_signature::Config<BitcoinInstance> for Runtime {
 	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
 }
 
+impl pallet_cf_threshold_signature::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Offence = chainflip::Offence;
+	type RuntimeOrigin = RuntimeOrigin;
+	type ThresholdCallable = RuntimeCall;
+	type ThresholdSignerNomination = chainflip::RandomSignerNomination;
+	type TargetChainCrypto = EvmCrypto;
+	type KeyProvider = ArbitrumVault;
+	type OffenceReporter = Reputation;
+	// Shares Ethereum's ceremony id sequence, see `pallet_cf_vaults::Config<ArbitrumInstance>`.
+	type CeremonyIdProvider = EthereumVault;
+	type CeremonyRetryDelay = ConstU32<1>;
+	type Weights = pallet_cf_threshold_signature::weights::PalletWeight<Self>;
+}
+
 impl pallet_cf_broadcast::Config<EthereumInstance> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -671,8 +740,10 @@ impl pallet_cf_broadcast::Config<EthereumInstance> for Runtime {
 	type WeightInfo = pallet_cf_broadcast::weights::PalletWeight<Runtime>;
 	type SafeMode = RuntimeSafeMode;
 	type SafeModeBlockMargin = ConstU32<10>;
+	type FeeBumpThreshold = ConstU32<0>;
 	type KeyProvider = EthereumVault;
 	type ChainTracking = EthereumChainTracking;
+	type CcmGasRefundHandler = EthereumIngressEgress;
 }
 
 impl pallet_cf_broadcast::Config<PolkadotInstance> for Runtime {
@@ -694,8 +765,10 @@ impl pallet_cf_broadcast::Config<PolkadotInstance> for Runtime {
 	type WeightInfo = pallet_cf_broadcast::weights::PalletWeight<Runtime>;
 	type SafeMode = RuntimeSafeMode;
 	type SafeModeBlockMargin = ConstU32<10>;
+	type FeeBumpThreshold = ConstU32<0>;
 	type KeyProvider = PolkadotVault;
 	type ChainTracking = PolkadotChainTracking;
+	type CcmGasRefundHandler = PolkadotIngressEgress;
 }
 
 impl pallet_cf_broadcast::Config<BitcoinInstance> for Runtime {
@@ -717,8 +790,35 @@ impl pallet_cf_broadcast::Config<BitcoinInstance> for Runtime {
 	type WeightInfo = pallet_cf_broadcast::weights::PalletWeight<Runtime>;
 	type SafeMode = RuntimeSafeMode;
 	type SafeModeBlockMargin = ConstU32<10>;
+	type FeeBumpThreshold = ConstU32<{ 3 * HOURS }>;
 	type KeyProvider = BitcoinVault;
 	type ChainTracking = BitcoinChainTracking;
+	type CcmGasRefundHandler = BitcoinIngressEgress;
+}
+
+impl pallet_cf_broadcast::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeOrigin = RuntimeOrigin;
+	type BroadcastCallable = RuntimeCall;
+	type Offence = chainflip::Offence;
+	type TargetChain = Arbitrum;
+	type ApiCall = ArbitrumApi<ArbEnvironment>;
+	type ThresholdSigner = ArbitrumThresholdSigner;
+	type TransactionBuilder = chainflip::ArbTransactionBuilder;
+	type BroadcastSignerNomination = chainflip::RandomSignerNomination;
+	type OffenceReporter = Reputation;
+	type EnsureThresholdSigned =
+		pallet_cf_threshold_signature::EnsureThresholdSigned<Self, ArbitrumInstance>;
+	type BroadcastReadyProvider = BroadcastReadyProvider;
+	type BroadcastTimeout = ConstU32<{ 10 * MINUTES }>;
+	type WeightInfo = pallet_cf_broadcast::weights::PalletWeight<Runtime>;
+	type SafeMode = RuntimeSafeMode;
+	type SafeModeBlockMargin = ConstU32<10>;
+	type FeeBumpThreshold = ConstU32<0>;
+	type KeyProvider = ArbitrumVault;
+	type ChainTracking = ArbitrumChainTracking;
+	type CcmGasRefundHandler = ArbitrumIngressEgress;
 }
 
 impl pallet_cf_chain_tracking::Config<EthereumInstance> for Runtime {
@@ -739,6 +839,12 @@ impl pallet_cf_chain_tracking::Config<BitcoinInstance> for Runtime {
 	type WeightInfo = pallet_cf_chain_tracking::weights::PalletWeight<Runtime>;
 }
 
+impl pallet_cf_chain_tracking::Config<ArbitrumInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type TargetChain = Arbitrum;
+	type WeightInfo = pallet_cf_chain_tracking::weights::PalletWeight<Runtime>;
+}
+
 construct_runtime!(
 	pub struct Runtime
 	{
@@ -765,18 +871,22 @@ construct_runtime!(
 		EthereumChainTracking: pallet_cf_chain_tracking::<Instance1>,
 		PolkadotChainTracking: pallet_cf_chain_tracking::<Instance2>,
 		BitcoinChainTracking: pallet_cf_chain_tracking::<Instance3>,
+		ArbitrumChainTracking: pallet_cf_chain_tracking::<Instance4>,
 
 		EthereumVault: pallet_cf_vaults::<Instance1>,
 		PolkadotVault: pallet_cf_vaults::<Instance2>,
 		BitcoinVault: pallet_cf_vaults::<Instance3>,
+		ArbitrumVault: pallet_cf_vaults::<Instance4>,
 
 		EthereumThresholdSigner: pallet_cf_threshold_signature::<Instance1>,
 		PolkadotThresholdSigner: pallet_cf_threshold_signature::<Instance2>,
 		BitcoinThresholdSigner: pallet_cf_threshold_signature::<Instance3>,
+		ArbitrumThresholdSigner: pallet_cf_threshold_signature::<Instance4>,
 
 		EthereumBroadcaster: pallet_cf_broadcast::<Instance1>,
 		PolkadotBroadcaster: pallet_cf_broadcast::<Instance2>,
 		BitcoinBroadcaster: pallet_cf_broadcast::<Instance3>,
+		ArbitrumBroadcaster: pallet_cf_broadcast::<Instance4>,
 
 		Swapping: pallet_cf_swapping,
 		LiquidityProvider: pallet_cf_lp,
@@ -784,6 +894,7 @@ construct_runtime!(
 		EthereumIngressEgress: pallet_cf_ingress_egress::<Instance1>,
 		PolkadotIngressEgress: pallet_cf_ingress_egress::<Instance2>,
 		BitcoinIngressEgress: pallet_cf_ingress_egress::<Instance3>,
+		ArbitrumIngressEgress: pallet_cf_ingress_egress::<Instance4>,
 
 		LiquidityPools: pallet_cf_pools,
 	}
@@ -837,18 +948,23 @@ type PalletMigrations = (
 	pallet_cf_threshold_signature::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_threshold_signature::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_threshold_signature::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_threshold_signature::migrations::PalletMigration<Runtime, Instance4>,
 	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_broadcast::migrations::PalletMigration<Runtime, Instance4>,
 	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_chain_tracking::migrations::PalletMigration<Runtime, Instance4>,
 	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_vaults::migrations::PalletMigration<Runtime, Instance4>,
 	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance1>,
 	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance2>,
 	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance3>,
+	pallet_cf_ingress_egress::migrations::PalletMigration<Runtime, Instance4>,
 	pallet_cf_swapping::migrations::PalletMigration<Runtime>,
 	pallet_cf_lp::migrations::PalletMigration<Runtime>,
 );
@@ -1034,6 +1150,14 @@ impl_runtime_apis! {
 			LiquidityPools::current_price(from, to)
 		}
 
+		fn cf_pool_twap(
+			base_asset: Asset,
+			pair_asset: Asset,
+			window: BlockNumber,
+		) -> Option<Result<Price, DispatchError>> {
+			LiquidityPools::pool_twap(base_asset, pair_asset, window)
+		}
+
 		/// Simulates a swap and return the intermediate (if any) and final output.
 		///
 		/// If no swap rate can be calculated, returns None. This can happen if the pools are not
@@ -1046,6 +1170,47 @@ impl_runtime_apis! {
 			LiquidityPools::swap_with_network_fee(from, to, amount).ok()
 		}
 
+		/// Note: This function must only be called through RPC, because RPC has its own storage buffer
+		/// layer and would not affect on-chain storage.
+		fn cf_pool_swap_route(from: Asset, to: Asset, amount: AssetAmount) -> Result<SwapRoute, DispatchError> {
+			LiquidityPools::swap_route(from, to, amount)
+		}
+
+		/// Simulates a swap and returns its output along with the fees that would be charged.
+		///
+		/// Note: This function must only be called through RPC, because RPC has its own storage buffer
+		/// layer and would not affect on-chain storage.
+		fn cf_swap_quote(
+			from: Asset,
+			to: Asset,
+			amount: AssetAmount,
+			broker_commission_bps: BasisPoints,
+		) -> Result<SwapQuote, DispatchError> {
+			let broker_commission = Permill::from_parts(u32::from(broker_commission_bps) * 100) * amount;
+			let breakdown = LiquidityPools::swap_with_breakdown(from, to, amount - broker_commission)?;
+
+			let ingress_fee = chainflip::TransactionFeeEstimation::estimate_ingress_fee_in_gas_asset(from)
+				.unwrap_or_default();
+			let egress_fee = chainflip::TransactionFeeEstimation::estimate_egress_fee_in_gas_asset(to)
+				.unwrap_or_default();
+
+			Ok(SwapQuote {
+				intermediary: breakdown.intermediary,
+				output: breakdown.output,
+				broker_commission,
+				network_fee: breakdown.network_fee,
+				pool_fees: breakdown.pool_fees,
+				ingress_fee: (ForeignChain::from(from).gas_asset(), ingress_fee),
+				egress_fee: (ForeignChain::from(to).gas_asset(), egress_fee),
+				price_impact: Permill::from_rational(
+					breakdown.spot_output.saturating_sub(breakdown.output),
+					breakdown.spot_output,
+				),
+				minimum_deposit: Self::cf_min_deposit_amount(from),
+				minimum_swap: Swapping::minimum_swap_amount(from),
+			})
+		}
+
 		fn cf_pool_info(base_asset: Asset, pair_asset: Asset) -> Option<PoolInfo> {
 			LiquidityPools::pool_info(base_asset, pair_asset)
 		}
@@ -1074,6 +1239,14 @@ impl_runtime_apis! {
 			LiquidityPools::pool_orders(base_asset, pair_asset, &lp)
 		}
 
+		fn cf_pool_stats(
+			base_asset: Asset,
+			pair_asset: Asset,
+			lp: Option<AccountId>,
+		) -> Option<PoolStats> {
+			LiquidityPools::pool_stats(base_asset, pair_asset, lp.as_ref())
+		}
+
 		fn cf_pool_range_order_liquidity_value(
 			base_asset: Asset,
 			pair_asset: Asset,
@@ -1093,7 +1266,7 @@ impl_runtime_apis! {
 
 		fn cf_min_deposit_amount(asset: Asset) -> AssetAmount {
 			use pallet_cf_ingress_egress::MinimumDeposit;
-			use cf_chains::assets::{eth, dot, btc};
+			use cf_chains::assets::{eth, dot, btc, arb};
 
 			match ForeignChain::from(asset) {
 				ForeignChain::Ethereum => MinimumDeposit::<Runtime, EthereumInstance>::get(
@@ -1108,6 +1281,10 @@ impl_runtime_apis! {
 					btc::Asset::try_from(asset)
 						.expect("Conversion must succeed: ForeignChain checked in match clause.")
 				).into(),
+				ForeignChain::Arbitrum => MinimumDeposit::<Runtime, ArbitrumInstance>::get(
+					arb::Asset::try_from(asset)
+						.expect("Conversion must succeed: ForeignChain checked in match clause.")
+				),
 			}
 		}
 
@@ -1231,6 +1408,11 @@ impl_runtime_apis! {
 							}) => {
 								all_prewitnessed_swaps.extend(filter_deposit_swaps::<Polkadot, PolkadotInstance>(from, to, deposit_witnesses));
 							}
+							RuntimeCall::ArbitrumIngressEgress(pallet_cf_ingress_egress::Call::process_deposits {
+								deposit_witnesses, ..
+							}) => {
+								all_prewitnessed_swaps.extend(filter_deposit_swaps::<Arbitrum, ArbitrumInstance>(from, to, deposit_witnesses));
+							}
 							RuntimeCall::Swapping(pallet_cf_swapping::Call::ccm_deposit {
 								source_asset, deposit_amount, destination_asset, deposit_metadata, ..
 							}) => {
@@ -5,14 +5,16 @@ use cf_amm::{
 };
 use cf_chains::{eth::Address as EthereumAddress, ForeignChainAddress};
 use cf_primitives::{
-	AccountRole, Asset, AssetAmount, EpochIndex, ForeignChain, NetworkEnvironment, SemVer,
-	SwapOutput,
+	AccountRole, Asset, AssetAmount, BasisPoints, EpochIndex, ForeignChain, NetworkEnvironment,
+	SemVer, SwapOutput,
 };
 use codec::{Decode, Encode};
 use core::ops::Range;
-use frame_support::sp_runtime::AccountId32;
+use frame_support::sp_runtime::{AccountId32, Permill};
 use pallet_cf_governance::GovCallHash;
-use pallet_cf_pools::{AssetsMap, PoolInfo, PoolLiquidity, PoolOrders, UnidirectionalPoolDepth};
+use pallet_cf_pools::{
+	AssetsMap, PoolInfo, PoolLiquidity, PoolOrders, PoolStats, SwapRoute, UnidirectionalPoolDepth,
+};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_api::decl_runtime_apis;
@@ -73,6 +75,26 @@ pub struct LiquidityProviderInfo {
 	pub balances: Vec<(Asset, AssetAmount)>,
 }
 
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo)]
+pub struct SwapQuote {
+	pub intermediary: Option<AssetAmount>,
+	pub output: AssetAmount,
+	/// The broker commission, in the input asset.
+	pub broker_commission: AssetAmount,
+	/// The network fee, in the stable asset.
+	pub network_fee: AssetAmount,
+	/// The liquidity provider fee of each pool, in the asset swapped into the pool.
+	pub pool_fees: Vec<(Asset, AssetAmount)>,
+	/// The estimated cost of fetching the deposit, in the input chain's gas asset.
+	pub ingress_fee: (Asset, AssetAmount),
+	/// The estimated cost of egressing the output, in the output chain's gas asset.
+	pub egress_fee: (Asset, AssetAmount),
+	/// How much less the output is than it would be at the current pool prices.
+	pub price_impact: Permill,
+	pub minimum_deposit: AssetAmount,
+	pub minimum_swap: AssetAmount,
+}
+
 decl_runtime_apis!(
 	/// Definition for all runtime API interfaces.
 	pub trait CustomRuntimeApi {
@@ -104,8 +126,28 @@ decl_runtime_apis!(
 		fn cf_generate_gov_key_call_hash(call: Vec<u8>) -> GovCallHash;
 		fn cf_auction_state() -> AuctionState;
 		fn cf_pool_price(from: Asset, to: Asset) -> Option<Price>;
+		/// Returns the time-weighted average price of the pool over the last `window` blocks.
+		fn cf_pool_twap(
+			base_asset: Asset,
+			pair_asset: Asset,
+			window: u32,
+		) -> Option<Result<Price, DispatchError>>;
 		fn cf_pool_simulate_swap(from: Asset, to: Asset, amount: AssetAmount)
 			-> Option<SwapOutput>;
+		/// Simulates a swap along the route it is executed along (through the stable asset), and
+		/// returns the route with the amounts of each hop.
+		fn cf_pool_swap_route(
+			from: Asset,
+			to: Asset,
+			amount: AssetAmount,
+		) -> Result<SwapRoute, DispatchError>;
+		/// Simulates a swap, including broker commission, and returns its output and fees.
+		fn cf_swap_quote(
+			from: Asset,
+			to: Asset,
+			amount: AssetAmount,
+			broker_commission_bps: BasisPoints,
+		) -> Result<SwapQuote, DispatchError>;
 		fn cf_pool_info(base_asset: Asset, pair_asset: Asset) -> Option<PoolInfo>;
 		fn cf_pool_depth(
 			base_asset: Asset,
@@ -119,6 +161,13 @@ decl_runtime_apis!(
 			tick_range: Range<cf_amm::common::Tick>,
 		) -> Option<Result<AssetsMap<Amount>, DispatchError>>;
 		fn cf_pool_orders(base: Asset, pair: Asset, lp: AccountId32) -> Option<PoolOrders>;
+		/// Returns the cumulative swap volume and fees of the pool, and optionally the fees the
+		/// given LP has collected from it.
+		fn cf_pool_stats(
+			base_asset: Asset,
+			pair_asset: Asset,
+			lp: Option<AccountId32>,
+		) -> Option<PoolStats>;
 		fn cf_pool_range_order_liquidity_value(
 			base_asset: Asset,
 			pair_asset: Asset,
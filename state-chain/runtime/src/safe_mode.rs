@@ -1,6 +1,8 @@
 //! For filtering runtime calls and other related utilities.
 
-use crate::{BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime, RuntimeCall};
+use crate::{
+	ArbitrumInstance, BitcoinInstance, EthereumInstance, PolkadotInstance, Runtime, RuntimeCall,
+};
 use cf_traits::{impl_runtime_safe_mode, CallDispatchFilter};
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
@@ -18,9 +20,11 @@ impl_runtime_safe_mode! {
 	vault_ethereum: pallet_cf_vaults::PalletSafeMode<EthereumInstance>,
 	vault_bitcoin: pallet_cf_vaults::PalletSafeMode<BitcoinInstance>,
 	vault_polkadot: pallet_cf_vaults::PalletSafeMode<PolkadotInstance>,
+	vault_arbitrum: pallet_cf_vaults::PalletSafeMode<ArbitrumInstance>,
 	broadcast_ethereum: pallet_cf_broadcast::PalletSafeMode<EthereumInstance>,
 	broadcast_bitcoin: pallet_cf_broadcast::PalletSafeMode<BitcoinInstance>,
 	broadcast_polkadot: pallet_cf_broadcast::PalletSafeMode<PolkadotInstance>,
+	broadcast_arbitrum: pallet_cf_broadcast::PalletSafeMode<ArbitrumInstance>,
 	witnesser: pallet_cf_witnesser::PalletSafeMode<WitnesserCallPermission>,
 }
 
@@ -62,6 +66,12 @@ pub struct WitnesserCallPermission {
 	pub bitcoin_chain_tracking: bool,
 	pub bitcoin_ingress_egress: bool,
 	pub bitcoin_vault: bool,
+
+	// Arbitrum pallets
+	pub arbitrum_broadcast: bool,
+	pub arbitrum_chain_tracking: bool,
+	pub arbitrum_ingress_egress: bool,
+	pub arbitrum_vault: bool,
 }
 
 impl WitnesserCallPermission {
@@ -82,6 +92,10 @@ impl WitnesserCallPermission {
 			bitcoin_chain_tracking: true,
 			bitcoin_ingress_egress: true,
 			bitcoin_vault: true,
+			arbitrum_broadcast: true,
+			arbitrum_chain_tracking: true,
+			arbitrum_ingress_egress: true,
+			arbitrum_vault: true,
 		}
 	}
 }
@@ -108,6 +122,11 @@ impl CallDispatchFilter<RuntimeCall> for WitnesserCallPermission {
 			RuntimeCall::BitcoinIngressEgress(..) => self.bitcoin_ingress_egress,
 			RuntimeCall::BitcoinVault(..) => self.bitcoin_vault,
 
+			RuntimeCall::ArbitrumBroadcaster(..) => self.arbitrum_broadcast,
+			RuntimeCall::ArbitrumChainTracking(..) => self.arbitrum_chain_tracking,
+			RuntimeCall::ArbitrumIngressEgress(..) => self.arbitrum_ingress_egress,
+			RuntimeCall::ArbitrumVault(..) => self.arbitrum_vault,
+
 			_ => {
 				cf_runtime_utilities::log_or_panic!(
 					"All witnesser calls must be controllable through `WitnesserCallPermission`. Call: {:?}",
@@ -15,7 +15,7 @@ pub use async_result::AsyncResult;
 
 use cf_chains::{
 	address::ForeignChainAddress, ApiCall, CcmChannelMetadata, CcmDepositMetadata, Chain,
-	ChainCrypto, DepositChannel, Ethereum, Polkadot, SwapOrigin,
+	ChainCrypto, DepositChannel, Ethereum, Polkadot, SwapOrigin, SwapRefundParameters,
 };
 use cf_primitives::{
 	chains::assets, AccountRole, Asset, AssetAmount, AuthorityCount, BasisPoints, BroadcastId,
@@ -497,6 +497,14 @@ pub trait Broadcaster<C: Chain> {
 		api_call: Self::ApiCall,
 		callback: Self::Callback,
 	) -> (BroadcastId, ThresholdSignatureRequestId);
+
+	/// Like `threshold_sign_and_broadcast` but, once the broadcast has succeeded, whatever is left
+	/// of the gas budget after paying the transaction fee is refunded to the given address.
+	fn threshold_sign_and_broadcast_with_gas_refund(
+		api_call: Self::ApiCall,
+		gas_budget: C::ChainAmount,
+		refund_address: C::ChainAccount,
+	) -> (BroadcastId, ThresholdSignatureRequestId);
 }
 
 /// The heartbeat of the network
@@ -656,9 +664,15 @@ pub trait DepositApi<C: Chain> {
 		broker_commission_bps: BasisPoints,
 		broker_id: Self::AccountId,
 		channel_metadata: Option<CcmChannelMetadata>,
+		refund_parameters: Option<SwapRefundParameters>,
 	) -> Result<(ChannelId, ForeignChainAddress, C::ChainBlockNumber), DispatchError>;
 }
 
+/// Allow pallets to configure the minimum amount accepted for deposits of an asset.
+pub trait SetMinimumDeposit<C: Chain> {
+	fn set_minimum_deposit(asset: C::ChainAsset, minimum_deposit: C::ChainAmount);
+}
+
 pub trait AccountRoleRegistry<T: frame_system::Config> {
 	fn register_account_role(who: &T::AccountId, role: AccountRole) -> DispatchResult;
 
@@ -701,20 +715,48 @@ pub trait AccountRoleRegistry<T: frame_system::Config> {
 
 /// API that allows other pallets to Egress assets out of the State Chain.
 pub trait EgressApi<C: Chain> {
+	/// Cross chain messages are scheduled with their gas budget and, optionally, the address that
+	/// was designated to receive any gas left unused.
 	fn schedule_egress(
 		asset: C::ChainAsset,
 		amount: C::ChainAmount,
 		destination_address: C::ChainAccount,
-		maybe_ccm_with_gas_budget: Option<(CcmDepositMetadata, C::ChainAmount)>,
+		maybe_ccm_with_gas_budget: Option<(
+			CcmDepositMetadata,
+			C::ChainAmount,
+			Option<ForeignChainAddress>,
+		)>,
 	) -> EgressId;
 }
 
+/// Returns the unused part of a cross chain message's gas budget.
+pub trait CcmGasRefundHandler<C: Chain> {
+	/// Schedules the egress of the given amount of the chain's gas asset to the refund address,
+	/// less the fee for egressing it. Returns the egress id and the amount egressed, or `None` if
+	/// too little is left after the fee for the refund to be worth egressing.
+	fn refund_gas(
+		amount: C::ChainAmount,
+		refund_address: C::ChainAccount,
+	) -> Option<(EgressId, C::ChainAmount)>;
+}
+
+/// Estimates the fee that is paid to egress an asset.
+pub trait EgressFeeEstimator<C: Chain> {
+	/// The estimated egress fee, denominated in the egressed asset. Returns `None` if the fee
+	/// cannot be expressed in the asset, for example because there is no price for it.
+	fn estimate_egress_fee(asset: C::ChainAsset) -> Option<C::ChainAmount>;
+}
+
 impl<T: frame_system::Config> EgressApi<Ethereum> for T {
 	fn schedule_egress(
 		_asset: assets::eth::Asset,
 		_amount: <Ethereum as Chain>::ChainAmount,
 		_destination_address: <Ethereum as Chain>::ChainAccount,
-		_maybe_ccm_with_gas_budget: Option<(CcmDepositMetadata, <Ethereum as Chain>::ChainAmount)>,
+		_maybe_ccm_with_gas_budget: Option<(
+			CcmDepositMetadata,
+			<Ethereum as Chain>::ChainAmount,
+			Option<ForeignChainAddress>,
+		)>,
 	) -> EgressId {
 		(ForeignChain::Ethereum, 0)
 	}
@@ -725,7 +767,11 @@ impl<T: frame_system::Config> EgressApi<Polkadot> for T {
 		_asset: assets::dot::Asset,
 		_amount: <Polkadot as Chain>::ChainAmount,
 		_destination_address: <Polkadot as Chain>::ChainAccount,
-		_maybe_ccm_with_gas_budget: Option<(CcmDepositMetadata, <Polkadot as Chain>::ChainAmount)>,
+		_maybe_ccm_with_gas_budget: Option<(
+			CcmDepositMetadata,
+			<Polkadot as Chain>::ChainAmount,
+			Option<ForeignChainAddress>,
+		)>,
 	) -> EgressId {
 		(ForeignChain::Polkadot, 0)
 	}
@@ -768,7 +814,8 @@ pub trait DepositHandler<C: Chain> {
 
 /// Trait for handling cross chain messages.
 pub trait CcmHandler {
-	/// Triggered when a ccm deposit is made.
+	/// Triggered when a ccm deposit is made. If the deposit is rejected, it is returned to the
+	/// `refund_address`, if provided.
 	fn on_ccm_deposit(
 		source_asset: Asset,
 		deposit_amount: AssetAmount,
@@ -776,6 +823,7 @@ pub trait CcmHandler {
 		destination_address: ForeignChainAddress,
 		deposit_metadata: CcmDepositMetadata,
 		origin: SwapOrigin,
+		refund_address: Option<ForeignChainAddress>,
 	);
 }
 
@@ -787,6 +835,7 @@ impl CcmHandler for () {
 		_destination_address: ForeignChainAddress,
 		_deposit_metadata: CcmDepositMetadata,
 		_origin: SwapOrigin,
+		_refund_address: Option<ForeignChainAddress>,
 	) {
 	}
 }
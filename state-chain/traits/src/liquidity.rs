@@ -1,4 +1,4 @@
-use cf_chains::address::ForeignChainAddress;
+use cf_chains::{address::ForeignChainAddress, SwapRefundParameters};
 use cf_primitives::{Asset, AssetAmount, BasisPoints, ChannelId};
 use frame_support::{dispatch::DispatchError, sp_runtime::DispatchResult};
 
@@ -16,6 +16,7 @@ pub trait SwapDepositHandler {
 		broker_id: Self::AccountId,
 		broker_commission_bps: BasisPoints,
 		channel_id: ChannelId,
+		refund_parameters: Option<SwapRefundParameters>,
 	);
 }
 
@@ -64,8 +65,9 @@ impl<T: frame_system::Config> PoolApi for T {
 
 pub trait SwappingApi {
 	/// Takes the swap amount in STABLE_ASSET, collect network fee from it
-	/// and return the remaining value
-	fn take_network_fee(input_amount: AssetAmount) -> AssetAmount;
+	/// and return the remaining value. `from` and `to` are the assets of the swap the amount is
+	/// part of.
+	fn take_network_fee(input_amount: AssetAmount, from: Asset, to: Asset) -> AssetAmount;
 
 	/// Process a single leg of a swap, into or from Stable asset. No network fee is taken.
 	fn swap_single_leg(
@@ -76,7 +78,7 @@ pub trait SwappingApi {
 }
 
 impl<T: frame_system::Config> SwappingApi for T {
-	fn take_network_fee(input_amount: AssetAmount) -> AssetAmount {
+	fn take_network_fee(input_amount: AssetAmount, _from: Asset, _to: Asset) -> AssetAmount {
 		input_amount
 	}
 
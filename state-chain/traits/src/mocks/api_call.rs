@@ -2,7 +2,8 @@ use core::marker::PhantomData;
 
 use cf_chains::{
 	evm::EvmCrypto, AllBatch, AllBatchError, ApiCall, Chain, ChainCrypto, ChainEnvironment,
-	Ethereum, ExecutexSwapAndCall, FetchAssetParams, ForeignChainAddress, TransferAssetParams,
+	Ethereum, ExecutexSwapAndCall, ExecutexSwapAndCallError, FetchAssetParams, ForeignChainAddress,
+	TransferAssetParams,
 };
 use cf_primitives::{chains::assets, EgressId, ForeignChain};
 use codec::{Decode, Encode};
@@ -68,22 +69,34 @@ impl MockAllBatch<MockEthEnvironment> {
 	pub fn set_success(success: bool) {
 		ALL_BATCH_SUCCESS.with(|cell| *cell.borrow_mut() = success);
 	}
+
+	pub fn set_max_transfers_per_batch(max_transfers: Option<usize>) {
+		MAX_TRANSFERS_PER_BATCH.with(|cell| *cell.borrow_mut() = max_transfers);
+	}
 }
 
 thread_local! {
 	static ALL_BATCH_SUCCESS: std::cell::RefCell<bool> = std::cell::RefCell::new(true);
+	static MAX_TRANSFERS_PER_BATCH: std::cell::RefCell<Option<usize>> = std::cell::RefCell::new(None);
 }
 
 impl AllBatch<Ethereum> for MockEthereumApiCall<MockEthEnvironment> {
+	fn max_transfers_per_batch() -> Option<usize> {
+		MAX_TRANSFERS_PER_BATCH.with(|cell| *cell.borrow())
+	}
+
 	fn new_unsigned(
 		fetch_params: Vec<FetchAssetParams<Ethereum>>,
-		transfer_params: Vec<TransferAssetParams<Ethereum>>,
+		transfer_params: Vec<(TransferAssetParams<Ethereum>, EgressId)>,
 	) -> Result<Self, AllBatchError> {
 		if ALL_BATCH_SUCCESS.with(|cell| *cell.borrow()) {
 			Ok(Self::AllBatch(MockAllBatch {
 				nonce: Default::default(),
 				fetch_params,
-				transfer_params,
+				transfer_params: transfer_params
+					.into_iter()
+					.map(|(transfer_param, _egress_id)| transfer_param)
+					.collect(),
 				_phantom: PhantomData,
 			}))
 		} else {
@@ -104,6 +117,16 @@ pub struct MockExecutexSwapAndCall<MockEthEnvironment> {
 	_phantom: PhantomData<MockEthEnvironment>,
 }
 
+impl MockExecutexSwapAndCall<MockEthEnvironment> {
+	pub fn set_available(available: bool) {
+		EXECUTE_X_SWAP_AND_CALL_AVAILABLE.with(|cell| *cell.borrow_mut() = available);
+	}
+}
+
+thread_local! {
+	static EXECUTE_X_SWAP_AND_CALL_AVAILABLE: std::cell::RefCell<bool> = std::cell::RefCell::new(true);
+}
+
 impl ExecutexSwapAndCall<Ethereum> for MockEthereumApiCall<MockEthEnvironment> {
 	fn new_unsigned(
 		egress_id: EgressId,
@@ -112,9 +135,11 @@ impl ExecutexSwapAndCall<Ethereum> for MockEthereumApiCall<MockEthEnvironment> {
 		source_address: Option<ForeignChainAddress>,
 		gas_budget: <Ethereum as Chain>::ChainAmount,
 		message: Vec<u8>,
-	) -> Result<Self, DispatchError> {
-		if MockEthEnvironment::lookup(transfer_param.asset).is_none() {
-			Err(DispatchError::CannotLookup)
+	) -> Result<Self, ExecutexSwapAndCallError> {
+		if !EXECUTE_X_SWAP_AND_CALL_AVAILABLE.with(|cell| *cell.borrow()) {
+			Err(ExecutexSwapAndCallError::Unavailable)
+		} else if MockEthEnvironment::lookup(transfer_param.asset).is_none() {
+			Err(ExecutexSwapAndCallError::Invalid(DispatchError::CannotLookup))
 		} else {
 			Ok(Self::ExecutexSwapAndCall(MockExecutexSwapAndCall {
 				nonce: Default::default(),
@@ -55,6 +55,22 @@ impl<
 		Self::put_storage(b"CALLBACKS", id, callback);
 		ids
 	}
+
+	fn threshold_sign_and_broadcast_with_gas_refund(
+		api_call: Self::ApiCall,
+		gas_budget: Api::ChainAmount,
+		refund_address: Api::ChainAccount,
+	) -> (BroadcastId, ThresholdSignatureRequestId) {
+		let ids @ (id, _) = <Self as Broadcaster<Api>>::threshold_sign_and_broadcast(api_call);
+		Self::put_storage(b"GAS_REFUNDS", id, (gas_budget, refund_address));
+		ids
+	}
+}
+
+impl<T> MockBroadcaster<T> {
+	pub fn get_gas_refund<C: Chain>(id: BroadcastId) -> Option<(C::ChainAmount, C::ChainAccount)> {
+		Self::get_storage(b"GAS_REFUNDS", id)
+	}
 }
 
 impl<
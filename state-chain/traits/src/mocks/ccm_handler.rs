@@ -21,6 +21,7 @@ pub struct CcmRequest {
 	pub destination_address: ForeignChainAddress,
 	pub deposit_metadata: CcmDepositMetadata,
 	pub origin: SwapOrigin,
+	pub refund_address: Option<ForeignChainAddress>,
 }
 
 impl MockCcmHandler {
@@ -37,6 +38,7 @@ impl CcmHandler for MockCcmHandler {
 		destination_address: ForeignChainAddress,
 		deposit_metadata: CcmDepositMetadata,
 		origin: SwapOrigin,
+		refund_address: Option<ForeignChainAddress>,
 	) {
 		<Self as MockPalletStorage>::mutate_value(CCM_HANDLER_PREFIX, |ccm_requests| {
 			if ccm_requests.is_none() {
@@ -50,6 +52,7 @@ impl CcmHandler for MockCcmHandler {
 					destination_address,
 					deposit_metadata,
 					origin,
+					refund_address,
 				});
 			})
 		});
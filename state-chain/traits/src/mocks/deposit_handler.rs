@@ -1,6 +1,7 @@
 use crate::{Chainflip, DepositApi};
 use cf_chains::{
 	address::ForeignChainAddress, dot::PolkadotAccountId, CcmChannelMetadata, Chain, ForeignChain,
+	SwapRefundParameters,
 };
 use cf_primitives::{chains::assets::any, BasisPoints, ChannelId};
 use codec::{Decode, Encode};
@@ -29,6 +30,7 @@ pub struct SwapChannel<C: Chain, T: Chainflip> {
 	pub broker_commission_bps: BasisPoints,
 	pub broker_id: <T as frame_system::Config>::AccountId,
 	pub channel_metadata: Option<CcmChannelMetadata>,
+	pub refund_parameters: Option<SwapRefundParameters>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
@@ -62,6 +64,7 @@ impl<C: Chain, T: Chainflip> MockDepositHandler<C, T> {
 					PolkadotAccountId::from_aliased([channel_id as u8; 32]),
 				),
 				ForeignChain::Bitcoin => todo!("Bitcoin address"),
+				ForeignChain::Arbitrum => ForeignChainAddress::Arb([channel_id as u8; 20].into()),
 			},
 		)
 	}
@@ -109,6 +112,7 @@ impl<C: Chain, T: Chainflip> DepositApi<C> for MockDepositHandler<C, T> {
 		broker_commission_bps: BasisPoints,
 		broker_id: Self::AccountId,
 		channel_metadata: Option<CcmChannelMetadata>,
+		refund_parameters: Option<SwapRefundParameters>,
 	) -> Result<
 		(cf_primitives::ChannelId, ForeignChainAddress, C::ChainBlockNumber),
 		sp_runtime::DispatchError,
@@ -128,6 +132,7 @@ impl<C: Chain, T: Chainflip> DepositApi<C> for MockDepositHandler<C, T> {
 					broker_commission_bps,
 					broker_id,
 					channel_metadata,
+					refund_parameters,
 				});
 			};
 		});
@@ -1,6 +1,6 @@
 use super::{MockPallet, MockPalletStorage};
-use crate::EgressApi;
-use cf_chains::{CcmCfParameters, CcmDepositMetadata, CcmMessage, Chain};
+use crate::{CcmGasRefundHandler, EgressApi, EgressFeeEstimator};
+use cf_chains::{CcmCfParameters, CcmDepositMetadata, CcmMessage, Chain, ForeignChainAddress};
 use cf_primitives::{AssetAmount, EgressId, ForeignChain};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -54,6 +54,15 @@ impl<C: Chain> MockEgressHandler<C> {
 	pub fn get_scheduled_egresses() -> Vec<MockEgressParameter<C>> {
 		<Self as MockPalletStorage>::get_value(b"SCHEDULED_EGRESSES").unwrap_or_default()
 	}
+
+	pub fn get_gas_refunds() -> Vec<(C::ChainAmount, C::ChainAccount)> {
+		<Self as MockPalletStorage>::get_value(b"GAS_REFUNDS").unwrap_or_default()
+	}
+
+	/// Sets the fee returned by [EgressFeeEstimator]. `None` means the fee cannot be estimated.
+	pub fn set_egress_fee(fee: Option<C::ChainAmount>) {
+		<Self as MockPalletStorage>::put_value(b"EGRESS_FEE", fee);
+	}
 }
 
 impl<C: Chain> EgressApi<C> for MockEgressHandler<C> {
@@ -61,7 +70,11 @@ impl<C: Chain> EgressApi<C> for MockEgressHandler<C> {
 		asset: <C as Chain>::ChainAsset,
 		amount: <C as Chain>::ChainAmount,
 		destination_address: <C as Chain>::ChainAccount,
-		maybe_ccm_with_gas_budget: Option<(CcmDepositMetadata, <C as Chain>::ChainAmount)>,
+		maybe_ccm_with_gas_budget: Option<(
+			CcmDepositMetadata,
+			<C as Chain>::ChainAmount,
+			Option<ForeignChainAddress>,
+		)>,
 	) -> EgressId {
 		<Self as MockPalletStorage>::mutate_value(b"SCHEDULED_EGRESSES", |storage| {
 			if storage.is_none() {
@@ -69,14 +82,15 @@ impl<C: Chain> EgressApi<C> for MockEgressHandler<C> {
 			}
 			storage.as_mut().map(|v| {
 				v.push(match maybe_ccm_with_gas_budget {
-					Some((message, gas_budget)) => MockEgressParameter::<C>::Ccm {
-						asset,
-						amount,
-						destination_address,
-						message: message.channel_metadata.message,
-						cf_parameters: message.channel_metadata.cf_parameters,
-						gas_budget,
-					},
+					Some((message, gas_budget, _gas_refund_address)) =>
+						MockEgressParameter::<C>::Ccm {
+							asset,
+							amount,
+							destination_address,
+							message: message.channel_metadata.message,
+							cf_parameters: message.channel_metadata.cf_parameters,
+							gas_budget,
+						},
 					None => MockEgressParameter::<C>::Swap { asset, amount, destination_address },
 				});
 			})
@@ -85,3 +99,31 @@ impl<C: Chain> EgressApi<C> for MockEgressHandler<C> {
 		(ForeignChain::Ethereum, len as u64)
 	}
 }
+
+/// Deducts the fee set with [MockEgressHandler::set_egress_fee] from refunds.
+impl<C: Chain> CcmGasRefundHandler<C> for MockEgressHandler<C> {
+	fn refund_gas(
+		amount: C::ChainAmount,
+		refund_address: C::ChainAccount,
+	) -> Option<(EgressId, C::ChainAmount)> {
+		let egress_fee = <Self as MockPalletStorage>::get_value(b"EGRESS_FEE")
+			.unwrap_or(Some(Default::default()))?;
+		if amount <= egress_fee {
+			return None
+		}
+		let amount = amount - egress_fee;
+		<Self as MockPalletStorage>::mutate_value(
+			b"GAS_REFUNDS",
+			|storage: &mut Option<Vec<(C::ChainAmount, C::ChainAccount)>>| {
+				storage.get_or_insert(Default::default()).push((amount, refund_address));
+			},
+		);
+		Some(((ForeignChain::Ethereum, Self::get_gas_refunds().len() as u64), amount))
+	}
+}
+
+impl<C: Chain> EgressFeeEstimator<C> for MockEgressHandler<C> {
+	fn estimate_egress_fee(_asset: C::ChainAsset) -> Option<C::ChainAmount> {
+		<Self as MockPalletStorage>::get_value(b"EGRESS_FEE").unwrap_or(Some(Default::default()))
+	}
+}
@@ -21,6 +21,7 @@ where
 		_broker_id: Self::AccountId,
 		_broker_commission_bps: cf_primitives::BasisPoints,
 		_channel_id: cf_primitives::ChannelId,
+		_refund_parameters: Option<cf_chains::SwapRefundParameters>,
 	) {
 		E::schedule_egress(
 			to.try_into().unwrap_or_else(|_| panic!("Unable to convert")),
@@ -88,3 +88,4 @@ macro_rules! mock_vault_rotator {
 mock_vault_rotator!(MockVaultRotatorA);
 mock_vault_rotator!(MockVaultRotatorB);
 mock_vault_rotator!(MockVaultRotatorC);
+mock_vault_rotator!(MockVaultRotatorD);
@@ -7,12 +7,18 @@ use crate::ArrayCollect;
 use async_channel::{unbounded, Receiver, Sender};
 use lazy_static;
 use prometheus::{
-	register_int_counter_vec_with_registry, register_int_counter_with_registry,
-	register_int_gauge_vec_with_registry, register_int_gauge_with_registry, IntCounter,
-	IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+	core::Collector, register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+	register_int_counter_with_registry, register_int_gauge_vec_with_registry,
+	register_int_gauge_with_registry, HistogramOpts, HistogramVec, IntCounter, IntCounterVec,
+	IntGauge, IntGaugeVec, Opts, Registry,
 };
 use serde::Deserialize;
-use std::{collections::HashSet, net::IpAddr};
+use std::{
+	collections::{BTreeMap, HashSet},
+	net::IpAddr,
+	sync::Mutex,
+	time::Duration,
+};
 use tracing::info;
 use warp::Filter;
 
@@ -116,6 +122,25 @@ impl<const N: usize> IntGaugeVecWrapper<N> {
 			Err(e) => tracing::error!("Conversion to i64 failed: {:?}", e),
 		}
 	}
+
+	/// Returns the current value of every series of the gauge, along with its labels by name
+	pub fn values(&self) -> Vec<(BTreeMap<String, String>, i64)> {
+		self.prom_metric
+			.collect()
+			.iter()
+			.flat_map(|family| family.get_metric())
+			.map(|metric| {
+				(
+					metric
+						.get_label()
+						.iter()
+						.map(|label| (label.get_name().to_string(), label.get_value().to_string()))
+						.collect(),
+					metric.get_gauge().get_value() as i64,
+				)
+			})
+			.collect()
+	}
 }
 
 #[derive(Clone)]
@@ -148,6 +173,37 @@ impl<const N: usize> IntCounterVecWrapper<N> {
 		}
 	}
 }
+/// wrapper used to enforce the correct number of labels when interacting with a HistogramVec
+pub struct HistogramVecWrapper<const N: usize> {
+	pub prom_metric: HistogramVec,
+}
+
+impl<const N: usize> HistogramVecWrapper<N> {
+	fn new(
+		name: &str,
+		help: &str,
+		buckets: Vec<f64>,
+		labels: &[&str; N],
+		registry: &REGISTRY,
+	) -> HistogramVecWrapper<N> {
+		HistogramVecWrapper {
+			prom_metric: register_histogram_vec_with_registry!(
+				HistogramOpts::new(name, help).buckets(buckets),
+				labels,
+				registry
+			)
+			.expect("A duplicate metric collector has already been registered."),
+		}
+	}
+
+	pub fn observe(&self, labels: &[&str; N], val: f64) {
+		match self.prom_metric.get_metric_with_label_values(labels) {
+			Ok(m) => m.observe(val),
+			Err(e) => tracing::error!("Failed to get the metric: {}", e),
+		}
+	}
+}
+
 macro_rules! build_gauge_vec {
 	($metric_ident:ident, $name:literal, $help:literal, $labels:tt) => {
 		lazy_static::lazy_static!{
@@ -164,6 +220,14 @@ macro_rules! build_counter_vec {
 	}
 }
 
+macro_rules! build_histogram_vec {
+	($metric_ident:ident, $name:literal, $help:literal, $buckets:expr, $labels:tt) => {
+		lazy_static::lazy_static!{
+			pub static ref $metric_ident: HistogramVecWrapper<{ $labels.len() }> = HistogramVecWrapper::new($name, $help, $buckets, &$labels, &REGISTRY);
+		}
+	}
+}
+
 macro_rules! build_gauge_vec_struct {
 	($metric_ident:ident, $struct_ident:ident, $name:literal, $help:literal, $drop:expr, $labels:tt) => {
 		build_gauge_vec!($metric_ident, $name, $help, $labels);
@@ -405,6 +469,40 @@ macro_rules! build_counter_vec_struct {
 	};
 }
 
+macro_rules! build_histogram_vec_struct {
+	($metric_ident:ident, $struct_ident:ident, $name:literal, $help:literal, $buckets:expr, $labels:tt, $const_labels:tt) => {
+		build_histogram_vec!($metric_ident, $name, $help, $buckets, $labels);
+
+		#[derive(Clone)]
+		pub struct $struct_ident {
+			metric: &'static $metric_ident,
+			const_labels: [String; { $const_labels.len() }],
+		}
+		impl $struct_ident {
+			pub fn new(
+				metric: &'static $metric_ident,
+				const_labels: [String; { $const_labels.len() }],
+			) -> $struct_ident {
+				$struct_ident { metric, const_labels }
+			}
+
+			pub fn observe(
+				&self,
+				non_const_labels: &[&str; { $labels.len() - $const_labels.len() }],
+				duration: Duration,
+			) {
+				let labels: [&str; { $labels.len() }] = self
+					.const_labels
+					.iter()
+					.map(|s| s.as_str())
+					.chain(*non_const_labels)
+					.collect_array();
+				self.metric.observe(&labels, duration.as_secs_f64());
+			}
+		}
+	};
+}
+
 lazy_static::lazy_static! {
 	static ref REGISTRY: Registry = Registry::new();
 	pub static ref DELETE_METRIC_CHANNEL: (Sender<DeleteMetricCommand>, Receiver<DeleteMetricCommand>) = unbounded::<DeleteMetricCommand>();
@@ -414,6 +512,7 @@ lazy_static::lazy_static! {
 	pub static ref P2P_RECONNECT_PEERS: IntGaugeWrapper = IntGaugeWrapper::new("p2p_reconnect_peers", "Count the number of peers we need to reconnect to", &REGISTRY);
 	pub static ref P2P_ACTIVE_CONNECTIONS: IntGaugeWrapper = IntGaugeWrapper::new("p2p_active_connections", "Count the number of active connections", &REGISTRY);
 	pub static ref P2P_ALLOWED_PUBKEYS: IntGaugeWrapper = IntGaugeWrapper::new("p2p_allowed_pubkeys", "Count the number of allowed pubkeys", &REGISTRY);
+	static ref BLAMED_PARTY_LABELS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
 	pub static ref P2P_DECLINED_CONNECTIONS: IntCounter = register_int_counter_with_registry!(Opts::new("p2p_declined_connections", "Count the number times we decline a connection"), &REGISTRY).expect("A duplicate metric collector has already been registered.");
 }
 
@@ -423,6 +522,12 @@ build_gauge_vec!(
 	"Gauge keeping track of the number of unauthorized ceremony currently awaiting authorisation",
 	["chain", "type"]
 );
+build_gauge_vec!(
+	AUTHORIZED_CEREMONY,
+	"authorized_ceremony",
+	"Gauge keeping track of the number of authorized ceremony currently in progress",
+	["chain", "type"]
+);
 build_counter_vec!(
 	RPC_RETRIER_REQUESTS,
 	"rpc_requests",
@@ -509,6 +614,67 @@ build_counter_vec_struct!(
 	["chain"]
 );
 
+build_histogram_vec_struct!(
+	CEREMONY_LATENCY,
+	CeremonyLatency,
+	"ceremony_latency_seconds",
+	"Distribution of the time taken by authorised ceremonies to complete, by outcome",
+	vec![0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0],
+	["chain", "ceremony_type", "outcome"],
+	["chain", "ceremony_type"]
+);
+build_histogram_vec_struct!(
+	STAGE_LATENCY,
+	StageLatency,
+	"stage_latency_seconds",
+	"Distribution of the time taken by ceremony stages, by phase (receiving or processing)",
+	vec![0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0],
+	["chain", "ceremony_type", "stage", "phase"],
+	["chain", "ceremony_type"]
+);
+build_counter_vec_struct!(
+	STAGE_TIMEOUT,
+	StageTimeoutNotDrop,
+	"stage_timeout",
+	"Count the number of stages which timed out before receiving all messages",
+	false,
+	["chain", "ceremony_type", "stage"],
+	["chain", "ceremony_type"]
+);
+build_counter_vec_struct!(
+	CEREMONY_FAILED,
+	CeremonyFailedNotDrop,
+	"ceremony_failed",
+	"Count the number of ceremonies which failed, with the reason of the failure attached",
+	false,
+	["chain", "ceremony_type", "reason"],
+	["chain", "ceremony_type"]
+);
+build_counter_vec_struct!(
+	CEREMONY_BLAMED_PARTY,
+	CeremonyBlamedPartyNotDrop,
+	"ceremony_blamed_party",
+	"Count the number of times each party was blamed for a failed ceremony",
+	false,
+	["chain", "ceremony_type", "party"],
+	["chain", "ceremony_type"]
+);
+
+/// The maximum number of parties that are given their own label in [CEREMONY_BLAMED_PARTY]. Any
+/// other party is counted under [OTHER_PARTIES_LABEL]. The authority set changes over time, so
+/// this bounds the cardinality of the metric.
+const MAX_BLAMED_PARTY_LABELS: usize = 256;
+const OTHER_PARTIES_LABEL: &str = "other";
+
+fn blamed_party_label(labels: &mut HashSet<String>, party: String) -> String {
+	if labels.contains(&party) || labels.len() < MAX_BLAMED_PARTY_LABELS {
+		labels.insert(party.clone());
+		party
+	} else {
+		OTHER_PARTIES_LABEL.to_string()
+	}
+}
+
 /// structure containing the metrics used during a ceremony
 #[derive(Clone)]
 pub struct CeremonyMetrics {
@@ -519,6 +685,11 @@ pub struct CeremonyMetrics {
 	pub stage_duration: StageDurationDrop,
 	pub stage_failing: StageFailingNotDrop,
 	pub stage_completing: StageCompletingNotDrop,
+	pub ceremony_latency: CeremonyLatency,
+	pub stage_latency: StageLatency,
+	pub stage_timeout: StageTimeoutNotDrop,
+	pub ceremony_failed: CeremonyFailedNotDrop,
+	pub blamed_party: CeremonyBlamedPartyNotDrop,
 }
 impl CeremonyMetrics {
 	pub fn new(ceremony_id: u64, chain_name: &str, ceremony_type: &str) -> Self {
@@ -537,14 +708,47 @@ impl CeremonyMetrics {
 			),
 			missing_messages: CeremonyTimeoutMissingMsgDrop::new(
 				&CEREMONY_TIMEOUT_MISSING_MSG,
-				[chain_name.clone(), ceremony_id.clone(), ceremony_type],
+				[chain_name.clone(), ceremony_id.clone(), ceremony_type.clone()],
 			),
 			stage_duration: StageDurationDrop::new(
 				&STAGE_DURATION,
 				[chain_name.clone(), ceremony_id],
 			),
 			stage_failing: StageFailingNotDrop::new(&STAGE_FAILING, [chain_name.clone()]),
-			stage_completing: StageCompletingNotDrop::new(&STAGE_COMPLETING, [chain_name]),
+			stage_completing: StageCompletingNotDrop::new(&STAGE_COMPLETING, [chain_name.clone()]),
+			ceremony_latency: CeremonyLatency::new(
+				&CEREMONY_LATENCY,
+				[chain_name.clone(), ceremony_type.clone()],
+			),
+			stage_latency: StageLatency::new(
+				&STAGE_LATENCY,
+				[chain_name.clone(), ceremony_type.clone()],
+			),
+			stage_timeout: StageTimeoutNotDrop::new(
+				&STAGE_TIMEOUT,
+				[chain_name.clone(), ceremony_type.clone()],
+			),
+			ceremony_failed: CeremonyFailedNotDrop::new(
+				&CEREMONY_FAILED,
+				[chain_name.clone(), ceremony_type.clone()],
+			),
+			blamed_party: CeremonyBlamedPartyNotDrop::new(
+				&CEREMONY_BLAMED_PARTY,
+				[chain_name, ceremony_type],
+			),
+		}
+	}
+
+	/// Count the failure of the ceremony, and each of the parties blamed for it
+	pub fn record_failure<Party: ToString>(
+		&mut self,
+		reason: &str,
+		blamed_parties: impl IntoIterator<Item = Party>,
+	) {
+		self.ceremony_failed.inc(&[reason]);
+		let mut labels = BLAMED_PARTY_LABELS.lock().unwrap();
+		for party in blamed_parties {
+			self.blamed_party.inc(&[&blamed_party_label(&mut labels, party.to_string())]);
 		}
 	}
 }
@@ -755,6 +959,22 @@ stage_failing{chain="Chain1",reason="NotEnoughMessages",stage="stage3"} 1
 		.unwrap();
 	}
 
+	#[test]
+	fn blamed_party_labels_are_bounded() {
+		let mut labels = HashSet::new();
+		for party in 0..MAX_BLAMED_PARTY_LABELS {
+			assert_eq!(blamed_party_label(&mut labels, party.to_string()), party.to_string());
+		}
+
+		assert_eq!(
+			blamed_party_label(&mut labels, MAX_BLAMED_PARTY_LABELS.to_string()),
+			OTHER_PARTIES_LABEL
+		);
+		// Parties which already have a label keep it
+		assert_eq!(blamed_party_label(&mut labels, "0".to_string()), "0");
+		assert_eq!(labels.len(), MAX_BLAMED_PARTY_LABELS);
+	}
+
 	fn create_and_register_metric() -> IntCounterVec {
 		let metric = register_int_counter_vec_with_registry!(
 			Opts::new("test", "test help"),